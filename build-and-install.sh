@@ -485,6 +485,17 @@ echo "[INSTALL] Installing binary to $BIN_DIR..."
 cp "$BINARY_PATH" "$BIN_DIR/rustora"
 chmod +x "$BIN_DIR/rustora"
 
+# Install the offline cfhdb profile bundle, fetching it first when it was not prepared
+if [ ! -f "data/cfhdb/manifest.json" ]; then
+    echo "[DATA] Fetching offline device profile bundle..."
+    data/cfhdb/update-bundle.sh || echo -e "${YELLOW}[WARN] Could not fetch the offline device profile bundle.${NC}"
+fi
+if [ -f "data/cfhdb/manifest.json" ] && [ -f "data/cfhdb/pci.json" ] && [ -f "data/cfhdb/usb.json" ]; then
+    echo "[DATA] Installing offline device profile bundle..."
+    mkdir -p "$INSTALL_PREFIX/share/rustora/cfhdb"
+    cp data/cfhdb/pci.json data/cfhdb/usb.json data/cfhdb/manifest.json "$INSTALL_PREFIX/share/rustora/cfhdb/"
+fi
+
 # Install icon if it exists
 ICON_DIR="$INSTALL_PREFIX/share/icons/hicolor/scalable/apps"
 if [ -f "src/assets/rustora.svg" ]; then
@@ -509,13 +520,13 @@ Type=Application
 Name=Rustora
 GenericName=Package Manager
 Comment=A modern package manager for Fedora
-Exec=$BIN_DIR/rustora %f
+Exec=$BIN_DIR/rustora %u
 Icon=$ICON_NAME
 Terminal=false
 Categories=System;PackageManager;
 Keywords=package;manager;dnf;rpm;install;update;
 StartupNotify=true
-MimeType=application/x-rpm;
+MimeType=application/x-rpm;application/vnd.flatpak.ref;application/vnd.flatpak.repo;x-scheme-handler/appstream;x-scheme-handler/flatpak+https;
 Actions=
 
 [Desktop Action InstallRPM]
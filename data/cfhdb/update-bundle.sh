@@ -0,0 +1,27 @@
+#!/bin/bash
+# Refreshes the offline device profile bundle from the upstream cfhdb database and records
+# the SHA-256 of each file in manifest.json, which Rustora checks before seeding its cache
+set -e
+
+cd "$(dirname "$0")"
+
+BASE_URL="${CFHDB_PROFILES_URL:-https://raw.githubusercontent.com/Nobara-Project/cfhdb/refs/heads/master/data/profiles}"
+
+for file in pci.json usb.json; do
+    curl -fL --retry 3 -o "$file.tmp" "$BASE_URL/$file"
+    mv "$file.tmp" "$file"
+done
+
+PCI_SHA256=$(sha256sum pci.json | cut -d' ' -f1)
+USB_SHA256=$(sha256sum usb.json | cut -d' ' -f1)
+
+cat > manifest.json <<MANIFEST
+{
+  "source": "$BASE_URL",
+  "created": "$(date '+%Y-%m-%d %H:%M:%S')",
+  "pci_sha256": "$PCI_SHA256",
+  "usb_sha256": "$USB_SHA256"
+}
+MANIFEST
+
+echo "Updated the offline profile bundle from $BASE_URL"
@@ -0,0 +1,375 @@
+use std::collections::HashSet;
+use std::fs;
+
+// x86-64 micro-architecture levels as defined by the x86-64 psABI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum X86Level {
+    V1 = 1,
+    V2 = 2,
+    V3 = 3,
+    V4 = 4,
+}
+
+impl X86Level {
+    pub fn from_u32(level: u32) -> Self {
+        match level {
+            0 | 1 => X86Level::V1,
+            2 => X86Level::V2,
+            3 => X86Level::V3,
+            _ => X86Level::V4,
+        }
+    }
+
+    pub fn as_u32(self) -> u32 {
+        self as u32
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            X86Level::V1 => "x86-64",
+            X86Level::V2 => "x86-64-v2",
+            X86Level::V3 => "x86-64-v3",
+            X86Level::V4 => "x86-64-v4",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CpuVendor {
+    Amd,
+    Intel,
+    Other,
+}
+
+impl CpuVendor {
+    pub fn label(self) -> &'static str {
+        match self {
+            CpuVendor::Amd => "AMD",
+            CpuVendor::Intel => "Intel",
+            CpuVendor::Other => "Unknown vendor",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Microarch {
+    Zen,
+    Zen2,
+    Zen3,
+    Zen4,
+    Zen5,
+    Skylake,
+    IceLake,
+    TigerLake,
+    RocketLake,
+    AlderLake,
+    RaptorLake,
+    MeteorLake,
+    ArrowLake,
+    LunarLake,
+    SapphireRapids,
+    Unknown,
+}
+
+impl Microarch {
+    pub fn label(self) -> &'static str {
+        match self {
+            Microarch::Zen => "Zen / Zen+",
+            Microarch::Zen2 => "Zen 2",
+            Microarch::Zen3 => "Zen 3",
+            Microarch::Zen4 => "Zen 4",
+            Microarch::Zen5 => "Zen 5",
+            Microarch::Skylake => "Skylake",
+            Microarch::IceLake => "Ice Lake",
+            Microarch::TigerLake => "Tiger Lake",
+            Microarch::RocketLake => "Rocket Lake",
+            Microarch::AlderLake => "Alder Lake",
+            Microarch::RaptorLake => "Raptor Lake",
+            Microarch::MeteorLake => "Meteor Lake",
+            Microarch::ArrowLake => "Arrow Lake",
+            Microarch::LunarLake => "Lunar Lake",
+            Microarch::SapphireRapids => "Sapphire Rapids",
+            Microarch::Unknown => "Unknown",
+        }
+    }
+
+    // Name fragments kernel builds use for -march specific variants (e.g. kernel-cachyos-znver4)
+    pub fn march_tokens(self) -> &'static [&'static str] {
+        match self {
+            Microarch::Zen => &["znver1"],
+            Microarch::Zen2 => &["znver2"],
+            Microarch::Zen3 => &["znver3"],
+            Microarch::Zen4 => &["znver4"],
+            Microarch::Zen5 => &["znver5"],
+            Microarch::Skylake => &["skylake"],
+            Microarch::IceLake => &["icelake"],
+            Microarch::TigerLake => &["tigerlake"],
+            Microarch::RocketLake => &["rocketlake"],
+            Microarch::AlderLake => &["alderlake"],
+            Microarch::RaptorLake => &["raptorlake"],
+            Microarch::MeteorLake => &["meteorlake"],
+            Microarch::ArrowLake => &["arrowlake"],
+            Microarch::LunarLake => &["lunarlake"],
+            Microarch::SapphireRapids => &["sapphirerapids"],
+            Microarch::Unknown => &[],
+        }
+    }
+
+    // -march targets whose instruction set this CPU fully implements: its own plus the
+    // older generations of the same line. Intel's hybrid parts dropped AVX-512, so
+    // Alder Lake and later do not run Ice Lake or Sapphire Rapids builds
+    pub fn supported_march_tokens(self) -> &'static [&'static str] {
+        match self {
+            Microarch::Zen => &["znver1"],
+            Microarch::Zen2 => &["znver1", "znver2"],
+            Microarch::Zen3 => &["znver1", "znver2", "znver3"],
+            Microarch::Zen4 => &["znver1", "znver2", "znver3", "znver4"],
+            Microarch::Zen5 => &["znver1", "znver2", "znver3", "znver4", "znver5"],
+            Microarch::Skylake => &["skylake"],
+            Microarch::IceLake => &["skylake", "icelake"],
+            Microarch::TigerLake => &["skylake", "icelake", "tigerlake"],
+            Microarch::RocketLake => &["skylake", "icelake", "rocketlake"],
+            Microarch::AlderLake => &["skylake", "alderlake"],
+            Microarch::RaptorLake => &["skylake", "alderlake", "raptorlake"],
+            Microarch::MeteorLake => &["skylake", "alderlake", "raptorlake", "meteorlake"],
+            Microarch::ArrowLake => &["skylake", "alderlake", "raptorlake", "meteorlake", "arrowlake"],
+            Microarch::LunarLake => &["skylake", "alderlake", "raptorlake", "meteorlake", "arrowlake", "lunarlake"],
+            Microarch::SapphireRapids => &["skylake", "icelake", "sapphirerapids"],
+            Microarch::Unknown => &[],
+        }
+    }
+}
+
+// Every -march token we know about, used to spot kernels built for a different CPU
+pub const ALL_MARCH_TOKENS: &[&str] = &[
+    "znver1", "znver2", "znver3", "znver4", "znver5",
+    "skylake", "icelake", "tigerlake", "rocketlake", "alderlake",
+    "raptorlake", "meteorlake", "arrowlake", "lunarlake", "sapphirerapids",
+];
+
+#[derive(Debug, Clone)]
+pub struct CpuInfo {
+    pub vendor: CpuVendor,
+    pub family: u32,
+    pub model: u32,
+    pub model_name: String,
+    pub level: X86Level,
+    pub microarch: Microarch,
+}
+
+impl CpuInfo {
+    // Detect the running CPU from /proc/cpuinfo, falling back to CPUID when it can't be read
+    pub fn detect() -> Self {
+        match fs::read_to_string("/proc/cpuinfo") {
+            Ok(content) => Self::from_cpuinfo(&content),
+            Err(_) => Self::from_cpuid(),
+        }
+    }
+
+    pub fn from_cpuinfo(content: &str) -> Self {
+        let mut vendor_id = String::new();
+        let mut family = 0u32;
+        let mut model = 0u32;
+        let mut model_name = String::new();
+        let mut flags: HashSet<String> = HashSet::new();
+
+        // Only the first processor block is needed, all cores report the same features
+        for line in content.lines() {
+            if line.trim().is_empty() {
+                if !flags.is_empty() {
+                    break;
+                }
+                continue;
+            }
+            let (key, value) = match line.split_once(':') {
+                Some((k, v)) => (k.trim(), v.trim()),
+                None => continue,
+            };
+            match key {
+                "vendor_id" => vendor_id = value.to_string(),
+                "cpu family" => family = value.parse().unwrap_or(0),
+                "model" => model = value.parse().unwrap_or(0),
+                "model name" => model_name = value.to_string(),
+                "flags" => flags = value.split_whitespace().map(|s| s.to_string()).collect(),
+                _ => {}
+            }
+        }
+
+        let vendor = parse_vendor(&vendor_id);
+        let level = if flags.is_empty() {
+            Self::from_cpuid().level
+        } else {
+            level_from_flags(&flags)
+        };
+
+        Self {
+            vendor,
+            family,
+            model,
+            model_name,
+            level,
+            microarch: detect_microarch(vendor, family, model),
+        }
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    fn from_cpuid() -> Self {
+        let v2 = is_x86_feature_detected!("cmpxchg16b")
+            && is_x86_feature_detected!("popcnt")
+            && is_x86_feature_detected!("sse3")
+            && is_x86_feature_detected!("sse4.1")
+            && is_x86_feature_detected!("sse4.2")
+            && is_x86_feature_detected!("ssse3");
+        let v3 = v2
+            && is_x86_feature_detected!("avx")
+            && is_x86_feature_detected!("avx2")
+            && is_x86_feature_detected!("bmi1")
+            && is_x86_feature_detected!("bmi2")
+            && is_x86_feature_detected!("f16c")
+            && is_x86_feature_detected!("fma")
+            && is_x86_feature_detected!("lzcnt")
+            && is_x86_feature_detected!("movbe")
+            && is_x86_feature_detected!("xsave");
+        let v4 = v3
+            && is_x86_feature_detected!("avx512f")
+            && is_x86_feature_detected!("avx512bw")
+            && is_x86_feature_detected!("avx512cd")
+            && is_x86_feature_detected!("avx512dq")
+            && is_x86_feature_detected!("avx512vl");
+
+        let level = if v4 {
+            X86Level::V4
+        } else if v3 {
+            X86Level::V3
+        } else if v2 {
+            X86Level::V2
+        } else {
+            X86Level::V1
+        };
+
+        Self {
+            vendor: CpuVendor::Other,
+            family: 0,
+            model: 0,
+            model_name: String::new(),
+            level,
+            microarch: Microarch::Unknown,
+        }
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    fn from_cpuid() -> Self {
+        Self {
+            vendor: CpuVendor::Other,
+            family: 0,
+            model: 0,
+            model_name: String::new(),
+            level: X86Level::V1,
+            microarch: Microarch::Unknown,
+        }
+    }
+
+    // Short human readable summary, e.g. "AMD Zen 4 · x86-64-v4"
+    pub fn summary(&self) -> String {
+        if self.microarch == Microarch::Unknown {
+            format!("{} · {}", self.vendor.label(), self.level.label())
+        } else {
+            format!("{} {} · {}", self.vendor.label(), self.microarch.label(), self.level.label())
+        }
+    }
+
+    // Why a package built for a specific -march or x86-64 level (detected from its name)
+    // can't run on this CPU, naming whichever check failed; builds for older targets than
+    // the CPU's own are fine
+    pub fn march_mismatch(&self, name: &str) -> Option<String> {
+        let name_lower = name.to_lowercase();
+        if let Some(level) = name_level(&name_lower).filter(|level| *level > self.level) {
+            return Some(format!("needs {}, this CPU supports {}", level.label(), self.level.label()));
+        }
+        let supported = self.microarch.supported_march_tokens();
+        ALL_MARCH_TOKENS
+            .iter()
+            .find(|token| name_lower.contains(*token) && !supported.contains(token))
+            .map(|token| format!("built with -march={}, which {} can't run", token, self.microarch_name()))
+    }
+
+    fn microarch_name(&self) -> String {
+        if self.microarch == Microarch::Unknown {
+            "this CPU".to_string()
+        } else {
+            format!("this CPU ({} {})", self.vendor.label(), self.microarch.label())
+        }
+    }
+
+    pub fn matches_march_name(&self, name: &str) -> bool {
+        let name_lower = name.to_lowercase();
+        self.microarch
+            .march_tokens()
+            .iter()
+            .any(|token| name_lower.contains(token))
+    }
+}
+
+// "x86-64-v3" / "x86_64_v3" style level suffixes
+fn name_level(name_lower: &str) -> Option<X86Level> {
+    let normalized = name_lower.replace('_', "-");
+    let rest = &normalized[normalized.find("x86-64-v")? + "x86-64-v".len()..];
+    let digit = rest.chars().next()?.to_digit(10)?;
+    Some(X86Level::from_u32(digit))
+}
+
+fn parse_vendor(vendor_id: &str) -> CpuVendor {
+    match vendor_id {
+        "AuthenticAMD" | "HygonGenuine" => CpuVendor::Amd,
+        "GenuineIntel" => CpuVendor::Intel,
+        _ => CpuVendor::Other,
+    }
+}
+
+fn level_from_flags(flags: &HashSet<String>) -> X86Level {
+    let has_all = |required: &[&str]| required.iter().all(|f| flags.contains(*f));
+
+    // Flag names as reported by the kernel in /proc/cpuinfo ("pni" is SSE3, "abm" covers LZCNT)
+    let v2 = has_all(&["cx16", "lahf_lm", "popcnt", "pni", "sse4_1", "sse4_2", "ssse3"]);
+    let v3 = v2 && has_all(&["avx", "avx2", "bmi1", "bmi2", "f16c", "fma", "abm", "movbe", "xsave"]);
+    let v4 = v3 && has_all(&["avx512f", "avx512bw", "avx512cd", "avx512dq", "avx512vl"]);
+
+    if v4 {
+        X86Level::V4
+    } else if v3 {
+        X86Level::V3
+    } else if v2 {
+        X86Level::V2
+    } else {
+        X86Level::V1
+    }
+}
+
+fn detect_microarch(vendor: CpuVendor, family: u32, model: u32) -> Microarch {
+    match vendor {
+        CpuVendor::Amd => match family {
+            0x17 if model < 0x30 => Microarch::Zen,
+            0x17 => Microarch::Zen2,
+            0x19 => match model {
+                0x10..=0x1f | 0x60..=0x7f | 0xa0..=0xaf => Microarch::Zen4,
+                _ => Microarch::Zen3,
+            },
+            0x1a => Microarch::Zen5,
+            _ => Microarch::Unknown,
+        },
+        CpuVendor::Intel if family == 6 => match model {
+            0x4e | 0x5e | 0x55 | 0x8e | 0x9e | 0xa5 | 0xa6 => Microarch::Skylake,
+            0x6a | 0x6c | 0x7d | 0x7e => Microarch::IceLake,
+            0x8c | 0x8d => Microarch::TigerLake,
+            0xa7 => Microarch::RocketLake,
+            0x97 | 0x9a => Microarch::AlderLake,
+            0xb7 | 0xba | 0xbf => Microarch::RaptorLake,
+            0xaa | 0xac => Microarch::MeteorLake,
+            0xc5 | 0xc6 => Microarch::ArrowLake,
+            0xbd => Microarch::LunarLake,
+            0x8f => Microarch::SapphireRapids,
+            _ => Microarch::Unknown,
+        },
+        _ => Microarch::Unknown,
+    }
+}
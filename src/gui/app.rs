@@ -115,9 +115,14 @@ impl Application for RustoraApp {
     fn new(_flags: Option<PathBuf>) -> (Self, Command<Message>) {
         logger::Logger::log_debug("RustoraApp initialized");
         let installed_tab = InstalledTab::new();
-        let load_command = Command::perform(async {}, |_| {
-            Message::InstalledTabMessage(installed::Message::LoadPackages)
-        });
+        let load_command = Command::batch(vec![
+            Command::perform(async {}, |_| {
+                Message::InstalledTabMessage(installed::Message::LoadPackages)
+            }),
+            Command::perform(async {}, |_| {
+                Message::SearchTabMessage(search::Message::LoadCatalog)
+            }),
+        ]);
 
         (
             Self {
@@ -229,6 +234,12 @@ impl Application for RustoraApp {
                 logger::Logger::log_tab_change(Some(from_tab), tab_name);
                 self.current_tab = tab;
                 let cmd = match tab {
+                    Tab::Search => Command::perform(async {}, |_| {
+                        Message::SearchTabMessage(search::Message::LoadCatalog)
+                    }),
+                    Tab::Flatpak => Command::perform(async {}, |_| {
+                        Message::FlatpakTabMessage(flatpak::Message::LoadCatalog)
+                    }),
                     Tab::Installed => Command::perform(async {}, |_| {
                         Message::InstalledTabMessage(installed::Message::LoadPackages)
                     }),
@@ -244,6 +255,9 @@ impl Application for RustoraApp {
                     Tab::Tweaks => Command::perform(async {}, |_| {
                         Message::TweaksTabMessage(tweaks::Message::LoadDnfConfig)
                     }),
+                    Tab::Maintenance => Command::perform(async {}, |_| {
+                        Message::MaintenanceTabMessage(maintenance::Message::LoadSecureBootStatus)
+                    }),
                     _ => Command::none(),
                 };
                 cmd
@@ -256,6 +270,14 @@ impl Application for RustoraApp {
                 logger::Logger::log_tab_action("Installed", &format!("{:?}", msg));
                 self.installed_tab.update(msg).map(Message::InstalledTabMessage)
             }
+            Message::UpdateTabMessage(update::Message::OpenFirmware) => {
+                logger::Logger::log_tab_change(Some("Update"), "Device");
+                self.current_tab = Tab::Device;
+                Command::batch(vec![
+                    self.device_tab.update(device::Message::RequestPermissions).map(Message::DeviceTabMessage),
+                    self.device_tab.update(device::Message::ShowFirmware).map(Message::DeviceTabMessage),
+                ])
+            }
             Message::UpdateTabMessage(msg) => {
                 logger::Logger::log_tab_action("Update", &format!("{:?}", msg));
                 self.update_tab.update(msg).map(Message::UpdateTabMessage)
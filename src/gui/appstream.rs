@@ -0,0 +1,487 @@
+use iced::widget::{column, container, image, row, text, Space};
+use iced::{Element, Length};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+use crate::gui::flatpak_remotes::{run_flatpak, Installation};
+
+// Main categories from the freedesktop menu spec, with the labels shown in the UI
+pub const CATEGORIES: &[(&str, &str)] = &[
+    ("AudioVideo", "Audio & Video"),
+    ("Development", "Developer Tools"),
+    ("Education", "Education"),
+    ("Game", "Games"),
+    ("Graphics", "Graphics & Photography"),
+    ("Network", "Networking"),
+    ("Office", "Productivity"),
+    ("Science", "Science"),
+    ("Settings", "Settings"),
+    ("System", "System"),
+    ("Utility", "Utilities"),
+];
+
+const OARS_LEVELS: &[&str] = &["none", "mild", "moderate", "intense"];
+
+pub fn category_label(category: &str) -> &str {
+    CATEGORIES.iter().find(|(id, _)| *id == category).map(|(_, label)| *label).unwrap_or(category)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AppSource {
+    Flatpak { remote: String },
+    Rpm { package: String },
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ContentRating {
+    // (attribute, level), e.g. ("violence-cartoon", "mild"); attributes rated "none" are omitted
+    pub attributes: Vec<(String, String)>,
+}
+
+impl ContentRating {
+    pub fn level(&self) -> &'static str {
+        self.attributes
+            .iter()
+            .filter_map(|(_, value)| OARS_LEVELS.iter().position(|l| l == value))
+            .max()
+            .map(|i| OARS_LEVELS[i])
+            .unwrap_or("none")
+    }
+
+    pub fn summary(&self) -> String {
+        if self.attributes.is_empty() {
+            return "Suitable for all ages".to_string();
+        }
+        let details: Vec<String> = self.attributes.iter().map(|(id, value)| format!("{} ({})", id, value)).collect();
+        format!("{}: {}", self.level(), details.join(", "))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AppComponent {
+    pub id: String,
+    pub name: String,
+    pub summary: String,
+    pub description: String,
+    pub developer: String,
+    pub license: String,
+    pub categories: Vec<String>,
+    pub icon: Option<PathBuf>,
+    pub screenshots: Vec<String>,
+    pub content_rating: Option<ContentRating>,
+    pub verified: bool,
+    pub source: AppSource,
+}
+
+impl AppComponent {
+    pub fn is_flatpak(&self) -> bool {
+        matches!(self.source, AppSource::Flatpak { .. })
+    }
+
+    pub fn package(&self) -> Option<&str> {
+        match &self.source {
+            AppSource::Rpm { package } => Some(package),
+            AppSource::Flatpak { .. } => None,
+        }
+    }
+
+    pub fn remote(&self) -> Option<&str> {
+        match &self.source {
+            AppSource::Flatpak { remote } => Some(remote),
+            AppSource::Rpm { .. } => None,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Catalog {
+    pub apps: Vec<AppComponent>,
+}
+
+// Messages carrying the catalog are logged with {:?}; printing every app would flood the log
+impl std::fmt::Debug for Catalog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Catalog({} apps)", self.apps.len())
+    }
+}
+
+impl Catalog {
+    pub fn categories(&self, flatpak: bool) -> Vec<String> {
+        CATEGORIES
+            .iter()
+            .filter(|(id, _)| self.apps.iter().any(|a| a.is_flatpak() == flatpak && a.categories.iter().any(|c| c == id)))
+            .map(|(id, _)| id.to_string())
+            .collect()
+    }
+
+    pub fn browse(&self, flatpak: bool, category: Option<&str>) -> Vec<&AppComponent> {
+        self.apps
+            .iter()
+            .filter(|a| a.is_flatpak() == flatpak)
+            .filter(|a| category.is_none_or(|c| a.categories.iter().any(|ac| ac == c)))
+            .collect()
+    }
+
+    pub fn find_flatpak(&self, app_id: &str) -> Option<&AppComponent> {
+        self.apps.iter().find(|a| a.is_flatpak() && a.id == app_id)
+    }
+
+    // Component IDs from appstream: links; the Flatpak wins when both exist
+    pub fn find(&self, id: &str) -> Option<&AppComponent> {
+        self.find_flatpak(id).or_else(|| self.apps.iter().find(|a| a.id == id))
+    }
+
+    pub fn find_package(&self, package: &str) -> Option<&AppComponent> {
+        self.apps.iter().find(|a| a.package() == Some(package))
+    }
+}
+
+fn is_translation(node: &roxmltree::Node) -> bool {
+    node.attributes().any(|a| a.name() == "lang")
+}
+
+fn child<'a>(node: &roxmltree::Node<'a, 'a>, name: &str) -> Option<roxmltree::Node<'a, 'a>> {
+    node.children().find(|c| c.has_tag_name(name) && !is_translation(c))
+}
+
+fn child_text(node: &roxmltree::Node, name: &str) -> String {
+    child(node, name).and_then(|c| c.text()).map(|t| t.trim().to_string()).unwrap_or_default()
+}
+
+// <description> holds <p>, <ul>/<ol> and <li> markup
+fn description_text(node: &roxmltree::Node) -> String {
+    let mut paragraphs = Vec::new();
+    for element in node.children().filter(|c| c.is_element() && !is_translation(c)) {
+        match element.tag_name().name() {
+            "p" => paragraphs.push(element.descendants().filter_map(|d| d.text()).collect::<String>().split_whitespace().collect::<Vec<_>>().join(" ")),
+            "ul" | "ol" => paragraphs.push(
+                element
+                    .children()
+                    .filter(|li| li.has_tag_name("li") && !is_translation(li))
+                    .map(|li| format!("• {}", li.descendants().filter_map(|d| d.text()).collect::<String>().trim()))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+            _ => {}
+        }
+    }
+    paragraphs.join("\n\n")
+}
+
+// Cached icons live next to the catalog, in <size>/<name> directories
+fn cached_icon(component: &roxmltree::Node, icon_dirs: &[PathBuf]) -> Option<PathBuf> {
+    let mut icons: Vec<(u32, String)> = component
+        .children()
+        .filter(|c| c.has_tag_name("icon") && c.attribute("type") == Some("cached"))
+        .filter_map(|c| Some((c.attribute("width").and_then(|w| w.parse().ok()).unwrap_or(64), c.text()?.trim().to_string())))
+        .collect();
+    icons.sort_by_key(|(width, _)| std::cmp::Reverse(*width));
+    icons.iter().find_map(|(width, name)| {
+        icon_dirs.iter().find_map(|dir| {
+            [format!("{}x{}", width, width), "128x128".to_string(), "64x64".to_string()]
+                .iter()
+                .map(|size| dir.join(size).join(name))
+                .find(|path| path.exists())
+        })
+    })
+}
+
+// `remote` is set for flatpak catalogs; RPM catalogs name the package in <pkgname>
+fn parse_component(component: &roxmltree::Node, remote: Option<&str>, icon_dirs: &[PathBuf]) -> Option<AppComponent> {
+    let kind = component.attribute("type").unwrap_or("");
+    if kind != "desktop-application" && kind != "desktop" {
+        return None;
+    }
+    let bundle_id = component
+        .children()
+        .find(|c| c.has_tag_name("bundle") && c.attribute("type") == Some("flatpak"))
+        .and_then(|b| b.text())
+        .and_then(|r| r.split('/').nth(1).map(str::to_string));
+    let id = bundle_id.unwrap_or_else(|| child_text(component, "id").trim_end_matches(".desktop").to_string());
+    let name = child_text(component, "name");
+    if id.is_empty() || name.is_empty() {
+        return None;
+    }
+
+    let developer = child(component, "developer")
+        .map(|d| child_text(&d, "name"))
+        .filter(|d| !d.is_empty())
+        .unwrap_or_else(|| child_text(component, "developer_name"));
+    let categories = child(component, "categories")
+        .map(|c| c.children().filter(|c| c.has_tag_name("category")).filter_map(|c| c.text()).map(|t| t.trim().to_string()).collect())
+        .unwrap_or_default();
+
+    // Prefer a mid-sized thumbnail over the full-size source image
+    let screenshots = child(component, "screenshots")
+        .map(|s| {
+            s.children()
+                .filter(|s| s.has_tag_name("screenshot"))
+                .filter_map(|shot| {
+                    let images: Vec<roxmltree::Node> = shot.children().filter(|i| i.has_tag_name("image")).collect();
+                    let thumbnail = images
+                        .iter()
+                        .filter(|i| i.attribute("type") == Some("thumbnail"))
+                        .filter(|i| i.attribute("width").and_then(|w| w.parse::<u32>().ok()).is_some_and(|w| (400..=800).contains(&w)))
+                        .chain(images.iter().filter(|i| i.attribute("type") != Some("thumbnail")))
+                        .find_map(|i| i.text());
+                    thumbnail.map(|t| t.trim().to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let content_rating = child(component, "content_rating").map(|rating| ContentRating {
+        attributes: rating
+            .children()
+            .filter(|a| a.has_tag_name("content_attribute"))
+            .filter_map(|a| Some((a.attribute("id")?.to_string(), a.text()?.trim().to_string())))
+            .filter(|(_, value)| value != "none")
+            .collect(),
+    });
+
+    // Flathub marks verified apps in <custom> (older catalogs use <metadata>)
+    let verified = component
+        .descendants()
+        .filter(|v| v.has_tag_name("value"))
+        .any(|v| v.attribute("key") == Some("flathub::verification::verified") && v.text().map(str::trim) == Some("true"));
+
+    let source = match remote {
+        Some(remote) => AppSource::Flatpak { remote: remote.to_string() },
+        None => AppSource::Rpm { package: Some(child_text(component, "pkgname")).filter(|p| !p.is_empty())? },
+    };
+
+    Some(AppComponent {
+        summary: child_text(component, "summary"),
+        description: child(component, "description").map(|d| description_text(&d)).unwrap_or_default(),
+        license: child_text(component, "project_license"),
+        icon: cached_icon(component, icon_dirs),
+        id,
+        name,
+        developer,
+        categories,
+        screenshots,
+        content_rating,
+        verified,
+        source,
+    })
+}
+
+fn read_catalog_file(path: &Path) -> Option<String> {
+    let bytes = std::fs::read(path).ok()?;
+    if path.extension().is_some_and(|e| e == "gz") {
+        let mut xml = String::new();
+        flate2::read::GzDecoder::new(bytes.as_slice()).read_to_string(&mut xml).ok()?;
+        Some(xml)
+    } else {
+        String::from_utf8(bytes).ok()
+    }
+}
+
+fn parse_file(path: &Path, remote: Option<&str>, icon_dirs: &[PathBuf], apps: &mut Vec<AppComponent>) {
+    let Some(xml) = read_catalog_file(path) else {
+        return;
+    };
+    let document = match roxmltree::Document::parse_with_options(&xml, roxmltree::ParsingOptions { allow_dtd: true, ..Default::default() }) {
+        Ok(document) => document,
+        Err(e) => {
+            crate::logger::Logger::log_debug(&format!("[AppStream] Failed to parse {}: {}", path.display(), e));
+            return;
+        }
+    };
+    let root = document.root_element();
+    // RPM catalogs keep icons per origin, e.g. /usr/share/swcatalog/icons/fedora/128x128
+    let icon_dirs: Vec<PathBuf> = match root.attribute("origin") {
+        Some(origin) => icon_dirs.iter().map(|d| d.join(origin)).collect(),
+        None => icon_dirs.to_vec(),
+    };
+    let before = apps.len();
+    for component in root.children().filter(|c| c.has_tag_name("component")) {
+        if let Some(app) = parse_component(&component, remote, &icon_dirs) {
+            if !apps.iter().any(|a| a.id == app.id && a.source == app.source) {
+                apps.push(app);
+            }
+        }
+    }
+    crate::logger::Logger::log_debug(&format!("[AppStream] {} apps from {}", apps.len() - before, path.display()));
+}
+
+fn flatpak_appstream_roots() -> Vec<PathBuf> {
+    let mut roots = vec![PathBuf::from("/var/lib/flatpak/appstream")];
+    if let Ok(home) = std::env::var("HOME") {
+        roots.push(PathBuf::from(home).join(".local/share/flatpak/appstream"));
+    }
+    roots
+}
+
+// Reads only what is already on disk: flatpak's per-remote appstream checkouts and the
+// catalogs installed by the appstream-data package
+fn load_catalog() -> Catalog {
+    let mut apps = Vec::new();
+
+    for root in flatpak_appstream_roots() {
+        let Ok(remotes) = std::fs::read_dir(&root) else {
+            continue;
+        };
+        for remote in remotes.flatten() {
+            let remote_name = remote.file_name().to_string_lossy().to_string();
+            let dir = remote.path().join(std::env::consts::ARCH).join("active");
+            let file = [dir.join("appstream.xml.gz"), dir.join("appstream.xml")].into_iter().find(|p| p.exists());
+            if let Some(file) = file {
+                parse_file(&file, Some(&remote_name), &[dir.join("icons")], &mut apps);
+            }
+        }
+    }
+
+    for (xml_dir, icon_dir) in [("/usr/share/swcatalog/xml", "/usr/share/swcatalog/icons"), ("/usr/share/app-info/xmls", "/usr/share/app-info/icons")] {
+        let Ok(files) = std::fs::read_dir(xml_dir) else {
+            continue;
+        };
+        for file in files.flatten().map(|f| f.path()) {
+            if file.to_string_lossy().ends_with(".xml.gz") || file.extension().is_some_and(|e| e == "xml") {
+                parse_file(&file, None, &[PathBuf::from(icon_dir)], &mut apps);
+            }
+        }
+    }
+
+    apps.sort_by_cached_key(|a| a.name.to_lowercase());
+    Catalog { apps }
+}
+
+// At most one `flatpak update --appstream` per installation in this window
+const APPSTREAM_REFRESH_SECS: u64 = 6 * 60 * 60;
+
+fn appstream_refresh_stamp(installation: Installation) -> PathBuf {
+    PathBuf::from(std::env::var("HOME").unwrap_or_default())
+        .join(format!(".rustora/cache/appstream_refreshed_{}", installation.label()))
+}
+
+// Keeps the on-disk catalog current; called from the update check, and picked up by the
+// catalog the next time Rustora starts. Failures (e.g. offline) are retried on the next check
+pub async fn refresh_flatpak_appstream() {
+    for installation in [Installation::System, Installation::User] {
+        let stamp = appstream_refresh_stamp(installation);
+        let fresh = std::fs::metadata(&stamp)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age.as_secs() < APPSTREAM_REFRESH_SECS);
+        if fresh {
+            continue;
+        }
+        match run_flatpak(&["update", "--appstream", installation.flag(), "-y", "--noninteractive"]).await {
+            Ok(_) => {
+                if let Some(dir) = stamp.parent() {
+                    let _ = std::fs::create_dir_all(dir);
+                }
+                let _ = std::fs::write(&stamp, b"");
+            }
+            Err(e) => crate::logger::Logger::log_debug(&format!("[AppStream] {} refresh failed: {}", installation.label(), e)),
+        }
+    }
+}
+
+static CATALOG: OnceLock<Arc<Catalog>> = OnceLock::new();
+
+// Parsed once per process; the Search and Flatpak tabs share it
+pub async fn catalog() -> Arc<Catalog> {
+    if let Some(catalog) = CATALOG.get() {
+        return catalog.clone();
+    }
+    let catalog = tokio::task::spawn_blocking(|| Arc::new(load_catalog())).await.unwrap_or_default();
+    CATALOG.get_or_init(|| catalog).clone()
+}
+
+fn screenshot_cache_dir() -> PathBuf {
+    PathBuf::from(std::env::var("HOME").unwrap_or_default()).join(".rustora/cache/screenshots")
+}
+
+// Screenshots are only URLs in the catalog; once fetched they are kept so they show offline too
+pub async fn fetch_screenshot(url: String) -> Result<PathBuf, String> {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    url.hash(&mut hasher);
+    let extension = Path::new(&url).extension().and_then(|e| e.to_str()).unwrap_or("png").to_string();
+    let path = screenshot_cache_dir().join(format!("{:016x}.{}", hasher.finish(), extension));
+    if path.exists() {
+        return Ok(path);
+    }
+
+    let response = reqwest::get(&url).await.map_err(|e| format!("Screenshot unavailable offline: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to download screenshot: HTTP {}", response.status()));
+    }
+    let bytes = response.bytes().await.map_err(|e| format!("Failed to download screenshot: {}", e))?;
+    tokio::fs::create_dir_all(screenshot_cache_dir()).await.map_err(|e| format!("Failed to create screenshot cache: {}", e))?;
+    tokio::fs::write(&path, &bytes).await.map_err(|e| format!("Failed to cache screenshot: {}", e))?;
+    Ok(path)
+}
+
+pub fn icon<'a, M: 'a>(app: Option<&AppComponent>, size: f32) -> Element<'a, M> {
+    match app.and_then(|a| a.icon.clone()) {
+        Some(path) => image(image::Handle::from_path(path))
+            .width(Length::Fixed(size))
+            .height(Length::Fixed(size))
+            .content_fit(iced::ContentFit::Contain)
+            .into(),
+        None => Space::new(Length::Fixed(size), Length::Fixed(size)).into(),
+    }
+}
+
+// Read-only catalog details shared by the Search and Flatpak panels
+pub fn details<'a, M: 'a>(
+    app: &'a AppComponent,
+    screenshot: Option<&Path>,
+    body_size: f32,
+    accent: iced::Color,
+    muted: iced::Color,
+) -> Element<'a, M> {
+    let mut header_text = column![text(&app.name).size(body_size * 1.3).style(iced::theme::Text::Color(accent))].spacing(2);
+    if !app.developer.is_empty() {
+        header_text = header_text.push(text(format!("by {}", app.developer)).size(body_size * 0.9).style(iced::theme::Text::Color(muted)));
+    }
+    if app.verified {
+        header_text = header_text.push(text("✓ Verified developer").size(body_size * 0.9).style(iced::theme::Text::Color(accent)));
+    }
+
+    let mut content = column![row![icon(Some(app), 64.0), header_text].spacing(12).align_items(iced::Alignment::Center)].spacing(10);
+    if !app.summary.is_empty() {
+        content = content.push(text(&app.summary).size(body_size));
+    }
+    if let Some(path) = screenshot {
+        content = content.push(
+            container(image(image::Handle::from_path(path)).width(Length::Fill).content_fit(iced::ContentFit::Contain))
+                .width(Length::Fill)
+                .max_height(320.0),
+        );
+    }
+
+    let field = |label: &'a str, value: String| {
+        row![
+            text(label).size(body_size * 0.9).style(iced::theme::Text::Color(muted)).width(Length::Fixed(110.0)),
+            text(value).size(body_size * 0.9).width(Length::Fill),
+        ]
+        .spacing(8)
+    };
+    if !app.license.is_empty() {
+        content = content.push(field("License", app.license.clone()));
+    }
+    if !app.categories.is_empty() {
+        content = content.push(field(
+            "Categories",
+            app.categories
+                .iter()
+                .filter(|c| CATEGORIES.iter().any(|(id, _)| id == c))
+                .map(|c| category_label(c))
+                .collect::<Vec<_>>()
+                .join(", "),
+        ));
+    }
+    if let Some(ref rating) = app.content_rating {
+        content = content.push(field("Content rating", rating.summary()));
+    }
+    if !app.description.is_empty() {
+        content = content.push(text(&app.description).size(body_size * 0.9));
+    }
+    content.into()
+}
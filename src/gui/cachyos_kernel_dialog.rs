@@ -687,11 +687,15 @@ async fn detect_gpu() -> String {
 }
 
 async fn rebuild_kernel_modules() -> Result<String, String> {
-    execute_command_with_output(
+    let output = execute_command_with_output(
         "akmods",
         &["--force", "--rebuild"],
         "$ pkexec akmods --force --rebuild",
-    ).await
+    ).await?;
+    let report = tokio::task::spawn_blocking(crate::gui::secure_boot::post_rebuild_report)
+        .await
+        .unwrap_or_default();
+    Ok(format!("{}\n{}", output, report))
 }
 
 async fn regenerate_initramfs() -> Result<String, String> {
@@ -0,0 +1,537 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::logger;
+
+pub const CACHE_DIR: &str = "/var/cache/cfhdb";
+const HISTORY_DIR: &str = "/var/cache/cfhdb/history";
+const MAX_SNAPSHOTS: usize = 5;
+const DEFAULT_KEYRING: &str = "/etc/cfhdb/profile-signing.gpg";
+const CHECK_INTERVAL_SECS: u64 = 24 * 60 * 60;
+
+// Where the profile database comes from and how downloads are verified (/etc/cfhdb/profile-config.json)
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProfileUrlConfig {
+    pub pci_json_url: String,
+    pub usb_json_url: String,
+    // Defaults to "<json url>.sha256" when not set
+    #[serde(default)]
+    pub pci_checksum_url: Option<String>,
+    #[serde(default)]
+    pub usb_checksum_url: Option<String>,
+    // Detached GPG signatures, checked with gpgv against `signing_keyring` when set
+    #[serde(default)]
+    pub pci_signature_url: Option<String>,
+    #[serde(default)]
+    pub usb_signature_url: Option<String>,
+    #[serde(default)]
+    pub signing_keyring: Option<String>,
+    // Reject downloads that have no published checksum instead of accepting them unverified.
+    // Off by default because the upstream repository publishes no checksums; unverified
+    // profiles are flagged instead and their scripts only run after an explicit confirmation
+    #[serde(default)]
+    pub require_verification: bool,
+}
+
+impl ProfileUrlConfig {
+    pub fn load() -> Self {
+        if let Ok(json_content) = std::fs::read_to_string("/etc/cfhdb/profile-config.json") {
+            if let Ok(config) = serde_json::from_str::<ProfileUrlConfig>(&json_content) {
+                return config;
+            }
+        }
+
+        ProfileUrlConfig {
+            pci_json_url: "https://raw.githubusercontent.com/Nobara-Project/cfhdb/refs/heads/master/data/profiles/pci.json".to_string(),
+            usb_json_url: "https://raw.githubusercontent.com/Nobara-Project/cfhdb/refs/heads/master/data/profiles/usb.json".to_string(),
+            pci_checksum_url: None,
+            usb_checksum_url: None,
+            pci_signature_url: None,
+            usb_signature_url: None,
+            signing_keyring: None,
+            require_verification: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileSnapshot {
+    pub id: String,
+    pub created: String,
+    pub source: String,
+    pub verified: bool,
+    pub pci_sha256: String,
+    pub usb_sha256: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProfileDownload {
+    pub pci: String,
+    pub usb: String,
+    pub verified: bool,
+}
+
+pub fn cache_path(file_name: &str) -> PathBuf {
+    Path::new(CACHE_DIR).join(file_name)
+}
+
+// Hashes through coreutils' sha256sum, fed over stdin
+pub fn sha256_hex(data: &[u8]) -> Result<String, String> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new("sha256sum")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run sha256sum: {}", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(data).map_err(|e| format!("Failed to hash data: {}", e))?;
+    }
+    let output = child.wait_with_output().map_err(|e| format!("Failed to run sha256sum: {}", e))?;
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .next()
+        .filter(|h| output.status.success() && h.len() == 64)
+        .map(|h| h.to_string())
+        .ok_or_else(|| "sha256sum returned no hash".to_string())
+}
+
+// Accepts a bare hash or sha256sum output ("<hash>  <file>"), picking the line for `file_name`
+fn parse_checksum(content: &str, file_name: &str) -> Option<String> {
+    let mut fallback = None;
+    for line in content.lines() {
+        let mut parts = line.split_whitespace();
+        let hash = match parts.next() {
+            Some(h) if h.len() == 64 && h.chars().all(|c| c.is_ascii_hexdigit()) => h.to_lowercase(),
+            _ => continue,
+        };
+        match parts.next() {
+            Some(name) if name.trim_start_matches('*').ends_with(file_name) => return Some(hash),
+            Some(_) => {}
+            None => fallback = fallback.or(Some(hash)),
+        }
+    }
+    fallback
+}
+
+// Downloads both profile files and verifies them; nothing is written to the cache here
+pub async fn download_profiles(config: &ProfileUrlConfig) -> Result<ProfileDownload, String> {
+    let client = reqwest::Client::new();
+    let (pci, pci_verified) = download_verified(
+        &client,
+        "pci.json",
+        &config.pci_json_url,
+        config.pci_checksum_url.clone().unwrap_or_else(|| format!("{}.sha256", config.pci_json_url)),
+        config.pci_signature_url.as_deref(),
+        config,
+    ).await?;
+    let (usb, usb_verified) = download_verified(
+        &client,
+        "usb.json",
+        &config.usb_json_url,
+        config.usb_checksum_url.clone().unwrap_or_else(|| format!("{}.sha256", config.usb_json_url)),
+        config.usb_signature_url.as_deref(),
+        config,
+    ).await?;
+
+    Ok(ProfileDownload {
+        pci,
+        usb,
+        verified: pci_verified && usb_verified,
+    })
+}
+
+async fn download_verified(
+    client: &reqwest::Client,
+    file_name: &str,
+    url: &str,
+    checksum_url: String,
+    signature_url: Option<&str>,
+    config: &ProfileUrlConfig,
+) -> Result<(String, bool), String> {
+    let response = client.get(url).send().await
+        .map_err(|e| format!("Failed to download {}: {}", file_name, e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to download {}: HTTP {}", file_name, response.status()));
+    }
+    let body = response.bytes().await
+        .map_err(|e| format!("Failed to read {} response: {}", file_name, e))?;
+
+    let mut verified = false;
+
+    match client.get(&checksum_url).send().await {
+        Ok(resp) if resp.status().is_success() => {
+            let checksum_text = resp.text().await
+                .map_err(|e| format!("Failed to read checksum for {}: {}", file_name, e))?;
+            let expected = parse_checksum(&checksum_text, file_name)
+                .ok_or_else(|| format!("Checksum file for {} is malformed", file_name))?;
+            let actual = sha256_hex(&body)?;
+            if expected != actual {
+                return Err(format!(
+                    "Checksum mismatch for {}: expected {}, got {}. The download was discarded.",
+                    file_name, expected, actual
+                ));
+            }
+            verified = true;
+        }
+        Ok(resp) if config.require_verification => {
+            return Err(format!("No checksum published for {} (HTTP {})", file_name, resp.status()));
+        }
+        Err(e) if config.require_verification => {
+            return Err(format!("Failed to download checksum for {}: {}", file_name, e));
+        }
+        _ => {
+            logger::Logger::log_debug(&format!(
+                "[cfhdb cache] WARNING: no checksum published for {}, accepting it unverified (set require_verification to reject)",
+                file_name
+            ));
+        }
+    }
+
+    if let Some(sig_url) = signature_url {
+        let sig = client.get(sig_url).send().await
+            .map_err(|e| format!("Failed to download signature for {}: {}", file_name, e))?;
+        if !sig.status().is_success() {
+            return Err(format!("Failed to download signature for {}: HTTP {}", file_name, sig.status()));
+        }
+        let sig_bytes = sig.bytes().await
+            .map_err(|e| format!("Failed to read signature for {}: {}", file_name, e))?;
+        let keyring = config.signing_keyring.clone().unwrap_or_else(|| DEFAULT_KEYRING.to_string());
+        verify_signature(file_name, &body, &sig_bytes, &keyring).await?;
+        verified = true;
+    }
+
+    let text = String::from_utf8(body.to_vec())
+        .map_err(|e| format!("{} is not valid UTF-8: {}", file_name, e))?;
+    Ok((text, verified))
+}
+
+async fn verify_signature(file_name: &str, data: &[u8], signature: &[u8], keyring: &str) -> Result<(), String> {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let data_path = std::env::temp_dir().join(format!("cfhdb_{}_{}", nanos, file_name));
+    let sig_path = std::env::temp_dir().join(format!("cfhdb_{}_{}.sig", nanos, file_name));
+    std::fs::write(&data_path, data).map_err(|e| format!("Failed to write temp file: {}", e))?;
+    std::fs::write(&sig_path, signature).map_err(|e| format!("Failed to write temp file: {}", e))?;
+
+    let output = tokio::process::Command::new("gpgv")
+        .arg("--keyring")
+        .arg(keyring)
+        .arg(&sig_path)
+        .arg(&data_path)
+        .output()
+        .await;
+
+    let _ = std::fs::remove_file(&data_path);
+    let _ = std::fs::remove_file(&sig_path);
+
+    let output = output.map_err(|e| format!("Failed to run gpgv: {}. Is gnupg2 installed?", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Signature verification failed for {}: {}",
+            file_name,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+// Written by data/cfhdb/update-bundle.sh next to the bundled profiles
+#[derive(Debug, Deserialize)]
+struct BundleManifest {
+    pci_sha256: String,
+    usb_sha256: String,
+}
+
+// Profile bundle installed with Rustora, used to seed an empty cache when the profile
+// server can't be used
+fn bundle_dir() -> Option<PathBuf> {
+    let mut candidates = Vec::new();
+    if let Ok(exe_path) = std::env::current_exe() {
+        if let Some(exe_dir) = exe_path.parent() {
+            candidates.push(exe_dir.join("data").join("cfhdb"));
+            candidates.push(exe_dir.join("..").join("data").join("cfhdb"));
+            candidates.push(exe_dir.join("..").join("share").join("rustora").join("cfhdb"));
+        }
+    }
+    candidates.push(PathBuf::from("/usr/share/rustora/cfhdb"));
+    candidates.into_iter().find(|dir| dir.join("manifest.json").is_file())
+}
+
+// The bundle is refused unless both files hash to its manifest. Upstream publishes no
+// checksums, so bundled profiles still count as unverified
+pub fn load_bundled_profiles() -> Result<ProfileDownload, String> {
+    let dir = bundle_dir().ok_or_else(|| "No offline profile bundle is installed".to_string())?;
+    let manifest: BundleManifest = std::fs::read_to_string(dir.join("manifest.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .ok_or_else(|| format!("{} is malformed", dir.join("manifest.json").display()))?;
+    let read = |file_name: &str, expected: &str| -> Result<String, String> {
+        let path = dir.join(file_name);
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if sha256_hex(content.as_bytes())? != expected.to_lowercase() {
+            return Err(format!("Bundled {} does not match its manifest", file_name));
+        }
+        Ok(content)
+    };
+    Ok(ProfileDownload {
+        pci: read("pci.json", &manifest.pci_sha256)?,
+        usb: read("usb.json", &manifest.usb_sha256)?,
+        verified: false,
+    })
+}
+
+// The last download is recorded in the user's own state rather than read from the cache's
+// mtime, so an unchanged upstream database never needs a root write to stay "fresh"
+fn last_check_path() -> PathBuf {
+    PathBuf::from(std::env::var("HOME").unwrap_or_default()).join(".rustora/cache/cfhdb_last_check")
+}
+
+pub fn checked_recently() -> bool {
+    std::fs::metadata(last_check_path())
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age.as_secs() < CHECK_INTERVAL_SECS)
+}
+
+pub fn mark_checked() {
+    let path = last_check_path();
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    let _ = std::fs::write(&path, b"");
+}
+
+// Writes both files into the live cache and records the set as a new history snapshot.
+// Only what changed is written, so an unchanged download needs no authentication
+pub async fn store_profiles(profiles: &ProfileDownload, source: &str) -> Result<(), String> {
+    let now = chrono::Local::now();
+    let snapshot = ProfileSnapshot {
+        id: now.format("%Y%m%d-%H%M%S").to_string(),
+        created: now.format("%Y-%m-%d %H:%M:%S").to_string(),
+        source: source.to_string(),
+        verified: profiles.verified,
+        pci_sha256: sha256_hex(profiles.pci.as_bytes())?,
+        usb_sha256: sha256_hex(profiles.usb.as_bytes())?,
+    };
+
+    // Identical to the newest snapshot: just refresh the live files
+    let snapshots = list_snapshots();
+    let is_duplicate = snapshots.first()
+        .map(|s| s.pci_sha256 == snapshot.pci_sha256 && s.usb_sha256 == snapshot.usb_sha256)
+        .unwrap_or(false);
+
+    let live_matches = |file_name: &str, hash: &str| {
+        std::fs::read(cache_path(file_name))
+            .ok()
+            .and_then(|data| sha256_hex(&data).ok())
+            .is_some_and(|live| live == hash)
+    };
+    let mut files = Vec::new();
+    if !live_matches("pci.json", &snapshot.pci_sha256) {
+        files.push((cache_path("pci.json"), profiles.pci.clone()));
+    }
+    if !live_matches("usb.json", &snapshot.usb_sha256) {
+        files.push((cache_path("usb.json"), profiles.usb.clone()));
+    }
+    let mut prune = Vec::new();
+    if !is_duplicate {
+        let dir = Path::new(HISTORY_DIR).join(&snapshot.id);
+        let manifest = serde_json::to_string_pretty(&snapshot)
+            .map_err(|e| format!("Failed to serialize snapshot manifest: {}", e))?;
+        files.push((dir.join("pci.json"), profiles.pci.clone()));
+        files.push((dir.join("usb.json"), profiles.usb.clone()));
+        files.push((dir.join("manifest.json"), manifest));
+        // The new snapshot takes one of the slots
+        prune = snapshots
+            .iter()
+            .skip(MAX_SNAPSHOTS - 1)
+            .map(|old| Path::new(HISTORY_DIR).join(&old.id))
+            .collect();
+    }
+    if files.is_empty() {
+        logger::Logger::log_debug(&format!("[cfhdb cache] Profiles from {} are unchanged", source));
+        return Ok(());
+    }
+    write_cache_files(&files, &prune).await?;
+
+    logger::Logger::log_debug(&format!(
+        "[cfhdb cache] Stored profiles from {} (verified: {})",
+        source, profiles.verified
+    ));
+    Ok(())
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+// Profile scripts later run through pkexec, so the cache must stay writable by root only.
+// Everything is staged in the user's temp dir and installed in a single pkexec call
+async fn write_cache_files(files: &[(PathBuf, String)], remove: &[PathBuf]) -> Result<(), String> {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let staging = std::env::temp_dir().join(format!("rustora_cfhdb_{}", nanos));
+    std::fs::create_dir_all(&staging).map_err(|e| format!("Failed to create staging directory: {}", e))?;
+
+    let mut script = format!(
+        "set -e\ninstall -d -o root -g root -m 755 {} {}\n",
+        shell_quote(CACHE_DIR),
+        shell_quote(HISTORY_DIR)
+    );
+    for (index, (path, content)) in files.iter().enumerate() {
+        let staged = staging.join(index.to_string());
+        if let Err(e) = std::fs::write(&staged, content) {
+            let _ = std::fs::remove_dir_all(&staging);
+            return Err(format!("Failed to stage {}: {}", path.display(), e));
+        }
+        script.push_str(&format!(
+            "install -D -o root -g root -m 644 {} {}\n",
+            shell_quote(&staged.to_string_lossy()),
+            shell_quote(&path.to_string_lossy())
+        ));
+    }
+    for dir in remove {
+        script.push_str(&format!("rm -rf -- {}\n", shell_quote(&dir.to_string_lossy())));
+    }
+
+    let mut cmd = tokio::process::Command::new("pkexec");
+    cmd.arg("sh").arg("-c").arg(&script);
+    if let Ok(display) = std::env::var("DISPLAY") {
+        cmd.env("DISPLAY", display);
+    }
+    let output = cmd.output().await;
+    let _ = std::fs::remove_dir_all(&staging);
+    let output = output.map_err(|e| format!("Failed to execute pkexec: {}. Make sure polkit is installed.", e))?;
+    if !output.status.success() {
+        if output.status.code() == Some(126) || output.status.code() == Some(127) {
+            return Err("Authentication cancelled or failed. Please try again.".to_string());
+        }
+        return Err(format!("Failed to cache profiles: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(())
+}
+
+fn is_root_only(path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    std::fs::symlink_metadata(path)
+        .map(|meta| meta.uid() == 0 && meta.mode() & 0o022 == 0 && !meta.file_type().is_symlink())
+        .unwrap_or(false)
+}
+
+// True when the cache and everything in it is owned by root and not group/world writable
+pub fn cache_is_trusted() -> bool {
+    fn walk(dir: &Path) -> bool {
+        is_root_only(dir)
+            && std::fs::read_dir(dir)
+                .map(|entries| {
+                    entries.flatten().all(|entry| {
+                        let path = entry.path();
+                        if path.is_dir() { walk(&path) } else { is_root_only(&path) }
+                    })
+                })
+                .unwrap_or(false)
+    }
+    walk(Path::new(CACHE_DIR)) && Path::new(HISTORY_DIR).is_dir()
+}
+
+// Checked right before a profile script runs as root: the live profiles must sit in a
+// root-only cache and hash to a snapshot manifest that root recorded when storing them
+pub fn verify_live_cache() -> Result<ProfileSnapshot, String> {
+    if !cache_is_trusted() {
+        return Err(format!(
+            "{} can be modified by non-root users. Reopen the Device Drivers tab to repair its permissions, then try again.",
+            CACHE_DIR
+        ));
+    }
+    current_snapshot().ok_or_else(|| {
+        "The cached device profiles do not match any recorded snapshot. Refresh the profiles and try again.".to_string()
+    })
+}
+
+// A profile from the live cache, by bus ("pci" or "usb") and codename
+#[derive(Debug, Clone)]
+pub struct CachedProfile {
+    pub bus: String,
+    pub codename: String,
+}
+
+// Re-reads a profile's install or remove script from the live cache, so the script that
+// runs as root is the one verify_live_cache just checked
+pub fn cached_profile_script(profile: &CachedProfile, is_removal: bool) -> Result<String, String> {
+    let file_name = format!("{}.json", profile.bus);
+    let data = std::fs::read_to_string(cache_path(&file_name))
+        .map_err(|e| format!("Failed to read cached {}: {}", file_name, e))?;
+    let scripts = match profile.bus.as_str() {
+        "pci" => crate::gui::tabs::device::parse_pci_profiles(&data)?
+            .into_iter()
+            .find(|p| p.codename == profile.codename)
+            .map(|p| (p.install_script, p.remove_script)),
+        "usb" => crate::gui::tabs::device::parse_usb_profiles(&data)?
+            .into_iter()
+            .find(|p| p.codename == profile.codename)
+            .map(|p| (p.install_script, p.remove_script)),
+        other => return Err(format!("Unknown profile bus \"{}\"", other)),
+    };
+    let (install_script, remove_script) = scripts
+        .ok_or_else(|| format!("Profile {} is not in the cached {}", profile.codename, file_name))?;
+    if is_removal { remove_script } else { install_script }
+        .ok_or_else(|| format!("Cached profile {} has no {} script", profile.codename, if is_removal { "remove" } else { "install" }))
+}
+
+// Newest first
+pub fn list_snapshots() -> Vec<ProfileSnapshot> {
+    let mut snapshots: Vec<ProfileSnapshot> = std::fs::read_dir(HISTORY_DIR)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| std::fs::read_to_string(entry.path().join("manifest.json")).ok())
+                .filter_map(|content| serde_json::from_str::<ProfileSnapshot>(&content).ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    snapshots.sort_by(|a, b| b.id.cmp(&a.id));
+    snapshots
+}
+
+// Snapshot the live profiles came from, matched by hash
+pub fn current_snapshot() -> Option<ProfileSnapshot> {
+    let pci = std::fs::read(cache_path("pci.json")).ok()?;
+    let usb = std::fs::read(cache_path("usb.json")).ok()?;
+    let (pci_hash, usb_hash) = (sha256_hex(&pci).ok()?, sha256_hex(&usb).ok()?);
+    list_snapshots()
+        .into_iter()
+        .find(|s| s.pci_sha256 == pci_hash && s.usb_sha256 == usb_hash)
+}
+
+pub fn current_snapshot_id() -> Option<String> {
+    current_snapshot().map(|s| s.id)
+}
+
+// Copies a history snapshot back over the live cache after re-checking its hashes
+pub async fn restore_snapshot(id: &str) -> Result<(), String> {
+    let dir = Path::new(HISTORY_DIR).join(id);
+    let snapshot: ProfileSnapshot = std::fs::read_to_string(dir.join("manifest.json"))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .ok_or_else(|| format!("Profile snapshot {} not found", id))?;
+    let pci = std::fs::read_to_string(dir.join("pci.json"))
+        .map_err(|e| format!("Failed to read snapshot pci.json: {}", e))?;
+    let usb = std::fs::read_to_string(dir.join("usb.json"))
+        .map_err(|e| format!("Failed to read snapshot usb.json: {}", e))?;
+
+    if sha256_hex(pci.as_bytes())? != snapshot.pci_sha256 || sha256_hex(usb.as_bytes())? != snapshot.usb_sha256 {
+        return Err(format!("Profile snapshot {} is corrupted (hash mismatch)", id));
+    }
+
+    write_cache_files(&[(cache_path("pci.json"), pci), (cache_path("usb.json"), usb)], &[]).await?;
+    logger::Logger::log_debug(&format!("[cfhdb cache] Rolled back profiles to snapshot {}", id));
+    Ok(())
+}
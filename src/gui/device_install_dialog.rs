@@ -1,4 +1,4 @@
-use iced::widget::{button, column, container, progress_bar, row, scrollable, text, Space};
+use iced::widget::{button, checkbox, column, container, progress_bar, row, scrollable, text, Space};
 use iced::{Alignment, Application, Command, Element, Length, Padding, Border, Theme as IcedTheme, Color};
 use crate::gui::dialog_design::DialogDesign;
 use iced::widget::container::Appearance;
@@ -6,9 +6,15 @@ use iced::widget::button::Appearance as ButtonAppearance;
 use iced::widget::button::StyleSheet as ButtonStyleSheet;
 use iced::window;
 use tokio::process::Command as TokioCommand;
+use crate::gui::script_review::{highlight_line, ScriptSummary, TokenKind};
+use crate::gui::cfhdb_cache::{CachedProfile, ProfileSnapshot};
 
 #[derive(Debug, Clone)]
 pub enum Message {
+    ProfileSnapshotLoaded(Option<ProfileSnapshot>),
+    ConfirmUnverified(bool),
+    Simulate,
+    SimulationComplete(Result<String, String>),
     StartInstallation,
     InstallationProgress(String),
     InstallationComplete,
@@ -25,6 +31,16 @@ pub struct DeviceInstallDialog {
     install_script: String,
     device_info: DeviceInfo,
     is_removal: bool,
+    // Script comes from the cfhdb cache; re-check it against the cache right before running it as root
+    cached_profile: Option<CachedProfile>,
+    // No checksum or signature backs the cached profiles; running needs an explicit confirmation.
+    // Assumed until the cache has been checked
+    profiles_unverified: bool,
+    confirmed_unverified: bool,
+    summary: ScriptSummary,
+    is_reviewing: bool,
+    is_simulating: bool,
+    simulation_output: Option<String>,
     is_running: bool,
     is_complete: bool,
     has_error: bool,
@@ -48,14 +64,22 @@ pub struct DeviceInfo {
 }
 
 impl DeviceInstallDialog {
-    pub fn new(profile_name: String, install_script: String, device_info: DeviceInfo, is_removal: bool) -> Self {
+    pub fn new(profile_name: String, install_script: String, device_info: DeviceInfo, is_removal: bool, cached_profile: Option<CachedProfile>) -> Self {
         let action_text = if is_removal { "Removing" } else { "Installing" };
+        let summary = ScriptSummary::analyze(&install_script);
         Self {
             profile_name: profile_name.clone(),
             install_script,
             device_info,
             is_removal,
-            is_running: true,
+            profiles_unverified: cached_profile.is_some(),
+            cached_profile,
+            confirmed_unverified: false,
+            summary,
+            is_reviewing: true,
+            is_simulating: false,
+            simulation_output: None,
+            is_running: false,
             is_complete: false,
             has_error: false,
             is_post_install: false,
@@ -66,11 +90,11 @@ impl DeviceInstallDialog {
         }
     }
 
-    pub fn run_separate_window(profile_name: String, install_script: String, device_info: DeviceInfo, is_removal: bool) -> Result<(), iced::Error> {
-        let dialog = Self::new(profile_name, install_script, device_info, is_removal);
+    pub fn run_separate_window(profile_name: String, install_script: String, device_info: DeviceInfo, is_removal: bool, cached_profile: Option<CachedProfile>) -> Result<(), iced::Error> {
+        let dialog = Self::new(profile_name, install_script, device_info, is_removal, cached_profile);
 
         let mut window_settings = iced::window::Settings::default();
-        window_settings.size = iced::Size::new(700.0, 550.0);
+        window_settings.size = iced::Size::new(820.0, 680.0);
         window_settings.min_size = Some(iced::Size::new(500.0, 400.0));
         window_settings.resizable = true;
         window_settings.decorations = true;
@@ -96,10 +120,20 @@ impl Application for DeviceInstallDialog {
     type Flags = Self;
 
     fn new(flags: Self) -> (Self, Command<Message>) {
-        let mut dialog = flags;
-
-        let cmd = dialog.update(Message::StartInstallation);
-        (dialog, cmd)
+        // Nothing runs until the script has been reviewed and approved
+        let command = if flags.cached_profile.is_some() {
+            Command::perform(
+                async {
+                    tokio::task::spawn_blocking(crate::gui::cfhdb_cache::current_snapshot)
+                        .await
+                        .unwrap_or_default()
+                },
+                Message::ProfileSnapshotLoaded,
+            )
+        } else {
+            Command::none()
+        };
+        (flags, command)
     }
 
     fn title(&self) -> String {
@@ -109,14 +143,51 @@ impl Application for DeviceInstallDialog {
 
     fn update(&mut self, message: Message) -> Command<Message> {
         match message {
+            Message::ProfileSnapshotLoaded(snapshot) => {
+                self.profiles_unverified = !snapshot.is_some_and(|s| s.verified);
+                Command::none()
+            }
+            Message::ConfirmUnverified(confirmed) => {
+                self.confirmed_unverified = confirmed;
+                Command::none()
+            }
+            Message::Simulate => {
+                if !self.may_run() {
+                    return Command::none();
+                }
+                let simulation = match self.summary.simulation_script() {
+                    Some(script) => script,
+                    None => return Command::none(),
+                };
+                self.is_simulating = true;
+                self.simulation_output = None;
+                iced::Command::perform(
+                    execute_simulation(simulation, self.install_script.clone(), self.cached_profile.clone(), self.confirmed_unverified),
+                    Message::SimulationComplete,
+                )
+            }
+            Message::SimulationComplete(result) => {
+                self.is_simulating = false;
+                self.simulation_output = Some(match result {
+                    Ok(output) => output,
+                    Err(e) => format!("Simulation failed: {}", e),
+                });
+                Command::none()
+            }
             Message::StartInstallation => {
+                if !self.may_run() {
+                    return Command::none();
+                }
+                self.is_reviewing = false;
                 self.is_running = true;
                 self.terminal_output.clear();
                 let script = self.install_script.clone();
                 let is_removal = self.is_removal;
+                let cached_profile = self.cached_profile.clone();
+                let allow_unverified = self.confirmed_unverified;
 
                 iced::Command::perform(
-                    execute_install_script(script, is_removal),
+                    execute_install_script(script, is_removal, cached_profile, allow_unverified),
                     |result| match result {
                         Ok(output) => Message::InstallationProgress(output),
                         Err(e) => Message::InstallationError(e),
@@ -218,6 +289,10 @@ impl Application for DeviceInstallDialog {
 }
 
 impl DeviceInstallDialog {
+    fn may_run(&self) -> bool {
+        !self.profiles_unverified || self.confirmed_unverified
+    }
+
     fn view_impl(&self, theme: &crate::gui::Theme) -> Element<'_, Message> {
         let settings = crate::gui::settings::AppSettings::load();
         let title_size = (settings.font_size_titles * settings.scale_titles).round();
@@ -321,7 +396,9 @@ impl DeviceInstallDialog {
         .style(iced::theme::Container::Custom(Box::new(CleanContainerStyle)))
         .width(Length::Fill);
 
-        let content = if self.is_running || self.is_post_install {
+        let content = if self.is_reviewing {
+            self.view_review(theme, device_info_section, title_size, body_size, button_size)
+        } else if self.is_running || self.is_post_install {
             // Show running state with terminal output
             let action = if self.is_removal { "Removing" } else { "Installing" };
             let title_text: String = if self.is_post_install {
@@ -577,16 +654,274 @@ impl DeviceInstallDialog {
             .padding(Padding::new(0.0))
             .into()
     }
+
+    // Script, static summary and optional dnf simulation, shown before the admin approves
+    fn view_review<'a>(&'a self, theme: &crate::gui::Theme, device_info_section: iced::widget::Container<'a, Message>, title_size: f32, body_size: f32, button_size: f32) -> iced::widget::Container<'a, Message> {
+        let action = if self.is_removal { "Removal" } else { "Installation" };
+
+        let summary_row = |label: &str, items: &[String]| -> Element<'a, Message> {
+            let value = if items.is_empty() { "—".to_string() } else { items.join("\n") };
+            row![
+                text(label.to_string()).size(body_size).width(Length::Fixed(150.0))
+                    .style(iced::theme::Text::Color(theme.secondary_text())),
+                text(value).size(body_size).font(iced::Font::MONOSPACE),
+            ]
+            .spacing(DialogDesign::SPACE_SMALL)
+            .width(Length::Fill)
+            .into()
+        };
+
+        let summary_section = container(
+            column![
+                text("What this script will do")
+                    .size(body_size * 1.1)
+                    .style(iced::theme::Text::Color(theme.primary())),
+                Space::with_height(DialogDesign::space_small()),
+                summary_row("Repositories enabled:", &self.summary.repos_enabled),
+                summary_row("Packages installed:", &self.summary.packages_installed),
+                summary_row("Packages removed:", &self.summary.packages_removed),
+                summary_row("Files written:", &self.summary.files_written),
+                summary_row("Services touched:", &self.summary.services),
+                if self.summary.is_empty() {
+                    text("No known actions were recognised. Read the script below carefully.")
+                        .size(body_size * 0.9)
+                        .style(iced::theme::Text::Color(theme.danger()))
+                } else {
+                    text("Extracted by reading the script, not by running it. Review the full script below.")
+                        .size(body_size * 0.9)
+                        .style(iced::theme::Text::Color(theme.secondary_text()))
+                },
+            ]
+            .spacing(DialogDesign::SPACE_TINY)
+            .padding(DialogDesign::pad_medium())
+        )
+        .style(iced::theme::Container::Custom(Box::new(CleanContainerStyle)))
+        .width(Length::Fill);
+
+        let script_lines: Vec<Element<Message>> = self.install_script.lines()
+            .enumerate()
+            .map(|(idx, line)| {
+                let mut spans = row![
+                    text(format!("{:>4}  ", idx + 1))
+                        .font(iced::Font::MONOSPACE)
+                        .size(body_size * 0.85)
+                        .style(iced::theme::Text::Color(theme.secondary_text())),
+                ];
+                for (span, kind) in highlight_line(line) {
+                    let color = match kind {
+                        TokenKind::Plain => theme.text(),
+                        TokenKind::Keyword => Color::from_rgb(0.78, 0.47, 0.87),
+                        TokenKind::Command => theme.primary(),
+                        TokenKind::String => Color::from_rgb(0.55, 0.78, 0.45),
+                        TokenKind::Variable => Color::from_rgb(0.93, 0.66, 0.33),
+                        TokenKind::Comment => theme.secondary_text(),
+                    };
+                    spans = spans.push(
+                        text(span)
+                            .font(iced::Font::MONOSPACE)
+                            .size(body_size * 0.85)
+                            .style(iced::theme::Text::Color(color))
+                    );
+                }
+                spans.into()
+            })
+            .collect();
+
+        let script_section = container(
+            column![
+                text(format!("{} Script", action))
+                    .size(body_size * 1.1)
+                    .style(iced::theme::Text::Color(theme.primary())),
+                Space::with_height(DialogDesign::space_small()),
+                container(column(script_lines).spacing(0))
+                    .padding(Padding::new(12.0))
+                    .width(Length::Fill),
+            ]
+            .spacing(0)
+            .padding(DialogDesign::pad_medium())
+        )
+        .style(iced::theme::Container::Custom(Box::new(CleanContainerStyle)))
+        .width(Length::Fill);
+
+        let mut body = column![
+            text(&self.profile_name)
+                .size(body_size * 0.95)
+                .style(iced::theme::Text::Color(theme.secondary_text())),
+        ]
+        .spacing(DialogDesign::SPACE_MEDIUM);
+        if self.profiles_unverified {
+            body = body.push(
+                container(
+                    column![
+                        text("Unverified profile")
+                            .size(body_size * 1.1)
+                            .style(iced::theme::Text::Color(theme.danger())),
+                        text("No published checksum or signature backs the cached device profiles, so this script cannot be traced to the profile server. It runs as root; only continue if you trust it.")
+                            .size(body_size * 0.9)
+                            .style(iced::theme::Text::Color(theme.danger())),
+                        checkbox("I have read the script and want to run this unverified profile", self.confirmed_unverified)
+                            .on_toggle(Message::ConfirmUnverified)
+                            .text_size(body_size * 0.9),
+                    ]
+                    .spacing(DialogDesign::SPACE_SMALL)
+                    .padding(DialogDesign::pad_medium())
+                )
+                .style(iced::theme::Container::Custom(Box::new(CleanContainerStyle)))
+                .width(Length::Fill)
+            );
+        }
+        body = body.push(device_info_section).push(summary_section);
+
+        if self.is_simulating || self.simulation_output.is_some() {
+            let output = if self.is_simulating {
+                "Resolving dnf transactions with --assumeno...".to_string()
+            } else {
+                self.simulation_output.clone().unwrap_or_default()
+            };
+            body = body.push(
+                container(
+                    column![
+                        text("Simulation (dnf --assumeno, nothing was changed)")
+                            .size(body_size * 1.1)
+                            .style(iced::theme::Text::Color(theme.primary())),
+                        Space::with_height(DialogDesign::space_small()),
+                        text(output)
+                            .font(iced::Font::MONOSPACE)
+                            .size(body_size * 0.85)
+                            .shaping(iced::widget::text::Shaping::Advanced),
+                    ]
+                    .spacing(0)
+                    .padding(DialogDesign::pad_medium())
+                )
+                .style(iced::theme::Container::Custom(Box::new(CleanContainerStyle)))
+                .width(Length::Fill)
+            );
+        }
+        body = body.push(script_section);
+
+        let can_simulate = !self.summary.dnf_steps.is_empty() && !self.is_simulating && self.may_run();
+        let buttons = row![
+            button(text("Cancel").size(button_size))
+                .on_press(Message::Close)
+                .style(iced::theme::Button::Custom(Box::new(CleanButtonStyle { is_primary: false })))
+                .padding(Padding::new(12.0)),
+            Space::with_width(Length::Fill),
+            button(text(if self.is_simulating { "Simulating..." } else { "Simulate" }).size(button_size))
+                .on_press_maybe(if can_simulate { Some(Message::Simulate) } else { None })
+                .style(iced::theme::Button::Custom(Box::new(CleanButtonStyle { is_primary: false })))
+                .padding(Padding::new(12.0)),
+            button(text(if self.is_removal { "Approve and Remove" } else { "Approve and Install" }).size(button_size))
+                .on_press_maybe(if self.is_simulating || !self.may_run() { None } else { Some(Message::StartInstallation) })
+                .style(iced::theme::Button::Custom(Box::new(CleanButtonStyle { is_primary: true })))
+                .padding(Padding::new(12.0)),
+        ]
+        .spacing(DialogDesign::SPACE_SMALL)
+        .align_items(Alignment::Center);
+
+        container(
+            column![
+                container(
+                    text(format!("Review Driver {}", action))
+                        .size(title_size)
+                        .style(iced::theme::Text::Color(if self.is_removal { theme.danger() } else { theme.primary() }))
+                )
+                .width(Length::Fill)
+                .padding(DialogDesign::pad_medium()),
+                container(Space::with_height(Length::Fixed(1.0)))
+                    .width(Length::Fill)
+                    .style(iced::theme::Container::Custom(Box::new(DividerStyle))),
+                scrollable(body.padding(DialogDesign::pad_medium()))
+                    .height(Length::Fill),
+                container(buttons)
+                    .width(Length::Fill)
+                    .padding(DialogDesign::pad_medium()),
+            ]
+            .spacing(0)
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(iced::theme::Container::Custom(Box::new(WindowContainerStyle {
+            background: theme.background(),
+        })))
+    }
 }
 
 // Execute install/remove script with streaming output
-async fn execute_install_script(script: String, is_removal: bool) -> Result<String, String> {
+async fn execute_install_script(script: String, is_removal: bool, cached_profile: Option<CachedProfile>, allow_unverified: bool) -> Result<String, String> {
+    if let Some(profile) = cached_profile {
+        verify_profile_script(profile, script.clone(), is_removal, allow_unverified).await?;
+    }
+    let file_prefix = if is_removal { "rustora_remove" } else { "rustora_install" };
+    let (success, exit_code, mut output) = run_root_script(&script, file_prefix).await?;
+
+    if success {
+        if output.is_empty() {
+            let success_msg = if is_removal {
+                "Removal completed successfully."
+            } else {
+                "Installation completed successfully."
+            };
+            output = success_msg.to_string();
+        }
+        Ok(output)
+    } else {
+        let error_msg = if is_removal {
+            format!("Removal failed with exit code {}. Output:\n{}", exit_code, output)
+        } else {
+            format!("Installation failed with exit code {}. Output:\n{}", exit_code, output)
+        };
+        Err(error_msg)
+    }
+}
+
+// Replay the script's dnf steps with --assumeno; dnf still needs root to resolve against the system rpmdb
+async fn execute_simulation(script: String, install_script: String, cached_profile: Option<CachedProfile>, allow_unverified: bool) -> Result<String, String> {
+    if let Some(profile) = cached_profile {
+        verify_profile_script(profile, install_script, false, allow_unverified).await?;
+    }
+    let (success, exit_code, output) = run_root_script(&script, "rustora_simulate").await?;
+    if !success && (exit_code == 126 || exit_code == 127) {
+        return Err("Authentication cancelled or failed.".to_string());
+    }
+    if output.is_empty() {
+        return Ok("The simulation produced no output.".to_string());
+    }
+    Ok(output)
+}
+
+// The cache is root-only and its files must hash to a snapshot manifest written by root; the
+// script this dialog was handed must then be byte for byte the one in that verified cache.
+// Unverified profiles only run after the user confirmed them
+async fn verify_profile_script(profile: CachedProfile, script: String, is_removal: bool, allow_unverified: bool) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        let snapshot = crate::gui::cfhdb_cache::verify_live_cache()?;
+        if !snapshot.verified && !allow_unverified {
+            return Err("The cached device profiles are unverified. Confirm the unverified profile in the review screen to run it.".to_string());
+        }
+        let cached = crate::gui::cfhdb_cache::cached_profile_script(&profile, is_removal)?;
+        if cached != script {
+            return Err(format!(
+                "The script for {} does not match the verified profile cache. Nothing was run; refresh the profiles and try again.",
+                profile.codename
+            ));
+        }
+        crate::logger::Logger::log_debug(&format!(
+            "[Device Install] Script for {} matches profile snapshot {}",
+            profile.codename, snapshot.id
+        ));
+        Ok(())
+    })
+    .await
+    .map_err(|e| format!("Profile cache check failed: {}", e))?
+}
+
+// Runs a script through pkexec bash, returning (success, exit code, combined output)
+async fn run_root_script(script: &str, file_prefix: &str) -> Result<(bool, i32, String), String> {
     use tokio::io::{AsyncBufReadExt, BufReader};
 
     // Write script to temporary file
     use std::io::Write;
     let mut temp_file = std::env::temp_dir();
-    let file_prefix = if is_removal { "rustora_remove" } else { "rustora_install" };
     temp_file.push(format!("{}_{}.sh", file_prefix, std::process::id()));
 
     {
@@ -680,25 +1015,7 @@ async fn execute_install_script(script: String, is_removal: bool) -> Result<Stri
     // Clean up temp file
     let _ = std::fs::remove_file(&temp_file);
 
-    if status.success() {
-        if output.is_empty() {
-            let success_msg = if is_removal {
-                "Removal completed successfully."
-            } else {
-                "Installation completed successfully."
-            };
-            output = success_msg.to_string();
-        }
-        Ok(output)
-    } else {
-        let exit_code = status.code().unwrap_or(-1);
-        let error_msg = if is_removal {
-            format!("Removal failed with exit code {}. Output:\n{}", exit_code, output)
-        } else {
-            format!("Installation failed with exit code {}. Output:\n{}", exit_code, output)
-        };
-        Err(error_msg)
-    }
+    Ok((status.success(), status.code().unwrap_or(-1), output))
 }
 
 // Run akmods --force --rebuild for NVIDIA drivers
@@ -771,6 +1088,11 @@ async fn run_nvidia_post_install() -> Result<String, String> {
         if output.is_empty() {
             output = "akmods --force --rebuild completed successfully.".to_string();
         }
+        output.push_str("\n\n");
+        let report = tokio::task::spawn_blocking(crate::gui::secure_boot::post_rebuild_report)
+            .await
+            .unwrap_or_default();
+        output.push_str(&report);
         Ok(output)
     } else {
         let exit_code = status.code().unwrap_or(-1);
@@ -0,0 +1,241 @@
+use serde_json::Value;
+use tokio::process::Command as TokioCommand;
+
+use crate::logger;
+
+// fwupd's FWUPD_UPDATE_STATE_NEEDS_REBOOT
+const UPDATE_STATE_NEEDS_REBOOT: u64 = 3;
+
+#[derive(Debug, Clone)]
+pub struct FirmwareRelease {
+    pub version: String,
+    pub summary: String,
+    pub description: String,
+    pub size: u64,
+    pub is_upgrade: bool,
+    pub is_downgrade: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct FirmwareDevice {
+    pub id: String,
+    pub name: String,
+    pub vendor: String,
+    pub version: String,
+    pub needs_reboot: bool,
+    pub pending_reboot: bool,
+    pub releases: Vec<FirmwareRelease>,
+}
+
+impl FirmwareDevice {
+    // Releases come newest first from fwupd
+    pub fn available_update(&self) -> Option<&FirmwareRelease> {
+        self.releases.iter().find(|r| r.is_upgrade)
+    }
+
+    pub fn downgrades(&self) -> impl Iterator<Item = &FirmwareRelease> {
+        self.releases.iter().filter(|r| r.is_downgrade)
+    }
+}
+
+async fn fwupdmgr_json(args: &[&str]) -> Result<Option<Value>, String> {
+    let output = TokioCommand::new("fwupdmgr")
+        .args(args)
+        .arg("--json")
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run fwupdmgr (is fwupd installed?): {}", e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    match serde_json::from_str::<Value>(stdout.trim()) {
+        Ok(value) => Ok(Some(value)),
+        // Exit code 2 means "nothing to do" (no updates, no releases)
+        Err(_) if output.status.code() == Some(2) => Ok(None),
+        Err(e) => Err(format!(
+            "fwupdmgr {} failed: {}{}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim(),
+            if output.status.success() { format!(" ({})", e) } else { String::new() }
+        )),
+    }
+}
+
+fn flags(value: &Value) -> Vec<String> {
+    value
+        .get("Flags")
+        .and_then(|f| f.as_array())
+        .map(|arr| arr.iter().filter_map(|f| f.as_str().map(String::from)).collect())
+        .unwrap_or_default()
+}
+
+fn string_field(value: &Value, key: &str) -> String {
+    value.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string()
+}
+
+fn parse_release(value: &Value) -> FirmwareRelease {
+    let flags = flags(value);
+    FirmwareRelease {
+        version: string_field(value, "Version"),
+        summary: string_field(value, "Summary"),
+        description: markup_to_text(&string_field(value, "Description")),
+        size: value.get("Size").and_then(|v| v.as_u64()).unwrap_or(0),
+        is_upgrade: flags.iter().any(|f| f == "is-upgrade"),
+        is_downgrade: flags.iter().any(|f| f == "is-downgrade"),
+    }
+}
+
+fn parse_device(value: &Value) -> FirmwareDevice {
+    let flags = flags(value);
+    FirmwareDevice {
+        id: string_field(value, "DeviceId"),
+        name: string_field(value, "Name"),
+        vendor: string_field(value, "Vendor"),
+        version: string_field(value, "Version"),
+        needs_reboot: flags.iter().any(|f| f == "needs-reboot" || f == "needs-shutdown"),
+        pending_reboot: value.get("UpdateState").and_then(|v| v.as_u64()) == Some(UPDATE_STATE_NEEDS_REBOOT),
+        releases: value
+            .get("Releases")
+            .and_then(|r| r.as_array())
+            .map(|arr| arr.iter().map(parse_release).collect())
+            .unwrap_or_default(),
+    }
+}
+
+fn parse_devices(value: &Value) -> Vec<FirmwareDevice> {
+    value
+        .get("Devices")
+        .and_then(|d| d.as_array())
+        .map(|arr| arr.iter().map(parse_device).collect())
+        .unwrap_or_default()
+}
+
+// Release descriptions are AppStream markup (<p>, <ul>, <li>)
+fn markup_to_text(markup: &str) -> String {
+    let mut out = String::new();
+    let mut rest = markup;
+    while let Some(start) = rest.find('<') {
+        out.push_str(&rest[..start]);
+        let end = match rest[start..].find('>') {
+            Some(end) => start + end,
+            None => break,
+        };
+        match rest[start + 1..end].trim_start_matches('/').split_whitespace().next().unwrap_or("") {
+            "li" if !rest[start + 1..end].starts_with('/') => out.push_str("\n• "),
+            "p" | "ul" | "ol" => out.push('\n'),
+            _ => {}
+        }
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    out.replace("&amp;", "&")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Every updatable device together with all releases fwupd knows for it
+pub async fn list_devices() -> Result<Vec<FirmwareDevice>, String> {
+    let devices = match fwupdmgr_json(&["get-devices"]).await? {
+        Some(value) => parse_devices(&value),
+        None => Vec::new(),
+    };
+
+    let mut result = Vec::new();
+    for mut device in devices {
+        if device.id.is_empty() {
+            continue;
+        }
+        if let Ok(Some(value)) = fwupdmgr_json(&["get-releases", &device.id]).await {
+            device.releases = value
+                .get("Releases")
+                .and_then(|r| r.as_array())
+                .map(|arr| arr.iter().map(parse_release).collect())
+                .unwrap_or_default();
+        }
+        result.push(device);
+    }
+    // get-devices lists everything; only keep what fwupd can actually flash
+    result.retain(|d| !d.releases.is_empty() || d.pending_reboot);
+    logger::Logger::log_debug(&format!("[Firmware] {} updatable device(s)", result.len()));
+    Ok(result)
+}
+
+// Devices with an upgrade available, as reported by `fwupdmgr get-updates`
+pub async fn pending_updates() -> Result<Vec<FirmwareDevice>, String> {
+    let devices = match fwupdmgr_json(&["get-updates"]).await? {
+        Some(value) => parse_devices(&value),
+        None => Vec::new(),
+    };
+    Ok(devices.into_iter().filter(|d| d.available_update().is_some()).collect())
+}
+
+async fn run_fwupdmgr(args: &[&str]) -> Result<String, String> {
+    let output = TokioCommand::new("fwupdmgr")
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run fwupdmgr: {}", e))?;
+    let combined = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    if output.status.success() {
+        Ok(combined.trim().to_string())
+    } else {
+        Err(format!(
+            "fwupdmgr {} failed (exit code: {}):\n{}",
+            args.first().unwrap_or(&""),
+            output.status.code().unwrap_or(-1),
+            combined.trim()
+        ))
+    }
+}
+
+pub async fn refresh_metadata() -> Result<String, String> {
+    match run_fwupdmgr(&["refresh", "--force"]).await {
+        Ok(out) => Ok(if out.is_empty() { "Firmware metadata refreshed".to_string() } else { out }),
+        // Exit code 2: metadata already current
+        Err(e) if e.contains("exit code: 2") => Ok("Firmware metadata is already up to date".to_string()),
+        Err(e) => Err(e),
+    }
+}
+
+// fwupd asks polkit for authorization itself. Reboot prompts and report uploads are
+// suppressed; the UI tells the user when a reboot is needed.
+pub async fn install_release(device_id: String, version: String, downgrade: bool) -> Result<String, String> {
+    logger::Logger::log_debug(&format!(
+        "[Firmware] {} {} to {}",
+        if downgrade { "Downgrading" } else { "Updating" },
+        device_id,
+        version
+    ));
+    let mut args = vec![
+        "install",
+        device_id.as_str(),
+        version.as_str(),
+        "--assume-yes",
+        "--no-reboot-check",
+        "--no-unreported-check",
+    ];
+    if downgrade {
+        args.push("--allow-older");
+    }
+    run_fwupdmgr(&args).await
+}
+
+pub fn format_size(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0))
+    } else if bytes >= 1024 {
+        format!("{:.0} KiB", bytes as f64 / 1024.0)
+    } else {
+        format!("{} B", bytes)
+    }
+}
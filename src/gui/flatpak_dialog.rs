@@ -8,12 +8,15 @@ use tokio::process::Command as TokioCommand;
 use std::path::PathBuf;
 use tokio::fs;
 use crate::gui::dialog_design::DialogDesign;
+use crate::gui::flatpak_ref::FlatpakRef;
+use crate::gui::flatpak_remotes::{self, Installation};
 use iced::Color;
 
 #[derive(Debug, Clone)]
 pub enum Message {
     LoadFlatpakInfo,
     FlatpakInfoLoaded(FlatpakInfo),
+    SelectInstallation(Installation),
     InstallFlatpak,
     InstallationProgress(String),
     InstallationComplete,
@@ -39,6 +42,9 @@ pub struct FlatpakInfo {
 pub struct FlatpakDialog {
     pub application_id: String,
     pub remote: Option<String>,
+    pub installation: Installation,
+    // Set when opened from a .flatpakref; flatpak then sets up the remote itself
+    pub flatpak_ref: Option<FlatpakRef>,
     pub flatpak_info: Option<FlatpakInfo>,
     pub is_loading: bool,
     pub is_installing: bool,
@@ -49,10 +55,12 @@ pub struct FlatpakDialog {
 }
 
 impl FlatpakDialog {
-    pub fn new(application_id: String, remote: Option<String>) -> Self {
+    pub fn new(application_id: String, remote: Option<String>, installation: Installation) -> Self {
         Self {
             application_id,
             remote,
+            installation,
+            flatpak_ref: None,
             flatpak_info: None,
             is_loading: true,
             is_installing: false,
@@ -63,9 +71,19 @@ impl FlatpakDialog {
         }
     }
 
-    pub fn run_separate_window(application_id: String, remote: Option<String>) -> Result<(), iced::Error> {
-        let dialog = Self::new(application_id, remote);
+    pub fn run_separate_window(application_id: String, remote: Option<String>, installation: Installation) -> Result<(), iced::Error> {
+        Self::run(Self::new(application_id, remote, installation))
+    }
+
+    pub fn run_from_ref(flatpak_ref: FlatpakRef, installation: Installation) -> Result<(), iced::Error> {
+        // The suggested remote is only used for details, in case it's already configured
+        let remote = Some(flatpak_ref.suggested_remote.clone()).filter(|r| !r.is_empty());
+        let mut dialog = Self::new(flatpak_ref.name.clone(), remote, installation);
+        dialog.flatpak_ref = Some(flatpak_ref);
+        Self::run(dialog)
+    }
 
+    fn run(dialog: Self) -> Result<(), iced::Error> {
         let mut window_settings = iced::window::Settings::default();
         window_settings.size = iced::Size::new(600.0, 550.0);
         window_settings.min_size = Some(iced::Size::new(480.0, 400.0));
@@ -356,6 +374,17 @@ impl FlatpakDialog {
                         .style(iced::theme::Button::Custom(Box::new(CleanButtonStyle { is_primary: false })))
                         .padding(DialogDesign::pad_small()),
                         Space::with_width(Length::Fill),
+                        // A user installation needs no polkit prompt
+                        text("Install for:").size(button_size),
+                        button(text("All users").size(button_size))
+                            .on_press_maybe((!self.is_installing).then_some(Message::SelectInstallation(Installation::System)))
+                            .style(iced::theme::Button::Custom(Box::new(CleanButtonStyle { is_primary: self.installation == Installation::System })))
+                            .padding(DialogDesign::pad_small()),
+                        button(text("Only me").size(button_size))
+                            .on_press_maybe((!self.is_installing).then_some(Message::SelectInstallation(Installation::User)))
+                            .style(iced::theme::Button::Custom(Box::new(CleanButtonStyle { is_primary: self.installation == Installation::User })))
+                            .padding(DialogDesign::pad_small()),
+                        Space::with_width(Length::Fixed(DialogDesign::SPACE_SMALL)),
                         {
                             if self.is_installing {
                                 button(
@@ -384,6 +413,7 @@ impl FlatpakDialog {
                         },
                     ]
                     .spacing(DialogDesign::SPACE_SMALL)
+                    .align_items(Alignment::Center)
                 }
             };
 
@@ -476,18 +506,35 @@ impl Application for FlatpakDialog {
                     }
                 })
             }
-            Message::FlatpakInfoLoaded(info) => {
+            Message::FlatpakInfoLoaded(mut info) => {
                 self.is_loading = false;
+                if let Some(ref flatpak_ref) = self.flatpak_ref {
+                    if info.name == info.application_id {
+                        info.name = flatpak_ref.title.clone();
+                    }
+                    if info.version == "N/A" && !flatpak_ref.comment.is_empty() {
+                        info.summary = flatpak_ref.comment.clone();
+                    }
+                    if !flatpak_ref.branch.is_empty() {
+                        info.branch = flatpak_ref.branch.clone();
+                    }
+                }
                 self.flatpak_info = Some(info);
                 iced::Command::none()
             }
+            Message::SelectInstallation(installation) => {
+                self.installation = installation;
+                iced::Command::none()
+            }
             Message::InstallFlatpak => {
                 self.is_installing = true;
                 self.installation_progress = "Preparing installation...".to_string();
                 self.terminal_output = String::new();
                 let app_id = self.application_id.clone();
                 let remote = self.remote.clone();
-                iced::Command::perform(install_flatpak_streaming(app_id, remote), |result| {
+                let installation = self.installation;
+                let from = self.flatpak_ref.as_ref().map(|r| r.path.clone());
+                iced::Command::perform(install_flatpak_streaming(app_id, remote, installation, from), |result| {
                     match result {
                         Ok(output) => Message::InstallationProgress(output),
                         Err(e) => Message::InstallationError(e.to_string()),
@@ -664,21 +711,25 @@ async fn write_flatpak_log(operation: &str, app_id: &str, remote: Option<&String
     }
 }
 
-async fn install_flatpak_streaming(app_id: String, remote: Option<String>) -> Result<String, String> {
+async fn install_flatpak_streaming(app_id: String, remote: Option<String>, installation: Installation, from: Option<PathBuf>) -> Result<String, String> {
     let mut cmd = TokioCommand::new("flatpak");
-    cmd.args(["install", "-y", "--noninteractive", "--verbose"]);
+    cmd.args(["install", installation.flag(), "-y", "--noninteractive", "--verbose"]);
 
-    if let Some(ref remote_name) = remote {
-        if !remote_name.is_empty() {
+    let command_str = if let Some(ref path) = from {
+        // A .flatpakref carries the remote, which flatpak adds as needed
+        cmd.arg("--from").arg(path);
+        format!("flatpak install {} -y --noninteractive --verbose --from {}", installation.flag(), path.display())
+    } else {
+        if let Some(remote_name) = remote.as_ref().filter(|r| !r.is_empty()) {
+            flatpak_remotes::ensure_remote(remote_name, installation).await?;
             cmd.arg(remote_name);
         }
-    }
-
-    cmd.arg(&app_id);
-
-    let command_str = format!("flatpak install -y --noninteractive --verbose {} {}",
-        remote.as_ref().map(|r| r.as_str()).unwrap_or(""),
-        &app_id);
+        cmd.arg(&app_id);
+        format!("flatpak install {} -y --noninteractive --verbose {} {}",
+            installation.flag(),
+            remote.as_ref().map(|r| r.as_str()).unwrap_or(""),
+            &app_id)
+    };
 
     cmd.stdout(std::process::Stdio::piped());
     cmd.stderr(std::process::Stdio::piped());
@@ -0,0 +1,144 @@
+use crate::gui::flatpak_remotes::{run_flatpak, Installation};
+
+#[derive(Debug, Clone)]
+pub struct CommitEntry {
+    pub commit: String,
+    pub subject: String,
+    pub date: String,
+}
+
+impl CommitEntry {
+    pub fn short(&self) -> String {
+        self.commit.chars().take(10).collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AppHistory {
+    pub app_id: String,
+    pub flatpak_ref: String,
+    pub installation: Installation,
+    pub installed_commit: String,
+    // Newest first; the first entry is the commit the remote currently serves
+    pub commits: Vec<CommitEntry>,
+}
+
+// A mask pattern as stored by `flatpak mask`, e.g. "org.gnome.Maps" or "org.gnome.*"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mask {
+    pub pattern: String,
+    pub installation: Installation,
+}
+
+impl Mask {
+    // Patterns are either an ID with optional trailing `*` or a ref with `/` separators
+    pub fn matches(&self, app_id: &str, flatpak_ref: &str) -> bool {
+        let glob = |pattern: &str, value: &str| match pattern.strip_suffix('*') {
+            Some(prefix) => value.starts_with(prefix),
+            None => pattern == value,
+        };
+        if self.pattern.contains('/') {
+            let parts: Vec<&str> = self.pattern.split('/').collect();
+            let ref_parts: Vec<&str> = flatpak_ref.split('/').collect();
+            let offset = if ref_parts.len() > parts.len() { 1 } else { 0 };
+            parts
+                .iter()
+                .enumerate()
+                .all(|(i, p)| p.is_empty() || ref_parts.get(i + offset).is_some_and(|r| glob(p, r)))
+        } else {
+            glob(&self.pattern, app_id)
+        }
+    }
+}
+
+pub fn is_masked(masks: &[Mask], installation: Installation, app_id: &str, flatpak_ref: &str) -> bool {
+    masks.iter().any(|m| m.installation == installation && m.matches(app_id, flatpak_ref))
+}
+
+// remote-info --log prints the head commit followed by a "History:" section of
+// Commit/Subject/Date blocks
+fn parse_log(output: &str) -> Vec<CommitEntry> {
+    let mut commits: Vec<CommitEntry> = Vec::new();
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim().to_string();
+        match key.trim() {
+            "Commit" if !commits.iter().any(|c| c.commit == value) => commits.push(CommitEntry {
+                commit: value,
+                subject: String::new(),
+                date: String::new(),
+            }),
+            "Subject" => {
+                if let Some(last) = commits.last_mut().filter(|c| c.subject.is_empty()) {
+                    last.subject = value;
+                }
+            }
+            "Date" => {
+                if let Some(last) = commits.last_mut().filter(|c| c.date.is_empty()) {
+                    last.date = value;
+                }
+            }
+            _ => {}
+        }
+    }
+    commits
+}
+
+pub async fn load(app_id: String, remote: String, installation: Installation) -> Result<AppHistory, String> {
+    let flatpak_ref = run_flatpak(&["info", installation.flag(), "--show-ref", &app_id]).await?.trim().to_string();
+    let installed_commit = run_flatpak(&["info", installation.flag(), "--show-commit", &app_id]).await?.trim().to_string();
+    let log = run_flatpak(&["remote-info", installation.flag(), "--log", &remote, &flatpak_ref]).await?;
+    Ok(AppHistory {
+        app_id,
+        flatpak_ref,
+        installation,
+        installed_commit,
+        commits: parse_log(&log),
+    })
+}
+
+// Deploys an older (or newer) commit; a later plain update moves back to the latest
+// unless the app is also pinned
+pub async fn deploy_commit(history: AppHistory, commit: String) -> Result<String, String> {
+    let commit_arg = format!("--commit={}", commit);
+    run_flatpak(&[
+        "update",
+        history.installation.flag(),
+        "-y",
+        "--noninteractive",
+        &commit_arg,
+        &history.flatpak_ref,
+    ])
+    .await?;
+    Ok(format!("{} is now at commit {}", history.app_id, commit.chars().take(10).collect::<String>()))
+}
+
+pub async fn load_masks() -> Result<Vec<Mask>, String> {
+    let mut masks = Vec::new();
+    for installation in [Installation::System, Installation::User] {
+        let output = run_flatpak(&["mask", installation.flag()]).await?;
+        masks.extend(
+            output
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with("No ") && !line.ends_with(':'))
+                .map(|pattern| Mask {
+                    pattern: pattern.to_string(),
+                    installation,
+                }),
+        );
+    }
+    Ok(masks)
+}
+
+pub async fn set_pinned(app_id: String, installation: Installation, pinned: bool) -> Result<String, String> {
+    if pinned {
+        run_flatpak(&["mask", installation.flag(), &app_id]).await?;
+        Ok(format!("{} is pinned and will not be updated", app_id))
+    } else {
+        run_flatpak(&["mask", installation.flag(), "--remove", &app_id]).await?;
+        Ok(format!("{} will receive updates again", app_id))
+    }
+}
@@ -0,0 +1,355 @@
+use tokio::process::Command as TokioCommand;
+use crate::gui::flatpak_remotes::Installation;
+
+// Which override file a change goes to: ~/.local/share/flatpak/overrides or /var/lib/flatpak/overrides
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverrideScope {
+    User,
+    System,
+}
+
+impl OverrideScope {
+    pub fn label(&self) -> &'static str {
+        match self {
+            OverrideScope::User => "User",
+            OverrideScope::System => "System",
+        }
+    }
+
+    fn flag(&self) -> &'static str {
+        match self {
+            OverrideScope::User => "--user",
+            OverrideScope::System => "--system",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermissionSection {
+    Shared,
+    Sockets,
+    Devices,
+    Filesystems,
+    SessionBus,
+    SystemBus,
+    Environment,
+}
+
+impl PermissionSection {
+    pub const ALL: [PermissionSection; 7] = [
+        PermissionSection::Shared,
+        PermissionSection::Sockets,
+        PermissionSection::Devices,
+        PermissionSection::Filesystems,
+        PermissionSection::SessionBus,
+        PermissionSection::SystemBus,
+        PermissionSection::Environment,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PermissionSection::Shared => "Share",
+            PermissionSection::Sockets => "Sockets",
+            PermissionSection::Devices => "Devices",
+            PermissionSection::Filesystems => "Filesystem",
+            PermissionSection::SessionBus => "Session Bus",
+            PermissionSection::SystemBus => "System Bus",
+            PermissionSection::Environment => "Environment",
+        }
+    }
+
+    // Values shown as toggles even when neither the app nor an override mentions them
+    pub fn known_values(&self) -> &'static [&'static str] {
+        match self {
+            PermissionSection::Shared => &["network", "ipc"],
+            PermissionSection::Sockets => &[
+                "x11", "fallback-x11", "wayland", "pulseaudio", "session-bus", "system-bus",
+                "ssh-auth", "pcsc", "cups", "gpg-agent",
+            ],
+            PermissionSection::Devices => &["dri", "input", "usb", "kvm", "shm", "all"],
+            PermissionSection::Filesystems => &["host", "host-os", "host-etc", "home"],
+            _ => &[],
+        }
+    }
+
+    // `flatpak override` arguments that grant `value`; bus values may carry a `=own` suffix
+    pub fn grant_args(&self, value: &str) -> Vec<String> {
+        match self {
+            PermissionSection::Shared => vec![format!("--share={}", value)],
+            PermissionSection::Sockets => vec![format!("--socket={}", value)],
+            PermissionSection::Devices => vec![format!("--device={}", value)],
+            PermissionSection::Filesystems => vec![format!("--filesystem={}", value)],
+            PermissionSection::SessionBus => match value.strip_suffix("=own") {
+                Some(name) => vec![format!("--own-name={}", name)],
+                None => vec![format!("--talk-name={}", value)],
+            },
+            PermissionSection::SystemBus => match value.strip_suffix("=own") {
+                Some(name) => vec![format!("--system-own-name={}", name)],
+                None => vec![format!("--system-talk-name={}", value)],
+            },
+            PermissionSection::Environment => vec![format!("--env={}", value)],
+        }
+    }
+
+    pub fn revoke_args(&self, value: &str) -> Vec<String> {
+        match self {
+            PermissionSection::Shared => vec![format!("--unshare={}", value)],
+            PermissionSection::Sockets => vec![format!("--nosocket={}", value)],
+            PermissionSection::Devices => vec![format!("--nodevice={}", value)],
+            PermissionSection::Filesystems => vec![format!("--nofilesystem={}", filesystem_path(value))],
+            PermissionSection::SessionBus => vec![format!("--no-talk-name={}", value)],
+            PermissionSection::SystemBus => vec![format!("--system-no-talk-name={}", value)],
+            PermissionSection::Environment => vec![format!("--unset-env={}", value)],
+        }
+    }
+}
+
+// `~/Games:ro` and `~/Games` name the same filesystem entry
+fn filesystem_path(value: &str) -> &str {
+    value
+        .strip_suffix(":ro")
+        .or_else(|| value.strip_suffix(":rw"))
+        .or_else(|| value.strip_suffix(":create"))
+        .unwrap_or(value)
+}
+
+// One layer of permissions: the app's metadata or an override file, in keyfile form
+#[derive(Debug, Clone, Default)]
+pub struct PermissionSet {
+    pub shared: Vec<String>,
+    pub sockets: Vec<String>,
+    pub devices: Vec<String>,
+    pub filesystems: Vec<String>,
+    pub session_bus: Vec<(String, String)>,
+    pub system_bus: Vec<(String, String)>,
+    pub environment: Vec<(String, String)>,
+    pub unset_environment: Vec<String>,
+}
+
+impl PermissionSet {
+    pub fn parse(keyfile: &str) -> Self {
+        let mut set = Self::default();
+        let mut group = String::new();
+        for line in keyfile.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with('[') && line.ends_with(']') {
+                group = line[1..line.len() - 1].to_string();
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let (key, value) = (key.trim(), value.trim());
+            let list = || value.split(';').map(str::trim).filter(|v| !v.is_empty()).map(String::from).collect::<Vec<_>>();
+            match group.as_str() {
+                "Context" => match key {
+                    "shared" => set.shared = list(),
+                    "sockets" => set.sockets = list(),
+                    "devices" => set.devices = list(),
+                    "filesystems" => set.filesystems = list(),
+                    "unset-environment" => set.unset_environment = list(),
+                    _ => {}
+                },
+                "Session Bus Policy" => set.session_bus.push((key.to_string(), value.to_string())),
+                "System Bus Policy" => set.system_bus.push((key.to_string(), value.to_string())),
+                "Environment" => set.environment.push((key.to_string(), value.to_string())),
+                _ => {}
+            }
+        }
+        set
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shared.is_empty()
+            && self.sockets.is_empty()
+            && self.devices.is_empty()
+            && self.filesystems.is_empty()
+            && self.session_bus.is_empty()
+            && self.system_bus.is_empty()
+            && self.environment.is_empty()
+            && self.unset_environment.is_empty()
+    }
+
+    fn list(&self, section: PermissionSection) -> &[String] {
+        match section {
+            PermissionSection::Shared => &self.shared,
+            PermissionSection::Sockets => &self.sockets,
+            PermissionSection::Devices => &self.devices,
+            PermissionSection::Filesystems => &self.filesystems,
+            _ => &[],
+        }
+    }
+
+    fn pairs(&self, section: PermissionSection) -> &[(String, String)] {
+        match section {
+            PermissionSection::SessionBus => &self.session_bus,
+            PermissionSection::SystemBus => &self.system_bus,
+            PermissionSection::Environment => &self.environment,
+            _ => &[],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PermissionEntry {
+    // Permission name, filesystem path (with mode), bus name or variable name
+    pub value: String,
+    // Bus policy or variable value
+    pub detail: String,
+    pub granted: bool,
+    // Which layer decided the effective state: "app", "system override" or "user override"
+    pub source: &'static str,
+}
+
+#[derive(Debug, Clone)]
+pub struct AppPermissions {
+    pub app_id: String,
+    pub metadata: PermissionSet,
+    pub system_overrides: PermissionSet,
+    pub user_overrides: PermissionSet,
+}
+
+impl AppPermissions {
+    pub fn overrides(&self, scope: OverrideScope) -> &PermissionSet {
+        match scope {
+            OverrideScope::User => &self.user_overrides,
+            OverrideScope::System => &self.system_overrides,
+        }
+    }
+
+    // Metadata, then system overrides, then user overrides, the order flatpak run applies them
+    pub fn effective(&self, section: PermissionSection) -> Vec<PermissionEntry> {
+        let layers = [
+            (&self.metadata, "app"),
+            (&self.system_overrides, "system override"),
+            (&self.user_overrides, "user override"),
+        ];
+        let mut entries: Vec<PermissionEntry> = section
+            .known_values()
+            .iter()
+            .map(|value| PermissionEntry { value: value.to_string(), detail: String::new(), granted: false, source: "" })
+            .collect();
+        let upsert = |entries: &mut Vec<PermissionEntry>, value: &str, detail: &str, granted: bool, source: &'static str| {
+            let key = if section == PermissionSection::Filesystems { filesystem_path(value) } else { value };
+            let existing = entries.iter_mut().find(|e| {
+                if section == PermissionSection::Filesystems { filesystem_path(&e.value) == key } else { e.value == key }
+            });
+            match existing {
+                Some(entry) => {
+                    // A revoke keeps the mode the grant had so the row reads the same
+                    if granted {
+                        entry.value = value.to_string();
+                    }
+                    entry.detail = detail.to_string();
+                    entry.granted = granted;
+                    entry.source = source;
+                }
+                None => entries.push(PermissionEntry {
+                    value: value.to_string(),
+                    detail: detail.to_string(),
+                    granted,
+                    source,
+                }),
+            }
+        };
+
+        for (set, source) in layers {
+            match section {
+                PermissionSection::SessionBus | PermissionSection::SystemBus => {
+                    for (name, policy) in set.pairs(section) {
+                        upsert(&mut entries, name, policy, policy != "none", source);
+                    }
+                }
+                PermissionSection::Environment => {
+                    for (name, value) in set.pairs(section) {
+                        upsert(&mut entries, name, value, !value.is_empty(), source);
+                    }
+                    for name in &set.unset_environment {
+                        upsert(&mut entries, name, "", false, source);
+                    }
+                }
+                _ => {
+                    for value in set.list(section) {
+                        match value.strip_prefix('!') {
+                            Some(revoked) => upsert(&mut entries, revoked, "", false, source),
+                            None => upsert(&mut entries, value, "", true, source),
+                        }
+                    }
+                }
+            }
+        }
+        entries
+    }
+}
+
+pub async fn load(app_id: String, installation: Option<Installation>) -> Result<AppPermissions, String> {
+    let metadata = TokioCommand::new("flatpak")
+        .arg("info")
+        .args(installation.map(|i| i.flag()))
+        .args(["--show-metadata", &app_id])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute flatpak info: {}", e))?;
+    if !metadata.status.success() {
+        return Err(format!("Failed to read metadata of {}: {}", app_id, String::from_utf8_lossy(&metadata.stderr).trim()));
+    }
+
+    let mut overrides = Vec::new();
+    for scope in [OverrideScope::System, OverrideScope::User] {
+        // A missing override file is not an error, so failures read as no overrides
+        let output = TokioCommand::new("flatpak")
+            .args(["override", "--show", scope.flag(), &app_id])
+            .output()
+            .await
+            .map_err(|e| format!("Failed to execute flatpak override: {}", e))?;
+        overrides.push(if output.status.success() {
+            PermissionSet::parse(&String::from_utf8_lossy(&output.stdout))
+        } else {
+            PermissionSet::default()
+        });
+    }
+    let user_overrides = overrides.pop().unwrap_or_default();
+    let system_overrides = overrides.pop().unwrap_or_default();
+
+    Ok(AppPermissions {
+        metadata: PermissionSet::parse(&String::from_utf8_lossy(&metadata.stdout)),
+        app_id,
+        system_overrides,
+        user_overrides,
+    })
+}
+
+// System overrides live under /var/lib/flatpak and need root
+async fn run_override(scope: OverrideScope, args: Vec<String>, app_id: &str) -> Result<String, String> {
+    let mut cmd = match scope {
+        OverrideScope::User => TokioCommand::new("flatpak"),
+        OverrideScope::System => {
+            let mut cmd = TokioCommand::new("pkexec");
+            cmd.arg("flatpak");
+            cmd
+        }
+    };
+    cmd.arg("override").arg(scope.flag()).args(&args).arg(app_id);
+    crate::logger::Logger::log_debug(&format!("[Flatpak Permissions] flatpak override {} {} {}", scope.flag(), args.join(" "), app_id));
+    let output = cmd.output().await.map_err(|e| format!("Failed to execute flatpak override: {}", e))?;
+    if output.status.success() {
+        Ok(format!("{} override for {}: {}", scope.label(), app_id, args.join(" ")))
+    } else {
+        Err(format!("flatpak override failed: {}", String::from_utf8_lossy(&output.stderr).trim()))
+    }
+}
+
+pub async fn grant(app_id: String, scope: OverrideScope, section: PermissionSection, value: String) -> Result<String, String> {
+    run_override(scope, section.grant_args(&value), &app_id).await
+}
+
+pub async fn revoke(app_id: String, scope: OverrideScope, section: PermissionSection, value: String) -> Result<String, String> {
+    run_override(scope, section.revoke_args(&value), &app_id).await
+}
+
+pub async fn reset(app_id: String, scope: OverrideScope) -> Result<String, String> {
+    run_override(scope, vec!["--reset".to_string()], &app_id).await
+}
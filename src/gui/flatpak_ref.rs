@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+// What a file or URI handed over by the desktop (file manager, browser) asks for
+#[derive(Debug, Clone)]
+pub enum OpenRequest {
+    Rpm(PathBuf),
+    FlatpakRef(FlatpakRef),
+    FlatpakRepo(FlatpakRepo),
+    // Component ID from an appstream: link, e.g. org.gnome.Maps
+    AppStream(String),
+}
+
+// [Flatpak Ref] group of a .flatpakref file
+#[derive(Debug, Clone)]
+pub struct FlatpakRef {
+    pub path: PathBuf,
+    pub name: String,
+    pub branch: String,
+    pub title: String,
+    pub comment: String,
+    pub suggested_remote: String,
+}
+
+// [Flatpak Repo] group of a .flatpakrepo file
+#[derive(Debug, Clone)]
+pub struct FlatpakRepo {
+    pub path: PathBuf,
+    pub name: String,
+    pub title: String,
+    pub comment: String,
+    pub description: String,
+    pub url: String,
+    pub homepage: String,
+}
+
+// Both formats are GKeyFiles; only the one group we need is read
+fn parse_group(content: &str, group: &str) -> Result<HashMap<String, String>, String> {
+    let header = format!("[{}]", group);
+    let mut in_group = false;
+    let mut found = false;
+    let mut values = HashMap::new();
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_group = line == header;
+            found |= in_group;
+        } else if in_group && !line.starts_with('#') {
+            if let Some((key, value)) = line.split_once('=') {
+                // Localized keys (Title[de]) are skipped
+                if !key.contains('[') {
+                    values.insert(key.trim().to_string(), value.trim().to_string());
+                }
+            }
+        }
+    }
+    if found {
+        Ok(values)
+    } else {
+        Err(format!("Missing {} section", header))
+    }
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+}
+
+pub fn parse_ref(path: &Path) -> Result<FlatpakRef, String> {
+    let mut values = parse_group(&read(path)?, "Flatpak Ref")?;
+    let mut take = |key: &str| values.remove(key).unwrap_or_default();
+    let name = take("Name");
+    if name.is_empty() {
+        return Err(format!("{} does not name an application", path.display()));
+    }
+    let suggested_remote = take("SuggestRemoteName");
+    Ok(FlatpakRef {
+        path: path.to_path_buf(),
+        title: match take("Title") {
+            title if title.is_empty() => name.clone(),
+            title => title,
+        },
+        branch: take("Branch"),
+        comment: take("Comment"),
+        suggested_remote,
+        name,
+    })
+}
+
+pub fn parse_repo(path: &Path) -> Result<FlatpakRepo, String> {
+    let mut values = parse_group(&read(path)?, "Flatpak Repo")?;
+    let mut take = |key: &str| values.remove(key).unwrap_or_default();
+    let url = take("Url");
+    if url.is_empty() {
+        return Err(format!("{} has no repository URL", path.display()));
+    }
+    // Without a suggested name, flatpak itself falls back to the file name
+    let name = match take("SuggestRemoteName") {
+        name if name.is_empty() => path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        name => name,
+    };
+    Ok(FlatpakRepo {
+        path: path.to_path_buf(),
+        title: match take("Title") {
+            title if title.is_empty() => name.clone(),
+            title => title,
+        },
+        comment: take("Comment"),
+        description: take("Description"),
+        homepage: take("Homepage"),
+        name,
+        url,
+    })
+}
+
+// file:// URIs percent-encode spaces and non-ASCII characters
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).and_then(|h| std::str::from_utf8(h).ok()).and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+fn download_dir() -> PathBuf {
+    PathBuf::from(std::env::var("HOME").unwrap_or_default()).join(".rustora/cache/downloads")
+}
+
+// flatpak+https://example.org/app.flatpakref is the plain https URL with a scheme
+// browsers can route to a Flatpak installer
+async fn download(url: &str) -> Result<PathBuf, String> {
+    let response = reqwest::get(url).await.map_err(|e| format!("Failed to download {}: {}", url, e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to download {}: HTTP {}", url, response.status()));
+    }
+    let content = response.text().await.map_err(|e| format!("Failed to download {}: {}", url, e))?;
+    let file_name = match url.rsplit('/').next().map(|n| n.split(['?', '#']).next().unwrap_or(n)) {
+        Some(name) if name.ends_with(".flatpakref") || name.ends_with(".flatpakrepo") => name.to_string(),
+        // Servers that hide the extension are recognized by the group header
+        _ if content.contains("[Flatpak Repo]") => "download.flatpakrepo".to_string(),
+        _ => "download.flatpakref".to_string(),
+    };
+    tokio::fs::create_dir_all(download_dir()).await.map_err(|e| format!("Failed to create download directory: {}", e))?;
+    let path = download_dir().join(file_name);
+    tokio::fs::write(&path, content).await.map_err(|e| format!("Failed to save {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+fn open_path(path: PathBuf) -> Result<OpenRequest, String> {
+    if !path.exists() {
+        return Err(format!("File not found: {}", path.display()));
+    }
+    let extension = path.extension().map(|e| e.to_string_lossy().to_lowercase());
+    match extension.as_deref() {
+        Some("rpm") => Ok(OpenRequest::Rpm(path)),
+        Some("flatpakref") => parse_ref(&path).map(OpenRequest::FlatpakRef),
+        Some("flatpakrepo") => parse_repo(&path).map(OpenRequest::FlatpakRepo),
+        Some(_) => Err(format!("Unsupported file type: {}", path.display())),
+        None => Err(format!("File does not have an extension: {}", path.display())),
+    }
+}
+
+pub async fn resolve(argument: &str) -> Result<OpenRequest, String> {
+    if let Some(id) = argument.strip_prefix("appstream:") {
+        let id = id.trim_start_matches('/').trim_end_matches('/');
+        let id = id.strip_suffix(".desktop").unwrap_or(id);
+        if id.is_empty() {
+            return Err(format!("{} does not name an application", argument));
+        }
+        return Ok(OpenRequest::AppStream(id.to_string()));
+    }
+    if let Some(url) = argument.strip_prefix("flatpak+") {
+        if !url.starts_with("https://") {
+            return Err(format!("Unsupported link: {}", argument));
+        }
+        return open_path(download(url).await?);
+    }
+    match argument.strip_prefix("file://") {
+        Some(path) => open_path(PathBuf::from(percent_decode(path))),
+        None => open_path(PathBuf::from(argument)),
+    }
+}
@@ -0,0 +1,377 @@
+use iced::widget::{button, column, container, row, scrollable, text, Space};
+use iced::{Alignment, Application, Command, Element, Length, Border, Theme as IcedTheme};
+use iced::widget::container::Appearance;
+use iced::widget::button::Appearance as ButtonAppearance;
+use iced::widget::button::StyleSheet as ButtonStyleSheet;
+use iced::window;
+use crate::gui::dialog_design::DialogDesign;
+use crate::gui::flatpak_ref::FlatpakRepo;
+use crate::gui::flatpak_remotes::{self, Installation};
+use iced::Color;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    SelectInstallation(Installation),
+    AddRemote,
+    RemoteAdded(Result<String, String>),
+    Cancel,
+}
+
+// Opened for .flatpakrepo files and links
+#[derive(Debug)]
+pub struct FlatpakRemoteDialog {
+    pub repo: FlatpakRepo,
+    pub installation: Installation,
+    pub is_adding: bool,
+    pub result: Option<Result<String, String>>,
+}
+
+impl FlatpakRemoteDialog {
+    pub fn new(repo: FlatpakRepo) -> Self {
+        Self {
+            repo,
+            installation: Installation::System,
+            is_adding: false,
+            result: None,
+        }
+    }
+
+    pub fn run_separate_window(repo: FlatpakRepo) -> Result<(), iced::Error> {
+        let dialog = Self::new(repo);
+
+        let window_settings = iced::window::Settings {
+            size: iced::Size::new(560.0, 460.0),
+            min_size: Some(iced::Size::new(460.0, 360.0)),
+            resizable: true,
+            decorations: true,
+            ..Default::default()
+        };
+
+        let default_font = crate::gui::fonts::get_inter_font();
+
+        <FlatpakRemoteDialog as Application>::run(iced::Settings {
+            window: window_settings,
+            flags: dialog,
+            default_font,
+            default_text_size: iced::Pixels::from(14.0),
+            antialiasing: true,
+            id: None,
+            fonts: Vec::new(),
+        })
+    }
+
+    pub fn view_impl(&self, theme: &crate::gui::Theme) -> Element<'_, Message> {
+        let settings = crate::gui::settings::AppSettings::load();
+        let title_size = (settings.font_size_titles * settings.scale_titles).round();
+        let body_size = (settings.font_size_body * settings.scale_body).round();
+        let button_size = (settings.font_size_buttons * settings.scale_buttons).round();
+        let material_font = crate::gui::fonts::get_material_symbols_font();
+
+        let header = container(
+            row![
+                text(crate::gui::fonts::glyphs::CLOUD_SYMBOL)
+                    .font(material_font)
+                    .size(title_size * 1.2)
+                    .style(iced::theme::Text::Color(theme.primary())),
+                Space::with_width(DialogDesign::space_small()),
+                column![
+                    text(format!("Add {}", self.repo.title))
+                        .size(title_size)
+                        .style(iced::theme::Text::Color(theme.primary())),
+                    text("Flatpak remote")
+                        .size(body_size * 0.8)
+                        .style(iced::theme::Text::Color(theme.secondary_text())),
+                ]
+                .spacing(DialogDesign::SPACE_TINY),
+                Space::with_width(Length::Fill),
+            ]
+            .align_items(Alignment::Center)
+        )
+        .width(Length::Fill)
+        .padding(DialogDesign::pad_medium());
+
+        let label_w = 95.0;
+        let field = |label: &'static str, value: &str| {
+            row![
+                text(label).size(body_size).width(Length::Fixed(label_w)).style(iced::theme::Text::Color(theme.secondary_text())),
+                text(value.to_string()).size(body_size).width(Length::Fill),
+            ]
+            .spacing(DialogDesign::SPACE_SMALL)
+        };
+        let mut details = column![
+            field("Name:", &self.repo.name),
+            field("URL:", &self.repo.url),
+        ]
+        .spacing(DialogDesign::SPACE_TINY);
+        if !self.repo.homepage.is_empty() {
+            details = details.push(field("Homepage:", &self.repo.homepage));
+        }
+        if !self.repo.comment.is_empty() {
+            details = details.push(Space::with_height(DialogDesign::space_tiny()));
+            details = details.push(text(&self.repo.comment).size(body_size));
+        }
+        if !self.repo.description.is_empty() {
+            details = details.push(text(&self.repo.description).size(body_size * 0.9).style(iced::theme::Text::Color(theme.secondary_text())));
+        }
+        details = details.push(Space::with_height(DialogDesign::space_small()));
+        details = details.push(
+            text("Apps installed from this remote are signed by its publisher. Only add remotes you trust.")
+                .size(body_size * 0.85)
+                .style(iced::theme::Text::Color(theme.secondary_text())),
+        );
+
+        let status: Element<Message> = match self.result {
+            Some(Ok(ref message)) => text(message).size(body_size).style(iced::theme::Text::Color(Color::from_rgb(0.0, 0.8, 0.0))).into(),
+            Some(Err(ref error)) => text(error).size(body_size).style(iced::theme::Text::Color(theme.danger())).into(),
+            None if self.is_adding => text("Adding remote...").size(body_size).into(),
+            None => Space::with_height(Length::Shrink).into(),
+        };
+
+        let info_section = container(column![details, status].spacing(DialogDesign::SPACE_MEDIUM))
+            .width(Length::Fill)
+            .padding(DialogDesign::pad_medium())
+            .style(iced::theme::Container::Custom(Box::new(CleanContainerStyle)));
+
+        let is_done = matches!(self.result, Some(Ok(_)));
+        let buttons = if is_done {
+            row![
+                Space::with_width(Length::Fill),
+                button(
+                    row![
+                        text(crate::gui::fonts::glyphs::EXIT_SYMBOL).font(material_font).size(button_size * 1.1),
+                        text(" Close").size(button_size)
+                    ]
+                    .spacing(DialogDesign::SPACE_TINY)
+                    .align_items(Alignment::Center)
+                )
+                .on_press(Message::Cancel)
+                .style(iced::theme::Button::Custom(Box::new(CleanButtonStyle { is_primary: true })))
+                .padding(DialogDesign::pad_small()),
+            ]
+        } else {
+            row![
+                button(
+                    row![
+                        text(crate::gui::fonts::glyphs::CANCEL_SYMBOL).font(material_font).size(button_size * 1.1),
+                        text(" Cancel").size(button_size)
+                    ]
+                    .spacing(DialogDesign::SPACE_TINY)
+                    .align_items(Alignment::Center)
+                )
+                .on_press(Message::Cancel)
+                .style(iced::theme::Button::Custom(Box::new(CleanButtonStyle { is_primary: false })))
+                .padding(DialogDesign::pad_small()),
+                Space::with_width(Length::Fill),
+                // A user installation needs no polkit prompt
+                text("Add for:").size(button_size),
+                button(text("All users").size(button_size))
+                    .on_press_maybe((!self.is_adding).then_some(Message::SelectInstallation(Installation::System)))
+                    .style(iced::theme::Button::Custom(Box::new(CleanButtonStyle { is_primary: self.installation == Installation::System })))
+                    .padding(DialogDesign::pad_small()),
+                button(text("Only me").size(button_size))
+                    .on_press_maybe((!self.is_adding).then_some(Message::SelectInstallation(Installation::User)))
+                    .style(iced::theme::Button::Custom(Box::new(CleanButtonStyle { is_primary: self.installation == Installation::User })))
+                    .padding(DialogDesign::pad_small()),
+                Space::with_width(Length::Fixed(DialogDesign::SPACE_SMALL)),
+                button(
+                    row![
+                        text(crate::gui::fonts::glyphs::ADD_SYMBOL).font(material_font).size(button_size * 1.1),
+                        text(if self.is_adding { " Adding..." } else { " Add Remote" }).size(button_size)
+                    ]
+                    .spacing(DialogDesign::SPACE_TINY)
+                    .align_items(Alignment::Center)
+                )
+                .on_press_maybe((!self.is_adding).then_some(Message::AddRemote))
+                .style(iced::theme::Button::Custom(Box::new(CleanButtonStyle { is_primary: true })))
+                .padding(DialogDesign::pad_small()),
+            ]
+        }
+        .spacing(DialogDesign::SPACE_SMALL)
+        .align_items(Alignment::Center);
+
+        container(
+            column![
+                header,
+                container(Space::with_height(Length::Fixed(1.0)))
+                    .width(Length::Fill)
+                    .style(iced::theme::Container::Custom(Box::new(DividerStyle))),
+                scrollable(
+                    column![info_section]
+                        .padding(DialogDesign::pad_medium())
+                )
+                .height(Length::Fill),
+                container(Space::with_height(Length::Fixed(1.0)))
+                    .width(Length::Fill)
+                    .style(iced::theme::Container::Custom(Box::new(DividerStyle))),
+                container(buttons)
+                    .width(Length::Fill)
+                    .padding(DialogDesign::pad_medium()),
+            ]
+            .spacing(0)
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(iced::theme::Container::Custom(Box::new(WindowContainerStyle {
+            background: theme.background(),
+        })))
+        .into()
+    }
+}
+
+impl Application for FlatpakRemoteDialog {
+    type Message = Message;
+    type Theme = IcedTheme;
+    type Executor = iced::executor::Default;
+    type Flags = Self;
+
+    fn new(flags: Self) -> (Self, Command<Message>) {
+        (flags, Command::none())
+    }
+
+    fn title(&self) -> String {
+        format!("Add {} - Rustora", self.repo.title)
+    }
+
+    fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::SelectInstallation(installation) => {
+                self.installation = installation;
+                self.result = None;
+                Command::none()
+            }
+            Message::AddRemote => {
+                self.is_adding = true;
+                self.result = None;
+                let location = self.repo.path.to_string_lossy().to_string();
+                Command::perform(
+                    flatpak_remotes::add(self.repo.name.clone(), location, self.installation),
+                    Message::RemoteAdded,
+                )
+            }
+            Message::RemoteAdded(result) => {
+                self.is_adding = false;
+                self.result = Some(result.map(|_| {
+                    format!("{} was added. Its apps now show up in Flatpak search.", self.repo.title)
+                }));
+                Command::none()
+            }
+            Message::Cancel => {
+                iced::window::close(window::Id::MAIN)
+            }
+        }
+    }
+
+    fn view(&self) -> Element<'_, Message> {
+        let theme = &crate::gui::Theme::Dark;
+        self.view_impl(theme)
+    }
+
+    fn theme(&self) -> IcedTheme {
+        crate::gui::Theme::Dark.iced_theme()
+    }
+}
+
+struct CleanButtonStyle {
+    is_primary: bool,
+}
+
+impl ButtonStyleSheet for CleanButtonStyle {
+    type Style = iced::Theme;
+
+    fn active(&self, style: &Self::Style) -> ButtonAppearance {
+        let palette = style.palette();
+        ButtonAppearance {
+            background: Some(iced::Background::Color(if self.is_primary {
+                palette.primary
+            } else {
+                Color::from_rgba(0.4, 0.4, 0.4, 0.2)
+            })),
+            border: Border {
+                radius: DialogDesign::RADIUS.into(),
+                width: 1.0,
+                color: if self.is_primary {
+                    palette.primary
+                } else {
+                    Color::from_rgba(0.5, 0.5, 0.5, 0.3)
+                },
+            },
+            text_color: if self.is_primary { Color::WHITE } else { palette.text },
+            ..Default::default()
+        }
+    }
+
+    fn hovered(&self, style: &Self::Style) -> ButtonAppearance {
+        let mut appearance = self.active(style);
+        let palette = style.palette();
+        if self.is_primary {
+            appearance.background = Some(iced::Background::Color(
+                Color::from_rgba(palette.primary.r * 0.85, palette.primary.g * 0.85, palette.primary.b * 0.85, 1.0)
+            ));
+        } else {
+            appearance.background = Some(iced::Background::Color(Color::from_rgba(0.4, 0.4, 0.4, 0.3)));
+        }
+        appearance
+    }
+}
+
+struct WindowContainerStyle {
+    background: iced::Color,
+}
+
+impl iced::widget::container::StyleSheet for WindowContainerStyle {
+    type Style = iced::Theme;
+
+    fn appearance(&self, _style: &Self::Style) -> Appearance {
+        Appearance {
+            background: Some(iced::Background::Color(self.background)),
+            border: Border {
+                radius: 0.0.into(),
+                width: 0.0,
+                color: iced::Color::TRANSPARENT,
+            },
+            ..Default::default()
+        }
+    }
+}
+
+struct CleanContainerStyle;
+
+impl iced::widget::container::StyleSheet for CleanContainerStyle {
+    type Style = iced::Theme;
+
+    fn appearance(&self, style: &Self::Style) -> Appearance {
+        let palette = style.palette();
+        Appearance {
+            background: Some(iced::Background::Color(Color::from_rgba(
+                palette.background.r * 0.98,
+                palette.background.g * 0.98,
+                palette.background.b * 0.98,
+                1.0,
+            ))),
+            border: Border {
+                radius: DialogDesign::RADIUS.into(),
+                width: 1.0,
+                color: Color::from_rgba(0.3, 0.3, 0.3, 0.2),
+            },
+            ..Default::default()
+        }
+    }
+}
+
+struct DividerStyle;
+
+impl iced::widget::container::StyleSheet for DividerStyle {
+    type Style = iced::Theme;
+
+    fn appearance(&self, _style: &Self::Style) -> Appearance {
+        Appearance {
+            background: Some(iced::Background::Color(Color::from_rgba(0.3, 0.3, 0.3, 0.2))),
+            border: Border {
+                radius: 0.0.into(),
+                width: 0.0,
+                color: Color::TRANSPARENT,
+            },
+            ..Default::default()
+        }
+    }
+}
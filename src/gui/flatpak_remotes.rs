@@ -0,0 +1,274 @@
+use tokio::process::Command as TokioCommand;
+
+// Flatpak keeps a per-user installation in ~/.local/share/flatpak next to the system one in /var/lib/flatpak
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Installation {
+    User,
+    System,
+}
+
+impl Installation {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Installation::User => "user",
+            Installation::System => "system",
+        }
+    }
+
+    pub fn flag(&self) -> &'static str {
+        match self {
+            Installation::User => "--user",
+            Installation::System => "--system",
+        }
+    }
+
+    pub fn other(&self) -> Self {
+        match self {
+            Installation::User => Installation::System,
+            Installation::System => Installation::User,
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label.trim() {
+            "user" => Some(Installation::User),
+            "system" | "default" => Some(Installation::System),
+            _ => None,
+        }
+    }
+}
+
+pub struct RemotePreset {
+    pub name: &'static str,
+    pub title: &'static str,
+    pub location: &'static str,
+}
+
+pub const PRESETS: &[RemotePreset] = &[
+    RemotePreset { name: "flathub", title: "Flathub", location: "https://dl.flathub.org/repo/flathub.flatpakrepo" },
+    RemotePreset { name: "flathub-beta", title: "Flathub Beta", location: "https://flathub.org/beta-repo/flathub-beta.flatpakrepo" },
+    RemotePreset { name: "fedora", title: "Fedora", location: "oci+https://registry.fedoraproject.org" },
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlatpakRemote {
+    pub name: String,
+    pub title: String,
+    pub url: String,
+    pub installation: Installation,
+    pub priority: i32,
+    pub filter: String,
+    pub disabled: bool,
+}
+
+impl FlatpakRemote {
+    // Remote names are only unique within one installation
+    pub fn key(&self) -> String {
+        format!("{}:{}", self.installation.label(), self.name)
+    }
+
+    pub fn is_fedora(&self) -> bool {
+        self.name == "fedora" || self.url.contains("registry.fedoraproject.org")
+    }
+
+    pub fn is_flathub(&self) -> bool {
+        self.name == "flathub" || self.url.starts_with("https://dl.flathub.org/repo")
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RemotesState {
+    pub remotes: Vec<FlatpakRemote>,
+    pub warnings: Vec<String>,
+}
+
+pub async fn load() -> Result<RemotesState, String> {
+    let output = TokioCommand::new("flatpak")
+        .args(["remotes", "--show-disabled", "--columns=name,title,url,priority,filter,options"])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute flatpak remotes: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("Failed to list remotes: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+
+    let remotes: Vec<FlatpakRemote> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split('\t').map(str::trim).collect();
+            let name = parts.first().filter(|n| !n.is_empty())?.to_string();
+            // options holds the installation plus flags such as "disabled" or "no-gpg-verify"
+            let options: Vec<&str> = parts.get(5).map(|o| o.split(',').map(str::trim).collect()).unwrap_or_default();
+            let installation = if options.contains(&"user") { Installation::User } else { Installation::System };
+            Some(FlatpakRemote {
+                title: parts.get(1).filter(|t| !t.is_empty()).unwrap_or(&name.as_str()).to_string(),
+                url: parts.get(2).unwrap_or(&"").to_string(),
+                priority: parts.get(3).and_then(|p| p.parse().ok()).unwrap_or(1),
+                filter: parts.get(4).unwrap_or(&"").to_string(),
+                disabled: options.contains(&"disabled"),
+                installation,
+                name,
+            })
+        })
+        .collect();
+
+    let warnings = shadow_warnings(&remotes).await;
+    Ok(RemotesState { remotes, warnings })
+}
+
+// Fedora's OCI remote repackages many Flathub apps; when both are enabled, installs may come from Fedora
+async fn shadow_warnings(remotes: &[FlatpakRemote]) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let enabled = |f: fn(&FlatpakRemote) -> bool| remotes.iter().filter(move |r| !r.disabled && f(r));
+    let Some(flathub) = enabled(FlatpakRemote::is_flathub).max_by_key(|r| r.priority) else {
+        return warnings;
+    };
+    for fedora in enabled(FlatpakRemote::is_fedora) {
+        if fedora.priority >= flathub.priority {
+            warnings.push(format!(
+                "The Fedora remote \"{}\" ({}) has priority {} and wins over Flathub ({}) when both offer an app. Lower its priority or disable it to get Flathub builds.",
+                fedora.name,
+                fedora.installation.label(),
+                fedora.priority,
+                flathub.priority
+            ));
+        }
+    }
+
+    let installed = TokioCommand::new("flatpak")
+        .args(["list", "--app", "--columns=application,origin"])
+        .output()
+        .await;
+    let from_fedora: Vec<String> = match installed {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let (app, origin) = line.split_once('\t')?;
+                remotes
+                    .iter()
+                    .any(|r| r.name == origin.trim() && r.is_fedora())
+                    .then(|| app.trim().to_string())
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    if from_fedora.is_empty() {
+        return warnings;
+    }
+
+    let flathub_apps = TokioCommand::new("flatpak")
+        .args(["remote-ls", flathub.installation.flag(), &flathub.name, "--app", "--columns=application"])
+        .output()
+        .await;
+    if let Ok(output) = flathub_apps {
+        let available = String::from_utf8_lossy(&output.stdout);
+        let shadowed: Vec<&String> = from_fedora
+            .iter()
+            .filter(|app| available.lines().any(|line| line.trim() == app.as_str()))
+            .collect();
+        if !shadowed.is_empty() {
+            warnings.push(format!(
+                "{} installed app(s) come from Fedora but are also on Flathub: {}",
+                shadowed.len(),
+                shadowed.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", ")
+            ));
+        }
+    }
+    warnings
+}
+
+// Changes to the system installation go through flatpak's own polkit helper, so no
+// pkexec here; returns stdout
+pub(crate) async fn run_flatpak(args: &[&str]) -> Result<String, String> {
+    crate::logger::Logger::log_debug(&format!("[Flatpak] flatpak {}", args.join(" ")));
+    let output = TokioCommand::new("flatpak")
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute flatpak: {}", e))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(format!("flatpak {} failed: {}", args[0], String::from_utf8_lossy(&output.stderr).trim()))
+    }
+}
+
+// Remote changes report the command that was run
+async fn run_remote_command(args: &[&str]) -> Result<String, String> {
+    run_flatpak(args).await?;
+    Ok(format!("flatpak {}", args.join(" ")))
+}
+
+// Installing into the user installation needs the remote configured there as well;
+// known remotes are added on the fly, anything else has to be added by the user
+pub async fn ensure_remote(name: &str, installation: Installation) -> Result<(), String> {
+    let output = TokioCommand::new("flatpak")
+        .args(["remotes", installation.flag(), "--columns=name"])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute flatpak remotes: {}", e))?;
+    if String::from_utf8_lossy(&output.stdout).lines().any(|line| line.trim() == name) {
+        return Ok(());
+    }
+    match PRESETS.iter().find(|p| p.name == name) {
+        Some(preset) => add(preset.name.to_string(), preset.location.to_string(), installation).await.map(|_| ()),
+        None => Err(format!(
+            "Remote \"{}\" is not configured for the {} installation. Add it in Flatpak > Remotes first.",
+            name,
+            installation.label()
+        )),
+    }
+}
+
+// `location` is a .flatpakrepo URL or file, or a plain repository URL
+pub async fn add(name: String, location: String, installation: Installation) -> Result<String, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() || name.contains(char::is_whitespace) || name.contains('/') {
+        return Err(format!("\"{}\" is not a valid remote name", name));
+    }
+    let location = location.trim();
+    // A local .flatpakrepo must be passed as an absolute path
+    let location = match location.strip_prefix('~') {
+        Some(rest) => format!("{}{}", std::env::var("HOME").unwrap_or_default(), rest),
+        None => location.to_string(),
+    };
+    if location.is_empty() {
+        return Err("Enter a .flatpakrepo URL or file".to_string());
+    }
+    run_remote_command(&["remote-add", "--if-not-exists", installation.flag(), &name, &location]).await
+}
+
+pub async fn set_enabled(remote: FlatpakRemote, enabled: bool) -> Result<String, String> {
+    run_remote_command(&[
+        "remote-modify",
+        remote.installation.flag(),
+        if enabled { "--enable" } else { "--disable" },
+        &remote.name,
+    ])
+    .await
+}
+
+// Priority and filter in one call; an empty filter removes it
+pub async fn modify(remote: FlatpakRemote, priority: i32, filter: String) -> Result<String, String> {
+    let filter = filter.trim();
+    let filter_arg = if filter.is_empty() {
+        "--no-filter".to_string()
+    } else {
+        let path = match filter.strip_prefix('~') {
+            Some(rest) => format!("{}{}", std::env::var("HOME").unwrap_or_default(), rest),
+            None => filter.to_string(),
+        };
+        if !std::path::Path::new(&path).is_file() {
+            return Err(format!("Filter file {} does not exist", path));
+        }
+        format!("--filter={}", path)
+    };
+    let prio_arg = format!("--prio={}", priority);
+    run_remote_command(&["remote-modify", remote.installation.flag(), &prio_arg, &filter_arg, &remote.name]).await
+}
+
+// --force deletes it even while apps are installed from it; those stop receiving updates
+pub async fn remove(remote: FlatpakRemote) -> Result<String, String> {
+    run_remote_command(&["remote-delete", remote.installation.flag(), "--force", &remote.name]).await
+}
@@ -8,6 +8,7 @@ use tokio::process::Command as TokioCommand;
 use std::path::PathBuf;
 use tokio::fs;
 use crate::gui::dialog_design::DialogDesign;
+use crate::gui::flatpak_remotes::Installation;
 use iced::Color;
 
 #[derive(Debug, Clone)]
@@ -32,6 +33,7 @@ pub struct FlatpakInfo {
 #[derive(Debug)]
 pub struct FlatpakRemoveDialog {
     pub application_ids: Vec<String>,
+    pub installation: Option<Installation>,
     pub flatpak_info: Option<Vec<FlatpakInfo>>,
     pub is_loading: bool,
     pub is_removing: bool,
@@ -41,9 +43,10 @@ pub struct FlatpakRemoveDialog {
 }
 
 impl FlatpakRemoveDialog {
-    pub fn new(application_ids: Vec<String>) -> Self {
+    pub fn new(application_ids: Vec<String>, installation: Option<Installation>) -> Self {
         Self {
             application_ids,
+            installation,
             flatpak_info: None,
             is_loading: true,
             is_removing: false,
@@ -53,8 +56,8 @@ impl FlatpakRemoveDialog {
         }
     }
 
-    pub fn run_separate_window(application_ids: Vec<String>) -> Result<(), iced::Error> {
-        let dialog = Self::new(application_ids);
+    pub fn run_separate_window(application_ids: Vec<String>, installation: Option<Installation>) -> Result<(), iced::Error> {
+        let dialog = Self::new(application_ids, installation);
 
         let mut window_settings = iced::window::Settings::default();
         window_settings.size = iced::Size::new(600.0, 600.0);
@@ -386,7 +389,7 @@ impl Application for FlatpakRemoveDialog {
             Message::LoadFlatpakInfo => {
                 self.is_loading = true;
                 let app_ids = self.application_ids.clone();
-                iced::Command::perform(load_flatpak_infos(app_ids), |result| {
+                iced::Command::perform(load_flatpak_infos(app_ids, self.installation), |result| {
                     match result {
                         Ok(infos) => Message::FlatpakInfoLoaded(infos),
                         Err(e) => Message::RemovalError(e.to_string()),
@@ -402,7 +405,7 @@ impl Application for FlatpakRemoveDialog {
                 self.is_removing = true;
                 self.removal_progress = "Preparing removal...".to_string();
                 let app_ids = self.application_ids.clone();
-                iced::Command::perform(remove_flatpaks(app_ids), |result| {
+                iced::Command::perform(remove_flatpaks(app_ids, self.installation), |result| {
                     match result {
                         Ok(progress) => Message::RemovalProgress(progress),
                         Err(e) => Message::RemovalError(e.to_string()),
@@ -447,12 +450,14 @@ impl Application for FlatpakRemoveDialog {
     }
 }
 
-async fn load_flatpak_infos(application_ids: Vec<String>) -> Result<Vec<FlatpakInfo>, String> {
+async fn load_flatpak_infos(application_ids: Vec<String>, installation: Option<Installation>) -> Result<Vec<FlatpakInfo>, String> {
     let mut infos = Vec::new();
 
     for app_id in application_ids {
         let output = TokioCommand::new("flatpak")
-            .args(["info", &app_id])
+            .arg("info")
+            .args(installation.map(|i| i.flag()))
+            .arg(&app_id)
             .output()
             .await
             .map_err(|e| format!("Failed to execute flatpak info: {}", e))?;
@@ -520,11 +525,14 @@ async fn write_flatpak_remove_log(application_ids: &[String], output: &str, succ
     }
 }
 
-async fn remove_flatpaks(application_ids: Vec<String>) -> Result<String, String> {
-    let command_str = format!("flatpak uninstall -y --noninteractive {}", application_ids.join(" "));
+async fn remove_flatpaks(application_ids: Vec<String>, installation: Option<Installation>) -> Result<String, String> {
+    let flag = installation.map(|i| format!("{} ", i.flag())).unwrap_or_default();
+    let command_str = format!("flatpak uninstall {}-y --noninteractive {}", flag, application_ids.join(" "));
 
     let output = TokioCommand::new("flatpak")
-        .args(["uninstall", "-y", "--noninteractive"])
+        .arg("uninstall")
+        .args(installation.map(|i| i.flag()))
+        .args(["-y", "--noninteractive"])
         .args(&application_ids)
         .output()
         .await
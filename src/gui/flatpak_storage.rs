@@ -0,0 +1,256 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use tokio::process::Command as TokioCommand;
+
+use crate::gui::flatpak_remotes::{run_flatpak, Installation};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefKind {
+    App,
+    Runtime,
+}
+
+#[derive(Debug, Clone)]
+pub struct StorageEntry {
+    pub name: String,
+    pub id: String,
+    pub branch: String,
+    pub kind: RefKind,
+    pub installation: Installation,
+    pub size: u64,
+    // ~/.var/app/<id>; counted once even when the app is in both installations
+    pub data_size: u64,
+    pub unused: bool,
+    pub eol: Option<String>,
+}
+
+// Data left in ~/.var/app by apps that are no longer installed
+#[derive(Debug, Clone)]
+pub struct LeftoverData {
+    pub app_id: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FlatpakStorage {
+    pub entries: Vec<StorageEntry>,
+    pub leftover_data: Vec<LeftoverData>,
+}
+
+impl FlatpakStorage {
+    pub fn total(&self, kind: RefKind) -> u64 {
+        self.entries.iter().filter(|e| e.kind == kind).map(|e| e.size).sum()
+    }
+
+    pub fn data_total(&self) -> u64 {
+        self.entries.iter().map(|e| e.data_size).sum::<u64>() + self.leftover_size()
+    }
+
+    pub fn unused(&self) -> impl Iterator<Item = &StorageEntry> {
+        self.entries.iter().filter(|e| e.unused)
+    }
+
+    pub fn unused_size(&self) -> u64 {
+        self.unused().map(|e| e.size).sum()
+    }
+
+    pub fn leftover_size(&self) -> u64 {
+        self.leftover_data.iter().map(|d| d.size).sum()
+    }
+}
+
+// Shared by the Flatpak tab and the storage view; flatpak uses decimal units
+pub fn format_size(bytes: u64) -> String {
+    let bytes = bytes as f64;
+    if bytes >= 1e9 {
+        format!("{:.1} GB", bytes / 1e9)
+    } else if bytes >= 1e6 {
+        format!("{:.1} MB", bytes / 1e6)
+    } else {
+        format!("{:.1} kB", bytes / 1e3)
+    }
+}
+
+// Sizes as flatpak prints them: "1.2 GB", "980.1 kB", "512 bytes"; None when unreadable
+pub fn parse_size(size: &str) -> Option<u64> {
+    let mut parts = size.split_whitespace();
+    let value: f64 = parts.next()?.replace(',', ".").parse().ok()?;
+    let factor = match parts.next().unwrap_or("bytes") {
+        "bytes" | "B" => 1.0,
+        "kB" | "KB" => 1e3,
+        "MB" => 1e6,
+        "GB" => 1e9,
+        _ => return None,
+    };
+    Some((value * factor) as u64)
+}
+
+fn app_data_dir() -> PathBuf {
+    PathBuf::from(std::env::var("HOME").unwrap_or_default()).join(".var/app")
+}
+
+async fn app_data_sizes() -> HashMap<String, u64> {
+    let Ok(entries) = std::fs::read_dir(app_data_dir()) else {
+        return HashMap::new();
+    };
+    let paths: Vec<PathBuf> = entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()).collect();
+    if paths.is_empty() {
+        return HashMap::new();
+    }
+    // du exits non-zero on unreadable files but still prints totals
+    let output = TokioCommand::new("du").arg("-sb").arg("--").args(&paths).output().await;
+    let Ok(output) = output else {
+        return HashMap::new();
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (size, path) = line.split_once('\t')?;
+            let app_id = std::path::Path::new(path.trim()).file_name()?.to_string_lossy().to_string();
+            Some((app_id, size.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+// Installed apps as (installation, ref); the ref column of `flatpak list` has no app/ or
+// runtime/ prefix, so apps have to be listed separately to tell them from runtimes
+pub async fn app_refs() -> Result<HashSet<(String, String)>, String> {
+    let output = TokioCommand::new("flatpak")
+        .args(["list", "--app", "--columns=ref,installation"])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute flatpak list: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("Failed to list Flatpak apps: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (flatpak_ref, installation) = line.split_once('\t')?;
+            Some((installation.trim().to_string(), flatpak_ref.trim().to_string()))
+        })
+        .collect())
+}
+
+pub async fn load() -> Result<FlatpakStorage, String> {
+    // ref stays last so rows keep every column when runtime is empty
+    let output = TokioCommand::new("flatpak")
+        .args(["list", "--columns=name,application,branch,installation,size,options,runtime,ref"])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute flatpak list: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("Failed to list Flatpaks: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let rows: Vec<Vec<&str>> = stdout
+        .lines()
+        .map(|line| line.split('\t').map(str::trim).collect::<Vec<_>>())
+        .filter(|parts| parts.len() >= 8 && !parts[1].is_empty())
+        .collect();
+
+    let app_refs = app_refs().await?;
+    let is_app = |parts: &[&str]| app_refs.contains(&(parts[3].to_string(), parts[7].to_string()));
+    let app_ids: HashSet<String> = app_refs
+        .iter()
+        .filter_map(|(_, flatpak_ref)| flatpak_ref.split('/').next().map(str::to_string))
+        .collect();
+
+    // Runtimes apps run on, as (id, branch)
+    let mut used_runtimes: HashSet<(String, String)> = HashSet::new();
+    for parts in rows.iter().filter(|p| is_app(p)) {
+        let mut runtime = parts[6].split('/');
+        if let (Some(id), Some(_arch), Some(branch)) = (runtime.next(), runtime.next(), runtime.next()) {
+            used_runtimes.insert((id.to_string(), branch.to_string()));
+        }
+    }
+
+    let mut data_sizes = app_data_sizes().await;
+    let mut entries: Vec<StorageEntry> = rows
+        .iter()
+        .map(|parts| {
+            let id = parts[1].to_string();
+            let branch = parts[2].to_string();
+            let kind = if is_app(parts) { RefKind::App } else { RefKind::Runtime };
+            // Extensions (GL drivers, codecs, locales, app plugins) are named after what they
+            // extend, which the list does not link up reliably, so they are never flagged.
+            // This is only a hint: `flatpak uninstall --unused` decides for itself what to remove
+            let is_extension = rows.iter().any(|other| other[1] != id && id.starts_with(&format!("{}.", other[1])));
+            let unused = kind == RefKind::Runtime
+                && !is_extension
+                && !used_runtimes.contains(&(id.clone(), branch.clone()));
+            StorageEntry {
+                name: if parts[0].is_empty() { id.clone() } else { parts[0].to_string() },
+                installation: Installation::from_label(parts[3]).unwrap_or(Installation::System),
+                size: parse_size(parts[4]).unwrap_or(0),
+                data_size: if kind == RefKind::App { data_sizes.remove(&id).unwrap_or(0) } else { 0 },
+                eol: parts[5].split(',').find_map(|o| o.trim().strip_prefix("eol=")).map(str::to_string),
+                unused,
+                kind,
+                branch,
+                id,
+            }
+        })
+        .collect();
+    entries.sort_by_key(|e| std::cmp::Reverse(e.size + e.data_size));
+
+    let mut leftover_data: Vec<LeftoverData> = data_sizes
+        .into_iter()
+        .filter(|(app_id, _)| !app_ids.contains(app_id))
+        .map(|(app_id, size)| LeftoverData { app_id, size })
+        .collect();
+    leftover_data.sort_by_key(|d| std::cmp::Reverse(d.size));
+
+    Ok(FlatpakStorage { entries, leftover_data })
+}
+
+pub async fn remove_unused() -> Result<String, String> {
+    let mut output = Vec::new();
+    for installation in [Installation::System, Installation::User] {
+        let result = run_flatpak(&["uninstall", installation.flag(), "--unused", "-y", "--noninteractive"]).await?;
+        if !result.trim().is_empty() {
+            output.push(result.trim().to_string());
+        }
+    }
+    Ok(output.join("\n"))
+}
+
+// Only removes data of apps that are not installed in either installation
+pub async fn delete_app_data(app_ids: Vec<String>) -> Result<String, String> {
+    let mut removed = Vec::new();
+    for app_id in app_ids {
+        if app_id.is_empty() || app_id.contains('/') || app_id.starts_with('.') {
+            return Err(format!("\"{}\" is not a valid application ID", app_id));
+        }
+        let installed = TokioCommand::new("flatpak")
+            .args(["info", &app_id])
+            .output()
+            .await
+            .map(|o| o.status.success())
+            .unwrap_or(true);
+        if installed {
+            return Err(format!("{} is still installed; uninstall it first", app_id));
+        }
+        tokio::fs::remove_dir_all(app_data_dir().join(&app_id))
+            .await
+            .map_err(|e| format!("Failed to delete data of {}: {}", app_id, e))?;
+        removed.push(app_id);
+    }
+    Ok(format!("Deleted app data of {}", removed.join(", ")))
+}
+
+// Repairing the system installation has to run as root
+pub async fn repair() -> Result<String, String> {
+    let user = run_flatpak(&["repair", "--user"]).await?;
+    let output = TokioCommand::new("pkexec")
+        .args(["flatpak", "repair", "--system"])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute pkexec: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("flatpak repair failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    let system = String::from_utf8_lossy(&output.stdout);
+    Ok(format!("{}\n{}", user.trim(), system.trim()).trim().to_string())
+}
@@ -1,6 +1,7 @@
 use iced::widget::{button, column, container, progress_bar, row, scrollable, text, Space};
 use iced::{Alignment, Application, Command, Element, Length, Border, Theme as IcedTheme, Color};
 use crate::gui::dialog_design::DialogDesign;
+use crate::gui::flatpak_remotes::Installation;
 use iced::widget::container::Appearance;
 use iced::widget::button::Appearance as ButtonAppearance;
 use iced::widget::button::StyleSheet as ButtonStyleSheet;
@@ -16,6 +17,14 @@ pub struct FlatpakUpdateInfo {
     pub application_id: String,
     pub version: String,
     pub remote: Option<String>,
+    // Full ref so runtimes and extensions update too; older callers only pass application_id
+    #[serde(default)]
+    pub flatpak_ref: String,
+    #[serde(default)]
+    pub download_size: String,
+    // "user" or "system"; refs can exist in both, so updates run once per installation
+    #[serde(default)]
+    pub installation: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -119,6 +128,7 @@ impl FlatpakUpdateDialog {
                                     row![
                                         text(&pkg.name).size(body_size).width(Length::FillPortion(3)),
                                         text(&pkg.version).size(body_size * 0.9).width(Length::FillPortion(2)),
+                                        text(&pkg.download_size).size(body_size * 0.9).width(Length::FillPortion(1)),
                                         text(pkg.remote.as_deref().unwrap_or("local"))
                                             .size(body_size * 0.9)
                                             .width(Length::FillPortion(2))
@@ -348,11 +358,17 @@ impl Application for FlatpakUpdateDialog {
                 self.progress_text = "Starting update...".to_string();
                 self.terminal_output = String::new();
 
-                let packages_to_update: Vec<String> = self.packages.iter()
-                    .map(|p| p.application_id.clone())
-                    .collect();
+                let mut groups: Vec<(Option<Installation>, Vec<String>)> = Vec::new();
+                for p in &self.packages {
+                    let installation = p.installation.as_deref().and_then(Installation::from_label);
+                    let package = if p.flatpak_ref.is_empty() { p.application_id.clone() } else { p.flatpak_ref.clone() };
+                    match groups.iter_mut().find(|(i, _)| *i == installation) {
+                        Some((_, packages)) => packages.push(package),
+                        None => groups.push((installation, vec![package])),
+                    }
+                }
 
-                iced::Command::perform(update_flatpaks_streaming(packages_to_update), |result| {
+                iced::Command::perform(update_installations(groups), |result| {
                     match result {
                         Ok(output) => Message::UpdateProgress(output),
                         Err(e) => Message::UpdateError(e),
@@ -422,16 +438,26 @@ impl Application for FlatpakUpdateDialog {
     }
 }
 
-async fn update_flatpaks_streaming(packages: Vec<String>) -> Result<String, String> {
+async fn update_installations(groups: Vec<(Option<Installation>, Vec<String>)>) -> Result<String, String> {
+    let mut outputs = Vec::new();
+    for (installation, packages) in groups {
+        outputs.push(update_flatpaks_streaming(packages, installation).await?);
+    }
+    Ok(outputs.join("\n\n"))
+}
+
+async fn update_flatpaks_streaming(packages: Vec<String>, installation: Option<Installation>) -> Result<String, String> {
     let mut cmd = TokioCommand::new("flatpak");
-    cmd.args(["update", "--app", "-y", "--noninteractive", "--verbose"]);
+    // No --app/--runtime: explicit refs already name their kind, and an empty list updates everything
+    let scope: Vec<&str> = installation.map(|i| i.flag()).into_iter().collect();
+    cmd.arg("update").args(&scope).args(["-y", "--noninteractive", "--verbose"]);
 
     if !packages.is_empty() {
         cmd.args(&packages);
     }
 
-    let command_str = format!("flatpak update --app -y --noninteractive --verbose {}",
-        packages.join(" "));
+    let command_str = format!("flatpak update {} -y --noninteractive --verbose {}",
+        scope.join(" "), packages.join(" "));
 
     cmd.stdout(std::process::Stdio::piped());
     cmd.stderr(std::process::Stdio::piped());
@@ -196,4 +196,6 @@ pub mod glyphs {
     pub const FOLDER_SYMBOL: &str = "\u{E2C7}"; // folder
     pub const INFO_SYMBOL: &str = "\u{E88E}"; // info
     pub const COPY_SYMBOL: &str = "\u{E14D}"; // content_copy
+    pub const SAVE_SYMBOL: &str = "\u{E161}"; // save
+    pub const CLOUD_SYMBOL: &str = "\u{E2BD}"; // cloud
 }
@@ -0,0 +1,371 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+
+use crate::cpu::CpuInfo;
+use crate::gui::tabs::device::{
+    get_driver_version, get_pci_class_name, get_pci_devices, get_usb_class_name, get_usb_devices,
+    parse_pci_profiles, parse_usb_profiles, PreCheckedPciProfile, PreCheckedUsbProfile,
+};
+
+// Packages that are almost always relevant in GPU/driver bug reports
+const BASE_PACKAGES: &[&str] = &[
+    "kernel",
+    "kernel-core",
+    "linux-firmware",
+    "mesa-dri-drivers",
+    "mesa-vulkan-drivers",
+    "akmod-nvidia",
+    "xorg-x11-drv-nvidia",
+    "nvidia-driver",
+    "akmods",
+    "mokutil",
+    "systemd",
+    "dnf",
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HardwareReport {
+    pub generated_at: String,
+    pub system: SystemInfo,
+    pub pci_devices: Vec<ReportDevice>,
+    pub usb_devices: Vec<ReportDevice>,
+    pub packages: Vec<PackageVersion>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SystemInfo {
+    pub os: String,
+    pub kernel: String,
+    pub cpu_model: String,
+    pub cpu_vendor: String,
+    pub cpu_microarch: String,
+    pub cpu_family: u32,
+    pub cpu_model_id: u32,
+    pub cpu_level: String,
+    pub secure_boot: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportDevice {
+    pub class: String,
+    pub vendor: String,
+    pub name: String,
+    pub vendor_id: String,
+    pub device_id: String,
+    pub bus_id: String,
+    pub driver: String,
+    pub driver_version: Option<String>,
+    pub firmware_version: Option<String>,
+    pub installed_profiles: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PackageVersion {
+    pub name: String,
+    pub version: Option<String>,
+}
+
+impl HardwareReport {
+    // Gathers everything synchronously; callers in the GUI should run this on a blocking thread
+    pub fn collect() -> Self {
+        let cpu = CpuInfo::detect();
+        let system = SystemInfo {
+            os: read_os_name(),
+            kernel: command_stdout("uname", &["-r"]).unwrap_or_else(|| "unknown".to_string()),
+            cpu_model: cpu.model_name.clone(),
+            cpu_vendor: cpu.vendor.label().to_string(),
+            cpu_microarch: cpu.microarch.label().to_string(),
+            cpu_family: cpu.family,
+            cpu_model_id: cpu.model,
+            cpu_level: cpu.level.label().to_string(),
+            secure_boot: command_stdout("mokutil", &["--sb-state"]),
+        };
+
+        // Only profiles already cached by the Device tab are used, the report never hits the network
+        let pci_profiles: Vec<Arc<PreCheckedPciProfile>> = std::fs::read_to_string("/var/cache/cfhdb/pci.json")
+            .ok()
+            .and_then(|data| parse_pci_profiles(&data).ok())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|p| Arc::new(PreCheckedPciProfile::new(p)))
+            .collect();
+        let usb_profiles: Vec<Arc<PreCheckedUsbProfile>> = std::fs::read_to_string("/var/cache/cfhdb/usb.json")
+            .ok()
+            .and_then(|data| parse_usb_profiles(&data).ok())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|p| Arc::new(PreCheckedUsbProfile::new(p)))
+            .collect();
+
+        let mut profile_packages: Vec<String> = Vec::new();
+
+        let mut pci_devices = Vec::new();
+        let mut pci_classes: Vec<_> = get_pci_devices(&pci_profiles).unwrap_or_default().into_iter().collect();
+        pci_classes.sort_by(|a, b| a.0.cmp(&b.0));
+        for (class, devices) in pci_classes {
+            for device in devices {
+                let mut installed_profiles = Vec::new();
+                for profile in &device.profiles {
+                    profile.update_installed();
+                    if profile.installed() {
+                        installed_profiles.push(profile.profile().codename.clone());
+                        if let Some(ref packages) = profile.profile().packages {
+                            profile_packages.extend(packages.iter().cloned());
+                        }
+                    }
+                }
+                let d = &device.device;
+                pci_devices.push(ReportDevice {
+                    class: get_pci_class_name(&class),
+                    vendor: d.vendor_name.clone(),
+                    name: d.device_name.clone(),
+                    vendor_id: d.vendor_id.clone(),
+                    device_id: d.device_id.clone(),
+                    bus_id: d.sysfs_busid.clone(),
+                    driver: d.kernel_driver.clone(),
+                    driver_version: non_empty(get_driver_version(&d.kernel_driver)),
+                    firmware_version: pci_firmware_version(&d.sysfs_busid, &d.kernel_driver),
+                    installed_profiles,
+                });
+            }
+        }
+
+        let mut usb_devices = Vec::new();
+        let mut usb_classes: Vec<_> = get_usb_devices(&usb_profiles).unwrap_or_default().into_iter().collect();
+        usb_classes.sort_by(|a, b| a.0.cmp(&b.0));
+        for (class, devices) in usb_classes {
+            for device in devices {
+                let mut installed_profiles = Vec::new();
+                for profile in &device.profiles {
+                    profile.update_installed();
+                    if profile.installed() {
+                        installed_profiles.push(profile.profile().codename.clone());
+                        if let Some(ref packages) = profile.profile().packages {
+                            profile_packages.extend(packages.iter().cloned());
+                        }
+                    }
+                }
+                let d = &device.device;
+                usb_devices.push(ReportDevice {
+                    class: get_usb_class_name(&class),
+                    vendor: d.manufacturer_string_index.clone(),
+                    name: d.product_string_index.clone(),
+                    vendor_id: d.vendor_id.clone(),
+                    device_id: d.product_id.clone(),
+                    bus_id: d.sysfs_busid.clone(),
+                    driver: d.kernel_driver.clone(),
+                    driver_version: non_empty(get_driver_version(&d.kernel_driver)),
+                    firmware_version: usb_firmware_version(&d.sysfs_busid),
+                    installed_profiles,
+                });
+            }
+        }
+
+        let mut package_names: Vec<String> = BASE_PACKAGES.iter().map(|s| s.to_string()).collect();
+        for pkg in profile_packages {
+            if !package_names.contains(&pkg) {
+                package_names.push(pkg);
+            }
+        }
+        let packages = package_names
+            .into_iter()
+            .map(|name| {
+                let version = query_package_version(&name);
+                PackageVersion { name, version }
+            })
+            .collect();
+
+        Self {
+            generated_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S %Z").to_string(),
+            system,
+            pci_devices,
+            usb_devices,
+            packages,
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| format!("Failed to serialize report: {}", e))
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+        md.push_str("# Rustora hardware report\n\n");
+        md.push_str(&format!("Generated: {}\n\n", self.generated_at));
+
+        md.push_str("## System\n\n");
+        md.push_str("| | |\n|---|---|\n");
+        md.push_str(&format!("| OS | {} |\n", escape_cell(&self.system.os)));
+        md.push_str(&format!("| Kernel | `{}` |\n", escape_cell(&self.system.kernel)));
+        md.push_str(&format!("| CPU | {} |\n", escape_cell(&self.system.cpu_model)));
+        md.push_str(&format!(
+            "| CPU architecture | {} {} (family {:#x}, model {:#x}), {} |\n",
+            self.system.cpu_vendor,
+            self.system.cpu_microarch,
+            self.system.cpu_family,
+            self.system.cpu_model_id,
+            self.system.cpu_level
+        ));
+        md.push_str(&format!(
+            "| Secure Boot | {} |\n",
+            escape_cell(self.system.secure_boot.as_deref().unwrap_or("unknown"))
+        ));
+        md.push('\n');
+
+        md.push_str("## PCI devices\n\n");
+        push_device_table(&mut md, &self.pci_devices);
+
+        md.push_str("## USB devices\n\n");
+        push_device_table(&mut md, &self.usb_devices);
+
+        md.push_str("## Package versions\n\n");
+        md.push_str("| Package | Version |\n|---|---|\n");
+        for pkg in &self.packages {
+            md.push_str(&format!(
+                "| {} | {} |\n",
+                pkg.name,
+                pkg.version.as_deref().unwrap_or("not installed")
+            ));
+        }
+        md
+    }
+}
+
+// Writes both Markdown and JSON copies of the report to ~/.rustora and returns their paths
+pub fn export_report(report: &HardwareReport) -> Result<(PathBuf, PathBuf), String> {
+    let home = std::env::var("HOME").map_err(|_| "HOME not set".to_string())?;
+    let report_dir = PathBuf::from(home).join(".rustora");
+    std::fs::create_dir_all(&report_dir)
+        .map_err(|e| format!("Failed to create {}: {}", report_dir.display(), e))?;
+
+    let timestamp = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S");
+    let md_path = report_dir.join(format!("hardware-report_{}.md", timestamp));
+    let json_path = report_dir.join(format!("hardware-report_{}.json", timestamp));
+
+    write_file(&md_path, &report.to_markdown())?;
+    write_file(&json_path, &report.to_json()?)?;
+    Ok((md_path, json_path))
+}
+
+fn write_file(path: &Path, content: &str) -> Result<(), String> {
+    std::fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn push_device_table(md: &mut String, devices: &[ReportDevice]) {
+    if devices.is_empty() {
+        md.push_str("_No devices found_\n\n");
+        return;
+    }
+    md.push_str("| Bus ID | Class | Device | IDs | Driver | Driver version | Firmware | Installed profiles |\n");
+    md.push_str("|---|---|---|---|---|---|---|---|\n");
+    for d in devices {
+        md.push_str(&format!(
+            "| `{}` | {} | {} {} | `{}:{}` | {} | {} | {} | {} |\n",
+            d.bus_id,
+            escape_cell(&d.class),
+            escape_cell(&d.vendor),
+            escape_cell(&d.name),
+            d.vendor_id,
+            d.device_id,
+            if d.driver.is_empty() { "none" } else { &d.driver },
+            d.driver_version.as_deref().unwrap_or("-"),
+            escape_cell(d.firmware_version.as_deref().unwrap_or("-")),
+            if d.installed_profiles.is_empty() { "-".to_string() } else { d.installed_profiles.join(", ") },
+        ));
+    }
+    md.push('\n');
+}
+
+fn escape_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+fn non_empty(value: String) -> Option<String> {
+    if value.trim().is_empty() { None } else { Some(value) }
+}
+
+fn command_stdout(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    non_empty(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn read_os_name() -> String {
+    std::fs::read_to_string("/etc/os-release")
+        .ok()
+        .and_then(|content| {
+            content
+                .lines()
+                .find_map(|line| line.strip_prefix("PRETTY_NAME="))
+                .map(|v| v.trim_matches('"').to_string())
+        })
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn query_package_version(package: &str) -> Option<String> {
+    // Multiple installed versions (e.g. kernels) are reported together
+    let output = Command::new("rpm")
+        .args(["-q", "--queryformat", "%{VERSION}-%{RELEASE}.%{ARCH}\\n", package])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let versions: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect();
+    if versions.is_empty() { None } else { Some(versions.join(", ")) }
+}
+
+fn pci_firmware_version(bus_id: &str, driver: &str) -> Option<String> {
+    let sysfs = PathBuf::from("/sys/bus/pci/devices").join(bus_id);
+
+    // amdgpu and some other GPU drivers expose the VBIOS directly
+    if let Ok(vbios) = std::fs::read_to_string(sysfs.join("vbios_version")) {
+        if let Some(v) = non_empty(vbios.trim().to_string()) {
+            return Some(format!("VBIOS {}", v));
+        }
+    }
+
+    if driver == "nvidia" {
+        let info_path = PathBuf::from("/proc/driver/nvidia/gpus").join(bus_id).join("information");
+        if let Ok(info) = std::fs::read_to_string(info_path) {
+            if let Some(line) = info.lines().find(|l| l.starts_with("Video BIOS:")) {
+                return Some(format!("VBIOS {}", line.trim_start_matches("Video BIOS:").trim()));
+            }
+        }
+    }
+
+    // Network adapters report firmware through ethtool
+    if let Ok(entries) = std::fs::read_dir(sysfs.join("net")) {
+        for entry in entries.flatten() {
+            let iface = entry.file_name().to_string_lossy().to_string();
+            if let Some(info) = command_stdout("ethtool", &["-i", &iface]) {
+                if let Some(fw) = info.lines().find_map(|l| l.strip_prefix("firmware-version:")) {
+                    if let Some(fw) = non_empty(fw.trim().to_string()) {
+                        return Some(fw);
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+fn usb_firmware_version(bus_id: &str) -> Option<String> {
+    // bcdDevice is the device release number, which vendors use as the firmware revision
+    let bcd = std::fs::read_to_string(PathBuf::from("/sys/bus/usb/devices").join(bus_id).join("bcdDevice")).ok()?;
+    let bcd = bcd.trim();
+    if bcd.len() == 4 {
+        Some(format!("rev {}.{}", &bcd[..2], &bcd[2..]))
+    } else {
+        non_empty(bcd.to_string())
+    }
+}
@@ -0,0 +1,284 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+// One place a launcher may be installed. `~` expands to $HOME; `markers` are files that
+// must exist inside `directory`, `detect` is a path that must exist when the directory
+// itself may not (the launcher reads builds from a directory it does not create)
+pub struct LauncherInstall {
+    pub directory: &'static str,
+    pub installation_type: &'static str,
+    pub markers: &'static [&'static str],
+    pub detect: Option<&'static str>,
+}
+
+pub struct LauncherDefinition {
+    // Matches the `launcher` key of directory_name_formats in runners.json
+    pub title: &'static str,
+    pub installs: &'static [LauncherInstall],
+    // Compat layer type -> directory below the launcher directory
+    pub compat_dirs: &'static [(&'static str, &'static str)],
+}
+
+impl LauncherDefinition {
+    pub fn compat_subdir(&self, compat_layer_type: &str) -> Option<&'static str> {
+        self.compat_dirs.iter()
+            .find(|(layer, _)| *layer == compat_layer_type)
+            .map(|(_, dir)| *dir)
+    }
+}
+
+const STEAM_MARKERS: &[&str] = &["steamclient.dll", "steamclient64.dll"];
+
+pub const LAUNCHERS: &[LauncherDefinition] = &[
+    LauncherDefinition {
+        title: "Steam",
+        installs: &[
+            LauncherInstall { directory: "~/.local/share/Steam", installation_type: "system", markers: STEAM_MARKERS, detect: None },
+            LauncherInstall { directory: "~/.steam/steam", installation_type: "system", markers: STEAM_MARKERS, detect: None },
+            LauncherInstall { directory: "~/.steam/root", installation_type: "system", markers: STEAM_MARKERS, detect: None },
+            LauncherInstall { directory: "~/.steam/debian-installation", installation_type: "system", markers: STEAM_MARKERS, detect: None },
+            LauncherInstall { directory: "~/.var/app/com.valvesoftware.Steam/.local/share/Steam", installation_type: "flatpak", markers: STEAM_MARKERS, detect: None },
+            LauncherInstall { directory: "~/.var/app/com.valvesoftware.Steam/data/Steam", installation_type: "flatpak", markers: STEAM_MARKERS, detect: None },
+            LauncherInstall { directory: "~/snap/steam/common/.local/share/Steam", installation_type: "snap", markers: STEAM_MARKERS, detect: None },
+            LauncherInstall { directory: "/snap/steam/common/.steam/root", installation_type: "snap", markers: STEAM_MARKERS, detect: None },
+        ],
+        // Steam only loads Proton-style tools from compatibilitytools.d
+        compat_dirs: &[("Proton", "/compatibilitytools.d")],
+    },
+    LauncherDefinition {
+        title: "Lutris",
+        installs: &[
+            LauncherInstall { directory: "~/.local/share/lutris", installation_type: "system", markers: &[], detect: None },
+            LauncherInstall { directory: "~/.var/app/net.lutris.Lutris/data/lutris", installation_type: "flatpak", markers: &[], detect: None },
+        ],
+        compat_dirs: &[
+            ("Proton", "/runners/proton"),
+            ("Wine", "/runners/wine"),
+            ("DXVK", "/runtime/dxvk"),
+            ("VKD3D", "/runtime/vkd3d"),
+        ],
+    },
+    LauncherDefinition {
+        title: "Heroic Games Launcher",
+        installs: &[
+            LauncherInstall { directory: "~/.config/heroic", installation_type: "system", markers: &[], detect: None },
+            LauncherInstall { directory: "~/.var/app/com.heroicgameslauncher.hgl/config/heroic", installation_type: "flatpak", markers: &[], detect: None },
+        ],
+        compat_dirs: &[("Proton", "/tools/proton"), ("Wine", "/tools/wine")],
+    },
+    LauncherDefinition {
+        title: "Bottles",
+        installs: &[
+            LauncherInstall { directory: "~/.local/share/bottles", installation_type: "system", markers: &[], detect: None },
+            LauncherInstall { directory: "~/.var/app/com.usebottles.bottles/data/bottles", installation_type: "flatpak", markers: &[], detect: None },
+        ],
+        compat_dirs: &[("Proton", "/runners"), ("Wine", "/runners"), ("DXVK", "/dxvk")],
+    },
+    // Faugus runs games through UMU and lists the builds in Steam's compatibilitytools.d
+    LauncherDefinition {
+        title: "Faugus Launcher",
+        installs: &[
+            LauncherInstall { directory: "~/.local/share/Steam", installation_type: "system", markers: &[], detect: Some("~/.config/faugus-launcher") },
+            LauncherInstall { directory: "~/.var/app/io.github.Faugus.faugus-launcher/data/Steam", installation_type: "flatpak", markers: &[], detect: Some("~/.var/app/io.github.Faugus.faugus-launcher") },
+        ],
+        compat_dirs: &[("Proton", "/compatibilitytools.d")],
+    },
+    LauncherDefinition {
+        title: "WineZGUI",
+        installs: &[
+            LauncherInstall { directory: "~/.local/share/winezgui", installation_type: "system", markers: &[], detect: None },
+            LauncherInstall { directory: "~/.var/app/io.github.fastrizwaan.WineZGUI/data/winezgui", installation_type: "flatpak", markers: &[], detect: None },
+        ],
+        compat_dirs: &[("Wine", "/Runners")],
+    },
+];
+
+pub fn definition(kind: &str) -> Option<&'static LauncherDefinition> {
+    LAUNCHERS.iter().find(|d| d.title == kind)
+}
+
+#[derive(Debug, Clone)]
+pub struct DetectedLauncher {
+    // Unique per install: the definition title for the first install of a kind,
+    // "<kind> (<type>)" for the others, and the chosen label for custom directories
+    pub title: String,
+    // Definition the install belongs to, used for directory names and compat dirs
+    pub kind: String,
+    pub directory: String,
+    pub installation_type: String,
+}
+
+impl DetectedLauncher {
+    pub fn label(&self) -> String {
+        if self.title == self.kind {
+            format!("{} ({})", self.title, self.installation_type)
+        } else {
+            self.title.clone()
+        }
+    }
+
+    pub fn supports(&self, compat_layer_type: &str) -> bool {
+        self.compat_directory(compat_layer_type).is_some()
+    }
+
+    // Where builds of this compat layer type go, or None if the launcher cannot use them
+    pub fn compat_directory(&self, compat_layer_type: &str) -> Option<String> {
+        definition(&self.kind)
+            .and_then(|d| d.compat_subdir(compat_layer_type))
+            .map(|subdir| format!("{}{}", self.directory.trim_end_matches('/'), subdir))
+    }
+}
+
+// A directory the user pointed at, treated as an install of `kind`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomLauncher {
+    pub kind: String,
+    pub directory: String,
+}
+
+impl CustomLauncher {
+    pub fn title(&self) -> String {
+        let home = std::env::var("HOME").unwrap_or_default();
+        let shown = match self.directory.strip_prefix(&home) {
+            Some(rest) if !home.is_empty() => format!("~{}", rest),
+            _ => self.directory.clone(),
+        };
+        format!("{} ({})", self.kind, shown)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CustomLaunchers {
+    pub launchers: Vec<CustomLauncher>,
+}
+
+impl CustomLaunchers {
+    pub fn load() -> Self {
+        if let Ok(home) = std::env::var("HOME") {
+            let path = PathBuf::from(&home).join(".rustora").join("custom_launchers.json");
+            if let Ok(content) = std::fs::read_to_string(&path) {
+                if let Ok(launchers) = serde_json::from_str::<CustomLaunchers>(&content) {
+                    return launchers;
+                }
+            }
+        }
+        Self::default()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        if let Ok(home) = std::env::var("HOME") {
+            let settings_dir = PathBuf::from(&home).join(".rustora");
+            if let Err(e) = std::fs::create_dir_all(&settings_dir) {
+                return Err(format!("Failed to create settings directory: {}", e));
+            }
+            let path = settings_dir.join("custom_launchers.json");
+            let json = serde_json::to_string_pretty(self)
+                .map_err(|e| format!("Failed to serialize launchers: {}", e))?;
+            std::fs::write(&path, json)
+                .map_err(|e| format!("Failed to write launchers: {}", e))?;
+        }
+        Ok(())
+    }
+
+    pub fn add(&mut self, kind: &str, directory: &str) -> Result<(), String> {
+        if definition(kind).is_none() {
+            return Err(format!("Unknown launcher type: {}", kind));
+        }
+        let directory = expand_home(directory.trim());
+        let path = Path::new(&directory);
+        if !path.is_absolute() {
+            return Err("Enter an absolute path (or one starting with ~)".to_string());
+        }
+        if !path.is_dir() {
+            return Err(format!("{} is not a directory", directory));
+        }
+        let directory = directory.trim_end_matches('/').to_string();
+        if self.launchers.iter().any(|l| l.kind == kind && l.directory == directory) {
+            return Err(format!("{} is already added as {}", directory, kind));
+        }
+        self.launchers.push(CustomLauncher { kind: kind.to_string(), directory });
+        Ok(())
+    }
+
+    pub fn remove(&mut self, title: &str) {
+        self.launchers.retain(|l| l.title() != title);
+    }
+}
+
+fn expand_home(path: &str) -> String {
+    match path.strip_prefix('~') {
+        Some(rest) => format!("{}{}", std::env::var("HOME").unwrap_or_else(|_| "/home".to_string()), rest),
+        None => path.to_string(),
+    }
+}
+
+// ~/.steam/steam and friends are usually symlinks to ~/.local/share/Steam
+fn canonical(path: &str) -> String {
+    std::fs::canonicalize(path)
+        .map(|p| p.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| path.trim_end_matches('/').to_string())
+}
+
+fn install_present(install: &LauncherInstall, directory: &str) -> bool {
+    if let Some(detect) = install.detect {
+        return Path::new(&expand_home(detect)).exists();
+    }
+    Path::new(directory).is_dir()
+        && install.markers.iter().all(|marker| Path::new(directory).join(marker).exists())
+}
+
+// Every install of every known launcher, plus the user's custom directories
+pub fn detect() -> Vec<DetectedLauncher> {
+    let mut launchers: Vec<DetectedLauncher> = Vec::new();
+    let mut seen: Vec<(String, String)> = Vec::new();
+
+    for definition in LAUNCHERS {
+        for install in definition.installs {
+            let directory = expand_home(install.directory);
+            if !install_present(install, &directory) {
+                continue;
+            }
+            let key = (definition.title.to_string(), canonical(&directory));
+            if seen.contains(&key) {
+                continue;
+            }
+            seen.push(key);
+
+            let same_kind = launchers.iter().filter(|l| l.kind == definition.title).count();
+            let mut title = if same_kind == 0 {
+                definition.title.to_string()
+            } else {
+                format!("{} ({})", definition.title, install.installation_type)
+            };
+            if launchers.iter().any(|l| l.title == title) {
+                title = format!("{} ({})", definition.title, directory);
+            }
+            launchers.push(DetectedLauncher {
+                title,
+                kind: definition.title.to_string(),
+                directory,
+                installation_type: install.installation_type.to_string(),
+            });
+        }
+    }
+
+    for custom in CustomLaunchers::load().launchers {
+        let key = (custom.kind.clone(), canonical(&custom.directory));
+        if seen.contains(&key) || definition(&custom.kind).is_none() {
+            continue;
+        }
+        seen.push(key);
+        launchers.push(DetectedLauncher {
+            title: custom.title(),
+            kind: custom.kind.clone(),
+            directory: custom.directory.clone(),
+            installation_type: "custom".to_string(),
+        });
+    }
+
+    crate::logger::Logger::log_debug(&format!(
+        "[Launchers] Detected: {}",
+        launchers.iter().map(|l| format!("{} -> {}", l.title, l.directory)).collect::<Vec<_>>().join(", ")
+    ));
+    launchers
+}
@@ -116,9 +116,23 @@ impl Application for MaintenanceDialog {
                 let (cmd_name, args) = self.get_task_command();
                 let cmd_name = cmd_name.to_string();
                 let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+                // akmods output alone does not reveal unsigned modules under Secure Boot
+                let check_signatures = matches!(self.task, MaintenanceTask::RebuildKernelModules);
 
                 iced::Command::perform(
-                    run_maintenance_task_streaming(cmd_name, args),
+                    async move {
+                        let result = run_maintenance_task_streaming(cmd_name, args).await;
+                        if !check_signatures {
+                            return result;
+                        }
+                        // mokutil and modinfo block, keep them off the async runtime
+                        let report = tokio::task::spawn_blocking(crate::gui::secure_boot::post_rebuild_report)
+                            .await
+                            .unwrap_or_default();
+                        result
+                            .map(|out| format!("{}\n{}", out, report))
+                            .map_err(|e| format!("{}\n{}", e, report))
+                    },
                     |result| {
                         match result {
                             Ok(output) => Message::TaskProgress(output),
@@ -0,0 +1,379 @@
+use std::path::PathBuf;
+
+// Target name for ~/.config/MangoHud/MangoHud.conf; everything else is `<app>.conf`
+pub const GLOBAL_TARGET: &str = "All games (MangoHud.conf)";
+
+pub const POSITIONS: &[&str] = &[
+    "top-left",
+    "top-center",
+    "top-right",
+    "middle-left",
+    "middle-right",
+    "bottom-left",
+    "bottom-center",
+    "bottom-right",
+];
+
+// On/off options the editor shows, in the order MangoHud draws them
+pub const TOGGLES: &[(&str, &str)] = &[
+    ("gpu_stats", "GPU load"),
+    ("gpu_temp", "GPU temperature"),
+    ("gpu_power", "GPU power"),
+    ("gpu_core_clock", "GPU clock"),
+    ("vram", "VRAM"),
+    ("cpu_stats", "CPU load"),
+    ("cpu_temp", "CPU temperature"),
+    ("cpu_power", "CPU power"),
+    ("ram", "RAM"),
+    ("fps", "FPS"),
+    ("frametime", "Frame time"),
+    ("frame_timing", "Frame time graph"),
+    ("fps_only", "FPS only"),
+    ("gpu_name", "GPU name"),
+    ("vulkan_driver", "Driver"),
+    ("engine_version", "Engine"),
+    ("wine", "Wine/Proton version"),
+    ("gamemode", "GameMode status"),
+    ("resolution", "Resolution"),
+    ("time", "Clock"),
+    ("battery", "Battery"),
+];
+
+// key=value options with a free-form input
+pub const VALUES: &[(&str, &str)] = &[
+    ("font_size", "Font size"),
+    ("background_alpha", "Background opacity"),
+    ("fps_limit", "FPS limit"),
+    ("toggle_hud", "Toggle key"),
+    ("output_folder", "Log folder"),
+    ("log_duration", "Log duration (s)"),
+    ("toggle_logging", "Logging key"),
+];
+
+// MangoHud shows these unless they are set to 0
+const DEFAULT_ON: &[&str] = &["gpu_stats", "cpu_stats", "fps", "frametime", "frame_timing"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MangoHudPreset {
+    MinimalFps,
+    FullStats,
+    Benchmark,
+}
+
+impl MangoHudPreset {
+    pub const ALL: [MangoHudPreset; 3] = [MangoHudPreset::MinimalFps, MangoHudPreset::FullStats, MangoHudPreset::Benchmark];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MangoHudPreset::MinimalFps => "Minimal FPS",
+            MangoHudPreset::FullStats => "Full stats",
+            MangoHudPreset::Benchmark => "Benchmark logging",
+        }
+    }
+
+    fn entries(&self) -> Vec<(&'static str, Option<String>)> {
+        match self {
+            MangoHudPreset::MinimalFps => vec![
+                ("fps_only", None),
+                ("position", Some("top-left".to_string())),
+                ("font_size", Some("20".to_string())),
+                ("background_alpha", Some("0.3".to_string())),
+            ],
+            MangoHudPreset::FullStats => {
+                let mut entries: Vec<(&'static str, Option<String>)> = [
+                    "gpu_stats", "gpu_temp", "gpu_power", "gpu_core_clock", "vram", "cpu_stats", "cpu_temp",
+                    "cpu_power", "ram", "fps", "frametime", "frame_timing", "gpu_name", "vulkan_driver",
+                    "engine_version", "wine", "gamemode", "resolution",
+                ]
+                .iter()
+                .map(|key| (*key, None))
+                .collect();
+                entries.push(("position", Some("top-left".to_string())));
+                entries
+            }
+            MangoHudPreset::Benchmark => {
+                let folder = std::env::var("HOME")
+                    .map(|home| format!("{}/mangohud-logs", home))
+                    .unwrap_or_else(|_| "/tmp/mangohud-logs".to_string());
+                vec![
+                    ("fps", None),
+                    ("frametime", None),
+                    ("frame_timing", None),
+                    ("cpu_stats", None),
+                    ("gpu_stats", None),
+                    ("position", Some("top-right".to_string())),
+                    ("output_folder", Some(folder)),
+                    ("log_duration", Some("60".to_string())),
+                    ("toggle_logging", Some("Shift_L+F2".to_string())),
+                ]
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ConfigLine {
+    // Comments and blank lines, kept verbatim
+    Other(String),
+    Entry { key: String, value: Option<String> },
+}
+
+// Line-preserving view of a MangoHud config; keys the editor does not know stay untouched
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MangoHudConfig {
+    lines: Vec<ConfigLine>,
+}
+
+impl MangoHudConfig {
+    pub fn parse(content: &str) -> Self {
+        let lines = content
+            .lines()
+            .map(|line| {
+                let trimmed = line.trim();
+                if trimmed.is_empty() || trimmed.starts_with('#') {
+                    return ConfigLine::Other(line.to_string());
+                }
+                match trimmed.split_once('=') {
+                    Some((key, value)) => ConfigLine::Entry {
+                        key: key.trim().to_string(),
+                        value: Some(value.trim().to_string()),
+                    },
+                    None => ConfigLine::Entry { key: trimmed.to_string(), value: None },
+                }
+            })
+            .collect();
+        Self { lines }
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        for line in &self.lines {
+            match line {
+                ConfigLine::Other(text) => out.push_str(text),
+                ConfigLine::Entry { key, value: Some(value) } => out.push_str(&format!("{}={}", key, value)),
+                ConfigLine::Entry { key, value: None } => out.push_str(key),
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    // Some(None) for a bare flag, Some(Some(v)) for key=value, None when absent
+    pub fn get(&self, key: &str) -> Option<Option<&str>> {
+        self.lines.iter().rev().find_map(|line| match line {
+            ConfigLine::Entry { key: k, value } if k == key => Some(value.as_deref()),
+            _ => None,
+        })
+    }
+
+    pub fn value(&self, key: &str) -> Option<&str> {
+        self.get(key).flatten()
+    }
+
+    pub fn is_enabled(&self, key: &str) -> bool {
+        match self.get(key) {
+            Some(None) => true,
+            Some(Some(value)) => value != "0",
+            None => DEFAULT_ON.contains(&key) && !self.is_enabled("fps_only") && self.get("no_display").is_none(),
+        }
+    }
+
+    pub fn set(&mut self, key: &str, value: Option<String>) {
+        let mut replaced = false;
+        self.lines.retain_mut(|line| match line {
+            ConfigLine::Entry { key: k, value: v } if k == key => {
+                if replaced {
+                    return false;
+                }
+                *v = value.clone();
+                replaced = true;
+                true
+            }
+            _ => true,
+        });
+        if !replaced {
+            self.lines.push(ConfigLine::Entry { key: key.to_string(), value });
+        }
+    }
+
+    pub fn remove(&mut self, key: &str) {
+        self.lines.retain(|line| !matches!(line, ConfigLine::Entry { key: k, .. } if k == key));
+    }
+
+    pub fn set_enabled(&mut self, key: &str, enabled: bool) {
+        if enabled {
+            self.set(key, None);
+        } else if DEFAULT_ON.contains(&key) {
+            // Removing the key would bring back the default, so switch it off explicitly
+            self.set(key, Some("0".to_string()));
+        } else {
+            self.remove(key);
+        }
+    }
+
+    // Values from the editor: empty clears the key
+    pub fn set_value(&mut self, key: &str, value: &str) {
+        if value.trim().is_empty() {
+            self.remove(key);
+        } else {
+            self.set(key, Some(value.trim().to_string()));
+        }
+    }
+
+    pub fn apply_preset(&mut self, preset: MangoHudPreset) {
+        for (key, _) in TOGGLES.iter().chain(VALUES.iter()) {
+            self.remove(key);
+        }
+        self.remove("position");
+        let entries = preset.entries();
+        // fps_only hides everything else by itself; otherwise default rows need an explicit 0
+        if !entries.iter().any(|(k, _)| *k == "fps_only") {
+            for key in DEFAULT_ON {
+                if !entries.iter().any(|(k, _)| k == key) {
+                    self.set(key, Some("0".to_string()));
+                }
+            }
+        }
+        for (key, value) in entries {
+            self.set(key, value);
+        }
+    }
+
+    pub fn unknown_keys(&self) -> Vec<String> {
+        self.lines
+            .iter()
+            .filter_map(|line| match line {
+                ConfigLine::Entry { key, .. } => Some(key.clone()),
+                _ => None,
+            })
+            .filter(|key| key != "position" && !TOGGLES.iter().chain(VALUES.iter()).any(|(k, _)| k == key))
+            .collect()
+    }
+
+    // Rough text mock of the overlay, row by row as MangoHud lays it out
+    pub fn preview(&self) -> Vec<String> {
+        let on = |key: &str| self.is_enabled(key);
+        let mut rows = Vec::new();
+        if on("fps_only") {
+            rows.push("144".to_string());
+        } else {
+            if on("gpu_name") {
+                rows.push("AMD Radeon RX 7800 XT".to_string());
+            }
+            if on("gpu_stats") || on("gpu_temp") || on("gpu_power") || on("gpu_core_clock") {
+                let mut row = String::from("GPU ");
+                if on("gpu_stats") { row.push_str("  54%"); }
+                if on("gpu_temp") { row.push_str("  62°C"); }
+                if on("gpu_power") { row.push_str("  180W"); }
+                if on("gpu_core_clock") { row.push_str("  2430MHz"); }
+                rows.push(row);
+            }
+            if on("cpu_stats") || on("cpu_temp") || on("cpu_power") {
+                let mut row = String::from("CPU ");
+                if on("cpu_stats") { row.push_str("  31%"); }
+                if on("cpu_temp") { row.push_str("  58°C"); }
+                if on("cpu_power") { row.push_str("  45W"); }
+                rows.push(row);
+            }
+            if on("vram") {
+                rows.push("VRAM  6.1 GiB".to_string());
+            }
+            if on("ram") {
+                rows.push("RAM   9.4 GiB".to_string());
+            }
+            if on("fps") || on("frametime") {
+                let mut row = String::new();
+                if on("vulkan_driver") { row.push_str("VULKAN"); } else { row.push_str("FPS   "); }
+                if on("fps") { row.push_str("  144 FPS"); }
+                if on("frametime") { row.push_str("  6.9 ms"); }
+                rows.push(row);
+            }
+            if on("engine_version") {
+                rows.push("DXVK 2.5".to_string());
+            }
+            if on("wine") {
+                rows.push("GE-Proton10-4".to_string());
+            }
+            if on("frame_timing") {
+                rows.push("Frametime ▁▂▁▁▃▁▂▁▁▁▂▁".to_string());
+            }
+            if on("gamemode") {
+                rows.push("GAMEMODE ON".to_string());
+            }
+            if on("resolution") {
+                rows.push("2560x1440".to_string());
+            }
+            if on("time") {
+                rows.push("21:37".to_string());
+            }
+            if on("battery") {
+                rows.push("BATT  78%".to_string());
+            }
+        }
+        if let Some(limit) = self.value("fps_limit").filter(|v| *v != "0") {
+            rows.push(format!("(FPS limited to {})", limit));
+        }
+        rows
+    }
+}
+
+pub fn config_dir() -> Option<PathBuf> {
+    std::env::var("HOME").ok().map(|home| PathBuf::from(home).join(".config").join("MangoHud"))
+}
+
+pub fn config_path(target: &str) -> Option<PathBuf> {
+    let file = if target == GLOBAL_TARGET {
+        "MangoHud.conf".to_string()
+    } else {
+        format!("{}.conf", target)
+    };
+    config_dir().map(|dir| dir.join(file))
+}
+
+// Global config first, then every per-app config already on disk
+pub fn list_targets() -> Vec<String> {
+    let mut apps: Vec<String> = config_dir()
+        .and_then(|dir| std::fs::read_dir(dir).ok())
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    name.strip_suffix(".conf").map(String::from)
+                })
+                .filter(|name| name != "MangoHud")
+                .collect()
+        })
+        .unwrap_or_default();
+    apps.sort();
+    let mut targets = vec![GLOBAL_TARGET.to_string()];
+    targets.extend(apps);
+    targets
+}
+
+// MangoHud names per-app files after the executable (`wine-<exe>` for Wine games)
+pub fn valid_app_name(name: &str) -> bool {
+    !name.is_empty()
+        && name != "MangoHud"
+        && !name.contains('/')
+        && !name.starts_with('.')
+}
+
+pub fn load(target: &str) -> Result<MangoHudConfig, String> {
+    let path = config_path(target).ok_or_else(|| "HOME not set".to_string())?;
+    match std::fs::read_to_string(&path) {
+        Ok(content) => Ok(MangoHudConfig::parse(&content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(MangoHudConfig::default()),
+        Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+pub fn save(target: &str, config: &MangoHudConfig) -> Result<String, String> {
+    let path = config_path(target).ok_or_else(|| "HOME not set".to_string())?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    std::fs::write(&path, config.to_text()).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    crate::logger::Logger::log_debug(&format!("[MangoHud] Saved {}", path.display()));
+    Ok(path.display().to_string())
+}
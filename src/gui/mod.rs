@@ -10,6 +10,7 @@ pub mod device_install_dialog;
 pub mod flatpak_dialog;
 pub mod flatpak_remove_dialog;
 pub mod flatpak_update_dialog;
+pub mod flatpak_remote_dialog;
 pub mod update_dialog;
 pub mod update_settings_dialog;
 pub mod maintenance_dialog;
@@ -23,6 +24,28 @@ pub mod hyprland_dialog;
 pub mod hyprland_dotfiles_dialog;
 pub mod proton_install_dialog;
 pub mod proton_changelog_dialog;
+pub mod launchers;
+pub mod proton_catalog;
+pub mod proton_download;
+pub mod proton_policy;
+pub mod steam;
+pub mod hw_report;
+pub mod cfhdb_cache;
+pub mod script_review;
+pub mod nvidia;
+pub mod secure_boot;
+pub mod firmware;
+pub mod multimedia;
+pub mod performance;
+pub mod mangohud;
+pub mod appstream;
+pub mod package_versions;
+pub mod flatpak_permissions;
+pub mod flatpak_history;
+pub mod flatpak_remotes;
+pub mod flatpak_storage;
+pub mod flatpak_ref;
+pub mod open_error_dialog;
 
 pub use app::RustoraApp;
 pub use theme::Theme;
@@ -0,0 +1,294 @@
+use std::process::Command;
+
+use crate::logger;
+
+// GStreamer plugin sets, free ones from Fedora and the patent encumbered ones from RPM Fusion
+pub const GSTREAMER_PACKAGES: &[&str] = &[
+    "gstreamer1-plugins-base",
+    "gstreamer1-plugins-good",
+    "gstreamer1-plugins-bad-free",
+    "gstreamer1-plugins-bad-freeworld",
+    "gstreamer1-plugins-ugly",
+    "gstreamer1-plugin-libav",
+    "gstreamer1-plugin-openh264",
+];
+
+const PCI_DEVICES: &str = "/sys/bus/pci/devices";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpuVendor {
+    Amd,
+    Intel,
+    Nvidia,
+}
+
+impl GpuVendor {
+    pub fn label(&self) -> &'static str {
+        match self {
+            GpuVendor::Amd => "AMD",
+            GpuVendor::Intel => "Intel",
+            GpuVendor::Nvidia => "NVIDIA",
+        }
+    }
+
+    // Packages that provide VA-API (and VDPAU where it exists) for this vendor
+    pub fn hwaccel_packages(&self) -> &'static [&'static str] {
+        match self {
+            GpuVendor::Amd => &["mesa-va-drivers-freeworld", "mesa-vdpau-drivers-freeworld"],
+            GpuVendor::Intel => &["intel-media-driver"],
+            GpuVendor::Nvidia => &["nvidia-vaapi-driver", "libva-vdpau-driver"],
+        }
+    }
+
+    fn from_pci_vendor(vendor_id: &str) -> Option<Self> {
+        match vendor_id.trim().trim_start_matches("0x") {
+            "1002" => Some(GpuVendor::Amd),
+            "8086" => Some(GpuVendor::Intel),
+            "10de" => Some(GpuVendor::Nvidia),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultimediaStep {
+    RpmFusion,
+    Ffmpeg,
+    GStreamer,
+    HwAccel,
+}
+
+impl MultimediaStep {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MultimediaStep::RpmFusion => "Enable RPM Fusion",
+            MultimediaStep::Ffmpeg => "Full FFmpeg",
+            MultimediaStep::GStreamer => "GStreamer plugins",
+            MultimediaStep::HwAccel => "Hardware video acceleration",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MultimediaStatus {
+    pub rpmfusion_free: bool,
+    pub rpmfusion_nonfree: bool,
+    pub ffmpeg_full: bool,
+    pub ffmpeg_free: bool,
+    pub gstreamer: Vec<(String, bool)>,
+    pub gpu_vendors: Vec<GpuVendor>,
+    pub hwaccel: Vec<(String, bool)>,
+    pub vainfo_installed: bool,
+}
+
+impl MultimediaStatus {
+    pub fn rpmfusion_enabled(&self) -> bool {
+        self.rpmfusion_free && self.rpmfusion_nonfree
+    }
+
+    pub fn gstreamer_complete(&self) -> bool {
+        self.gstreamer.iter().all(|(_, installed)| *installed)
+    }
+
+    pub fn hwaccel_complete(&self) -> bool {
+        self.hwaccel.iter().all(|(_, installed)| *installed)
+    }
+}
+
+// Display controllers (PCI class 0x03xxxx) by vendor, without depending on lspci
+pub fn detect_gpu_vendors() -> Vec<GpuVendor> {
+    let mut vendors = Vec::new();
+    let entries = match std::fs::read_dir(PCI_DEVICES) {
+        Ok(entries) => entries,
+        Err(_) => return vendors,
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let class = std::fs::read_to_string(path.join("class")).unwrap_or_default();
+        if !class.trim().trim_start_matches("0x").starts_with("03") {
+            continue;
+        }
+        let vendor_id = std::fs::read_to_string(path.join("vendor")).unwrap_or_default();
+        if let Some(vendor) = GpuVendor::from_pci_vendor(&vendor_id) {
+            if !vendors.contains(&vendor) {
+                vendors.push(vendor);
+            }
+        }
+    }
+    vendors
+}
+
+// Missing packages print "package x is not installed" and multilib packages print one line
+// per arch, so match the installed names instead of pairing lines with arguments
+fn installed_packages(packages: &[&str]) -> Vec<(String, bool)> {
+    let output = Command::new("rpm")
+        .args(["-q", "--qf", "%{NAME}\n"])
+        .args(packages)
+        .output();
+    let stdout = output
+        .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+        .unwrap_or_default();
+    let names: std::collections::HashSet<&str> = stdout.lines().map(str::trim).collect();
+    packages
+        .iter()
+        .map(|pkg| (pkg.to_string(), names.contains(pkg)))
+        .collect()
+}
+
+pub fn detect_status() -> MultimediaStatus {
+    let base = installed_packages(&[
+        "rpmfusion-free-release",
+        "rpmfusion-nonfree-release",
+        "ffmpeg",
+        "ffmpeg-free",
+        "libva-utils",
+    ]);
+    let is_installed = |name: &str| base.iter().any(|(pkg, installed)| pkg == name && *installed);
+
+    let gpu_vendors = detect_gpu_vendors();
+    let hwaccel_list: Vec<&str> = gpu_vendors
+        .iter()
+        .flat_map(|v| v.hwaccel_packages().iter().copied())
+        .collect();
+    let hwaccel = if hwaccel_list.is_empty() {
+        Vec::new()
+    } else {
+        installed_packages(&hwaccel_list)
+    };
+
+    let status = MultimediaStatus {
+        rpmfusion_free: is_installed("rpmfusion-free-release"),
+        rpmfusion_nonfree: is_installed("rpmfusion-nonfree-release"),
+        ffmpeg_full: is_installed("ffmpeg"),
+        ffmpeg_free: is_installed("ffmpeg-free"),
+        gstreamer: installed_packages(GSTREAMER_PACKAGES),
+        gpu_vendors,
+        hwaccel,
+        vainfo_installed: is_installed("libva-utils"),
+    };
+    logger::Logger::log_debug(&format!(
+        "[Multimedia] ffmpeg full: {}, ffmpeg-free: {}, GPUs: {:?}",
+        status.ffmpeg_full, status.ffmpeg_free, status.gpu_vendors
+    ));
+    status
+}
+
+pub fn ffmpeg_swap_script() -> String {
+    "set -e\n\
+     # Replace the codec-limited Fedora build with the full RPM Fusion one\n\
+     if rpm -q ffmpeg-free >/dev/null 2>&1; then\n\
+         dnf swap -y ffmpeg-free ffmpeg --allowerasing\n\
+     else\n\
+         dnf install -y ffmpeg\n\
+     fi\n"
+        .to_string()
+}
+
+pub fn gstreamer_script() -> String {
+    let mut script = String::from("set -e\n");
+    script.push_str("dnf update -y @multimedia --setopt=install_weak_deps=False --exclude=PackageKit-gstreamer-plugin\n");
+    script.push_str(&format!("dnf install -y {}\n", GSTREAMER_PACKAGES.join(" ")));
+    script
+}
+
+// Mesa in Fedora ships VA-API/VDPAU without H.264/HEVC; the freeworld builds replace it
+pub fn hwaccel_script(vendors: &[GpuVendor]) -> String {
+    let mut script = String::from("set -e\n");
+    for vendor in vendors {
+        script.push_str(&format!("# {}\n", vendor.label()));
+        match vendor {
+            GpuVendor::Amd => {
+                script.push_str("rpm -q mesa-va-drivers-freeworld >/dev/null 2>&1 || dnf swap -y mesa-va-drivers mesa-va-drivers-freeworld\n");
+                script.push_str("rpm -q mesa-vdpau-drivers-freeworld >/dev/null 2>&1 || dnf swap -y mesa-vdpau-drivers mesa-vdpau-drivers-freeworld\n");
+            }
+            GpuVendor::Intel | GpuVendor::Nvidia => {
+                script.push_str(&format!("dnf install -y {}\n", vendor.hwaccel_packages().join(" ")));
+            }
+        }
+    }
+    script.push_str("dnf install -y libva-utils vdpauinfo\n");
+    script
+}
+
+pub async fn run_wizard_step(step: MultimediaStep, vendors: Vec<GpuVendor>) -> Result<String, String> {
+    logger::Logger::log_debug(&format!("[Multimedia] Running step: {}", step.label()));
+    match step {
+        MultimediaStep::RpmFusion => crate::gui::tabs::repo::install_rpmfusion_repos()
+            .await
+            .map(|_| "RPM Fusion free and nonfree repositories enabled".to_string()),
+        MultimediaStep::Ffmpeg => run_script(ffmpeg_swap_script()).await,
+        MultimediaStep::GStreamer => run_script(gstreamer_script()).await,
+        MultimediaStep::HwAccel => run_script(hwaccel_script(&vendors)).await,
+    }
+}
+
+// Runs a shell script as root through pkexec; also used by the gaming tuning panel
+pub async fn run_script(script: String) -> Result<String, String> {
+    let output = tokio::process::Command::new("pkexec")
+        .args(["sh", "-c", &script])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run pkexec: {}", e))?;
+    let combined = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    if output.status.success() {
+        Ok(combined)
+    } else {
+        Err(format!(
+            "Step failed (exit code: {}):\n{}",
+            output.status.code().unwrap_or(-1),
+            combined
+        ))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VaInfo {
+    pub driver: String,
+    pub decode: Vec<String>,
+    pub encode: Vec<String>,
+}
+
+// Hardware decode/encode support as reported by `vainfo`
+pub async fn verify_vaapi() -> Result<VaInfo, String> {
+    let output = tokio::process::Command::new("vainfo")
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run vainfo (install libva-utils): {}", e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        return Err(format!("vainfo could not initialize a VA-API driver:\n{}{}", stdout, stderr).trim().to_string());
+    }
+
+    let mut info = VaInfo {
+        driver: String::new(),
+        decode: Vec::new(),
+        encode: Vec::new(),
+    };
+    for line in stdout.lines().chain(stderr.lines()) {
+        let line = line.trim();
+        if let Some(driver) = line.split("Driver version:").nth(1) {
+            info.driver = driver.trim().to_string();
+        }
+        let (profile, entrypoint) = match line.split_once(':') {
+            Some((p, e)) if p.trim().starts_with("VAProfile") => (p.trim(), e.trim()),
+            _ => continue,
+        };
+        let codec = profile.trim_start_matches("VAProfile").to_string();
+        let target = if entrypoint == "VAEntrypointVLD" {
+            &mut info.decode
+        } else if entrypoint.starts_with("VAEntrypointEncSlice") {
+            &mut info.encode
+        } else {
+            continue;
+        };
+        if !target.contains(&codec) {
+            target.push(codec);
+        }
+    }
+    Ok(info)
+}
@@ -0,0 +1,540 @@
+use std::process::Command;
+
+use crate::logger;
+
+// GPU architecture, derived from the PCI device ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpuGeneration {
+    Tesla,
+    Fermi,
+    Kepler,
+    Maxwell,
+    Pascal,
+    Volta,
+    Turing,
+    Ampere,
+    Ada,
+    Blackwell,
+    Unknown,
+}
+
+impl GpuGeneration {
+    // Coarse device ID ranges; NVIDIA assigns IDs roughly in chip order
+    pub fn from_device_id(device_id: &str) -> Self {
+        let id = match u16::from_str_radix(device_id.trim_start_matches("0x"), 16) {
+            Ok(id) => id,
+            Err(_) => return GpuGeneration::Unknown,
+        };
+        const RANGES: &[(u16, u16, GpuGeneration)] = &[
+            (0x0600, 0x06BF, GpuGeneration::Tesla),
+            (0x06C0, 0x06DF, GpuGeneration::Fermi),
+            (0x06E0, 0x0BFF, GpuGeneration::Tesla),
+            (0x0CA0, 0x0CBF, GpuGeneration::Tesla),
+            (0x0DC0, 0x0F3F, GpuGeneration::Fermi),
+            (0x0FC0, 0x103F, GpuGeneration::Kepler),
+            (0x1040, 0x117F, GpuGeneration::Fermi),
+            (0x1180, 0x11FF, GpuGeneration::Kepler),
+            (0x1200, 0x127F, GpuGeneration::Fermi),
+            (0x1280, 0x12BF, GpuGeneration::Kepler),
+            (0x1340, 0x143F, GpuGeneration::Maxwell),
+            (0x15F0, 0x15FF, GpuGeneration::Pascal),
+            (0x1600, 0x17FF, GpuGeneration::Maxwell),
+            (0x1B00, 0x1D7F, GpuGeneration::Pascal),
+            (0x1D80, 0x1DBF, GpuGeneration::Volta),
+            (0x20B0, 0x20FF, GpuGeneration::Ampere),
+            (0x1E00, 0x21FF, GpuGeneration::Turing),
+            (0x2200, 0x25FF, GpuGeneration::Ampere),
+            (0x2600, 0x28FF, GpuGeneration::Ada),
+            (0x2900, 0x2FFF, GpuGeneration::Blackwell),
+        ];
+        RANGES
+            .iter()
+            .find(|(start, end, _)| id >= *start && id <= *end)
+            .map(|(_, _, generation)| *generation)
+            .unwrap_or(GpuGeneration::Unknown)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GpuGeneration::Tesla => "Tesla",
+            GpuGeneration::Fermi => "Fermi",
+            GpuGeneration::Kepler => "Kepler",
+            GpuGeneration::Maxwell => "Maxwell",
+            GpuGeneration::Pascal => "Pascal",
+            GpuGeneration::Volta => "Volta",
+            GpuGeneration::Turing => "Turing",
+            GpuGeneration::Ampere => "Ampere",
+            GpuGeneration::Ada => "Ada Lovelace",
+            GpuGeneration::Blackwell => "Blackwell",
+            GpuGeneration::Unknown => "Unknown architecture",
+        }
+    }
+
+    // Newest driver series that still supports this architecture
+    pub fn max_driver_major(self) -> Option<u32> {
+        match self {
+            GpuGeneration::Tesla => Some(340),
+            GpuGeneration::Fermi => Some(390),
+            GpuGeneration::Kepler => Some(470),
+            GpuGeneration::Maxwell | GpuGeneration::Pascal | GpuGeneration::Volta => Some(580),
+            _ => None,
+        }
+    }
+
+    // Open kernel modules need a GSP, which starts with Turing
+    pub fn supports_open_modules(self) -> bool {
+        matches!(self, GpuGeneration::Turing | GpuGeneration::Ampere | GpuGeneration::Ada | GpuGeneration::Blackwell)
+    }
+
+    pub fn requires_open_modules(self) -> bool {
+        self == GpuGeneration::Blackwell
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NvidiaBranch {
+    Production,
+    NewFeature,
+    Legacy580,
+    Legacy470,
+    Legacy390,
+    Legacy340,
+}
+
+impl NvidiaBranch {
+    pub fn label(self) -> &'static str {
+        match self {
+            NvidiaBranch::Production => "Production",
+            NvidiaBranch::NewFeature => "New Feature",
+            NvidiaBranch::Legacy580 => "Legacy 580.xx",
+            NvidiaBranch::Legacy470 => "Legacy 470.xx",
+            NvidiaBranch::Legacy390 => "Legacy 390.xx",
+            NvidiaBranch::Legacy340 => "Legacy 340.xx",
+        }
+    }
+
+    fn legacy_suffix(self) -> Option<&'static str> {
+        match self {
+            NvidiaBranch::Legacy580 => Some("580xx"),
+            NvidiaBranch::Legacy470 => Some("470xx"),
+            NvidiaBranch::Legacy390 => Some("390xx"),
+            NvidiaBranch::Legacy340 => Some("340xx"),
+            _ => None,
+        }
+    }
+
+    fn from_package_name(name: &str) -> Option<Self> {
+        for branch in [NvidiaBranch::Legacy580, NvidiaBranch::Legacy470, NvidiaBranch::Legacy390, NvidiaBranch::Legacy340] {
+            if name.ends_with(branch.legacy_suffix().unwrap_or_default()) {
+                return Some(branch);
+            }
+        }
+        if name == "akmod-nvidia" || name == "nvidia-driver" || name == "dkms-nvidia" {
+            return Some(NvidiaBranch::Production);
+        }
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    RpmFusion,
+    Negativo17,
+}
+
+impl Provider {
+    pub fn label(self) -> &'static str {
+        match self {
+            Provider::RpmFusion => "RPM Fusion",
+            Provider::Negativo17 => "negativo17.org",
+        }
+    }
+
+    fn from_vendor(vendor: &str) -> Option<Self> {
+        let vendor = vendor.to_lowercase();
+        if vendor.contains("rpm fusion") {
+            Some(Provider::RpmFusion)
+        } else if vendor.contains("negativo17") {
+            Some(Provider::Negativo17)
+        } else {
+            None
+        }
+    }
+
+    // Repos that carry this provider's driver packages
+    fn repos(self) -> &'static [&'static str] {
+        match self {
+            Provider::RpmFusion => &["rpmfusion-nonfree", "rpmfusion-nonfree-updates", "rpmfusion-nonfree-updates-testing", "rpmfusion-nonfree-nvidia-driver"],
+            Provider::Negativo17 => &["fedora-nvidia"],
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BranchOption {
+    pub branch: NvidiaBranch,
+    pub provider: Provider,
+    pub open_modules: bool,
+    pub packages: Vec<String>,
+    pub available_version: Option<String>,
+    pub repo: Option<String>,
+    pub supported: bool,
+    pub recommended: bool,
+    pub installed: bool,
+}
+
+impl BranchOption {
+    pub fn title(&self) -> String {
+        if self.open_modules {
+            format!("{} (open kernel modules)", self.branch.label())
+        } else {
+            self.branch.label().to_string()
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InstalledDriver {
+    pub branch: Option<NvidiaBranch>,
+    pub provider: Option<Provider>,
+    pub open_modules: bool,
+    pub version: String,
+    // Every installed driver package, removed before switching
+    pub packages: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NvidiaState {
+    pub generation: GpuGeneration,
+    pub options: Vec<BranchOption>,
+    pub installed: Option<InstalledDriver>,
+    pub secure_boot: bool,
+}
+
+const DRIVER_PACKAGE_PREFIXES: &[&str] = &[
+    "akmod-nvidia", "kmod-nvidia", "dkms-nvidia", "xorg-x11-drv-nvidia", "nvidia-driver",
+    "nvidia-kmod", "nvidia-settings", "nvidia-persistenced", "nvidia-modprobe", "nvidia-xconfig",
+    "nvidia-libXNVCtrl",
+];
+
+fn branch_packages(branch: NvidiaBranch, provider: Provider) -> Vec<String> {
+    match (provider, branch.legacy_suffix()) {
+        (Provider::RpmFusion, Some(suffix)) => {
+            let mut packages = vec![
+                format!("akmod-nvidia-{}", suffix),
+                format!("xorg-x11-drv-nvidia-{}", suffix),
+            ];
+            // 340xx predates the split CUDA libraries
+            if suffix != "340xx" {
+                packages.push(format!("xorg-x11-drv-nvidia-{}-cuda", suffix));
+            }
+            packages
+        }
+        (Provider::RpmFusion, None) => vec![
+            "akmod-nvidia".to_string(),
+            "xorg-x11-drv-nvidia".to_string(),
+            "xorg-x11-drv-nvidia-cuda".to_string(),
+        ],
+        (Provider::Negativo17, _) => vec![
+            "akmod-nvidia".to_string(),
+            "nvidia-driver".to_string(),
+            "nvidia-driver-cuda".to_string(),
+            "nvidia-settings".to_string(),
+        ],
+    }
+}
+
+// Extra repos to enable when querying or installing a branch
+fn branch_enable_repos(branch: NvidiaBranch, provider: Provider) -> Vec<&'static str> {
+    match (provider, branch) {
+        (Provider::RpmFusion, NvidiaBranch::NewFeature) => vec!["rpmfusion-nonfree-updates-testing"],
+        (Provider::RpmFusion, _) => vec!["rpmfusion-nonfree", "rpmfusion-nonfree-updates"],
+        (Provider::Negativo17, _) => vec!["fedora-nvidia"],
+    }
+}
+
+pub fn detect_state(device_id: &str) -> NvidiaState {
+    let generation = GpuGeneration::from_device_id(device_id);
+    let installed = detect_installed();
+    let secure_boot = Command::new("mokutil")
+        .arg("--sb-state")
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).contains("SecureBoot enabled"))
+        .unwrap_or(false);
+
+    let candidates: &[(NvidiaBranch, Provider)] = &[
+        (NvidiaBranch::Production, Provider::RpmFusion),
+        (NvidiaBranch::NewFeature, Provider::RpmFusion),
+        (NvidiaBranch::Production, Provider::Negativo17),
+        (NvidiaBranch::Legacy580, Provider::RpmFusion),
+        (NvidiaBranch::Legacy470, Provider::RpmFusion),
+        (NvidiaBranch::Legacy390, Provider::RpmFusion),
+        (NvidiaBranch::Legacy340, Provider::RpmFusion),
+    ];
+
+    let mut options = Vec::new();
+    for (branch, provider) in candidates {
+        let packages = branch_packages(*branch, *provider);
+        let (available_version, repo) = match query_available(&packages[0], &branch_enable_repos(*branch, *provider), *branch) {
+            Some((version, repo)) => (Some(version), Some(repo)),
+            None => (None, None),
+        };
+
+        let major = available_version.as_deref()
+            .and_then(|v| v.split('.').next())
+            .and_then(|m| m.parse::<u32>().ok());
+        let within_limit = match (generation.max_driver_major(), major) {
+            (Some(limit), Some(major)) => major <= limit,
+            (Some(limit), None) => branch.legacy_suffix()
+                .and_then(|s| s.trim_end_matches("xx").parse::<u32>().ok())
+                .map(|m| m <= limit)
+                .unwrap_or(false),
+            (None, _) => true,
+        };
+
+        let mut flavors = vec![false];
+        if generation.supports_open_modules() || generation == GpuGeneration::Unknown {
+            if branch.legacy_suffix().is_none() {
+                flavors.push(true);
+            }
+        }
+        for open_modules in flavors {
+            let supported = within_limit
+                && available_version.is_some()
+                && !(generation.requires_open_modules() && !open_modules);
+            let installed_here = installed.as_ref()
+                .map(|i| i.branch == Some(*branch) && i.provider == Some(*provider) && i.open_modules == open_modules)
+                .unwrap_or(false);
+            options.push(BranchOption {
+                branch: *branch,
+                provider: *provider,
+                open_modules,
+                packages: packages.clone(),
+                available_version: available_version.clone(),
+                repo: repo.clone(),
+                supported,
+                recommended: false,
+                installed: installed_here,
+            });
+        }
+    }
+
+    mark_recommended(&mut options, generation);
+
+    NvidiaState {
+        generation,
+        options,
+        installed,
+        secure_boot,
+    }
+}
+
+// Newest supported stable branch, preferring RPM Fusion and open modules where NVIDIA recommends them
+fn mark_recommended(options: &mut [BranchOption], generation: GpuGeneration) {
+    let prefer_open = generation.supports_open_modules();
+    let best = options.iter()
+        .enumerate()
+        .filter(|(_, o)| o.supported && o.branch != NvidiaBranch::NewFeature)
+        .max_by(|(_, a), (_, b)| {
+            let version_a = a.available_version.clone().unwrap_or_default();
+            let version_b = b.available_version.clone().unwrap_or_default();
+            compare_versions(&version_a, &version_b)
+                .then_with(|| (a.open_modules == prefer_open).cmp(&(b.open_modules == prefer_open)))
+                .then_with(|| (a.provider == Provider::RpmFusion).cmp(&(b.provider == Provider::RpmFusion)))
+        })
+        .map(|(idx, _)| idx);
+    if let Some(idx) = best {
+        options[idx].recommended = true;
+    }
+}
+
+fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    let parse = |v: &str| v.split('.').filter_map(|p| p.parse::<u32>().ok()).collect::<Vec<u32>>();
+    parse(a).cmp(&parse(b))
+}
+
+fn query_available(package: &str, enable_repos: &[&str], branch: NvidiaBranch) -> Option<(String, String)> {
+    let mut cmd = Command::new("dnf");
+    cmd.args(["repoquery", "--available", "--quiet", "--qf", "%{version}|%{repoid}\n"]);
+    for repo in enable_repos {
+        cmd.arg(format!("--enablerepo={}", repo));
+    }
+    cmd.arg(package);
+
+    let output = cmd.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut candidates: Vec<(String, String)> = stdout.lines()
+        .filter_map(|line| {
+            let (version, repo) = line.trim().split_once('|')?;
+            Some((version.to_string(), repo.to_string()))
+        })
+        .filter(|(_, repo)| enable_repos.iter().any(|r| repo.starts_with(r)))
+        .collect();
+    // The new feature branch only makes sense when testing carries something newer
+    if branch == NvidiaBranch::NewFeature {
+        candidates.retain(|(_, repo)| repo.contains("testing"));
+    }
+    candidates.sort_by(|a, b| compare_versions(&a.0, &b.0));
+    let result = candidates.pop();
+    logger::Logger::log_debug(&format!("[NVIDIA] {} ({:?}): {:?}", package, branch, result));
+    result
+}
+
+fn detect_installed() -> Option<InstalledDriver> {
+    let output = Command::new("rpm")
+        .args(["-qa", "--qf", "%{NAME}|%{VERSION}|%{VENDOR}\n"])
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    let mut packages = Vec::new();
+    let mut branch = None;
+    let mut provider = None;
+    let mut version = String::new();
+    for line in stdout.lines() {
+        let parts: Vec<&str> = line.split('|').collect();
+        if parts.len() < 3 || !DRIVER_PACKAGE_PREFIXES.iter().any(|p| parts[0].starts_with(p)) {
+            continue;
+        }
+        packages.push(parts[0].to_string());
+        if parts[0].starts_with("akmod-nvidia") || parts[0].starts_with("dkms-nvidia") || parts[0] == "nvidia-driver" || branch.is_none() {
+            if let Some(b) = NvidiaBranch::from_package_name(parts[0]) {
+                branch = Some(b);
+                version = parts[1].to_string();
+            }
+        }
+        if provider.is_none() {
+            provider = Provider::from_vendor(parts[2]);
+        }
+    }
+    if packages.is_empty() {
+        return None;
+    }
+    packages.sort();
+
+    Some(InstalledDriver {
+        branch,
+        provider,
+        open_modules: open_modules_configured(provider),
+        version,
+        packages,
+    })
+}
+
+fn open_modules_configured(provider: Option<Provider>) -> bool {
+    // The loaded module is the most reliable answer
+    if let Ok(output) = Command::new("modinfo").args(["-F", "license", "nvidia"]).output() {
+        let license = String::from_utf8_lossy(&output.stdout);
+        if output.status.success() && !license.trim().is_empty() {
+            return license.contains("MIT/GPL");
+        }
+    }
+    match provider {
+        Some(Provider::RpmFusion) => std::fs::read_to_string("/etc/rpm/macros.nvidia-kmod")
+            .map(|c| c.contains("_with_kmod_nvidia_open 1"))
+            .unwrap_or(false),
+        Some(Provider::Negativo17) => std::fs::read_to_string("/etc/nvidia/kernel.conf")
+            .map(|c| c.contains("MODULE_VARIANT=kernel-open"))
+            .unwrap_or(false),
+        None => false,
+    }
+}
+
+// Script that moves the system to `target`: repo setup, removal of conflicting packages,
+// kernel module flavor, MOK signing key (Secure Boot) and the install itself
+pub fn switch_script(target: &BranchOption, state: &NvidiaState) -> String {
+    let mut script = String::from("#!/bin/bash\nset -e\n\n");
+    script.push_str("setopt() { dnf config-manager setopt \"$1\" 2>/dev/null || dnf config-manager --save --setopt=\"$1\"; }\n\n");
+
+    match target.provider {
+        Provider::RpmFusion => {
+            script.push_str("# RPM Fusion repositories\n");
+            script.push_str("rpm -q rpmfusion-nonfree-release >/dev/null 2>&1 || dnf install -y \\\n");
+            script.push_str("    https://mirrors.rpmfusion.org/free/fedora/rpmfusion-free-release-$(rpm -E %fedora).noarch.rpm \\\n");
+            script.push_str("    https://mirrors.rpmfusion.org/nonfree/fedora/rpmfusion-nonfree-release-$(rpm -E %fedora).noarch.rpm\n");
+            script.push_str("# Stop negativo17 from offering the same package names\n");
+            script.push_str("if dnf repoinfo fedora-nvidia >/dev/null 2>&1; then setopt fedora-nvidia.enabled=0; fi\n");
+            for repo in Provider::RpmFusion.repos() {
+                script.push_str(&format!("if dnf repoinfo {0} >/dev/null 2>&1; then setopt {0}.excludepkgs=; fi\n", repo));
+            }
+        }
+        Provider::Negativo17 => {
+            script.push_str("# negativo17 repository\n");
+            script.push_str("dnf repoinfo fedora-nvidia >/dev/null 2>&1 || dnf config-manager addrepo --from-repofile=https://negativo17.org/repos/fedora-nvidia.repo || dnf config-manager --add-repo https://negativo17.org/repos/fedora-nvidia.repo\n");
+            script.push_str("setopt fedora-nvidia.enabled=1\n");
+            script.push_str("# Keep RPM Fusion's driver packages out of the way\n");
+            for repo in Provider::RpmFusion.repos() {
+                script.push_str(&format!("if dnf repoinfo {0} >/dev/null 2>&1; then setopt '{0}.excludepkgs=*nvidia*'; fi\n", repo));
+            }
+        }
+    }
+    script.push('\n');
+
+    if let Some(ref installed) = state.installed {
+        let same_packages = installed.provider == Some(target.provider) && installed.branch == Some(target.branch);
+        if !same_packages && !installed.packages.is_empty() {
+            script.push_str("# Remove the currently installed driver packages\n");
+            script.push_str(&format!(
+                "dnf remove -y --setopt=clean_requirements_on_remove=False {}\n",
+                installed.packages.join(" ")
+            ));
+            if installed.packages.iter().any(|p| p.starts_with("dkms-nvidia")) {
+                script.push_str("rm -rf /var/lib/dkms/nvidia*\n");
+            }
+            script.push('\n');
+        }
+    }
+
+    script.push_str(&format!("# {} kernel modules\n", if target.open_modules { "Open" } else { "Proprietary" }));
+    match target.provider {
+        Provider::RpmFusion => {
+            if target.open_modules {
+                script.push_str("echo '%_with_kmod_nvidia_open 1' > /etc/rpm/macros.nvidia-kmod\n");
+            } else {
+                script.push_str("rm -f /etc/rpm/macros.nvidia-kmod\n");
+            }
+        }
+        Provider::Negativo17 => {
+            script.push_str("mkdir -p /etc/nvidia\n");
+            script.push_str(&format!(
+                "echo 'MODULE_VARIANT={}' > /etc/nvidia/kernel.conf\n",
+                if target.open_modules { "kernel-open" } else { "kernel" }
+            ));
+        }
+    }
+    script.push('\n');
+
+    if state.secure_boot {
+        script.push_str(&mok_signing_script());
+        script.push('\n');
+    }
+
+    let enable = branch_enable_repos(target.branch, target.provider)
+        .iter()
+        .map(|r| format!("--enablerepo={} ", r))
+        .collect::<String>();
+    script.push_str("# Install the driver\n");
+    script.push_str(&format!("dnf install -y {}{}\n", enable, target.packages.join(" ")));
+    script
+}
+
+// Secure Boot: make sure akmods has a signing key and that it is queued for MOK enrollment
+pub fn mok_signing_script() -> String {
+    let key = crate::gui::secure_boot::AKMODS_PUBLIC_KEY;
+    let mut script = String::from("# Secure Boot module signing\n");
+    script.push_str("dnf install -y akmods mokutil openssl\n");
+    script.push_str(&format!("[ -f {} ] || kmodgenca -a\n", key));
+    script.push_str(&format!("if ! mokutil --test-key {} 2>&1 | grep -q 'already enrolled'; then\n", key));
+    script.push_str("    MOK_PASSWORD=$(openssl rand -hex 4)\n");
+    script.push_str("    HASH_FILE=$(mktemp)\n");
+    script.push_str("    mokutil --generate-hash=\"$MOK_PASSWORD\" > \"$HASH_FILE\"\n");
+    script.push_str(&format!("    mokutil --import {} --hash-file \"$HASH_FILE\"\n", key));
+    script.push_str("    rm -f \"$HASH_FILE\"\n");
+    script.push_str("    echo \"MOK enrollment password: $MOK_PASSWORD\"\n");
+    script.push_str("    echo \"Reboot, choose 'Enroll MOK' in MokManager and enter this password.\"\n");
+    script.push_str("fi\n");
+    script
+}
@@ -0,0 +1,277 @@
+use iced::widget::{button, column, container, row, scrollable, text, Space};
+use iced::{Alignment, Application, Command, Element, Length, Border, Theme as IcedTheme};
+use iced::widget::container::Appearance;
+use iced::widget::button::Appearance as ButtonAppearance;
+use iced::widget::button::StyleSheet as ButtonStyleSheet;
+use iced::window;
+use crate::gui::dialog_design::DialogDesign;
+use iced::Color;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Close,
+}
+
+// Shown when a file or link handed over by a browser or file manager cannot be opened,
+// since there is no terminal to print the error to
+#[derive(Debug)]
+pub struct OpenErrorDialog {
+    pub argument: String,
+    pub error: String,
+}
+
+impl OpenErrorDialog {
+    pub fn new(argument: String, error: String) -> Self {
+        Self { argument, error }
+    }
+
+    pub fn run_separate_window(argument: String, error: String) -> Result<(), iced::Error> {
+        let dialog = Self::new(argument, error);
+
+        let window_settings = iced::window::Settings {
+            size: iced::Size::new(520.0, 300.0),
+            min_size: Some(iced::Size::new(400.0, 240.0)),
+            resizable: true,
+            decorations: true,
+            ..Default::default()
+        };
+
+        let default_font = crate::gui::fonts::get_inter_font();
+
+        <OpenErrorDialog as Application>::run(iced::Settings {
+            window: window_settings,
+            flags: dialog,
+            default_font,
+            default_text_size: iced::Pixels::from(14.0),
+            antialiasing: true,
+            id: None,
+            fonts: Vec::new(),
+        })
+    }
+
+    // File name for paths, the whole argument for links
+    fn display_name(&self) -> &str {
+        if self.argument.contains("://") || self.argument.starts_with("appstream:") {
+            &self.argument
+        } else {
+            self.argument.rsplit('/').next().unwrap_or(&self.argument)
+        }
+    }
+
+    pub fn view_impl(&self, theme: &crate::gui::Theme) -> Element<'_, Message> {
+        let settings = crate::gui::settings::AppSettings::load();
+        let title_size = (settings.font_size_titles * settings.scale_titles).round();
+        let body_size = (settings.font_size_body * settings.scale_body).round();
+        let button_size = (settings.font_size_buttons * settings.scale_buttons).round();
+        let material_font = crate::gui::fonts::get_material_symbols_font();
+
+        let header = container(
+            row![
+                text(crate::gui::fonts::glyphs::INFO_SYMBOL)
+                    .font(material_font)
+                    .size(title_size * 1.2)
+                    .style(iced::theme::Text::Color(theme.danger())),
+                Space::with_width(DialogDesign::space_small()),
+                column![
+                    text("Could not open")
+                        .size(title_size)
+                        .style(iced::theme::Text::Color(theme.danger())),
+                    text(self.display_name())
+                        .size(body_size * 0.8)
+                        .style(iced::theme::Text::Color(theme.secondary_text())),
+                ]
+                .spacing(DialogDesign::SPACE_TINY),
+                Space::with_width(Length::Fill),
+            ]
+            .align_items(Alignment::Center)
+        )
+        .width(Length::Fill)
+        .padding(DialogDesign::pad_medium());
+
+        let info_section = container(text(&self.error).size(body_size))
+            .width(Length::Fill)
+            .padding(DialogDesign::pad_medium())
+            .style(iced::theme::Container::Custom(Box::new(CleanContainerStyle)));
+
+        let buttons = row![
+            Space::with_width(Length::Fill),
+            button(
+                row![
+                    text(crate::gui::fonts::glyphs::EXIT_SYMBOL).font(material_font).size(button_size * 1.1),
+                    text(" Close").size(button_size)
+                ]
+                .spacing(DialogDesign::SPACE_TINY)
+                .align_items(Alignment::Center)
+            )
+            .on_press(Message::Close)
+            .style(iced::theme::Button::Custom(Box::new(CleanButtonStyle { is_primary: true })))
+            .padding(DialogDesign::pad_small()),
+        ]
+        .spacing(DialogDesign::SPACE_SMALL)
+        .align_items(Alignment::Center);
+
+        container(
+            column![
+                header,
+                container(Space::with_height(Length::Fixed(1.0)))
+                    .width(Length::Fill)
+                    .style(iced::theme::Container::Custom(Box::new(DividerStyle))),
+                scrollable(
+                    column![info_section]
+                        .padding(DialogDesign::pad_medium())
+                )
+                .height(Length::Fill),
+                container(Space::with_height(Length::Fixed(1.0)))
+                    .width(Length::Fill)
+                    .style(iced::theme::Container::Custom(Box::new(DividerStyle))),
+                container(buttons)
+                    .width(Length::Fill)
+                    .padding(DialogDesign::pad_medium()),
+            ]
+            .spacing(0)
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(iced::theme::Container::Custom(Box::new(WindowContainerStyle {
+            background: theme.background(),
+        })))
+        .into()
+    }
+}
+
+impl Application for OpenErrorDialog {
+    type Message = Message;
+    type Theme = IcedTheme;
+    type Executor = iced::executor::Default;
+    type Flags = Self;
+
+    fn new(flags: Self) -> (Self, Command<Message>) {
+        (flags, Command::none())
+    }
+
+    fn title(&self) -> String {
+        format!("Could not open {} - Rustora", self.display_name())
+    }
+
+    fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::Close => {
+                iced::window::close(window::Id::MAIN)
+            }
+        }
+    }
+
+    fn view(&self) -> Element<'_, Message> {
+        let theme = &crate::gui::Theme::Dark;
+        self.view_impl(theme)
+    }
+
+    fn theme(&self) -> IcedTheme {
+        crate::gui::Theme::Dark.iced_theme()
+    }
+}
+
+struct CleanButtonStyle {
+    is_primary: bool,
+}
+
+impl ButtonStyleSheet for CleanButtonStyle {
+    type Style = iced::Theme;
+
+    fn active(&self, style: &Self::Style) -> ButtonAppearance {
+        let palette = style.palette();
+        ButtonAppearance {
+            background: Some(iced::Background::Color(if self.is_primary {
+                palette.primary
+            } else {
+                Color::from_rgba(0.4, 0.4, 0.4, 0.2)
+            })),
+            border: Border {
+                radius: DialogDesign::RADIUS.into(),
+                width: 1.0,
+                color: if self.is_primary {
+                    palette.primary
+                } else {
+                    Color::from_rgba(0.5, 0.5, 0.5, 0.3)
+                },
+            },
+            text_color: if self.is_primary { Color::WHITE } else { palette.text },
+            ..Default::default()
+        }
+    }
+
+    fn hovered(&self, style: &Self::Style) -> ButtonAppearance {
+        let mut appearance = self.active(style);
+        let palette = style.palette();
+        if self.is_primary {
+            appearance.background = Some(iced::Background::Color(
+                Color::from_rgba(palette.primary.r * 0.85, palette.primary.g * 0.85, palette.primary.b * 0.85, 1.0)
+            ));
+        } else {
+            appearance.background = Some(iced::Background::Color(Color::from_rgba(0.4, 0.4, 0.4, 0.3)));
+        }
+        appearance
+    }
+}
+
+struct WindowContainerStyle {
+    background: iced::Color,
+}
+
+impl iced::widget::container::StyleSheet for WindowContainerStyle {
+    type Style = iced::Theme;
+
+    fn appearance(&self, _style: &Self::Style) -> Appearance {
+        Appearance {
+            background: Some(iced::Background::Color(self.background)),
+            border: Border {
+                radius: 0.0.into(),
+                width: 0.0,
+                color: iced::Color::TRANSPARENT,
+            },
+            ..Default::default()
+        }
+    }
+}
+
+struct CleanContainerStyle;
+
+impl iced::widget::container::StyleSheet for CleanContainerStyle {
+    type Style = iced::Theme;
+
+    fn appearance(&self, style: &Self::Style) -> Appearance {
+        let palette = style.palette();
+        Appearance {
+            background: Some(iced::Background::Color(Color::from_rgba(
+                palette.background.r * 0.98,
+                palette.background.g * 0.98,
+                palette.background.b * 0.98,
+                1.0,
+            ))),
+            border: Border {
+                radius: DialogDesign::RADIUS.into(),
+                width: 1.0,
+                color: Color::from_rgba(0.3, 0.3, 0.3, 0.2),
+            },
+            ..Default::default()
+        }
+    }
+}
+
+struct DividerStyle;
+
+impl iced::widget::container::StyleSheet for DividerStyle {
+    type Style = iced::Theme;
+
+    fn appearance(&self, _style: &Self::Style) -> Appearance {
+        Appearance {
+            background: Some(iced::Background::Color(Color::from_rgba(0.3, 0.3, 0.3, 0.2))),
+            border: Border {
+                radius: 0.0.into(),
+                width: 0.0,
+                color: Color::TRANSPARENT,
+            },
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,324 @@
+use iced::widget::{button, column, container, row, text, Space};
+use iced::{Alignment, Element, Length, Padding};
+use std::cmp::Ordering;
+use tokio::process::Command as TokioCommand;
+
+#[derive(Debug, Clone)]
+pub struct PackageVersion {
+    pub epoch: String,
+    pub version: String,
+    pub release: String,
+    pub arch: String,
+    pub repo: String,
+    pub installed: bool,
+}
+
+impl PackageVersion {
+    // Epoch 0 is left out, as dnf and rpm print it
+    pub fn evr(&self) -> String {
+        if self.epoch.is_empty() || self.epoch == "0" {
+            format!("{}-{}", self.version, self.release)
+        } else {
+            format!("{}:{}-{}", self.epoch, self.version, self.release)
+        }
+    }
+
+    pub fn spec(&self, name: &str) -> String {
+        format!("{}-{}.{}", name, self.evr(), self.arch)
+    }
+
+    fn compare(&self, other: &PackageVersion) -> Ordering {
+        let epoch = |e: &str| e.parse::<u64>().unwrap_or(0);
+        epoch(&self.epoch)
+            .cmp(&epoch(&other.epoch))
+            .then_with(|| rpmvercmp(&self.version, &other.version))
+            .then_with(|| rpmvercmp(&self.release, &other.release))
+    }
+}
+
+// A `dnf versionlock` entry
+#[derive(Debug, Clone)]
+pub struct VersionLock {
+    pub name: String,
+    pub evr: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct PackageVersions {
+    pub name: String,
+    // Newest first
+    pub versions: Vec<PackageVersion>,
+    pub lock: Option<VersionLock>,
+}
+
+impl PackageVersions {
+    fn installed(&self) -> Option<&PackageVersion> {
+        self.versions.iter().find(|v| v.installed)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum VersionAction {
+    Install(String),
+    Downgrade(String),
+    Lock(String),
+    Unlock(String),
+}
+
+pub fn is_locked(locks: &[VersionLock], name: &str) -> bool {
+    locks.iter().any(|l| l.name == name)
+}
+
+// rpm's version comparison: alternating digit and letter segments, `~` sorts before
+// anything and `^` after the base version
+fn rpmvercmp(a: &str, b: &str) -> Ordering {
+    let is_separator = |c: char| !c.is_ascii_alphanumeric() && c != '~' && c != '^';
+    let (mut a, mut b) = (a, b);
+    loop {
+        a = a.trim_start_matches(is_separator);
+        b = b.trim_start_matches(is_separator);
+
+        if a.starts_with('~') || b.starts_with('~') {
+            if !a.starts_with('~') {
+                return Ordering::Greater;
+            }
+            if !b.starts_with('~') {
+                return Ordering::Less;
+            }
+            (a, b) = (&a[1..], &b[1..]);
+            continue;
+        }
+        if a.starts_with('^') || b.starts_with('^') {
+            if a.is_empty() {
+                return Ordering::Less;
+            }
+            if b.is_empty() {
+                return Ordering::Greater;
+            }
+            if !a.starts_with('^') {
+                return Ordering::Greater;
+            }
+            if !b.starts_with('^') {
+                return Ordering::Less;
+            }
+            (a, b) = (&a[1..], &b[1..]);
+            continue;
+        }
+        if a.is_empty() || b.is_empty() {
+            return a.len().cmp(&b.len());
+        }
+
+        let numeric = a.starts_with(|c: char| c.is_ascii_digit());
+        let segment_end = |s: &str| {
+            s.find(|c: char| if numeric { !c.is_ascii_digit() } else { !c.is_ascii_alphabetic() })
+                .unwrap_or(s.len())
+        };
+        let (seg_a, rest_a) = a.split_at(segment_end(a));
+        let (seg_b, rest_b) = b.split_at(segment_end(b));
+        if seg_b.is_empty() {
+            // Numeric segments are newer than alphabetic ones
+            return if numeric { Ordering::Greater } else { Ordering::Less };
+        }
+        let ordering = if numeric {
+            let (seg_a, seg_b) = (seg_a.trim_start_matches('0'), seg_b.trim_start_matches('0'));
+            seg_a.len().cmp(&seg_b.len()).then_with(|| seg_a.cmp(seg_b))
+        } else {
+            seg_a.cmp(seg_b)
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+        (a, b) = (rest_a, rest_b);
+    }
+}
+
+async fn run(program: &str, args: &[&str]) -> Result<String, String> {
+    crate::logger::Logger::log_debug(&format!("[Package Versions] {} {}", program, args.join(" ")));
+    let output = TokioCommand::new(program)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute {}: {}", program, e))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Err(if stderr.is_empty() { stdout } else { stderr })
+    }
+}
+
+pub async fn load(name: String) -> Result<PackageVersions, String> {
+    // dnf5 needs the explicit newline, dnf4 adds its own and leaves blank lines
+    let available = run(
+        "dnf",
+        &["repoquery", "--showduplicates", "--quiet", "--qf", "%{epoch}|%{version}|%{release}|%{arch}|%{repoid}\n", &name],
+    )
+    .await?;
+    let installed = run("rpm", &["-q", "--qf", "%{EPOCH}|%{VERSION}|%{RELEASE}|%{ARCH}\n", &name]).await.unwrap_or_default();
+
+    let parse = |line: &str| -> Option<PackageVersion> {
+        let parts: Vec<&str> = line.trim().split('|').collect();
+        if parts.len() < 4 || parts[1].is_empty() {
+            return None;
+        }
+        Some(PackageVersion {
+            epoch: if parts[0] == "(none)" { String::new() } else { parts[0].to_string() },
+            version: parts[1].to_string(),
+            release: parts[2].to_string(),
+            arch: parts[3].to_string(),
+            repo: parts.get(4).map(|r| r.to_string()).unwrap_or_else(|| "@System".to_string()),
+            installed: false,
+        })
+    };
+
+    let mut versions: Vec<PackageVersion> = available
+        .lines()
+        .filter_map(parse)
+        .filter(|v| !v.repo.starts_with('@'))
+        .collect();
+    for local in installed.lines().filter_map(parse) {
+        let matching: Vec<&mut PackageVersion> = versions
+            .iter_mut()
+            .filter(|v| v.arch == local.arch && v.compare(&local) == Ordering::Equal)
+            .collect();
+        if matching.is_empty() {
+            // Installed from a repository that no longer offers it, or from a local file
+            versions.push(PackageVersion { installed: true, ..local });
+        } else {
+            for version in matching {
+                version.installed = true;
+            }
+        }
+    }
+    versions.sort_by(|a, b| b.compare(a).then_with(|| a.arch.cmp(&b.arch)).then_with(|| a.repo.cmp(&b.repo)));
+    versions.dedup_by(|a, b| a.compare(b) == Ordering::Equal && a.arch == b.arch && a.repo == b.repo);
+
+    if versions.is_empty() {
+        return Err(format!("No versions of {} found in the enabled repositories", name));
+    }
+    let lock = load_locks().await.unwrap_or_default().into_iter().find(|l| l.name == name);
+    Ok(PackageVersions { name, versions, lock })
+}
+
+// Splits "name-[epoch:]version-release[.arch|.*]" from dnf4's versionlock list
+fn parse_lock_spec(spec: &str) -> Option<VersionLock> {
+    let spec = spec.trim().strip_suffix(".*").unwrap_or(spec.trim());
+    let mut parts = spec.rsplitn(3, '-');
+    let release = parts.next()?;
+    let version = parts.next()?;
+    let name = parts.next()?;
+    let version = version.strip_prefix("0:").unwrap_or(version);
+    Some(VersionLock {
+        name: name.to_string(),
+        evr: format!("{}-{}", version, release),
+    })
+}
+
+pub async fn load_locks() -> Result<Vec<VersionLock>, String> {
+    let output = run("dnf", &["versionlock", "list", "--quiet"])
+        .await
+        .map_err(|e| format!("dnf versionlock is not available ({}). Install python3-dnf-plugin-versionlock on DNF 4.", e))?;
+    let mut locks = Vec::new();
+    let mut dnf5_name: Option<String> = None;
+    for line in output.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') || line.starts_with("Last metadata") {
+            continue;
+        }
+        // DNF 5 prints "Package name: foo" followed by "evr = 1.0-1.fc41"
+        if let Some(name) = line.strip_prefix("Package name:") {
+            dnf5_name = Some(name.trim().to_string());
+        } else if let Some(evr) = line.strip_prefix("evr = ") {
+            if let Some(name) = dnf5_name.clone() {
+                locks.push(VersionLock { name, evr: evr.trim().to_string() });
+            }
+        } else if dnf5_name.is_none() && !line.starts_with('!') {
+            locks.extend(parse_lock_spec(line));
+        }
+    }
+    locks.sort_by(|a, b| a.name.cmp(&b.name));
+    locks.dedup_by(|a, b| a.name == b.name);
+    Ok(locks)
+}
+
+pub async fn apply(action: VersionAction) -> Result<String, String> {
+    match action {
+        VersionAction::Install(spec) => run("pkexec", &["dnf", "install", "-y", &spec])
+            .await
+            .map(|_| format!("Installed {}", spec)),
+        VersionAction::Downgrade(spec) => run("pkexec", &["dnf", "downgrade", "-y", &spec])
+            .await
+            .map(|_| format!("Downgraded to {}", spec)),
+        VersionAction::Lock(spec) => run("pkexec", &["dnf", "versionlock", "add", &spec])
+            .await
+            .map(|_| format!("Locked {}; updates will skip it", spec)),
+        VersionAction::Unlock(name) => run("pkexec", &["dnf", "versionlock", "delete", &name])
+            .await
+            .map(|_| format!("Unlocked {}", name)),
+    }
+}
+
+// Version list shared by the Search and Installed panels; the tab supplies its button styles
+pub fn view<'a, M: Clone + 'a>(
+    versions: &'a PackageVersions,
+    busy: bool,
+    on_action: impl Fn(VersionAction) -> M,
+    button_style: impl Fn(bool) -> iced::theme::Button,
+    text_size: f32,
+    accent: iced::Color,
+    muted: iced::Color,
+) -> Element<'a, M> {
+    let installed = versions.installed();
+    let mut list = column![].spacing(6);
+
+    let lock_row: Element<M> = match versions.lock {
+        Some(ref lock) => row![
+            text(format!("Locked at {}", lock.evr)).size(text_size).style(iced::theme::Text::Color(accent)).width(Length::Fill),
+            button(text("Unlock").size(text_size))
+                .on_press_maybe((!busy).then(|| on_action(VersionAction::Unlock(versions.name.clone()))))
+                .style(button_style(false))
+                .padding(Padding::from([6.0, 10.0, 6.0, 10.0])),
+        ]
+        .spacing(8)
+        .align_items(Alignment::Center)
+        .into(),
+        None => Space::with_height(Length::Shrink).into(),
+    };
+    list = list.push(lock_row);
+
+    for version in &versions.versions {
+        let spec = version.spec(&versions.name);
+        let (label, action, is_primary) = match installed {
+            _ if version.installed => ("Lock", VersionAction::Lock(spec), false),
+            Some(current) if version.compare(current) == Ordering::Less => ("Downgrade", VersionAction::Downgrade(spec), false),
+            _ => ("Install", VersionAction::Install(spec), true),
+        };
+        // A lock blocks every other version, and locking twice does nothing
+        let enabled = !busy && versions.lock.is_none();
+        let mut details = column![text(version.evr()).size(text_size)].spacing(2).width(Length::Fill);
+        details = details.push(
+            text(if version.installed {
+                format!("{} · {} · installed", version.arch, version.repo)
+            } else {
+                format!("{} · {}", version.arch, version.repo)
+            })
+            .size(text_size * 0.85)
+            .style(iced::theme::Text::Color(if version.installed { accent } else { muted })),
+        );
+        list = list.push(
+            container(
+                row![
+                    details,
+                    button(text(label).size(text_size))
+                        .on_press_maybe(enabled.then(|| on_action(action)))
+                        .style(button_style(is_primary))
+                        .padding(Padding::from([6.0, 10.0, 6.0, 10.0])),
+                ]
+                .spacing(8)
+                .align_items(Alignment::Center),
+            )
+            .padding(Padding::from([4.0, 0.0, 4.0, 0.0])),
+        );
+    }
+    list.into()
+}
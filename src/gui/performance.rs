@@ -0,0 +1,541 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::gui::multimedia::run_script;
+use crate::logger;
+
+// Drop-ins written by the tuning panel; each records the value it replaced so revert can restore it
+const MAX_MAP_COUNT_DROPIN: &str = "/etc/sysctl.d/90-rustora-max-map-count.conf";
+const SPLIT_LOCK_DROPIN: &str = "/etc/sysctl.d/90-rustora-split-lock.conf";
+const THP_DROPIN: &str = "/etc/tmpfiles.d/rustora-thp.conf";
+const EPP_DROPIN: &str = "/etc/tmpfiles.d/rustora-epp.conf";
+const NTSYNC_MODULES_DROPIN: &str = "/etc/modules-load.d/rustora-ntsync.conf";
+const NTSYNC_UDEV_DROPIN: &str = "/etc/udev/rules.d/70-rustora-ntsync.rules";
+const PREVIOUS_MARKER: &str = "# rustora-previous:";
+
+const MAX_MAP_COUNT: &str = "/proc/sys/vm/max_map_count";
+const SPLIT_LOCK: &str = "/proc/sys/kernel/split_lock_mitigate";
+const THP_ENABLED: &str = "/sys/kernel/mm/transparent_hugepage/enabled";
+const THP_DEFRAG: &str = "/sys/kernel/mm/transparent_hugepage/defrag";
+const EPP: &str = "/sys/devices/system/cpu/cpu0/cpufreq/energy_performance_preference";
+const AMD_PSTATE_STATUS: &str = "/sys/devices/system/cpu/amd_pstate/status";
+
+// SteamOS' value; some games (DayZ, Hogwarts Legacy, CS2) crash with the old 65530 default
+const RECOMMENDED_MAX_MAP_COUNT: u64 = 2147483642;
+const MIN_MAX_MAP_COUNT: u64 = 1048576;
+
+const GAMEMODE_HEADER: &str = "; Managed by Rustora";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TuningKnob {
+    GameMode,
+    PowerProfile,
+    MaxMapCount,
+    SplitLock,
+    TransparentHugepages,
+    Ntsync,
+}
+
+impl TuningKnob {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TuningKnob::GameMode => "GameMode",
+            TuningKnob::PowerProfile => "CPU power profile",
+            TuningKnob::MaxMapCount => "vm.max_map_count",
+            TuningKnob::SplitLock => "Split-lock mitigation",
+            TuningKnob::TransparentHugepages => "Transparent hugepages",
+            TuningKnob::Ntsync => "NTSYNC / fsync",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            TuningKnob::GameMode => "Config for `gamemoderun`: renice, soft real-time scheduling, performance governor and no screensaver while a game runs.",
+            TuningKnob::PowerProfile => "power-profiles-daemon profile, or the amd_pstate/intel_pstate energy-performance preference when the daemon is missing.",
+            TuningKnob::MaxMapCount => "Memory map limit per process. Some games and anti-cheat crash with the old 65530 default.",
+            TuningKnob::SplitLock => "The kernel slows down processes doing split locks, which several Windows games do constantly.",
+            TuningKnob::TransparentHugepages => "Hugepages only where requested, with background compaction instead of stalls.",
+            TuningKnob::Ntsync => "Windows sync primitives in the kernel (Proton 10+/Wine 10+); fsync via futex_waitv is the fallback.",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KnobStatus {
+    pub knob: TuningKnob,
+    pub current: String,
+    pub recommended: String,
+    pub is_recommended: bool,
+    pub available: bool,
+    // A Rustora drop-in or config is in place and can be reverted
+    pub persisted: bool,
+    pub note: String,
+}
+
+fn read_trimmed(path: &str) -> Option<String> {
+    std::fs::read_to_string(path).ok().map(|s| s.trim().to_string())
+}
+
+// "always [madvise] never" -> "madvise"
+fn bracketed(value: &str) -> String {
+    value.split_whitespace()
+        .find(|w| w.starts_with('['))
+        .map(|w| w.trim_matches(|c| c == '[' || c == ']').to_string())
+        .unwrap_or_else(|| value.to_string())
+}
+
+fn command_exists(name: &str) -> bool {
+    Command::new("sh")
+        .args(["-c", &format!("command -v {} >/dev/null 2>&1", name)])
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+fn gamemode_config_path() -> Option<PathBuf> {
+    std::env::var("HOME").ok().map(|home| PathBuf::from(home).join(".config").join("gamemode.ini"))
+}
+
+fn gamemode_status() -> KnobStatus {
+    let installed = command_exists("gamemoderun");
+    let config = gamemode_config_path().and_then(|p| std::fs::read_to_string(p).ok());
+    let managed = config.as_deref().map(|c| c.starts_with(GAMEMODE_HEADER)).unwrap_or(false);
+    let current = match (&config, installed) {
+        (_, false) => "Not installed".to_string(),
+        (Some(_), true) if managed => "Rustora config".to_string(),
+        (Some(_), true) => "Custom ~/.config/gamemode.ini".to_string(),
+        (None, true) => "Installed, default config".to_string(),
+    };
+    KnobStatus {
+        knob: TuningKnob::GameMode,
+        current,
+        recommended: "Rustora config".to_string(),
+        is_recommended: installed && managed,
+        available: installed,
+        persisted: managed,
+        note: if installed {
+            "Launch games with `gamemoderun %command%` in Steam launch options.".to_string()
+        } else {
+            "Install GameMode from the Gaming Meta view first.".to_string()
+        },
+    }
+}
+
+fn power_profile_status() -> KnobStatus {
+    if command_exists("powerprofilesctl") {
+        let current = Command::new("powerprofilesctl")
+            .arg("get")
+            .output()
+            .ok()
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+            .unwrap_or_default();
+        let profiles = Command::new("powerprofilesctl")
+            .arg("list")
+            .output()
+            .ok()
+            .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+            .unwrap_or_default();
+        let has_performance = profiles.contains("performance:");
+        return KnobStatus {
+            knob: TuningKnob::PowerProfile,
+            current: if current.is_empty() { "unknown".to_string() } else { current.clone() },
+            recommended: "performance".to_string(),
+            is_recommended: current == "performance",
+            available: has_performance,
+            // Revert switches back to the profile recorded on apply
+            persisted: current == "performance",
+            note: if has_performance {
+                "power-profiles-daemon remembers the profile across reboots.".to_string()
+            } else {
+                "This CPU/driver does not offer a performance profile.".to_string()
+            },
+        };
+    }
+
+    match read_trimmed(EPP) {
+        Some(current) => {
+            let driver = match read_trimmed(AMD_PSTATE_STATUS) {
+                Some(mode) => format!("amd_pstate ({})", mode),
+                None => "intel_pstate".to_string(),
+            };
+            KnobStatus {
+                knob: TuningKnob::PowerProfile,
+                is_recommended: current == "performance",
+                current,
+                recommended: "performance".to_string(),
+                available: true,
+                persisted: Path::new(EPP_DROPIN).exists(),
+                note: format!("Energy-performance preference of {}, set on all CPUs at boot.", driver),
+            }
+        }
+        None => KnobStatus {
+            knob: TuningKnob::PowerProfile,
+            current: "unavailable".to_string(),
+            recommended: "performance".to_string(),
+            is_recommended: false,
+            available: false,
+            persisted: false,
+            note: "Neither power-profiles-daemon nor an EPP-capable cpufreq driver was found.".to_string(),
+        },
+    }
+}
+
+fn max_map_count_status() -> KnobStatus {
+    let current = read_trimmed(MAX_MAP_COUNT).unwrap_or_default();
+    let value = current.parse::<u64>().unwrap_or(0);
+    KnobStatus {
+        knob: TuningKnob::MaxMapCount,
+        current,
+        recommended: RECOMMENDED_MAX_MAP_COUNT.to_string(),
+        is_recommended: value >= MIN_MAX_MAP_COUNT,
+        available: value > 0,
+        persisted: Path::new(MAX_MAP_COUNT_DROPIN).exists(),
+        note: format!("Anything from {} up is fine.", MIN_MAX_MAP_COUNT),
+    }
+}
+
+fn split_lock_status() -> KnobStatus {
+    match read_trimmed(SPLIT_LOCK) {
+        Some(current) => KnobStatus {
+            knob: TuningKnob::SplitLock,
+            is_recommended: current == "0",
+            current: if current == "0" { "0 (off)".to_string() } else { format!("{} (on)", current) },
+            recommended: "0 (off)".to_string(),
+            available: true,
+            persisted: Path::new(SPLIT_LOCK_DROPIN).exists(),
+            note: "Only affects processes that trigger split locks; the warning stays in the kernel log.".to_string(),
+        },
+        None => KnobStatus {
+            knob: TuningKnob::SplitLock,
+            current: "not supported".to_string(),
+            recommended: "0 (off)".to_string(),
+            is_recommended: true,
+            available: false,
+            persisted: false,
+            note: "This kernel or CPU has no split-lock detection.".to_string(),
+        },
+    }
+}
+
+fn thp_status() -> KnobStatus {
+    let enabled = read_trimmed(THP_ENABLED).map(|v| bracketed(&v));
+    let defrag = read_trimmed(THP_DEFRAG).map(|v| bracketed(&v));
+    match (enabled, defrag) {
+        (Some(enabled), Some(defrag)) => KnobStatus {
+            knob: TuningKnob::TransparentHugepages,
+            is_recommended: enabled == "madvise" && defrag == "defer+madvise",
+            current: format!("enabled={} defrag={}", enabled, defrag),
+            recommended: "enabled=madvise defrag=defer+madvise".to_string(),
+            available: true,
+            persisted: Path::new(THP_DROPIN).exists(),
+            note: "Avoids compaction stalls during gameplay without forcing hugepages on every process.".to_string(),
+        },
+        _ => KnobStatus {
+            knob: TuningKnob::TransparentHugepages,
+            current: "not supported".to_string(),
+            recommended: "enabled=madvise defrag=defer+madvise".to_string(),
+            is_recommended: true,
+            available: false,
+            persisted: false,
+            note: "This kernel was built without transparent hugepages.".to_string(),
+        },
+    }
+}
+
+// futex_waitv (fsync) landed in 5.16, ntsync in 6.14
+fn kernel_at_least(major: u32, minor: u32) -> bool {
+    let release = read_trimmed("/proc/sys/kernel/osrelease").unwrap_or_default();
+    let mut parts = release.split(|c: char| !c.is_ascii_digit()).filter_map(|p| p.parse::<u32>().ok());
+    match (parts.next(), parts.next()) {
+        (Some(ma), Some(mi)) => (ma, mi) >= (major, minor),
+        _ => false,
+    }
+}
+
+fn ntsync_status() -> KnobStatus {
+    let device = Path::new("/dev/ntsync");
+    let module_available = device.exists()
+        || Command::new("modinfo")
+            .arg("ntsync")
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false);
+    let accessible = std::fs::OpenOptions::new().read(true).open(device).is_ok();
+    let fsync = kernel_at_least(5, 16);
+
+    let ntsync = if accessible {
+        "ntsync ready"
+    } else if device.exists() {
+        "ntsync loaded, no access"
+    } else if module_available {
+        "ntsync not loaded"
+    } else {
+        "no ntsync"
+    };
+    KnobStatus {
+        knob: TuningKnob::Ntsync,
+        current: format!("{}, fsync {}", ntsync, if fsync { "supported" } else { "unsupported" }),
+        recommended: "ntsync ready".to_string(),
+        is_recommended: accessible,
+        available: module_available,
+        persisted: Path::new(NTSYNC_MODULES_DROPIN).exists(),
+        note: if module_available {
+            "Loads the module at boot and makes /dev/ntsync usable for your user.".to_string()
+        } else {
+            "Needs kernel 6.14 or newer; Proton falls back to fsync.".to_string()
+        },
+    }
+}
+
+pub fn detect_all() -> Vec<KnobStatus> {
+    let statuses = vec![
+        gamemode_status(),
+        power_profile_status(),
+        max_map_count_status(),
+        split_lock_status(),
+        thp_status(),
+        ntsync_status(),
+    ];
+    logger::Logger::log_debug(&format!(
+        "[Performance] {}",
+        statuses.iter().map(|s| format!("{}: {}", s.knob.label(), s.current)).collect::<Vec<_>>().join(", ")
+    ));
+    statuses
+}
+
+fn gamemode_config() -> String {
+    format!(
+        "{}\n\
+         ; Used by `gamemoderun`; remove this file (or use Revert in Rustora) for the defaults\n\
+         [general]\n\
+         renice=10\n\
+         softrealtime=auto\n\
+         inhibit_screensaver=1\n\
+         desiredgov=performance\n\
+         defaultgov=powersave\n\
+         \n\
+         [gpu]\n\
+         apply_gpu_optimisations=0\n",
+        GAMEMODE_HEADER
+    )
+}
+
+fn apply_gamemode() -> Result<String, String> {
+    let path = gamemode_config_path().ok_or_else(|| "HOME not set".to_string())?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let mut message = format!("Wrote {}", path.display());
+    if let Ok(existing) = std::fs::read_to_string(&path) {
+        if !existing.starts_with(GAMEMODE_HEADER) {
+            let backup = path.with_extension("ini.rustora.bak");
+            std::fs::write(&backup, existing).map_err(|e| format!("Failed to back up gamemode.ini: {}", e))?;
+            message.push_str(&format!(" (previous config saved as {})", backup.display()));
+        }
+    }
+    std::fs::write(&path, gamemode_config()).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(message)
+}
+
+fn revert_gamemode() -> Result<String, String> {
+    let path = gamemode_config_path().ok_or_else(|| "HOME not set".to_string())?;
+    let backup = path.with_extension("ini.rustora.bak");
+    if backup.exists() {
+        std::fs::rename(&backup, &path).map_err(|e| format!("Failed to restore gamemode.ini: {}", e))?;
+        return Ok(format!("Restored {}", path.display()));
+    }
+    if path.exists() {
+        std::fs::remove_file(&path).map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+    }
+    Ok("GameMode is back to its built-in defaults".to_string())
+}
+
+// Previous value recorded in a drop-in by `dropin_script`
+fn previous_value(dropin: &str) -> Option<String> {
+    std::fs::read_to_string(dropin).ok()?
+        .lines()
+        .find_map(|line| line.strip_prefix(PREVIOUS_MARKER))
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+// Value to record in a new drop-in: a second apply keeps the marker of the first one,
+// since the live value is then the one Rustora set
+fn original_value(dropin: &str, live: impl FnOnce() -> String) -> String {
+    previous_value(dropin).unwrap_or_else(live)
+}
+
+// powerprofilesctl runs as the user, so the profile it replaced is kept in the user's home
+fn power_profile_record_path() -> Option<PathBuf> {
+    std::env::var("HOME").ok().map(|home| PathBuf::from(home).join(".rustora").join("power_profile_previous"))
+}
+
+fn write_file_script(path: &str, content: &str) -> String {
+    format!("mkdir -p \"$(dirname {path})\"\ncat > {path} <<'RUSTORA_EOF'\n{content}RUSTORA_EOF\n")
+}
+
+fn sysctl_apply_script(dropin: &str, key: &str, value: &str, previous: &str) -> String {
+    let content = format!(
+        "# Written by Rustora's gaming tuning panel\n{} {}\n{} = {}\n",
+        PREVIOUS_MARKER, previous, key, value
+    );
+    format!("set -e\n{}sysctl -q -p {}\n", write_file_script(dropin, &content), dropin)
+}
+
+fn sysctl_revert_script(dropin: &str, key: &str) -> String {
+    let mut script = format!("set -e\nrm -f {}\n", dropin);
+    match previous_value(dropin) {
+        Some(previous) => script.push_str(&format!("sysctl -q -w {}={}\n", key, previous)),
+        None => script.push_str("sysctl -q --system\n"),
+    }
+    script
+}
+
+pub fn apply_script(knob: TuningKnob) -> Option<String> {
+    match knob {
+        TuningKnob::GameMode => None,
+        TuningKnob::PowerProfile => {
+            if command_exists("powerprofilesctl") {
+                return None;
+            }
+            let previous = original_value(EPP_DROPIN, || read_trimmed(EPP).unwrap_or_else(|| "default".to_string()));
+            let content = format!(
+                "# Written by Rustora's gaming tuning panel\n{} {}\nw /sys/devices/system/cpu/cpu*/cpufreq/energy_performance_preference - - - - performance\n",
+                PREVIOUS_MARKER, previous
+            );
+            Some(format!(
+                "set -e\n{}for f in /sys/devices/system/cpu/cpu*/cpufreq/energy_performance_preference; do echo performance > \"$f\"; done\n",
+                write_file_script(EPP_DROPIN, &content)
+            ))
+        }
+        TuningKnob::MaxMapCount => Some(sysctl_apply_script(
+            MAX_MAP_COUNT_DROPIN,
+            "vm.max_map_count",
+            &RECOMMENDED_MAX_MAP_COUNT.to_string(),
+            &original_value(MAX_MAP_COUNT_DROPIN, || read_trimmed(MAX_MAP_COUNT).unwrap_or_default()),
+        )),
+        TuningKnob::SplitLock => Some(sysctl_apply_script(
+            SPLIT_LOCK_DROPIN,
+            "kernel.split_lock_mitigate",
+            "0",
+            &original_value(SPLIT_LOCK_DROPIN, || read_trimmed(SPLIT_LOCK).unwrap_or_default()),
+        )),
+        TuningKnob::TransparentHugepages => {
+            let previous = original_value(THP_DROPIN, || {
+                format!(
+                    "{} {}",
+                    read_trimmed(THP_ENABLED).map(|v| bracketed(&v)).unwrap_or_else(|| "madvise".to_string()),
+                    read_trimmed(THP_DEFRAG).map(|v| bracketed(&v)).unwrap_or_else(|| "madvise".to_string())
+                )
+            });
+            let content = format!(
+                "# Written by Rustora's gaming tuning panel\n{} {}\nw {} - - - - madvise\nw {} - - - - defer+madvise\n",
+                PREVIOUS_MARKER, previous, THP_ENABLED, THP_DEFRAG
+            );
+            Some(format!(
+                "set -e\n{}echo madvise > {}\necho defer+madvise > {}\n",
+                write_file_script(THP_DROPIN, &content),
+                THP_ENABLED,
+                THP_DEFRAG
+            ))
+        }
+        TuningKnob::Ntsync => Some(format!(
+            "set -e\n{}{}modprobe ntsync\nudevadm control --reload\nudevadm trigger --name-match=ntsync || true\n",
+            write_file_script(NTSYNC_MODULES_DROPIN, "# Written by Rustora's gaming tuning panel\nntsync\n"),
+            write_file_script(NTSYNC_UDEV_DROPIN, "# Written by Rustora's gaming tuning panel\nKERNEL==\"ntsync\", MODE=\"0666\"\n")
+        )),
+    }
+}
+
+pub fn revert_script(knob: TuningKnob) -> Option<String> {
+    match knob {
+        TuningKnob::GameMode => None,
+        TuningKnob::PowerProfile => {
+            if command_exists("powerprofilesctl") {
+                return None;
+            }
+            let previous = previous_value(EPP_DROPIN).unwrap_or_else(|| "default".to_string());
+            Some(format!(
+                "set -e\nrm -f {}\nfor f in /sys/devices/system/cpu/cpu*/cpufreq/energy_performance_preference; do echo {} > \"$f\"; done\n",
+                EPP_DROPIN, previous
+            ))
+        }
+        TuningKnob::MaxMapCount => Some(sysctl_revert_script(MAX_MAP_COUNT_DROPIN, "vm.max_map_count")),
+        TuningKnob::SplitLock => Some(sysctl_revert_script(SPLIT_LOCK_DROPIN, "kernel.split_lock_mitigate")),
+        TuningKnob::TransparentHugepages => {
+            let previous = previous_value(THP_DROPIN).unwrap_or_else(|| "madvise madvise".to_string());
+            let mut values = previous.split_whitespace();
+            let enabled = values.next().unwrap_or("madvise").to_string();
+            let defrag = values.next().unwrap_or("madvise").to_string();
+            Some(format!(
+                "set -e\nrm -f {}\necho {} > {}\necho {} > {}\n",
+                THP_DROPIN, enabled, THP_ENABLED, defrag, THP_DEFRAG
+            ))
+        }
+        TuningKnob::Ntsync => Some(format!(
+            "set -e\nrm -f {} {}\nudevadm control --reload\nmodprobe -r ntsync 2>/dev/null || true\n",
+            NTSYNC_MODULES_DROPIN, NTSYNC_UDEV_DROPIN
+        )),
+    }
+}
+
+pub async fn apply(knob: TuningKnob) -> Result<String, String> {
+    logger::Logger::log_debug(&format!("[Performance] Applying {}", knob.label()));
+    match knob {
+        TuningKnob::GameMode => apply_gamemode(),
+        TuningKnob::PowerProfile if apply_script(knob).is_none() => {
+            let current = run_user_command("powerprofilesctl", &["get"]).await?.trim().to_string();
+            if !current.is_empty() && current != "performance" {
+                if let Some(path) = power_profile_record_path() {
+                    if let Some(parent) = path.parent() {
+                        let _ = std::fs::create_dir_all(parent);
+                    }
+                    std::fs::write(&path, &current).map_err(|e| format!("Failed to record the power profile: {}", e))?;
+                }
+            }
+            run_user_command("powerprofilesctl", &["set", "performance"]).await
+                .map(|_| "Power profile set to performance".to_string())
+        }
+        _ => match apply_script(knob) {
+            Some(script) => run_script(script).await.map(|_| format!("{} applied", knob.label())),
+            None => Err(format!("{} has nothing to apply", knob.label())),
+        },
+    }
+}
+
+pub async fn revert(knob: TuningKnob) -> Result<String, String> {
+    logger::Logger::log_debug(&format!("[Performance] Reverting {}", knob.label()));
+    match knob {
+        TuningKnob::GameMode => revert_gamemode(),
+        TuningKnob::PowerProfile if revert_script(knob).is_none() => {
+            let record = power_profile_record_path();
+            let previous = record.as_ref()
+                .and_then(|path| std::fs::read_to_string(path).ok())
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| "balanced".to_string());
+            run_user_command("powerprofilesctl", &["set", &previous]).await?;
+            if let Some(path) = record {
+                let _ = std::fs::remove_file(path);
+            }
+            Ok(format!("Power profile set back to {}", previous))
+        }
+        _ => match revert_script(knob) {
+            Some(script) => run_script(script).await.map(|_| format!("{} reverted", knob.label())),
+            None => Err(format!("{} has nothing to revert", knob.label())),
+        },
+    }
+}
+
+async fn run_user_command(program: &str, args: &[&str]) -> Result<String, String> {
+    let output = tokio::process::Command::new(program)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(format!("{} failed: {}", program, String::from_utf8_lossy(&output.stderr).trim()))
+    }
+}
@@ -0,0 +1,312 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+// Shipped with the binary so the Proton view works offline and behind proxies
+const BUNDLED_RUNNERS_JSON: &str = include_str!("../../data/runners.json");
+
+const UPSTREAM_URLS: &[&str] = &[
+    "https://raw.githubusercontent.com/Vysp3r/ProtonPlus/main/data/runners.json",
+    "https://github.com/Vysp3r/ProtonPlus/raw/main/data/runners.json",
+    "https://raw.githubusercontent.com/Vysp3r/ProtonPlus/refs/heads/main/data/runners.json",
+];
+
+const GITHUB_API: &str = "https://api.github.com";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub enum CatalogSource {
+    // data/runners.json next to the executable, or the copy built into it
+    #[default]
+    Bundled,
+    // ProtonPlus' runners.json on GitHub
+    Upstream,
+    LocalFile(String),
+    Mirror(String),
+}
+
+impl CatalogSource {
+    pub const LABELS: [&'static str; 4] = ["Bundled", "ProtonPlus (GitHub)", "Local file", "Mirror URL"];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CatalogSource::Bundled => Self::LABELS[0],
+            CatalogSource::Upstream => Self::LABELS[1],
+            CatalogSource::LocalFile(_) => Self::LABELS[2],
+            CatalogSource::Mirror(_) => Self::LABELS[3],
+        }
+    }
+
+    pub fn location(&self) -> &str {
+        match self {
+            CatalogSource::LocalFile(location) | CatalogSource::Mirror(location) => location,
+            _ => "",
+        }
+    }
+
+    pub fn from_label(label: &str, location: &str) -> Self {
+        match label {
+            l if l == Self::LABELS[1] => CatalogSource::Upstream,
+            l if l == Self::LABELS[2] => CatalogSource::LocalFile(location.trim().to_string()),
+            l if l == Self::LABELS[3] => CatalogSource::Mirror(location.trim().to_string()),
+            _ => CatalogSource::Bundled,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CatalogSettings {
+    #[serde(default)]
+    pub source: CatalogSource,
+    // Raises the GitHub API limit from 60 to 5000 requests per hour
+    #[serde(default)]
+    pub github_token: Option<String>,
+    // Replaces https://api.github.com in runner endpoints, for a mirror speaking the same API
+    #[serde(default)]
+    pub api_base: Option<String>,
+}
+
+impl CatalogSettings {
+    pub fn load() -> Self {
+        if let Ok(home) = std::env::var("HOME") {
+            let path = PathBuf::from(&home).join(".rustora").join("proton_catalog.json");
+            if let Ok(content) = std::fs::read_to_string(&path) {
+                if let Ok(settings) = serde_json::from_str::<CatalogSettings>(&content) {
+                    return settings;
+                }
+            }
+        }
+        Self::default()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        if let Ok(home) = std::env::var("HOME") {
+            let settings_dir = PathBuf::from(&home).join(".rustora");
+            if let Err(e) = std::fs::create_dir_all(&settings_dir) {
+                return Err(format!("Failed to create settings directory: {}", e));
+            }
+            let path = settings_dir.join("proton_catalog.json");
+            let json = serde_json::to_string_pretty(self)
+                .map_err(|e| format!("Failed to serialize catalog settings: {}", e))?;
+            std::fs::write(&path, json)
+                .map_err(|e| format!("Failed to write catalog settings: {}", e))?;
+            // The file may hold a token
+            use std::os::unix::fs::PermissionsExt;
+            let _ = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600));
+        }
+        Ok(())
+    }
+
+    // Saved token first, then GITHUB_TOKEN for `rustora proton sync` on a timer
+    pub fn token(&self) -> Option<String> {
+        self.github_token.clone()
+            .filter(|t| !t.trim().is_empty())
+            .or_else(|| std::env::var("GITHUB_TOKEN").ok().filter(|t| !t.trim().is_empty()))
+    }
+
+    pub fn releases_url(&self, endpoint: &str) -> String {
+        match self.api_base.as_deref().map(str::trim).filter(|b| !b.is_empty()) {
+            Some(base) if endpoint.starts_with(GITHUB_API) => {
+                format!("{}{}", base.trim_end_matches('/'), &endpoint[GITHUB_API.len()..])
+            }
+            _ => endpoint.to_string(),
+        }
+    }
+}
+
+// Load runners.json from the configured source, falling back to the bundled copy.
+// The second value explains a fallback so the tab can tell the user
+pub async fn load_catalog(settings: &CatalogSettings) -> Result<(serde_json::Value, Option<String>), String> {
+    let result = match &settings.source {
+        CatalogSource::Bundled => return Ok((bundled_catalog(), None)),
+        CatalogSource::Upstream => fetch_first(UPSTREAM_URLS.iter().map(|u| u.to_string()).collect()).await,
+        CatalogSource::Mirror(url) => fetch_first(vec![url.clone()]).await,
+        CatalogSource::LocalFile(path) => std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path, e))
+            .and_then(|content| {
+                serde_json::from_str(&content).map_err(|e| format!("Invalid JSON in {}: {}", path, e))
+            }),
+    };
+    match result {
+        Ok(json) => Ok((json, None)),
+        Err(e) => {
+            let warning = format!("{} failed ({}), using the bundled runner list", settings.source.label(), e);
+            crate::logger::Logger::log_debug(&format!("[Proton Catalog] {}", warning));
+            Ok((bundled_catalog(), Some(warning)))
+        }
+    }
+}
+
+fn bundled_catalog() -> serde_json::Value {
+    // A newer data/runners.json installed next to the binary wins over the built-in copy
+    if let Ok(exe_path) = std::env::current_exe() {
+        if let Some(exe_dir) = exe_path.parent() {
+            for path in [exe_dir.join("data").join("runners.json"), exe_dir.join("..").join("data").join("runners.json")] {
+                if let Ok(content) = std::fs::read_to_string(&path) {
+                    if let Ok(json) = serde_json::from_str(&content) {
+                        return json;
+                    }
+                }
+            }
+        }
+    }
+    serde_json::from_str(BUNDLED_RUNNERS_JSON).unwrap_or(serde_json::Value::Null)
+}
+
+async fn fetch_first(urls: Vec<String>) -> Result<serde_json::Value, String> {
+    let client = reqwest::Client::new();
+    let mut last_error = "No catalog URL configured".to_string();
+    for url in urls {
+        let response = match client.get(&url).header("User-Agent", "Rustora/1.0").send().await {
+            Ok(response) => response,
+            Err(e) => {
+                last_error = format!("Failed to fetch from {}: {}", url, e);
+                continue;
+            }
+        };
+        if !response.status().is_success() {
+            last_error = format!("HTTP {} from {}", response.status(), url);
+            continue;
+        }
+        match response.json::<serde_json::Value>().await {
+            Ok(json) => return Ok(json),
+            Err(e) => last_error = format!("Invalid JSON from {}: {}", url, e),
+        }
+    }
+    Err(last_error)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedResponse {
+    etag: String,
+    body: String,
+}
+
+// Release listings keyed by URL; a 304 reply costs nothing against the GitHub rate limit
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct HttpCache {
+    entries: HashMap<String, CachedResponse>,
+}
+
+impl HttpCache {
+    fn path() -> Option<PathBuf> {
+        let home = std::env::var("HOME").ok()?;
+        Some(PathBuf::from(home).join(".cache").join("rustora").join("proton_http.json"))
+    }
+
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Some(path) = Self::path() {
+            if let Some(parent) = path.parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+            if let Ok(json) = serde_json::to_string(self) {
+                let _ = std::fs::write(path, json);
+            }
+        }
+    }
+}
+
+// GET a GitHub releases listing with the token, ETag and rate-limit handling applied.
+// Once rate limited, a cached listing is returned with the rate-limit message as a warning
+pub async fn fetch_releases(
+    client: &reqwest::Client,
+    settings: &CatalogSettings,
+    cache: &mut HttpCache,
+    endpoint: &str,
+    page: usize,
+) -> Result<(serde_json::Value, Option<String>), String> {
+    let url = format!("{}?per_page=25&page={}", settings.releases_url(endpoint), page);
+    let mut request = client
+        .get(&url)
+        .header("User-Agent", "Rustora/1.0")
+        .header("Accept", "application/vnd.github+json");
+    if let Some(token) = settings.token() {
+        request = request.bearer_auth(token.trim());
+    }
+    if let Some(cached) = cache.entries.get(&url) {
+        request = request.header("If-None-Match", cached.etag.as_str());
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch releases: {}", e))?;
+    let status = response.status();
+
+    let cached_json = |cache: &HttpCache| {
+        cache.entries.get(&url).map(|cached| {
+            serde_json::from_str(&cached.body).map_err(|e| format!("Failed to parse cached releases: {}", e))
+        })
+    };
+    if status == reqwest::StatusCode::NOT_MODIFIED {
+        if let Some(json) = cached_json(cache) {
+            return json.map(|json| (json, None));
+        }
+    }
+    if let Some(message) = rate_limit_message(&response, settings) {
+        return match cached_json(cache) {
+            Some(Ok(json)) => Ok((json, Some(format!("Showing cached releases. {}", message)))),
+            _ => Err(message),
+        };
+    }
+    if !status.is_success() {
+        return Err(format!("Failed to fetch releases: HTTP {} from {}", status, url));
+    }
+
+    let etag = response.headers()
+        .get(reqwest::header::ETAG)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string());
+    let body = response.text().await
+        .map_err(|e| format!("Failed to read releases: {}", e))?;
+    let json = serde_json::from_str(&body)
+        .map_err(|e| format!("Failed to parse releases JSON: {}", e))?;
+    if let Some(etag) = etag {
+        cache.entries.insert(url, CachedResponse { etag, body });
+    }
+    Ok((json, None))
+}
+
+// GitHub answers 403 or 429 with x-ratelimit-remaining: 0 once the hourly budget is spent
+fn rate_limit_message(response: &reqwest::Response, settings: &CatalogSettings) -> Option<String> {
+    let status = response.status();
+    if status != reqwest::StatusCode::FORBIDDEN && status != reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return None;
+    }
+    let header = |name: &str| {
+        response.headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string())
+    };
+    let remaining = header("x-ratelimit-remaining");
+    if remaining.as_deref() != Some("0") && header("retry-after").is_none() && status != reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return None;
+    }
+
+    let limit = header("x-ratelimit-limit").unwrap_or_else(|| "?".to_string());
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let wait_secs = header("x-ratelimit-reset")
+        .and_then(|reset| reset.parse::<u64>().ok())
+        .map(|reset| reset.saturating_sub(now))
+        .or_else(|| header("retry-after").and_then(|v| v.parse::<u64>().ok()));
+    let wait = match wait_secs {
+        Some(secs) => format!("resets in {} min", secs.div_ceil(60)),
+        None => "try again later".to_string(),
+    };
+    let hint = if settings.token().is_some() {
+        "Switch to a mirror or wait for the reset."
+    } else {
+        "Add a GitHub token in the catalog settings or use a mirror."
+    };
+    Some(format!("GitHub API rate limit reached ({} requests/hour, {}). {}", limit, wait, hint))
+}
@@ -0,0 +1,522 @@
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+use crate::logger;
+
+// Checksum assets GitHub releases publish next to the archive, in lookup order
+const CHECKSUM_EXTENSIONS: &[&str] = &[".sha512sum", ".sha256sum"];
+const ARCHIVE_EXTENSIONS: &[&str] = &[".tar.gz", ".tar.xz", ".tar.zst", ".tgz", ".7z", ".zip"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumKind {
+    Sha256,
+    Sha512,
+}
+
+impl ChecksumKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChecksumKind::Sha256 => "SHA-256",
+            ChecksumKind::Sha512 => "SHA-512",
+        }
+    }
+
+    fn command(&self) -> &'static str {
+        match self {
+            ChecksumKind::Sha256 => "sha256sum",
+            ChecksumKind::Sha512 => "sha512sum",
+        }
+    }
+
+    fn from_hex_len(len: usize) -> Option<Self> {
+        match len {
+            64 => Some(ChecksumKind::Sha256),
+            128 => Some(ChecksumKind::Sha512),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Checksum {
+    pub kind: ChecksumKind,
+    pub hex: String,
+}
+
+// ~/.cache/rustora/proton holds downloaded archives, extract/ the unpacked trees
+pub fn proton_cache_dir() -> Result<PathBuf, String> {
+    let home = std::env::var("HOME").map_err(|_| "HOME not set".to_string())?;
+    let dir = Path::new(&home).join(".cache").join("rustora").join("proton");
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create download cache dir: {}", e))?;
+    Ok(dir)
+}
+
+pub fn extract_dir(build_title: &str) -> Result<PathBuf, String> {
+    Ok(proton_cache_dir()?.join("extract").join(sanitize_file_name(build_title)))
+}
+
+// Keep the asset's own file name (and therefore its extension) when it has one
+pub fn archive_path(build_title: &str, download_url: &str) -> Result<PathBuf, String> {
+    let from_url = download_url
+        .split(['?', '#'])
+        .next()
+        .and_then(|u| u.rsplit('/').next())
+        .map(sanitize_file_name)
+        .filter(|name| ARCHIVE_EXTENSIONS.iter().any(|ext| name.ends_with(ext)));
+    let name = from_url.unwrap_or_else(|| format!("{}.tar.gz", sanitize_file_name(build_title)));
+    Ok(proton_cache_dir()?.join(name))
+}
+
+fn sanitize_file_name(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| if c == '/' || c == '\\' || c.is_control() { '_' } else { c })
+        .collect();
+    match cleaned.trim_start_matches('.') {
+        "" => "download".to_string(),
+        rest => rest.to_string(),
+    }
+}
+
+fn part_path(archive: &Path) -> PathBuf {
+    let mut name = archive.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+    name.push(".part");
+    archive.with_file_name(name)
+}
+
+// Downloads into `<archive>.part`, resuming with an HTTP Range request when a partial
+// file from an earlier attempt exists. `on_progress` gets (downloaded, total) bytes.
+pub async fn download_resumable<F>(download_url: &str, archive: &Path, mut on_progress: F) -> Result<(), String>
+where
+    F: FnMut(u64, u64),
+{
+    use futures::StreamExt;
+    use tokio::io::AsyncWriteExt;
+
+    let part = part_path(archive);
+    let existing = std::fs::metadata(&part).map(|m| m.len()).unwrap_or(0);
+
+    let client = reqwest::Client::new();
+    let mut request = client.get(download_url).header("User-Agent", "Rustora/1.0");
+    if existing > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={}-", existing));
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download: {}", e))?;
+
+    let status = response.status();
+    // The server says the range starts past the end: the partial file is already complete
+    if status == reqwest::StatusCode::RANGE_NOT_SATISFIABLE && existing > 0 {
+        std::fs::rename(&part, archive).map_err(|e| format!("Failed to finalize download: {}", e))?;
+        on_progress(existing, existing);
+        return Ok(());
+    }
+    if !status.is_success() {
+        return Err(format!("Failed to download: HTTP {}", status));
+    }
+
+    let resumed = status == reqwest::StatusCode::PARTIAL_CONTENT;
+    let offset = if resumed { existing } else { 0 };
+    let total = response.content_length().map(|len| len + offset).unwrap_or(0);
+    if existing > 0 {
+        logger::Logger::log_debug(&format!(
+            "[Proton Download] {} at byte {} of {}",
+            if resumed { "Resuming" } else { "Server ignored range, restarting" },
+            existing,
+            total
+        ));
+    }
+
+    let file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resumed)
+        .truncate(!resumed)
+        .open(&part)
+        .await
+        .map_err(|e| format!("Failed to create file: {}", e))?;
+    let mut writer = tokio::io::BufWriter::new(file);
+    let mut stream = response.bytes_stream();
+    let mut downloaded = offset;
+    on_progress(downloaded, total);
+
+    while let Some(chunk) = stream.next().await {
+        // The .part file is kept on errors so the next attempt can resume
+        let chunk = chunk.map_err(|e| format!("Download interrupted after {} bytes: {}", downloaded, e))?;
+        writer
+            .write_all(&chunk)
+            .await
+            .map_err(|e| format!("Write error: {}", e))?;
+        downloaded += chunk.len() as u64;
+        on_progress(downloaded, total);
+    }
+    writer.flush().await.map_err(|e| format!("Flush error: {}", e))?;
+    drop(writer);
+
+    if downloaded == 0 {
+        let _ = std::fs::remove_file(&part);
+        return Err("Downloaded file is empty".to_string());
+    }
+    if total > 0 && downloaded != total {
+        return Err(format!(
+            "Downloaded file size mismatch: expected {} bytes, got {} bytes",
+            total, downloaded
+        ));
+    }
+    std::fs::rename(&part, archive).map_err(|e| format!("Failed to finalize download: {}", e))?;
+    Ok(())
+}
+
+// The runner catalog records the checksum asset when the release has one; otherwise
+// try the sibling names GE-Proton and friends use (`<name>.sha512sum`).
+fn checksum_candidates(download_url: &str, checksum_url: Option<&str>) -> Vec<String> {
+    if let Some(url) = checksum_url {
+        return vec![url.to_string()];
+    }
+    let base = ARCHIVE_EXTENSIONS
+        .iter()
+        .find_map(|ext| download_url.strip_suffix(ext))
+        .unwrap_or(download_url);
+    let mut candidates = Vec::new();
+    for ext in CHECKSUM_EXTENSIONS {
+        candidates.push(format!("{}{}", base, ext));
+        if base != download_url {
+            candidates.push(format!("{}{}", download_url, ext));
+        }
+    }
+    candidates
+}
+
+// `sha512sum` output format: "<hex>  <file name>", possibly for several files
+fn parse_checksum_file(content: &str, file_name: &str) -> Option<Checksum> {
+    let mut fallback = None;
+    let mut lines = 0;
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        lines += 1;
+        let mut parts = line.split_whitespace();
+        let hex = parts.next()?.to_ascii_lowercase();
+        let kind = match ChecksumKind::from_hex_len(hex.len()) {
+            Some(kind) if hex.chars().all(|c| c.is_ascii_hexdigit()) => kind,
+            _ => continue,
+        };
+        let name = parts.next().unwrap_or("").trim_start_matches('*');
+        let checksum = Checksum { kind, hex };
+        if name.rsplit('/').next() == Some(file_name) {
+            return Some(checksum);
+        }
+        fallback = Some(checksum);
+    }
+    // A single unnamed line is the common "just the hash" form
+    if lines == 1 { fallback } else { None }
+}
+
+pub async fn fetch_expected_checksum(download_url: &str, checksum_url: Option<&str>, file_name: &str) -> Result<Option<Checksum>, String> {
+    let client = reqwest::Client::new();
+    for url in checksum_candidates(download_url, checksum_url) {
+        let response = match client.get(&url).header("User-Agent", "Rustora/1.0").send().await {
+            Ok(response) => response,
+            Err(e) if checksum_url.is_some() => return Err(format!("Failed to fetch checksum: {}", e)),
+            Err(_) => continue,
+        };
+        if !response.status().is_success() {
+            if checksum_url.is_some() {
+                return Err(format!("Failed to fetch checksum: HTTP {}", response.status()));
+            }
+            continue;
+        }
+        let content = response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read checksum file: {}", e))?;
+        return match parse_checksum_file(&content, file_name) {
+            Some(checksum) => Ok(Some(checksum)),
+            None => Err(format!("Checksum file {} has no entry for {}", url, file_name)),
+        };
+    }
+    Ok(None)
+}
+
+pub async fn compute_checksum(path: &Path, kind: ChecksumKind) -> Result<String, String> {
+    let output = tokio::process::Command::new(kind.command())
+        .arg(path)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run {}: {}", kind.command(), e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "{} failed: {}",
+            kind.command(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .next()
+        .map(|h| h.to_ascii_lowercase())
+        .ok_or_else(|| format!("{} returned no hash", kind.command()))
+}
+
+// Download (or reuse a cached copy of) a build archive and verify it against the
+// published checksum. Returns the archive path and a one-line verification summary.
+pub async fn fetch_verified_archive<F>(
+    build_title: &str,
+    download_url: &str,
+    checksum_url: Option<&str>,
+    on_progress: F,
+) -> Result<(PathBuf, String), String>
+where
+    F: FnMut(u64, u64),
+{
+    let archive = archive_path(build_title, download_url)?;
+    let file_name = archive
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let expected = fetch_expected_checksum(download_url, checksum_url, &file_name).await?;
+
+    let cached = archive.exists();
+    if !cached {
+        download_resumable(download_url, &archive, on_progress).await?;
+    }
+
+    let summary = match &expected {
+        Some(checksum) => {
+            let actual = compute_checksum(&archive, checksum.kind).await?;
+            if actual != checksum.hex {
+                // Never keep a bad archive around; the next attempt starts clean
+                let _ = std::fs::remove_file(&archive);
+                return Err(format!(
+                    "{} mismatch for {}: expected {}, got {}. The download was discarded.",
+                    checksum.kind.label(),
+                    file_name,
+                    checksum.hex,
+                    actual
+                ));
+            }
+            format!("{} verified ({})", checksum.kind.label(), file_name)
+        }
+        None => format!("No checksum published for {}; integrity not verified", file_name),
+    };
+    logger::Logger::log_debug(&format!(
+        "[Proton Download] {}{}",
+        summary,
+        if cached { " (cached archive)" } else { "" }
+    ));
+    Ok((archive, summary))
+}
+
+// Relative, no `..`, no root or prefix components
+fn is_safe_relative(path: &Path) -> bool {
+    path.components().all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+// Lexically resolve a symlink target against the link's directory and make sure it
+// stays inside the extraction root
+fn link_stays_inside(entry_path: &Path, target: &Path) -> bool {
+    if target.is_absolute() {
+        return false;
+    }
+    let mut depth: i64 = 0;
+    let parent = entry_path.parent().unwrap_or_else(|| Path::new(""));
+    for component in parent.components().chain(target.components()) {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir => {
+                depth -= 1;
+                if depth < 0 {
+                    return false;
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => return false,
+        }
+    }
+    true
+}
+
+// Unpack entry by entry, refusing anything that would land outside `dest`
+pub fn unpack_tar_safely<R: Read>(reader: R, dest: &Path) -> Result<(), String> {
+    let mut archive = tar::Archive::new(reader);
+    let entries = archive
+        .entries()
+        .map_err(|e| format!("Failed to read archive: {}", e))?;
+
+    for entry in entries {
+        let mut entry = entry.map_err(|e| {
+            format!("Archive appears to be corrupted or in an unsupported format. Please try downloading again. Original error: {}", e)
+        })?;
+        let path = entry
+            .path()
+            .map_err(|e| format!("Invalid path in archive: {}", e))?
+            .into_owned();
+        if !is_safe_relative(&path) {
+            return Err(format!("Refusing to extract unsafe path: {}", path.display()));
+        }
+
+        let entry_type = entry.header().entry_type();
+        if entry_type.is_symlink() || entry_type.is_hard_link() {
+            let target = entry
+                .link_name()
+                .map_err(|e| format!("Invalid link in archive: {}", e))?
+                .map(|t| t.into_owned())
+                .unwrap_or_default();
+            let safe = if entry_type.is_symlink() {
+                link_stays_inside(&path, &target)
+            } else {
+                is_safe_relative(&target)
+            };
+            if !safe {
+                return Err(format!(
+                    "Refusing to extract link {} -> {} pointing outside the archive",
+                    path.display(),
+                    target.display()
+                ));
+            }
+        }
+
+        let unpacked = entry
+            .unpack_in(dest)
+            .map_err(|e| format!("Failed to extract {}: {}", path.display(), e))?;
+        if !unpacked {
+            return Err(format!("Refusing to extract unsafe path: {}", path.display()));
+        }
+    }
+    Ok(())
+}
+
+// 7z/zip go through external tools, so check their listings before extracting
+// Entries start after `separator` when the tool prints a header first (7z's header has a
+// "Path = " line holding the archive's own absolute path)
+fn check_listing(tool: &str, args: &[&str], archive: &Path, separator: Option<&str>, prefix: &str) -> Result<(), String> {
+    let output = std::process::Command::new(tool)
+        .args(args)
+        .arg(archive)
+        .output()
+        .map_err(|e| format!("Failed to execute {} command. Is it installed? Error: {}", tool, e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "{} could not list the archive: {}",
+            tool,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let listing = String::from_utf8_lossy(&output.stdout);
+    let mut in_entries = separator.is_none();
+    for line in listing.lines() {
+        if !in_entries {
+            in_entries = Some(line.trim()) == separator;
+            continue;
+        }
+        let name = match line.strip_prefix(prefix) {
+            Some(name) => name.trim(),
+            None => continue,
+        };
+        if !name.is_empty() && !is_safe_relative(Path::new(name)) {
+            return Err(format!("Refusing to extract unsafe path: {}", name));
+        }
+    }
+    Ok(())
+}
+
+// Symlinks created by 7z/unzip can't be vetted up front; reject the tree afterwards
+fn check_extracted_links(root: &Path, dir: &Path) -> Result<(), String> {
+    let entries = std::fs::read_dir(dir).map_err(|e| format!("Failed to read extract dir: {}", e))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let file_type = entry.file_type().map_err(|e| format!("Failed to read entry: {}", e))?;
+        if file_type.is_symlink() {
+            let target = std::fs::read_link(&path).unwrap_or_default();
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            if !link_stays_inside(relative, &target) {
+                return Err(format!(
+                    "Refusing to install link {} -> {} pointing outside the archive",
+                    relative.display(),
+                    target.display()
+                ));
+            }
+        } else if file_type.is_dir() {
+            check_extracted_links(root, &path)?;
+        }
+    }
+    Ok(())
+}
+
+// Detect the archive format from its magic bytes and unpack into `dest`
+pub fn unpack_archive(archive: &Path, dest: &Path) -> Result<(), String> {
+    let mut file = std::fs::File::open(archive)
+        .map_err(|e| format!("Failed to open archive: {} ({})", e, archive.display()))?;
+    let mut magic = [0u8; 6];
+    if file.read_exact(&mut magic).is_err() {
+        return Err("Archive file appears to be corrupted or incomplete".to_string());
+    }
+    let file = std::fs::File::open(archive).map_err(|e| format!("Failed to reopen archive: {}", e))?;
+
+    let is_gzip = magic[0] == 0x1f && magic[1] == 0x8b;
+    let is_zstd = magic[..4] == [0x28, 0xb5, 0x2f, 0xfd];
+    let is_xz = magic == [0xfd, 0x37, 0x7a, 0x58, 0x5a, 0x00];
+    let is_7z = magic == [0x37, 0x7a, 0xbc, 0xaf, 0x27, 0x1c];
+    let is_zip = magic[0] == 0x50 && magic[1] == 0x4b && matches!(magic[2], 0x03 | 0x05 | 0x07);
+
+    if is_gzip {
+        unpack_tar_safely(flate2::read::GzDecoder::new(file), dest)
+    } else if is_zstd {
+        let decoder = zstd::stream::Decoder::new(file)
+            .map_err(|e| format!("Failed to create zstd decoder: {}", e))?;
+        unpack_tar_safely(decoder, dest)
+    } else if is_xz {
+        unpack_tar_safely(xz2::read::XzDecoder::new(file), dest)
+    } else if is_7z || is_zip {
+        let (tool, args): (&str, Vec<String>) = if is_7z {
+            check_listing("7z", &["l", "-slt"], archive, Some("----------"), "Path = ")?;
+            ("7z", vec!["x".to_string(), format!("-o{}", dest.display()), "-y".to_string()])
+        } else {
+            check_listing("unzip", &["-Z1"], archive, None, "")?;
+            ("unzip", vec!["-q".to_string(), "-o".to_string()])
+        };
+        let mut command = std::process::Command::new(tool);
+        command.args(&args).arg(archive);
+        if is_zip {
+            command.arg("-d").arg(dest);
+        }
+        let output = command
+            .output()
+            .map_err(|e| format!("Failed to execute {} command. Error: {}", tool, e))?;
+        if !output.status.success() {
+            return Err(format!("{} extraction failed: {}", tool, String::from_utf8_lossy(&output.stderr)));
+        }
+        check_extracted_links(dest, dest)
+    } else {
+        Err(format!(
+            "Unsupported archive format (magic bytes: {:02x} {:02x} {:02x} {:02x}). Expected gzip (.tar.gz), zstd (.tar.zst), xz (.tar.xz), 7z, or zip format.",
+            magic[0], magic[1], magic[2], magic[3]
+        ))
+    }
+}
+
+// Fresh extraction directory for a build, then the top-level directory the archive unpacked to
+pub fn prepare_extract_dir(build_title: &str) -> Result<PathBuf, String> {
+    let dir = extract_dir(build_title)?;
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir).map_err(|e| format!("Failed to clean temp extract: {}", e))?;
+    }
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create temp extract: {}", e))?;
+    Ok(dir)
+}
+
+pub fn find_extracted_dir(extract_root: &Path) -> Result<PathBuf, String> {
+    let entries = std::fs::read_dir(extract_root).map_err(|e| format!("Failed to read extract dir: {}", e))?;
+    entries
+        .flatten()
+        .find(|entry| entry.file_type().map(|t| t.is_dir()).unwrap_or(false))
+        .map(|entry| entry.path())
+        .ok_or_else(|| "No directory found in archive".to_string())
+}
+
+// Installed builds don't need the archive or the unpacked copy any more
+pub fn cleanup(archive: &Path, extract_root: &Path) {
+    let _ = std::fs::remove_file(archive);
+    let _ = std::fs::remove_dir_all(extract_root);
+}
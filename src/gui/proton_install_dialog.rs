@@ -8,10 +8,14 @@ use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::time::sleep;
 
+use crate::gui::launchers::{self, DetectedLauncher};
+use crate::gui::proton_download;
+use crate::gui::tabs::tweaks::{build_directory_name, ProtonRunner};
+
 #[derive(Debug, Clone)]
 pub enum Message {
     StartDownload,
-    DownloadComplete(Result<String, String>),
+    DownloadComplete(Result<(String, String), String>),
     StartExtraction,
     ExtractionComplete(Result<String, String>),
     StartInstallation,
@@ -47,9 +51,13 @@ pub struct ProtonInstallDialog {
     runner_title: String,
     build_title: String,
     download_url: String,
-    selected_launcher: Option<String>,
+    checksum_url: Option<String>,
+    launchers: Vec<String>,
     runner_info: Option<String>,
 
+    archive_path: Option<String>,
+    extracted_dir: Option<String>,
+
     is_downloading: bool,
     is_extracting: bool,
     is_installing: bool,
@@ -66,15 +74,19 @@ impl ProtonInstallDialog {
         runner_title: String,
         build_title: String,
         download_url: String,
-        selected_launcher: Option<String>,
+        checksum_url: Option<String>,
+        launchers: Vec<String>,
         runner_info: Option<String>,
     ) -> Self {
         Self {
             runner_title,
             build_title,
             download_url,
-            selected_launcher,
+            checksum_url,
+            launchers,
             runner_info,
+            archive_path: None,
+            extracted_dir: None,
             is_downloading: false,
             is_extracting: false,
             is_installing: false,
@@ -89,10 +101,11 @@ impl ProtonInstallDialog {
         runner_title: String,
         build_title: String,
         download_url: String,
-        selected_launcher: Option<String>,
+        checksum_url: Option<String>,
+        launchers: Vec<String>,
         runner_info: Option<String>,
     ) -> Result<(), iced::Error> {
-        let dialog = Self::new(runner_title, build_title, download_url, selected_launcher, runner_info);
+        let dialog = Self::new(runner_title, build_title, download_url, checksum_url, launchers, runner_info);
 
         let mut window_settings = iced::window::Settings::default();
         window_settings.size = iced::Size::new(650.0, 550.0);
@@ -139,19 +152,22 @@ impl Application for ProtonInstallDialog {
                 self.terminal_output.push_str("=====================================\n\n");
 
                 let download_url = self.download_url.clone();
+                let checksum_url = self.checksum_url.clone();
                 let build_title = self.build_title.clone();
                 let progress_state = Arc::clone(&self.progress_state);
 
                 Command::perform(
-                    download_with_progress(download_url, build_title, progress_state),
+                    download_with_progress(download_url, checksum_url, build_title, progress_state),
                     |result| Message::DownloadComplete(result),
                 )
             }
             Message::DownloadComplete(result) => {
                 self.is_downloading = false;
                 match result {
-                    Ok(tar_path) => {
-                        self.terminal_output.push_str(&format!("Download complete: {}\n\n", tar_path));
+                    Ok((tar_path, verification)) => {
+                        self.terminal_output.push_str(&format!("Download complete: {}\n", tar_path));
+                        self.terminal_output.push_str(&format!("{}\n\n", verification));
+                        self.archive_path = Some(tar_path);
                         Command::perform(async {}, |_| Message::StartExtraction)
                     }
                     Err(e) => {
@@ -165,13 +181,12 @@ impl Application for ProtonInstallDialog {
                 self.is_extracting = true;
                 self.terminal_output.push_str("Starting extraction...\n");
 
-                let temp_dir = std::env::temp_dir();
-                let tar_path = temp_dir.join(format!("{}.tar.gz", self.build_title));
+                let tar_path = self.archive_path.clone().unwrap_or_default();
                 let build_title = self.build_title.clone();
                 let progress_state = Arc::clone(&self.progress_state);
 
                 Command::perform(
-                    extract_with_progress(tar_path.to_string_lossy().to_string(), build_title, progress_state),
+                    extract_with_progress(tar_path, build_title, progress_state),
                     |result| Message::ExtractionComplete(result),
                 )
             }
@@ -180,6 +195,7 @@ impl Application for ProtonInstallDialog {
                 match result {
                     Ok(extracted_dir) => {
                         self.terminal_output.push_str(&format!("Extraction complete: {}\n\n", extracted_dir));
+                        self.extracted_dir = Some(extracted_dir);
                         Command::perform(async {}, |_| Message::StartInstallation)
                     }
                     Err(e) => {
@@ -193,11 +209,11 @@ impl Application for ProtonInstallDialog {
                 self.is_installing = true;
                 self.terminal_output.push_str("Starting installation...\n");
 
-                let temp_dir = std::env::temp_dir();
-                let tar_path = temp_dir.join(format!("{}.tar.gz", self.build_title));
+                let tar_path = self.archive_path.clone().unwrap_or_default();
+                let extracted_dir = self.extracted_dir.clone().unwrap_or_default();
                 let runner_title = self.runner_title.clone();
                 let build_title = self.build_title.clone();
-                let selected_launcher = self.selected_launcher.clone();
+                let launchers = self.launchers.clone();
                 let runner_info = self.runner_info.clone();
                 let progress_state = Arc::clone(&self.progress_state);
 
@@ -205,8 +221,9 @@ impl Application for ProtonInstallDialog {
                     install_with_progress(
                         runner_title,
                         build_title,
-                        tar_path.to_string_lossy().to_string(),
-                        selected_launcher,
+                        tar_path,
+                        extracted_dir,
+                        launchers,
                         runner_info,
                         progress_state,
                     ),
@@ -414,86 +431,48 @@ impl ProtonInstallDialog {
 // Async functions with progress reporting
 async fn download_with_progress(
     download_url: String,
+    checksum_url: Option<String>,
     build_title: String,
     progress_state: Arc<Mutex<ProgressState>>,
-) -> Result<String, String> {
-    use reqwest::Client;
-
-    let client = Client::new();
-    let temp_dir = std::env::temp_dir();
-    let tar_path = temp_dir.join(format!("{}.tar.gz", build_title));
-
-    // Update progress
+) -> Result<(String, String), String> {
     if let Ok(mut state) = progress_state.lock() {
         state.download_progress = 0.0;
         state.download_message = "Connecting...".to_string();
     }
 
-    let response = client
-        .get(&download_url)
-        .header("User-Agent", "Rustora/1.0")
-        .send()
-        .await
-        .map_err(|e| format!("Failed to download: {}", e))?;
-
-    let total_size = response.content_length().unwrap_or(0);
-
-    if let Ok(mut state) = progress_state.lock() {
-        state.download_progress = 0.1;
-        state.download_message = format!("Downloading... ({} MB)", total_size as f64 / 1_048_576.0);
-    }
-
-    use tokio::fs::File as TokioFile;
-    use tokio::io::{AsyncWriteExt, BufWriter};
-
-    let mut file = TokioFile::create(&tar_path).await
-        .map_err(|e| format!("Failed to create file: {}", e))?;
-
-    let mut writer = BufWriter::new(&mut file);
-    let mut stream = response.bytes_stream();
-    let mut downloaded = 0u64;
-
-    use futures::StreamExt;
-    while let Some(chunk_result) = stream.next().await {
-        let chunk = chunk_result.map_err(|e| format!("Download error: {}", e))?;
-        writer.write_all(&chunk).await
-            .map_err(|e| format!("Write error: {}", e))?;
-        downloaded += chunk.len() as u64;
-
-        if total_size > 0 {
-            let progress = downloaded as f32 / total_size as f32;
-            if let Ok(mut state) = progress_state.lock() {
+    let progress_for_download = Arc::clone(&progress_state);
+    let (tar_path, verification) = proton_download::fetch_verified_archive(
+        &build_title,
+        &download_url,
+        checksum_url.as_deref(),
+        move |downloaded, total| {
+            if total == 0 {
+                return;
+            }
+            let progress = downloaded as f32 / total as f32;
+            if let Ok(mut state) = progress_for_download.lock() {
                 state.download_progress = progress.min(0.99);
-                state.download_message = format!(
-                    "Downloading... {:.1} MB / {:.1} MB ({:.1}%)",
-                    downloaded as f64 / 1_048_576.0,
-                    total_size as f64 / 1_048_576.0,
-                    progress * 100.0
-                );
+                state.download_message = if progress >= 1.0 {
+                    "Verifying checksum...".to_string()
+                } else {
+                    format!(
+                        "Downloading... {:.1} MB / {:.1} MB ({:.1}%)",
+                        downloaded as f64 / 1_048_576.0,
+                        total as f64 / 1_048_576.0,
+                        progress * 100.0
+                    )
+                };
             }
-        }
-    }
-
-    writer.flush().await.map_err(|e| format!("Flush error: {}", e))?;
-    drop(writer); // Ensure writer is dropped and file is closed
-    drop(file); // Ensure file handle is closed
-
-    // Verify file was written correctly
-    let final_metadata = std::fs::metadata(&tar_path)
-        .map_err(|e| format!("Failed to verify downloaded file: {}", e))?;
-    if final_metadata.len() == 0 {
-        return Err("Downloaded file is empty".to_string());
-    }
-    if total_size > 0 && final_metadata.len() != total_size {
-        return Err(format!("Downloaded file size mismatch: expected {} bytes, got {} bytes", total_size, final_metadata.len()));
-    }
+        },
+    )
+    .await?;
 
     if let Ok(mut state) = progress_state.lock() {
         state.download_progress = 1.0;
         state.download_message = "Download complete".to_string();
     }
 
-    Ok(tar_path.to_string_lossy().to_string())
+    Ok((tar_path.to_string_lossy().to_string(), verification))
 }
 
 async fn extract_with_progress(
@@ -501,189 +480,35 @@ async fn extract_with_progress(
     build_title: String,
     progress_state: Arc<Mutex<ProgressState>>,
 ) -> Result<String, String> {
-    use std::fs::File;
-    use flate2::read::GzDecoder;
-    use tar::Archive;
-
     if let Ok(mut state) = progress_state.lock() {
         state.extraction_progress = 0.0;
         state.extraction_message = "Validating archive...".to_string();
     }
 
     // Validate file exists and is not empty
-    let tar_path_buf = std::path::Path::new(&tar_path);
-    if !tar_path_buf.exists() {
-        return Err(format!("Archive file does not exist: {}", tar_path));
-    }
-
-    let metadata = std::fs::metadata(tar_path_buf)
-        .map_err(|e| format!("Failed to read archive metadata: {}", e))?;
-    if metadata.len() == 0 {
-        return Err("Archive file is empty".to_string());
+    let tar_path_buf = std::path::PathBuf::from(&tar_path);
+    let metadata = std::fs::metadata(&tar_path_buf)
+        .map_err(|e| format!("Archive file does not exist: {} ({})", tar_path, e))?;
+    if metadata.len() < 100 {
+        return Err("Archive file is too small to be valid".to_string());
     }
 
-    let home = std::env::var("HOME").unwrap_or_else(|_| "/home".to_string());
-    let home_tmp = std::path::Path::new(&home).join(".tmp");
-    std::fs::create_dir_all(&home_tmp)
-        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
-
-    let temp_extract = home_tmp.join(format!("proton_extract_{}", build_title));
-    if temp_extract.exists() {
-        std::fs::remove_dir_all(&temp_extract)
-            .map_err(|e| format!("Failed to clean temp extract: {}", e))?;
-    }
-    std::fs::create_dir_all(&temp_extract)
-        .map_err(|e| format!("Failed to create temp extract: {}", e))?;
+    let temp_extract = proton_download::prepare_extract_dir(&build_title)?;
 
     if let Ok(mut state) = progress_state.lock() {
         state.extraction_progress = 0.1;
         state.extraction_message = "Opening archive...".to_string();
     }
 
-    // Extract with progress tracking
-    // Use unpack which doesn't require holding Archive across await
-    // We'll simulate progress since tar crate doesn't support async extraction easily
-    let tar_path_clone = tar_path.clone();
+    // The tar crate has no async API, so extract on a blocking thread and simulate progress
     let temp_extract_clone = temp_extract.clone();
-    let progress_state_clone = Arc::clone(&progress_state);
-
-    // Start extraction in a blocking task with progress simulation
     let extract_handle = tokio::task::spawn_blocking(move || {
-        // Small delay to ensure file is fully written and closed
-        std::thread::sleep(std::time::Duration::from_millis(100));
-
-        // Verify file exists again in blocking context
-        if !std::path::Path::new(&tar_path_clone).exists() {
-            return Err(format!("Archive file does not exist: {}", tar_path_clone));
-        }
-
-        // Verify file is not empty and has reasonable size
-        let file_metadata = std::fs::metadata(&tar_path_clone)
-            .map_err(|e| format!("Failed to read file metadata: {}", e))?;
-        if file_metadata.len() < 100 {
-            return Err("Archive file is too small to be valid".to_string());
-        }
-
-        let file = File::open(&tar_path_clone)
-            .map_err(|e| format!("Failed to open archive: {} ({})", e, tar_path_clone))?;
-
-        // Try to read first few bytes to detect file format
-        use std::io::{Read, Seek, SeekFrom};
-        let mut peek_buf = [0u8; 6];
-        let mut peek_file = file.try_clone()
-            .map_err(|e| format!("Failed to clone file handle: {}", e))?;
-
-        if peek_file.read_exact(&mut peek_buf).is_err() {
-            return Err("Archive file appears to be corrupted or incomplete".to_string());
-        }
-
-        // Check file format by magic bytes
-        let is_gzip = peek_buf[0] == 0x1f && peek_buf[1] == 0x8b;
-        // Zstd format signature: 28 B5 2F FD (bytes 0-3)
-        let is_zstd = peek_buf[0] == 0x28 && peek_buf[1] == 0xb5 && peek_buf[2] == 0x2f && peek_buf[3] == 0xfd;
-        // XZ format signature: FD 37 7A 58 5A 00 (bytes 0-5)
-        let is_xz = peek_buf[0] == 0xfd && peek_buf[1] == 0x37 && peek_buf[2] == 0x7a && peek_buf[3] == 0x58 && peek_buf[4] == 0x5a && peek_buf[5] == 0x00;
-        // 7z format signature: 37 7A BC AF 27 1C (bytes 0-5)
-        let is_7z = peek_buf[0] == 0x37 && peek_buf[1] == 0x7a && peek_buf[2] == 0xbc && peek_buf[3] == 0xaf && peek_buf[4] == 0x27 && peek_buf[5] == 0x1c;
-        // ZIP format signature: 50 4B (PK) followed by 03, 05, or 07
-        let is_zip = peek_buf[0] == 0x50 && peek_buf[1] == 0x4b && (peek_buf[2] == 0x03 || peek_buf[2] == 0x05 || peek_buf[2] == 0x07);
-
-
-        if is_7z {
-            // Use system's 7z command to extract
-            let output = std::process::Command::new("7z")
-                .arg("x")
-                .arg(&tar_path_clone)
-                .arg(format!("-o{}", temp_extract_clone.to_string_lossy()))
-                .arg("-y") // Assume yes to all prompts
-                .output()
-                .map_err(|e| format!("Failed to execute 7z command. Is p7zip installed? Error: {}", e))?;
-
-            if !output.status.success() {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                return Err(format!("7z extraction failed: {}", stderr));
-            }
-
-            return Ok(());
-        } else if is_zip {
-            // Use system's unzip command
-            let output = std::process::Command::new("unzip")
-                .arg("-q") // Quiet mode
-                .arg("-o") // Overwrite files
-                .arg(&tar_path_clone)
-                .arg("-d")
-                .arg(&temp_extract_clone)
-                .output()
-                .map_err(|e| format!("Failed to execute unzip command. Is unzip installed? Error: {}", e))?;
-
-            if !output.status.success() {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                return Err(format!("unzip extraction failed: {}", stderr));
-            }
-
-            return Ok(());
-        } else if !is_gzip && !is_zstd && !is_xz {
-            return Err(format!("Unsupported archive format (magic bytes: {:02x} {:02x} {:02x} {:02x}). Expected gzip (.tar.gz), zstd (.tar.zst), xz (.tar.xz), 7z, or zip format.",
-                peek_buf[0], peek_buf[1], peek_buf[2], peek_buf[3]));
-        }
-
-        // Reset file position for actual extraction
-        let mut file = File::open(&tar_path_clone)
-            .map_err(|e| format!("Failed to reopen archive: {}", e))?;
-        file.seek(SeekFrom::Start(0))
-            .map_err(|e| format!("Failed to seek file: {}", e))?;
-
-        // Create appropriate decoder and extract based on format
-        if is_gzip {
-        let gz = GzDecoder::new(file);
-        let mut archive = Archive::new(gz);
-        archive.unpack(&temp_extract_clone)
-            .map_err(|e| {
-                    let error_msg = format!("Failed to extract gzip archive: {}", e);
-                if error_msg.contains("failed to iterate") {
-                    format!("Archive appears to be corrupted or in an unsupported format. Please try downloading again. Original error: {}", e)
-                } else {
-                    error_msg
-                }
-                })?;
-        } else if is_zstd {
-            // zstd
-            use zstd::stream::Decoder;
-            let decoder = Decoder::new(file)
-                .map_err(|e| format!("Failed to create zstd decoder: {}", e))?;
-            let mut archive = Archive::new(decoder);
-            archive.unpack(&temp_extract_clone)
-                .map_err(|e| {
-                    let error_msg = format!("Failed to extract zstd archive: {}", e);
-                    if error_msg.contains("failed to iterate") {
-                        format!("Archive appears to be corrupted or in an unsupported format. Please try downloading again. Original error: {}", e)
-                    } else {
-                        error_msg
-                    }
-                })?;
-        } else {
-            // xz
-            use xz2::read::XzDecoder;
-            let xz = XzDecoder::new(file);
-            let mut archive = Archive::new(xz);
-            archive.unpack(&temp_extract_clone)
-                .map_err(|e| {
-                    let error_msg = format!("Failed to extract xz archive: {}", e);
-                    if error_msg.contains("failed to iterate") {
-                        format!("Archive appears to be corrupted or in an unsupported format. Please try downloading again. Original error: {}", e)
-                    } else {
-                        error_msg
-                    }
-                })?;
-        }
-
-        Ok(())
+        proton_download::unpack_archive(&tar_path_buf, &temp_extract_clone)
     });
 
-    // Simulate progress while extraction happens
     let mut current_progress = 0.1;
     while !extract_handle.is_finished() {
-        if let Ok(mut state) = progress_state_clone.lock() {
+        if let Ok(mut state) = progress_state.lock() {
             state.extraction_progress = current_progress;
             state.extraction_message = format!("Extracting... {:.0}%", current_progress * 100.0);
         }
@@ -691,43 +516,29 @@ async fn extract_with_progress(
         sleep(Duration::from_millis(200)).await;
     }
 
-    // Wait for extraction to complete
-    extract_handle.await
-        .map_err(|e| format!("Extraction task error: {}", e))??;
+    let extracted = extract_handle.await
+        .map_err(|e| format!("Extraction task error: {}", e))?;
+    if let Err(e) = extracted {
+        let _ = std::fs::remove_dir_all(&temp_extract);
+        return Err(e);
+    }
 
-    // Update progress to complete
     if let Ok(mut state) = progress_state.lock() {
         state.extraction_progress = 1.0;
         state.extraction_message = "Extraction complete".to_string();
     }
 
-    // Find extracted directory
-    let entries: Vec<_> = std::fs::read_dir(&temp_extract)
-        .map_err(|e| format!("Failed to read extract dir: {}", e))?
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| format!("Failed to read entry: {}", e))?;
-
-    let extracted_dir = entries
-        .iter()
-        .find(|entry| {
-            if let Ok(metadata) = entry.metadata() {
-                metadata.is_dir()
-            } else {
-                false
-            }
-        })
-        .ok_or_else(|| "No directory found in archive".to_string())?
-        .path();
-
+    let extracted_dir = proton_download::find_extracted_dir(&temp_extract)?;
     Ok(extracted_dir.to_string_lossy().to_string())
 }
 
 async fn install_with_progress(
-    _runner_title: String,
+    runner_title: String,
     build_title: String,
     tar_path: String,
-    selected_launcher: Option<String>,
-    _runner_info: Option<String>,
+    extracted_dir: String,
+    launchers: Vec<String>,
+    runner_info: Option<String>,
     progress_state: Arc<Mutex<ProgressState>>,
 ) -> Result<(), String> {
 
@@ -736,76 +547,41 @@ async fn install_with_progress(
         state.installation_message = "Preparing installation...".to_string();
     }
 
-    // Get launcher directory
-    let home = std::env::var("HOME").unwrap_or_else(|_| "/home".to_string());
-    let launcher_title = selected_launcher.as_ref().map(|s| s.as_str()).unwrap_or("Steam");
-
-    let compat_dir = match launcher_title {
-        "Steam" => {
-            let paths = vec![
-                format!("{}/.steam/root/compatibilitytools.d", home),
-                format!("{}/.local/share/Steam/compatibilitytools.d", home),
-                format!("{}/.steam/steam/compatibilitytools.d", home),
-            ];
-            paths.iter()
-                .find(|p| std::path::Path::new(p).exists())
-                .cloned()
-                .unwrap_or_else(|| paths[1].clone())
-        }
-        _ => format!("{}/.local/share/{}/compatibilitytools.d", home, launcher_title),
-    };
+    let runner: Option<ProtonRunner> = runner_info
+        .as_deref()
+        .and_then(|info| serde_json::from_str(info).ok());
+    let compat_layer_type = runner.as_ref().map(|r| r.compat_layer_type.clone()).unwrap_or_else(|| "Proton".to_string());
 
-    std::fs::create_dir_all(&compat_dir)
-        .map_err(|e| format!("Failed to create compat directory: {}", e))?;
-
-    if let Ok(mut state) = progress_state.lock() {
-        state.installation_progress = 0.2;
-        state.installation_message = "Copying files...".to_string();
-    }
-
-    // The extracted directory should already exist from extraction step
-    // For now, we'll assume it's in the temp extract location
-    let home_tmp = std::path::Path::new(&home).join(".tmp");
-    let temp_extract = home_tmp.join(format!("proton_extract_{}", build_title));
-
-    let entries: Vec<_> = std::fs::read_dir(&temp_extract)
-        .map_err(|e| format!("Failed to read extract dir: {}", e))?
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| format!("Failed to read entry: {}", e))?;
-
-    let extracted_dir = entries
-        .iter()
-        .find(|entry| {
-            if let Ok(metadata) = entry.metadata() {
-                metadata.is_dir()
-            } else {
-                false
-            }
-        })
-        .ok_or_else(|| "No directory found in archive".to_string())?
-        .path();
-
-    let dest_path = std::path::Path::new(&compat_dir).join(&build_title);
-
-    if dest_path.exists() {
-        std::fs::remove_dir_all(&dest_path)
-            .map_err(|e| format!("Failed to remove existing: {}", e))?;
+    // Same build into every launcher that was ticked, each with its own directory layout
+    let detected = launchers::detect();
+    let targets: Vec<&DetectedLauncher> = if launchers.is_empty() {
+        detected.iter().filter(|l| l.kind == "Steam").take(1).collect()
+    } else {
+        launchers.iter()
+            .map(|title| detected.iter().find(|l| &l.title == title).ok_or_else(|| format!("Launcher '{}' not found", title)))
+            .collect::<Result<Vec<_>, String>>()?
+    };
+    if targets.is_empty() {
+        return Err("No launcher to install into".to_string());
     }
 
-    if let Ok(mut state) = progress_state.lock() {
-        state.installation_progress = 0.5;
-        state.installation_message = "Installing files...".to_string();
+    let extracted_dir = std::path::PathBuf::from(extracted_dir);
+    if !extracted_dir.is_dir() {
+        return Err(format!("Extracted directory not found: {}", extracted_dir.display()));
     }
 
-    // Copy directory
+    // Copy directory, recreating symlinks instead of following them
     fn copy_dir_all(src: &std::path::Path, dst: &std::path::Path) -> std::io::Result<()> {
         std::fs::create_dir_all(dst)?;
         for entry in std::fs::read_dir(src)? {
             let entry = entry?;
             let path = entry.path();
             let dst_path = dst.join(entry.file_name());
+            let file_type = entry.file_type()?;
 
-            if path.is_dir() {
+            if file_type.is_symlink() {
+                std::os::unix::fs::symlink(std::fs::read_link(&path)?, &dst_path)?;
+            } else if file_type.is_dir() {
                 copy_dir_all(&path, &dst_path)?;
             } else {
                 std::fs::copy(&path, &dst_path)?;
@@ -814,8 +590,33 @@ async fn install_with_progress(
         Ok(())
     }
 
-    copy_dir_all(&extracted_dir, &dest_path)
-        .map_err(|e| format!("Failed to copy: {}", e))?;
+    let step = 0.8 / targets.len() as f32;
+    for (idx, launcher) in targets.iter().enumerate() {
+        if let Ok(mut state) = progress_state.lock() {
+            state.installation_progress = 0.1 + step * idx as f32;
+            state.installation_message = format!("Installing into {}...", launcher.label());
+        }
+
+        let compat_dir = launcher.compat_directory(&compat_layer_type)
+            .ok_or_else(|| format!("{} does not use {} builds", launcher.title, compat_layer_type))?;
+        std::fs::create_dir_all(&compat_dir)
+            .map_err(|e| format!("Failed to create compat directory: {}", e))?;
+
+        let directory_name = match &runner {
+            Some(runner) => build_directory_name(runner, &launcher.kind, &build_title),
+            None => build_title.clone(),
+        };
+        let dest_path = std::path::Path::new(&compat_dir).join(&directory_name);
+
+        if dest_path.exists() {
+            std::fs::remove_dir_all(&dest_path)
+                .map_err(|e| format!("Failed to remove existing: {}", e))?;
+        }
+
+        copy_dir_all(&extracted_dir, &dest_path)
+            .map_err(|e| format!("Failed to copy to {}: {}", compat_dir, e))?;
+        crate::logger::Logger::log_debug(&format!("[Proton] Installed {} {} into {}", runner_title, build_title, dest_path.display()));
+    }
 
     if let Ok(mut state) = progress_state.lock() {
         state.installation_progress = 0.9;
@@ -823,8 +624,9 @@ async fn install_with_progress(
     }
 
     // Cleanup
-    let _ = std::fs::remove_file(&tar_path);
-    let _ = std::fs::remove_dir_all(&temp_extract);
+    if let Ok(extract_root) = proton_download::extract_dir(&build_title) {
+        proton_download::cleanup(std::path::Path::new(&tar_path), &extract_root);
+    }
 
     if let Ok(mut state) = progress_state.lock() {
         state.installation_progress = 1.0;
@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+// Retention rule for one runner installed into one launcher
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProtonPolicy {
+    pub runner: String,
+    pub launcher: String,
+    // Number of newest installed releases to keep; 0 never removes anything
+    pub keep_latest: usize,
+    pub auto_install: bool,
+    pub protect_in_use: bool,
+}
+
+impl ProtonPolicy {
+    pub fn new(runner: &str, launcher: &str) -> Self {
+        Self {
+            runner: runner.to_string(),
+            launcher: launcher.to_string(),
+            keep_latest: 3,
+            auto_install: true,
+            protect_in_use: true,
+        }
+    }
+
+    // Only Steam's config records which tool each game uses, so for other launchers
+    // protect_in_use can't tell builds apart and keeps all of them
+    pub fn summary(&self, usage_known: bool) -> String {
+        let keep = if self.keep_latest == 0 {
+            "keep all builds".to_string()
+        } else {
+            format!("keep latest {}", self.keep_latest)
+        };
+        format!(
+            "{}{}{}",
+            keep,
+            if self.auto_install { ", auto-install new releases" } else { "" },
+            match (self.protect_in_use, usage_known) {
+                (false, _) => "",
+                (true, true) => ", never remove builds in use",
+                (true, false) => ", nothing removed (builds in use are only detected for Steam)",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProtonPolicies {
+    pub policies: Vec<ProtonPolicy>,
+}
+
+impl ProtonPolicies {
+    pub fn load() -> Self {
+        if let Ok(home) = std::env::var("HOME") {
+            let path = PathBuf::from(&home).join(".rustora").join("proton_policies.json");
+            if let Ok(content) = std::fs::read_to_string(&path) {
+                if let Ok(policies) = serde_json::from_str::<ProtonPolicies>(&content) {
+                    return policies;
+                }
+            }
+        }
+        Self::default()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        if let Ok(home) = std::env::var("HOME") {
+            let settings_dir = PathBuf::from(&home).join(".rustora");
+            if let Err(e) = std::fs::create_dir_all(&settings_dir) {
+                return Err(format!("Failed to create settings directory: {}", e));
+            }
+            let path = settings_dir.join("proton_policies.json");
+            let json = serde_json::to_string_pretty(self)
+                .map_err(|e| format!("Failed to serialize policies: {}", e))?;
+            std::fs::write(&path, json)
+                .map_err(|e| format!("Failed to write policies: {}", e))?;
+        }
+        Ok(())
+    }
+
+    pub fn find(&self, runner: &str, launcher: &str) -> Option<&ProtonPolicy> {
+        self.policies.iter().find(|p| p.runner == runner && p.launcher == launcher)
+    }
+
+    pub fn upsert(&mut self, policy: ProtonPolicy) {
+        match self.policies.iter_mut().find(|p| p.runner == policy.runner && p.launcher == policy.launcher) {
+            Some(existing) => *existing = policy,
+            None => self.policies.push(policy),
+        }
+    }
+
+    pub fn remove(&mut self, runner: &str, launcher: &str) {
+        self.policies.retain(|p| !(p.runner == runner && p.launcher == launcher));
+    }
+}
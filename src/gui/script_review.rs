@@ -0,0 +1,559 @@
+// Static analysis of cfhdb install/remove scripts, shown to the admin before anything runs as root
+
+#[derive(Debug, Clone, Default)]
+pub struct ScriptSummary {
+    pub repos_enabled: Vec<String>,
+    pub packages_installed: Vec<String>,
+    pub packages_removed: Vec<String>,
+    pub files_written: Vec<String>,
+    pub services: Vec<String>,
+    // dnf invocations, kept for the simulate run
+    pub dnf_steps: Vec<Vec<String>>,
+}
+
+impl ScriptSummary {
+    pub fn analyze(script: &str) -> Self {
+        let mut summary = ScriptSummary::default();
+        for command in split_commands(script) {
+            summary.inspect(&command);
+        }
+        summary
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.repos_enabled.is_empty()
+            && self.packages_installed.is_empty()
+            && self.packages_removed.is_empty()
+            && self.files_written.is_empty()
+            && self.services.is_empty()
+    }
+
+    fn inspect(&mut self, words: &[String]) {
+        let mut words: Vec<String> = words.to_vec();
+
+        // Redirections can appear anywhere in the command
+        let mut i = 0;
+        while i < words.len() {
+            let word = words[i].clone();
+            let target = if word == ">" || word == ">>" || word == "&>" || word == "1>" || word == "2>" {
+                let t = words.get(i + 1).cloned();
+                words.drain(i..(i + 2).min(words.len()));
+                t
+            } else if let Some(rest) = redirect_target(&word) {
+                words.remove(i);
+                Some(rest)
+            } else {
+                i += 1;
+                None
+            };
+            if let Some(t) = target {
+                if !t.starts_with("/dev/") && !t.starts_with('&') {
+                    push_unique(&mut self.files_written, t);
+                }
+            }
+        }
+
+        // Strip privilege wrappers and leading VAR=value assignments
+        while let Some(first) = words.first() {
+            if first == "sudo" || first == "pkexec" || first == "env" || first == "exec"
+                || (first.contains('=') && !first.starts_with('-') && !first.starts_with('='))
+            {
+                words.remove(0);
+            } else {
+                break;
+            }
+        }
+
+        let program = match words.first() {
+            Some(p) => p.rsplit('/').next().unwrap_or(p).to_string(),
+            None => return,
+        };
+        let args: Vec<String> = words[1..].to_vec();
+        let positional: Vec<String> = args.iter().filter(|a| !a.starts_with('-')).cloned().collect();
+
+        match program.as_str() {
+            "dnf" | "dnf5" | "yum" => self.inspect_dnf(&words, &args, &positional),
+            "rpm" => {
+                let installs = args.iter().any(|a| a.starts_with("-i") || a.starts_with("-U") || a.starts_with("-F") || a == "--install" || a == "--upgrade");
+                let removes = args.iter().any(|a| a.starts_with("-e") || a == "--erase");
+                if args.iter().any(|a| a == "--import") {
+                    for key in &positional {
+                        push_unique(&mut self.repos_enabled, format!("GPG key {}", key));
+                    }
+                } else if installs {
+                    for pkg in &positional {
+                        push_unique(&mut self.packages_installed, pkg.clone());
+                    }
+                } else if removes {
+                    for pkg in &positional {
+                        push_unique(&mut self.packages_removed, pkg.clone());
+                    }
+                }
+            }
+            "flatpak" => {
+                if let Some(sub) = positional.first() {
+                    let target = match sub.as_str() {
+                        "install" => Some(&mut self.packages_installed),
+                        "uninstall" | "remove" => Some(&mut self.packages_removed),
+                        _ => None,
+                    };
+                    if let Some(list) = target {
+                        for pkg in positional.iter().skip(1) {
+                            push_unique(list, format!("flatpak:{}", pkg));
+                        }
+                    } else if sub == "remote-add" {
+                        if let Some(name) = positional.get(1) {
+                            push_unique(&mut self.repos_enabled, format!("flatpak remote {}", name));
+                        }
+                    }
+                }
+            }
+            "systemctl" => {
+                if let Some(action) = positional.first() {
+                    if action == "daemon-reload" {
+                        push_unique(&mut self.services, "daemon-reload".to_string());
+                    } else {
+                        for unit in positional.iter().skip(1) {
+                            push_unique(&mut self.services, format!("{} {}", action, unit));
+                        }
+                    }
+                }
+            }
+            "tee" => {
+                for file in &positional {
+                    push_unique(&mut self.files_written, file.clone());
+                }
+            }
+            "cp" | "mv" | "install" | "ln" => {
+                if program == "install" && args.iter().any(|a| a == "-d") {
+                    return;
+                }
+                if let Some(dest) = positional.last() {
+                    if positional.len() >= 2 {
+                        push_unique(&mut self.files_written, dest.clone());
+                    }
+                }
+            }
+            "sed" if args.iter().any(|a| a.starts_with("-i") || a.starts_with("--in-place")) => {
+                if let Some(file) = positional.last() {
+                    push_unique(&mut self.files_written, file.clone());
+                }
+            }
+            "rm" => {
+                for file in &positional {
+                    push_unique(&mut self.files_written, format!("{} (deleted)", file));
+                }
+            }
+            "touch" | "chmod" | "chown" => {
+                let skip = if program == "touch" { 0 } else { 1 };
+                for file in positional.iter().skip(skip) {
+                    push_unique(&mut self.files_written, file.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn inspect_dnf(&mut self, words: &[String], args: &[String], positional: &[String]) {
+        for arg in args {
+            if let Some(repo) = arg.strip_prefix("--enablerepo=") {
+                for r in repo.split(',') {
+                    push_unique(&mut self.repos_enabled, r.to_string());
+                }
+            }
+            if let Some(url) = arg.strip_prefix("--add-repo=").or_else(|| arg.strip_prefix("--from-repofile=")) {
+                push_unique(&mut self.repos_enabled, url.to_string());
+            }
+        }
+
+        let sub = match positional.first() {
+            Some(s) => s.as_str(),
+            None => return,
+        };
+        let rest: Vec<String> = positional.iter().skip(1).cloned().collect();
+
+        match sub {
+            "install" | "reinstall" | "downgrade" | "groupinstall" => {
+                for pkg in &rest {
+                    push_unique(&mut self.packages_installed, pkg.clone());
+                }
+                self.dnf_steps.push(words.to_vec());
+            }
+            "remove" | "erase" | "autoremove" | "groupremove" => {
+                for pkg in &rest {
+                    push_unique(&mut self.packages_removed, pkg.clone());
+                }
+                self.dnf_steps.push(words.to_vec());
+            }
+            "swap" => {
+                if let Some(old) = rest.first() {
+                    push_unique(&mut self.packages_removed, old.clone());
+                }
+                if let Some(new) = rest.get(1) {
+                    push_unique(&mut self.packages_installed, new.clone());
+                }
+                self.dnf_steps.push(words.to_vec());
+            }
+            "group" | "groups" => {
+                match rest.first().map(|s| s.as_str()) {
+                    Some("install") => rest.iter().skip(1).for_each(|g| push_unique(&mut self.packages_installed, format!("@{}", g.trim_start_matches('@')))),
+                    Some("remove") => rest.iter().skip(1).for_each(|g| push_unique(&mut self.packages_removed, format!("@{}", g.trim_start_matches('@')))),
+                    _ => return,
+                }
+                self.dnf_steps.push(words.to_vec());
+            }
+            "upgrade" | "update" | "distro-sync" => {
+                if rest.is_empty() {
+                    push_unique(&mut self.packages_installed, "(all available updates)".to_string());
+                } else {
+                    for pkg in &rest {
+                        push_unique(&mut self.packages_installed, pkg.clone());
+                    }
+                }
+                self.dnf_steps.push(words.to_vec());
+            }
+            "config-manager" => {
+                let mut enable_next = false;
+                for arg in args {
+                    if arg == "--set-enabled" || arg == "--add-repo" || arg == "addrepo" || arg == "--enable" {
+                        enable_next = true;
+                        continue;
+                    }
+                    if let Some(repo) = arg.strip_suffix(".enabled=1") {
+                        push_unique(&mut self.repos_enabled, repo.to_string());
+                    } else if let Some(url) = arg.strip_prefix("--from-repofile=") {
+                        push_unique(&mut self.repos_enabled, url.to_string());
+                    } else if enable_next && !arg.starts_with('-') {
+                        push_unique(&mut self.repos_enabled, arg.clone());
+                    } else if arg.starts_with('-') {
+                        enable_next = false;
+                    }
+                }
+            }
+            "copr" => {
+                if rest.first().map(|s| s == "enable").unwrap_or(false) {
+                    if let Some(project) = rest.get(1) {
+                        push_unique(&mut self.repos_enabled, format!("copr:{}", project));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Bash script that replays each dnf step with --assumeno, so dependency resolution runs without changing anything
+    pub fn simulation_script(&self) -> Option<String> {
+        if self.dnf_steps.is_empty() {
+            return None;
+        }
+
+        // Repos the real script would enable first are not active yet during the simulation
+        let enable_repos: Vec<&String> = self.repos_enabled.iter()
+            .filter(|r| !r.contains('/') && !r.contains(':') && !r.contains(' '))
+            .collect();
+
+        let mut script = String::from("#!/bin/bash\n");
+        for step in &self.dnf_steps {
+            let mut words: Vec<String> = step.iter()
+                .filter(|w| *w != "-y" && *w != "--assumeyes")
+                .map(|w| {
+                    // Combined short flags like -yq
+                    if w.starts_with('-') && !w.starts_with("--") && w.contains('y') {
+                        w.replace('y', "")
+                    } else {
+                        w.clone()
+                    }
+                })
+                .filter(|w| w != "-")
+                .collect();
+            words.push("--assumeno".to_string());
+            for repo in &enable_repos {
+                words.push(format!("--enablerepo={}", repo));
+            }
+            let line = words.iter().map(|w| shell_quote(w)).collect::<Vec<_>>().join(" ");
+            // The simulation runs as root, so expansions and command substitutions are never replayed
+            if words.iter().any(|w| w.contains('$') || w.contains('`')) {
+                script.push_str(&format!(
+                    "echo {}\necho\n",
+                    shell_quote(&format!("[simulate] skipped, uses shell expansion: {}", step.join(" ")))
+                ));
+                continue;
+            }
+            script.push_str(&format!("echo {}\n", shell_quote(&format!("==> {}", line))));
+            script.push_str(&format!("{}\n", line));
+            // --assumeno always ends with "Operation aborted" and exit code 1
+            script.push_str("rc=$?; [ $rc -le 1 ] || echo \"[simulate] step exited with code $rc\"\necho\n");
+        }
+        Some(script)
+    }
+}
+
+fn push_unique(list: &mut Vec<String>, value: String) {
+    let value = value.trim().to_string();
+    if !value.is_empty() && !list.contains(&value) {
+        list.push(value);
+    }
+}
+
+fn redirect_target(word: &str) -> Option<String> {
+    for prefix in ["&>>", "&>", "2>>", "2>", "1>>", "1>", ">>", ">"] {
+        if let Some(rest) = word.strip_prefix(prefix) {
+            if !rest.is_empty() {
+                return Some(rest.to_string());
+            }
+        }
+    }
+    None
+}
+
+fn shell_quote(word: &str) -> String {
+    if word.chars().all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c)) {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', "'\\''"))
+    }
+}
+
+// Splits a script into simple commands (word lists), following line continuations,
+// control operators and skipping heredoc bodies and shell keywords
+fn split_commands(script: &str) -> Vec<Vec<String>> {
+    let mut commands = Vec::new();
+    let mut heredoc_end: Option<String> = None;
+    let mut logical = String::new();
+
+    for raw in script.lines() {
+        if let Some(ref end) = heredoc_end {
+            if raw.trim() == end {
+                heredoc_end = None;
+            }
+            continue;
+        }
+        if let Some(stripped) = raw.strip_suffix('\\') {
+            logical.push_str(stripped);
+            logical.push(' ');
+            continue;
+        }
+        logical.push_str(raw);
+        let line = std::mem::take(&mut logical);
+
+        for mut words in tokenize(&line) {
+            if let Some(pos) = words.iter().position(|w| w.starts_with("<<")) {
+                let marker = words[pos].trim_start_matches("<<").trim_start_matches('-').to_string();
+                let marker = if marker.is_empty() {
+                    words.get(pos + 1).cloned().unwrap_or_default()
+                } else {
+                    marker
+                };
+                let marker = marker.trim_matches(|c| c == '\'' || c == '"').to_string();
+                if !marker.is_empty() {
+                    heredoc_end = Some(marker);
+                }
+                let end = if words[pos] == "<<" || words[pos] == "<<-" { pos + 2 } else { pos + 1 };
+                words.drain(pos..end.min(words.len()));
+            }
+            while let Some(first) = words.first() {
+                if SHELL_KEYWORDS.contains(&first.as_str()) {
+                    words.remove(0);
+                } else {
+                    break;
+                }
+            }
+            if !words.is_empty() {
+                commands.push(words);
+            }
+        }
+    }
+    commands
+}
+
+const SHELL_KEYWORDS: &[&str] = &["if", "then", "else", "elif", "fi", "do", "done", "while", "until", "!", "{", "}", "(", ")"];
+
+// Word splitting with quote handling; control operators start a new command
+fn tokenize(line: &str) -> Vec<Vec<String>> {
+    let mut commands = Vec::new();
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = line.chars().peekable();
+
+    let finish_word = |word: &mut String, in_word: &mut bool, words: &mut Vec<String>| {
+        if *in_word {
+            words.push(std::mem::take(word));
+            *in_word = false;
+        }
+    };
+
+    while let Some(c) = chars.next() {
+        match c {
+            '#' if !in_word => break,
+            '\'' => {
+                in_word = true;
+                for q in chars.by_ref() {
+                    if q == '\'' {
+                        break;
+                    }
+                    word.push(q);
+                }
+            }
+            '"' => {
+                in_word = true;
+                while let Some(q) = chars.next() {
+                    match q {
+                        '"' => break,
+                        '\\' => {
+                            if let Some(n) = chars.next() {
+                                word.push(n);
+                            }
+                        }
+                        _ => word.push(q),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                if let Some(n) = chars.next() {
+                    word.push(n);
+                }
+            }
+            '$' if chars.peek() == Some(&'(') => {
+                // Keep command substitutions inside the word, e.g. kernel-devel-$(uname -r)
+                in_word = true;
+                word.push('$');
+                let mut depth = 0;
+                for q in chars.by_ref() {
+                    word.push(q);
+                    if q == '(' {
+                        depth += 1;
+                    } else if q == ')' {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                }
+            }
+            ';' | '&' | '|' => {
+                if c == '&' && chars.peek() == Some(&'>') {
+                    in_word = true;
+                    word.push(c);
+                    continue;
+                }
+                if c == '&' && word.ends_with('>') {
+                    word.push(c);
+                    continue;
+                }
+                finish_word(&mut word, &mut in_word, &mut words);
+                if chars.peek() == Some(&c) {
+                    chars.next();
+                }
+                if !words.is_empty() {
+                    commands.push(std::mem::take(&mut words));
+                }
+            }
+            c if c.is_whitespace() => finish_word(&mut word, &mut in_word, &mut words),
+            _ => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+    finish_word(&mut word, &mut in_word, &mut words);
+    if !words.is_empty() {
+        commands.push(words);
+    }
+    commands
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Plain,
+    Keyword,
+    Command,
+    String,
+    Variable,
+    Comment,
+}
+
+// Splits one script line into colored spans for the review view
+pub fn highlight_line(line: &str) -> Vec<(String, TokenKind)> {
+    let mut spans: Vec<(String, TokenKind)> = Vec::new();
+    let push = |text: String, kind: TokenKind, spans: &mut Vec<(String, TokenKind)>| {
+        if text.is_empty() {
+            return;
+        }
+        match spans.last_mut() {
+            Some((last, last_kind)) if *last_kind == kind => last.push_str(&text),
+            _ => spans.push((text, kind)),
+        }
+    };
+
+    let chars: Vec<char> = line.chars().collect();
+    let mut i = 0;
+    let mut expect_command = true;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '#' && (i == 0 || chars[i - 1].is_whitespace()) {
+            push(chars[i..].iter().collect(), TokenKind::Comment, &mut spans);
+            break;
+        }
+        if c == '\'' || c == '"' {
+            let start = i;
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                if chars[i] == '\\' && c == '"' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i = (i + 1).min(chars.len());
+            push(chars[start..i].iter().collect(), TokenKind::String, &mut spans);
+            expect_command = false;
+            continue;
+        }
+        if c == '$' {
+            let start = i;
+            i += 1;
+            if i < chars.len() && chars[i] == '{' {
+                while i < chars.len() && chars[i] != '}' {
+                    i += 1;
+                }
+                i = (i + 1).min(chars.len());
+            } else {
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_' || "?#@*!".contains(chars[i])) {
+                    i += 1;
+                    if "?#@*!".contains(chars[i - 1]) {
+                        break;
+                    }
+                }
+            }
+            push(chars[start..i].iter().collect(), TokenKind::Variable, &mut spans);
+            continue;
+        }
+        if c.is_whitespace() || ";&|(){}".contains(c) {
+            if ";&|(".contains(c) {
+                expect_command = true;
+            }
+            push(c.to_string(), TokenKind::Plain, &mut spans);
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        while i < chars.len() && !chars[i].is_whitespace() && !";&|(){}'\"$".contains(chars[i]) {
+            i += 1;
+        }
+        let word: String = chars[start..i].iter().collect();
+        let kind = if SHELL_KEYWORDS.contains(&word.as_str()) || ["for", "in", "case", "esac", "function", "return", "exit", "local", "export"].contains(&word.as_str()) {
+            expect_command = !["in", "case", "for", "function", "local", "export"].contains(&word.as_str());
+            TokenKind::Keyword
+        } else if expect_command && !word.contains('=') {
+            expect_command = false;
+            TokenKind::Command
+        } else {
+            TokenKind::Plain
+        };
+        push(word, kind, &mut spans);
+    }
+    spans
+}
@@ -0,0 +1,342 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::logger;
+
+pub const AKMODS_PUBLIC_KEY: &str = "/etc/pki/akmods/certs/public_key.der";
+const MODULES_ROOT: &str = "/lib/modules";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyEnrollment {
+    Enrolled,
+    Pending,
+    NotEnrolled,
+    Unknown,
+}
+
+#[derive(Debug, Clone)]
+pub struct SecureBootStatus {
+    pub mokutil_available: bool,
+    // None when the firmware state could not be read (legacy BIOS, no EFI vars)
+    pub enabled: Option<bool>,
+    pub setup_mode: bool,
+    pub key_present: bool,
+    pub enrollment: KeyEnrollment,
+}
+
+impl SecureBootStatus {
+    pub fn state_label(&self) -> &'static str {
+        match self.enabled {
+            Some(true) => "Enabled",
+            Some(false) if self.setup_mode => "Disabled (setup mode)",
+            Some(false) => "Disabled",
+            None if !self.mokutil_available => "Unknown (mokutil not installed)",
+            None => "Unknown (EFI variables unavailable)",
+        }
+    }
+
+    pub fn enrollment_label(&self) -> &'static str {
+        if !self.key_present {
+            return "No signing key";
+        }
+        match self.enrollment {
+            KeyEnrollment::Enrolled => "Enrolled",
+            KeyEnrollment::Pending => "Pending reboot",
+            KeyEnrollment::NotEnrolled => "Not enrolled",
+            KeyEnrollment::Unknown => "Unknown",
+        }
+    }
+
+    // True when akmods-built modules will be rejected by the kernel
+    pub fn needs_attention(&self) -> bool {
+        self.enabled == Some(true) && (!self.key_present || self.enrollment != KeyEnrollment::Enrolled)
+    }
+}
+
+pub fn detect_status() -> SecureBootStatus {
+    let sb_state = Command::new("mokutil").arg("--sb-state").output();
+    let mokutil_available = sb_state.is_ok();
+
+    let (enabled, setup_mode) = match &sb_state {
+        Ok(output) => {
+            let text = format!(
+                "{}{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            );
+            let enabled = if text.contains("SecureBoot enabled") {
+                Some(true)
+            } else if text.contains("SecureBoot disabled") {
+                Some(false)
+            } else {
+                None
+            };
+            (enabled, text.contains("SetupMode"))
+        }
+        Err(_) => (None, false),
+    };
+
+    let key_present = Path::new(AKMODS_PUBLIC_KEY).exists();
+    let enrollment = if key_present && mokutil_available {
+        test_key_enrollment()
+    } else {
+        KeyEnrollment::Unknown
+    };
+
+    SecureBootStatus {
+        mokutil_available,
+        enabled,
+        setup_mode,
+        key_present,
+        enrollment,
+    }
+}
+
+fn test_key_enrollment() -> KeyEnrollment {
+    let output = match Command::new("mokutil").args(["--test-key", AKMODS_PUBLIC_KEY]).output() {
+        Ok(output) => output,
+        Err(_) => return KeyEnrollment::Unknown,
+    };
+    let text = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    if text.contains("already enrolled") {
+        KeyEnrollment::Enrolled
+    } else if text.contains("enrollment request") {
+        KeyEnrollment::Pending
+    } else if text.contains("not enrolled") {
+        KeyEnrollment::NotEnrolled
+    } else {
+        KeyEnrollment::Unknown
+    }
+}
+
+// Create the akmods signing key pair with kmodgenca (installs akmods/mokutil if missing)
+pub async fn generate_signing_key() -> Result<String, String> {
+    let script = "set -e\n\
+        rpm -q akmods mokutil >/dev/null 2>&1 || dnf install -y akmods mokutil\n\
+        if [ -f /etc/pki/akmods/certs/public_key.der ]; then\n\
+            echo 'An akmods signing key already exists; keeping it.'\n\
+        else\n\
+            kmodgenca -a\n\
+            echo 'Generated a new akmods signing key.'\n\
+        fi\n";
+
+    let output = tokio::process::Command::new("pkexec")
+        .args(["sh", "-c", script])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run kmodgenca: {}", e))?;
+
+    let combined = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    if output.status.success() {
+        logger::Logger::log_debug("[Secure Boot] akmods signing key ready");
+        Ok(combined.trim().to_string())
+    } else {
+        Err(format!(
+            "kmodgenca failed (exit code: {}):\n{}",
+            output.status.code().unwrap_or(-1),
+            combined.trim()
+        ))
+    }
+}
+
+// Queue the akmods key for MOK enrollment. Returns the one-time password the user
+// has to type into MokManager on the next boot.
+pub async fn enroll_signing_key() -> Result<String, String> {
+    if !Path::new(AKMODS_PUBLIC_KEY).exists() {
+        return Err("No akmods signing key found. Generate the key first.".to_string());
+    }
+
+    let password = one_time_password()?;
+    let script = "set -e\n\
+        HASH_FILE=$(mktemp)\n\
+        trap 'rm -f \"$HASH_FILE\"' EXIT\n\
+        mokutil --generate-hash=\"$1\" > \"$HASH_FILE\"\n\
+        mokutil --import /etc/pki/akmods/certs/public_key.der --hash-file \"$HASH_FILE\"\n";
+
+    let output = tokio::process::Command::new("pkexec")
+        .args(["sh", "-c", script, "sh", &password])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run mokutil: {}", e))?;
+
+    if output.status.success() {
+        logger::Logger::log_debug("[Secure Boot] akmods key queued for MOK enrollment");
+        Ok(password)
+    } else {
+        Err(format!(
+            "mokutil --import failed (exit code: {}):\n{}{}",
+            output.status.code().unwrap_or(-1),
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        ))
+    }
+}
+
+pub fn reboot_instructions(password: &str) -> Vec<String> {
+    vec![
+        format!("One-time MOK password: {}", password),
+        "1. Reboot the computer.".to_string(),
+        "2. The blue MokManager screen appears before Fedora boots; press a key within 10 seconds.".to_string(),
+        "3. Choose 'Enroll MOK', then 'Continue' and 'Yes'.".to_string(),
+        "4. Enter the password above (the keyboard layout is US QWERTY) and choose 'Reboot'.".to_string(),
+        "If the screen is missed the request stays queued; reboot and try again.".to_string(),
+    ]
+}
+
+fn one_time_password() -> Result<String, String> {
+    use std::io::Read;
+    let mut bytes = [0u8; 4];
+    std::fs::File::open("/dev/urandom")
+        .and_then(|mut f| f.read_exact(&mut bytes))
+        .map_err(|e| format!("Failed to generate password: {}", e))?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+#[derive(Debug, Clone)]
+pub struct ModuleSignature {
+    pub kernel: String,
+    pub module: String,
+    pub signer: Option<String>,
+    // None when the akmods key serial could not be read
+    pub matches_akmods_key: Option<bool>,
+}
+
+impl ModuleSignature {
+    pub fn is_ok(&self) -> bool {
+        self.signer.is_some() && self.matches_akmods_key != Some(false)
+    }
+}
+
+// Check every akmods-built module (installed under extra/) for a signature
+pub fn verify_module_signatures() -> Vec<ModuleSignature> {
+    let key_serial = akmods_key_serial();
+    let mut results = Vec::new();
+
+    let kernels = match std::fs::read_dir(MODULES_ROOT) {
+        Ok(entries) => entries,
+        Err(_) => return results,
+    };
+
+    for kernel in kernels.flatten() {
+        let kernel_name = kernel.file_name().to_string_lossy().to_string();
+        let mut modules = Vec::new();
+        collect_modules(&kernel.path().join("extra"), &mut modules);
+        modules.sort();
+
+        for path in modules {
+            let module = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let signer = modinfo_field(&path, "signer");
+            let matches_akmods_key = match (&key_serial, modinfo_field(&path, "sig_key")) {
+                (Some(expected), Some(actual)) => Some(normalize_serial(&actual) == *expected),
+                _ => None,
+            };
+            results.push(ModuleSignature {
+                kernel: kernel_name.clone(),
+                module,
+                signer,
+                matches_akmods_key,
+            });
+        }
+    }
+
+    results.sort_by(|a, b| a.kernel.cmp(&b.kernel).then(a.module.cmp(&b.module)));
+    results
+}
+
+fn collect_modules(dir: &Path, out: &mut Vec<PathBuf>) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_modules(&path, out);
+        } else {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.ends_with(".ko") || name.ends_with(".ko.xz") || name.ends_with(".ko.zst") || name.ends_with(".ko.gz") {
+                out.push(path);
+            }
+        }
+    }
+}
+
+fn modinfo_field(path: &Path, field: &str) -> Option<String> {
+    let output = Command::new("modinfo").args(["-F", field]).arg(path).output().ok()?;
+    let value = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|l| l.trim())
+        .collect::<Vec<_>>()
+        .join("");
+    if value.is_empty() { None } else { Some(value) }
+}
+
+fn akmods_key_serial() -> Option<String> {
+    let output = Command::new("openssl")
+        .args(["x509", "-inform", "der", "-noout", "-serial", "-in", AKMODS_PUBLIC_KEY])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&output.stdout);
+    text.trim().strip_prefix("serial=").map(normalize_serial)
+}
+
+// modinfo prints "0A:1B:..." while openssl prints "0A1B..."
+fn normalize_serial(serial: &str) -> String {
+    let hex: String = serial
+        .chars()
+        .filter(|c| c.is_ascii_hexdigit())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    hex.trim_start_matches('0').to_string()
+}
+
+// Text block appended to rebuild output so Secure Boot problems are not silent
+pub fn post_rebuild_report() -> String {
+    let status = detect_status();
+    let modules = verify_module_signatures();
+    let mut report = String::from("--- Secure Boot module signature check ---\n");
+    report.push_str(&format!("Secure Boot: {}\n", status.state_label()));
+    report.push_str(&format!("akmods signing key: {}\n", status.enrollment_label()));
+
+    if modules.is_empty() {
+        report.push_str("No akmods-built modules found under /lib/modules/*/extra\n");
+    }
+    for module in &modules {
+        let verdict = match (&module.signer, module.matches_akmods_key) {
+            (None, _) => "[FAIL] unsigned".to_string(),
+            (Some(signer), Some(false)) => format!("[FAIL] signed by '{}', not the akmods key", signer),
+            (Some(signer), _) => format!("[OK] signed by '{}'", signer),
+        };
+        report.push_str(&format!("{} {}: {}\n", module.kernel, module.module, verdict));
+    }
+
+    if status.enabled == Some(true) {
+        if !status.key_present {
+            report.push_str("WARNING: Secure Boot is enabled but akmods has no signing key. Modules will not load.\n");
+            report.push_str("Open Maintenance > Secure Boot to generate and enroll a key.\n");
+        } else if status.enrollment == KeyEnrollment::Pending {
+            report.push_str("NOTE: the signing key is waiting for enrollment. Reboot and complete MokManager.\n");
+        } else if status.enrollment != KeyEnrollment::Enrolled {
+            report.push_str("WARNING: the akmods signing key is not enrolled. Modules will not load until it is.\n");
+            report.push_str("Open Maintenance > Secure Boot to enroll the key.\n");
+        }
+        if modules.iter().any(|m| !m.is_ok()) {
+            report.push_str("WARNING: some modules are unsigned or signed with another key and will be rejected.\n");
+        }
+    }
+    report
+}
@@ -36,6 +36,11 @@ pub struct AppSettings {
     
     #[serde(default = "default_true")]
     pub show_cfhdb_profiles: bool,
+
+    // Never contact the profile server, use the cached cfhdb profiles (seeded from the
+    // offline bundle when the cache is empty)
+    #[serde(default)]
+    pub offline_profiles: bool,
 }
 
 fn default_true() -> bool {
@@ -111,6 +116,7 @@ impl AppSettings {
             scale_icons: 1.0,
             scale_package_cards: 1.0,
             show_cfhdb_profiles: true,
+            offline_profiles: false,
         }
     }
 
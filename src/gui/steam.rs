@@ -0,0 +1,547 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::logger;
+
+// Valve KeyValues ("VDF") text format as used by config.vdf, libraryfolders.vdf and
+// appmanifest_*.acf. Keys keep their file order so rewritten files diff cleanly.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VdfValue {
+    Str(String),
+    Obj(Vec<(String, VdfValue)>),
+}
+
+impl VdfValue {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            VdfValue::Str(s) => Some(s),
+            VdfValue::Obj(_) => None,
+        }
+    }
+
+    pub fn entries(&self) -> &[(String, VdfValue)] {
+        match self {
+            VdfValue::Obj(entries) => entries,
+            VdfValue::Str(_) => &[],
+        }
+    }
+
+    // Steam is inconsistent about key case ("Valve" vs "valve"), so lookups ignore it
+    pub fn get(&self, key: &str) -> Option<&VdfValue> {
+        self.entries()
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v)
+    }
+
+    pub fn get_path(&self, path: &[&str]) -> Option<&VdfValue> {
+        path.iter().try_fold(self, |value, key| value.get(key))
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(|v| v.as_str())
+    }
+
+    // Walks (creating as needed) a chain of objects and returns the innermost entries
+    pub fn object_at_mut(&mut self, path: &[&str]) -> Result<&mut Vec<(String, VdfValue)>, String> {
+        let mut current = self;
+        for key in path {
+            let entries = match current {
+                VdfValue::Obj(entries) => entries,
+                VdfValue::Str(_) => return Err(format!("'{}' is not a section", key)),
+            };
+            let idx = match entries.iter().position(|(k, _)| k.eq_ignore_ascii_case(key)) {
+                Some(idx) => idx,
+                None => {
+                    entries.push((key.to_string(), VdfValue::Obj(Vec::new())));
+                    entries.len() - 1
+                }
+            };
+            current = &mut entries[idx].1;
+        }
+        match current {
+            VdfValue::Obj(entries) => Ok(entries),
+            VdfValue::Str(_) => Err(format!("'{}' is not a section", path.join("/"))),
+        }
+    }
+}
+
+struct VdfParser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+}
+
+enum Token {
+    Str(String),
+    Open,
+    Close,
+}
+
+impl<'a> VdfParser<'a> {
+    fn skip_whitespace_and_comments(&mut self) {
+        while let Some(&c) = self.chars.peek() {
+            if c == '\n' {
+                self.line += 1;
+                self.chars.next();
+            } else if c.is_whitespace() {
+                self.chars.next();
+            } else if c == '/' {
+                // `//` comment to end of line
+                let mut lookahead = self.chars.clone();
+                lookahead.next();
+                if lookahead.peek() != Some(&'/') {
+                    return;
+                }
+                while let Some(&c) = self.chars.peek() {
+                    if c == '\n' {
+                        break;
+                    }
+                    self.chars.next();
+                }
+            } else {
+                return;
+            }
+        }
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token>, String> {
+        self.skip_whitespace_and_comments();
+        let c = match self.chars.peek() {
+            Some(&c) => c,
+            None => return Ok(None),
+        };
+        match c {
+            '{' => {
+                self.chars.next();
+                Ok(Some(Token::Open))
+            }
+            '}' => {
+                self.chars.next();
+                Ok(Some(Token::Close))
+            }
+            '"' => {
+                self.chars.next();
+                let mut s = String::new();
+                loop {
+                    match self.chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match self.chars.next() {
+                            Some('n') => s.push('\n'),
+                            Some('t') => s.push('\t'),
+                            Some(other) => s.push(other),
+                            None => return Err(format!("Unterminated string on line {}", self.line)),
+                        },
+                        Some('\n') => {
+                            self.line += 1;
+                            s.push('\n');
+                        }
+                        Some(other) => s.push(other),
+                        None => return Err(format!("Unterminated string on line {}", self.line)),
+                    }
+                }
+                Ok(Some(Token::Str(s)))
+            }
+            _ => {
+                let mut s = String::new();
+                while let Some(&c) = self.chars.peek() {
+                    if c.is_whitespace() || c == '{' || c == '}' || c == '"' {
+                        break;
+                    }
+                    s.push(c);
+                    self.chars.next();
+                }
+                Ok(Some(Token::Str(s)))
+            }
+        }
+    }
+
+    // Platform conditionals like `[$WIN32]` may follow a key or value; they are ignored
+    fn next_non_conditional(&mut self) -> Result<Option<Token>, String> {
+        loop {
+            match self.next_token()? {
+                Some(Token::Str(s)) if s.starts_with("[$") || s.starts_with("[!$") => continue,
+                other => return Ok(other),
+            }
+        }
+    }
+
+    fn parse_object(&mut self, nested: bool) -> Result<Vec<(String, VdfValue)>, String> {
+        let mut entries = Vec::new();
+        loop {
+            let key = match self.next_non_conditional()? {
+                Some(Token::Str(key)) => key,
+                Some(Token::Close) if nested => return Ok(entries),
+                Some(Token::Close) => return Err(format!("Unexpected '}}' on line {}", self.line)),
+                Some(Token::Open) => return Err(format!("Unexpected '{{' on line {}", self.line)),
+                None if nested => return Err("Unexpected end of file: missing '}'".to_string()),
+                None => return Ok(entries),
+            };
+            let value = match self.next_non_conditional()? {
+                Some(Token::Str(value)) => VdfValue::Str(value),
+                Some(Token::Open) => VdfValue::Obj(self.parse_object(true)?),
+                _ => return Err(format!("Missing value for '{}' on line {}", key, self.line)),
+            };
+            entries.push((key, value));
+        }
+    }
+}
+
+pub fn parse_vdf(content: &str) -> Result<VdfValue, String> {
+    let mut parser = VdfParser {
+        chars: content.chars().peekable(),
+        line: 1,
+    };
+    parser.parse_object(false).map(VdfValue::Obj)
+}
+
+fn escape_vdf(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn write_vdf_entries(entries: &[(String, VdfValue)], depth: usize, out: &mut String) {
+    let indent = "\t".repeat(depth);
+    for (key, value) in entries {
+        match value {
+            VdfValue::Str(s) => {
+                out.push_str(&format!("{}\"{}\"\t\t\"{}\"\n", indent, escape_vdf(key), escape_vdf(s)));
+            }
+            VdfValue::Obj(children) => {
+                out.push_str(&format!("{}\"{}\"\n{}{{\n", indent, escape_vdf(key), indent));
+                write_vdf_entries(children, depth + 1, out);
+                out.push_str(&format!("{}}}\n", indent));
+            }
+        }
+    }
+}
+
+// Same layout Steam writes: tab indentation, braces on their own lines
+pub fn serialize_vdf(value: &VdfValue) -> String {
+    let mut out = String::new();
+    write_vdf_entries(value.entries(), 0, &mut out);
+    out
+}
+
+fn read_vdf(path: &Path) -> Result<VdfValue, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    parse_vdf(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+const COMPAT_TOOL_MAPPING: &[&str] = &["InstallConfigStore", "Software", "Valve", "Steam", "CompatToolMapping"];
+
+// Steam tools installed through appmanifests that are not games
+const TOOL_NAME_PREFIXES: &[&str] = &[
+    "Proton",
+    "Steam Linux Runtime",
+    "Steamworks Common Redistributables",
+];
+
+pub const STEAM_DEFAULT_TOOL: &str = "Steam default";
+
+#[derive(Debug, Clone)]
+pub struct SteamGame {
+    pub appid: String,
+    pub name: String,
+    pub library: String,
+    pub compat_tool: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompatTool {
+    // Internal name stored in CompatToolMapping
+    pub name: String,
+    pub display_name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct SteamLibrary {
+    pub steam_dir: String,
+    pub games: Vec<SteamGame>,
+    pub tools: Vec<CompatTool>,
+    pub steam_running: bool,
+}
+
+impl SteamLibrary {
+    // Choices offered in the per-game picker: Steam's default plus every known tool
+    pub fn tool_choices(&self) -> Vec<String> {
+        let mut choices = vec![STEAM_DEFAULT_TOOL.to_string()];
+        for tool in &self.tools {
+            if !choices.contains(&tool.name) {
+                choices.push(tool.name.clone());
+            }
+        }
+        // Keep tools games still point at even if they were removed from disk
+        for game in &self.games {
+            if let Some(tool) = &game.compat_tool {
+                if !choices.contains(tool) {
+                    choices.push(tool.clone());
+                }
+            }
+        }
+        choices
+    }
+
+    pub fn tools_in_use(&self) -> Vec<String> {
+        let mut used: Vec<String> = self.games.iter().filter_map(|g| g.compat_tool.clone()).collect();
+        used.sort();
+        used.dedup();
+        used
+    }
+}
+
+// The main Steam client process; steamwebhelper alone lingers briefly after exit
+pub fn is_steam_running() -> bool {
+    let entries = match std::fs::read_dir("/proc") {
+        Ok(entries) => entries,
+        Err(_) => return false,
+    };
+    entries.flatten().any(|entry| {
+        let name = entry.file_name();
+        if !name.to_string_lossy().chars().all(|c| c.is_ascii_digit()) {
+            return false;
+        }
+        std::fs::read_to_string(entry.path().join("comm"))
+            .map(|comm| matches!(comm.trim(), "steam" | "steam.sh"))
+            .unwrap_or(false)
+    })
+}
+
+pub fn library_folders(steam_dir: &str) -> Vec<PathBuf> {
+    let mut folders = vec![PathBuf::from(steam_dir)];
+    let path = Path::new(steam_dir).join("steamapps").join("libraryfolders.vdf");
+    if let Ok(vdf) = read_vdf(&path) {
+        if let Some(root) = vdf.get("libraryfolders") {
+            for (key, value) in root.entries() {
+                if !key.chars().all(|c| c.is_ascii_digit()) {
+                    continue;
+                }
+                // Current format has an object with "path", the old one just the path
+                let folder = match value {
+                    VdfValue::Obj(_) => value.get_str("path"),
+                    VdfValue::Str(s) => Some(s.as_str()),
+                };
+                if let Some(folder) = folder {
+                    let folder = PathBuf::from(folder);
+                    let duplicate = folders.iter().any(|f| {
+                        f == &folder || f.canonicalize().ok() == folder.canonicalize().ok()
+                    });
+                    if !duplicate {
+                        folders.push(folder);
+                    }
+                }
+            }
+        }
+    }
+    folders
+}
+
+pub fn compat_tool_mapping(steam_dir: &str) -> HashMap<String, String> {
+    let path = Path::new(steam_dir).join("config").join("config.vdf");
+    let vdf = match read_vdf(&path) {
+        Ok(vdf) => vdf,
+        Err(_) => return HashMap::new(),
+    };
+    let mut mapping = HashMap::new();
+    if let Some(section) = vdf.get_path(COMPAT_TOOL_MAPPING) {
+        for (appid, entry) in section.entries() {
+            if let Some(name) = entry.get_str("name").filter(|n| !n.is_empty()) {
+                mapping.insert(appid.clone(), name.to_string());
+            }
+        }
+    }
+    mapping
+}
+
+// "Proton 9.0" is stored as proton_9, "Proton 6.3" as proton_63
+fn official_proton_name(display_name: &str) -> Option<String> {
+    let version = display_name.strip_prefix("Proton ")?;
+    match version {
+        "Experimental" => return Some("proton_experimental".to_string()),
+        "Hotfix" => return Some("proton_hotfix".to_string()),
+        _ => {}
+    }
+    let (major, minor) = version.split_once('.')?;
+    if !major.chars().all(|c| c.is_ascii_digit()) || !minor.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(if minor == "0" {
+        format!("proton_{}", major)
+    } else {
+        format!("proton_{}{}", major, minor)
+    })
+}
+
+fn custom_compat_tools(steam_dir: &str) -> Vec<CompatTool> {
+    let mut tools = Vec::new();
+    let dir = Path::new(steam_dir).join("compatibilitytools.d");
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(_) => return tools,
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let dir_name = entry.file_name().to_string_lossy().to_string();
+        let declared = read_vdf(&path.join("compatibilitytool.vdf"))
+            .ok()
+            .and_then(|vdf| vdf.get_path(&["compatibilitytools", "compat_tools"]).cloned());
+        match declared {
+            Some(section) if !section.entries().is_empty() => {
+                for (name, info) in section.entries() {
+                    tools.push(CompatTool {
+                        name: name.clone(),
+                        display_name: info.get_str("display_name").unwrap_or(name).to_string(),
+                    });
+                }
+            }
+            _ => tools.push(CompatTool {
+                name: dir_name.clone(),
+                display_name: dir_name,
+            }),
+        }
+    }
+    tools
+}
+
+pub fn load_library(steam_dir: &str) -> SteamLibrary {
+    let mapping = compat_tool_mapping(steam_dir);
+    let mut games = Vec::new();
+    let mut tools = custom_compat_tools(steam_dir);
+
+    for library in library_folders(steam_dir) {
+        let steamapps = library.join("steamapps");
+        let entries = match std::fs::read_dir(&steamapps) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if !file_name.starts_with("appmanifest_") || !file_name.ends_with(".acf") {
+                continue;
+            }
+            let manifest = match read_vdf(&entry.path()) {
+                Ok(manifest) => manifest,
+                Err(_) => continue,
+            };
+            let state = match manifest.get("AppState") {
+                Some(state) => state,
+                None => continue,
+            };
+            let appid = state.get_str("appid").unwrap_or_default().to_string();
+            let name = state.get_str("name").unwrap_or_default().to_string();
+            if appid.is_empty() {
+                continue;
+            }
+            if TOOL_NAME_PREFIXES.iter().any(|p| name.starts_with(p)) {
+                if let Some(internal) = official_proton_name(&name) {
+                    tools.push(CompatTool { name: internal, display_name: name });
+                }
+                continue;
+            }
+            games.push(SteamGame {
+                compat_tool: mapping.get(&appid).cloned(),
+                appid,
+                name,
+                library: library.to_string_lossy().to_string(),
+            });
+        }
+    }
+
+    games.sort_by_key(|g| g.name.to_lowercase());
+    games.dedup_by(|a, b| a.appid == b.appid);
+    tools.sort_by(|a, b| a.display_name.to_lowercase().cmp(&b.display_name.to_lowercase()));
+    tools.dedup_by(|a, b| a.name == b.name);
+
+    SteamLibrary {
+        steam_dir: steam_dir.to_string(),
+        games,
+        tools,
+        steam_running: is_steam_running(),
+    }
+}
+
+// Steam keeps config.vdf in memory and writes it back on exit, so edits made while it
+// runs are lost (or clobber Steam's own changes). Refuse instead.
+fn update_compat_mapping<F>(steam_dir: &str, apply: F) -> Result<usize, String>
+where
+    F: FnOnce(&mut Vec<(String, VdfValue)>) -> usize,
+{
+    if is_steam_running() {
+        return Err("Steam is running. Close Steam completely before changing compatibility tools, otherwise Steam overwrites the change on exit.".to_string());
+    }
+
+    let config_path = Path::new(steam_dir).join("config").join("config.vdf");
+    let mut config = read_vdf(&config_path)?;
+    let mapping = config.object_at_mut(COMPAT_TOOL_MAPPING)?;
+    let changed = apply(mapping);
+    if changed == 0 {
+        return Ok(0);
+    }
+
+    let backup = config_path.with_extension("vdf.rustora.bak");
+    std::fs::copy(&config_path, &backup)
+        .map_err(|e| format!("Failed to back up config.vdf: {}", e))?;
+    let tmp = config_path.with_extension("vdf.rustora.tmp");
+    std::fs::write(&tmp, serialize_vdf(&config))
+        .map_err(|e| format!("Failed to write config.vdf: {}", e))?;
+    std::fs::rename(&tmp, &config_path)
+        .map_err(|e| format!("Failed to replace config.vdf: {}", e))?;
+
+    logger::Logger::log_debug(&format!("[Steam] Updated compat tool for {} app(s)", changed));
+    Ok(changed)
+}
+
+fn mapping_entry(tool: &str) -> VdfValue {
+    VdfValue::Obj(vec![
+        ("name".to_string(), VdfValue::Str(tool.to_string())),
+        ("config".to_string(), VdfValue::Str(String::new())),
+        ("priority".to_string(), VdfValue::Str("250".to_string())),
+    ])
+}
+
+// `None` clears the override so the game falls back to Steam's default
+pub async fn assign_compat_tool(steam_dir: String, appid: String, tool: Option<String>) -> Result<String, String> {
+    let changed = update_compat_mapping(&steam_dir, |mapping| {
+        let existing = mapping.iter().position(|(k, _)| *k == appid);
+        match (&tool, existing) {
+            (Some(tool), Some(idx)) => mapping[idx].1 = mapping_entry(tool),
+            (Some(tool), None) => mapping.push((appid.clone(), mapping_entry(tool))),
+            (None, Some(idx)) => {
+                mapping.remove(idx);
+            }
+            (None, None) => return 0,
+        }
+        1
+    })?;
+    Ok(match (changed, tool) {
+        (0, _) => format!("App {} already uses Steam's default tool", appid),
+        (_, Some(tool)) => format!("App {} now uses {}", appid, tool),
+        (_, None) => format!("App {} now uses Steam's default tool", appid),
+    })
+}
+
+// Point every game using one of `from` at `to`
+pub async fn migrate_compat_tool(steam_dir: String, from: Vec<String>, to: String) -> Result<String, String> {
+    let changed = update_compat_mapping(&steam_dir, |mapping| {
+        let mut changed = 0;
+        for (appid, entry) in mapping.iter_mut() {
+            // "0" is the global default, not a game
+            if appid == "0" {
+                continue;
+            }
+            let uses_old = entry.get_str("name").map(|name| from.iter().any(|f| f == name)).unwrap_or(false);
+            if uses_old {
+                *entry = mapping_entry(&to);
+                changed += 1;
+            }
+        }
+        changed
+    })?;
+    Ok(if changed == 0 {
+        format!("No games use {}", from.join(", "))
+    } else {
+        format!("Moved {} game{} from {} to {}", changed, if changed == 1 { "" } else { "s" }, from.join(", "), to)
+    })
+}
@@ -7,11 +7,11 @@ use iced::widget::button::Appearance as ButtonAppearance;
 use iced::widget::button::StyleSheet as ButtonStyleSheet;
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::path::Path;
 
 use libcfhdb::pci::{CfhdbPciDevice, CfhdbPciProfile};
 use libcfhdb::usb::{CfhdbUsbDevice, CfhdbUsbProfile};
 use crate::logger;
+use crate::gui::cfhdb_cache;
 
 #[derive(Debug, Clone)]
 pub struct PreCheckedPciDevice {
@@ -22,6 +22,8 @@ pub struct PreCheckedPciDevice {
 #[derive(Debug, Clone)]
 pub struct PreCheckedPciProfile {
     profile: CfhdbPciProfile,
+    // Generated from the dnf repos rather than read from the cfhdb cache
+    from_repo: bool,
 
     installed: Arc<std::sync::RwLock<bool>>,
     driver_version: Arc<std::sync::RwLock<Option<String>>>,
@@ -34,6 +36,7 @@ impl PreCheckedPciProfile {
     pub fn new(profile: CfhdbPciProfile) -> Self {
         Self {
             profile,
+            from_repo: false,
             installed: Arc::new(std::sync::RwLock::new(false)),
             driver_version: Arc::new(std::sync::RwLock::new(None)),
             repository: Arc::new(std::sync::RwLock::new(None)),
@@ -42,10 +45,21 @@ impl PreCheckedPciProfile {
         }
     }
 
+    pub fn from_repo(profile: CfhdbPciProfile) -> Self {
+        Self {
+            from_repo: true,
+            ..Self::new(profile)
+        }
+    }
+
     pub fn profile(&self) -> &CfhdbPciProfile {
         &self.profile
     }
 
+    pub fn is_from_repo(&self) -> bool {
+        self.from_repo
+    }
+
     pub fn installed(&self) -> bool {
         *self.installed.read().unwrap()
     }
@@ -163,6 +177,26 @@ pub enum Message {
     ClearError,
     UpdateStatus,
     ToggleCfhdbProfiles,
+    ExportHardwareReport,
+    HardwareReportExported(Result<String, String>),
+    ToggleOfflineProfiles,
+    ShowProfileHistory,
+    ProfileHistoryLoaded(Vec<cfhdb_cache::ProfileSnapshot>, Option<String>),
+    ActiveProfilesLoaded(Option<cfhdb_cache::ProfileSnapshot>),
+    CloseProfileHistory,
+    RestoreProfileSnapshot(String),
+    ProfileSnapshotRestored(Result<(), String>),
+    ShowNvidiaManager(String, usize),
+    NvidiaStateLoaded(crate::gui::nvidia::NvidiaState),
+    CloseNvidiaManager,
+    SwitchNvidiaBranch(usize),
+    ShowFirmware,
+    FirmwareLoaded(Result<Vec<crate::gui::firmware::FirmwareDevice>, String>),
+    CloseFirmware,
+    RefreshFirmwareMetadata,
+    FirmwareMetadataRefreshed(Result<String, String>),
+    InstallFirmware(String, String, bool),
+    FirmwareInstalled(Result<String, String>),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -214,10 +248,44 @@ pub struct DeviceTab {
     selected_device: Option<(DeviceType, String, usize)>,
     selected_profiles: std::collections::HashSet<String>,
 
+    exporting_report: bool,
+    report_status: Option<String>,
+
+    profile_history: Option<Vec<cfhdb_cache::ProfileSnapshot>>,
+    active_snapshot: Option<String>,
+    restoring_snapshot: bool,
+    // Snapshot the loaded profiles came from; None when the cache matches no snapshot
+    active_profiles: Option<cfhdb_cache::ProfileSnapshot>,
+
+    nvidia_device: Option<(String, usize)>,
+    nvidia_state: Option<crate::gui::nvidia::NvidiaState>,
+
+    show_firmware: bool,
+    firmware_devices: Option<Vec<crate::gui::firmware::FirmwareDevice>>,
+    firmware_busy: bool,
+    firmware_status: Option<Result<String, String>>,
+
     error: Option<String>,
 }
 
 impl DeviceTab {
+    // Profiles from the cfhdb cache are identified to the install dialog, which re-reads their
+    // script from the verified cache; profiles generated from the dnf repos are not in it
+    fn cached_profile(&self, dev_type: DeviceType, class: &str, device_idx: usize, codename: &str) -> Option<cfhdb_cache::CachedProfile> {
+        let in_cache = match dev_type {
+            DeviceType::Pci => self.pci_devices.iter()
+                .find(|(c, _)| c == class)
+                .and_then(|(_, devices)| devices.get(device_idx))
+                .and_then(|device| device.profiles.iter().find(|p| p.profile().codename == codename))
+                .is_some_and(|profile| !profile.is_from_repo()),
+            DeviceType::Usb => true,
+        };
+        in_cache.then(|| cfhdb_cache::CachedProfile {
+            bus: if dev_type == DeviceType::Pci { "pci" } else { "usb" }.to_string(),
+            codename: codename.to_string(),
+        })
+    }
+
     pub fn new() -> Self {
         Self {
             is_loading: false,
@@ -229,6 +297,18 @@ impl DeviceTab {
             selected_category: None,
             selected_device: None,
             selected_profiles: std::collections::HashSet::new(),
+            exporting_report: false,
+            report_status: None,
+            profile_history: None,
+            active_snapshot: None,
+            restoring_snapshot: false,
+            active_profiles: None,
+            nvidia_device: None,
+            nvidia_state: None,
+            show_firmware: false,
+            firmware_devices: None,
+            firmware_busy: false,
+            firmware_status: None,
             error: None,
         }
     }
@@ -348,11 +428,23 @@ impl DeviceTab {
                 self.pci_profiles = pci_profiles;
                 self.usb_profiles = usb_profiles;
 
-                iced::Command::perform(load_profile_versions(self.pci_profiles.clone(), self.usb_profiles.clone()), |_| Message::UpdateStatus)
+                iced::Command::batch(vec![
+                    iced::Command::perform(load_profile_versions(self.pci_profiles.clone(), self.usb_profiles.clone()), |_| Message::UpdateStatus),
+                    iced::Command::perform(
+                        async { tokio::task::spawn_blocking(cfhdb_cache::current_snapshot).await.unwrap_or_default() },
+                        Message::ActiveProfilesLoaded,
+                    ),
+                ])
+            }
+            Message::ActiveProfilesLoaded(snapshot) => {
+                self.active_profiles = snapshot;
+                iced::Command::none()
             }
             Message::SelectCategory(cat_type, class) => {
                 self.selected_category = Some((cat_type, class));
                 self.selected_device = None;
+                self.profile_history = None;
+                self.nvidia_device = None;
                 iced::Command::none()
             }
             Message::SelectDevice(dev_type, class, index) => {
@@ -362,6 +454,7 @@ impl DeviceTab {
             }
             Message::BackToDeviceList => {
                 self.selected_device = None;
+                self.nvidia_device = None;
                 self.selected_profiles.clear();
                 iced::Command::none()
             }
@@ -627,55 +720,17 @@ impl DeviceTab {
 
                 if let Some((profile_name, install_script, vendor_name, device_name, driver, driver_version, bus_id, vendor_id, device_id, repositories)) = profile_data {
                     if let Some(script) = install_script {
-
-                        let exe_path = std::env::current_exe()
-                            .unwrap_or_else(|_| std::path::PathBuf::from("rustora"));
-                        let exe_str = exe_path.to_string_lossy().into_owned();
-                        let profile_name_clone = profile_name.clone();
-                        let script_clone = script.clone();
-
-                        iced::Command::perform(
-                            async move {
-                                use tokio::process::Command as TokioCommand;
-
-                                use base64::{Engine as _, engine::general_purpose};
-                                let encoded_script = general_purpose::STANDARD.encode(script_clone.as_bytes());
-                                let encoded_vendor = general_purpose::STANDARD.encode(vendor_name.as_bytes());
-                                let encoded_device = general_purpose::STANDARD.encode(device_name.as_bytes());
-                                let encoded_driver = general_purpose::STANDARD.encode(driver.as_bytes());
-                                let encoded_drv_ver = general_purpose::STANDARD.encode(driver_version.as_bytes());
-                                let encoded_bus = general_purpose::STANDARD.encode(bus_id.as_bytes());
-                                let encoded_vid = general_purpose::STANDARD.encode(vendor_id.as_bytes());
-                                let encoded_did = general_purpose::STANDARD.encode(device_id.as_bytes());
-                                let encoded_repos = general_purpose::STANDARD.encode(serde_json::to_string(&repositories).unwrap_or_default().as_bytes());
-
-                                TokioCommand::new(&exe_str)
-                                    .arg("device-install-dialog")
-                                    .arg("--profile-name")
-                                    .arg(&profile_name_clone)
-                                    .arg("--install-script")
-                                    .arg(&encoded_script)
-                                    .arg("--vendor-name")
-                                    .arg(&encoded_vendor)
-                                    .arg("--device-name")
-                                    .arg(&encoded_device)
-                                    .arg("--driver")
-                                    .arg(&encoded_driver)
-                                    .arg("--driver-version")
-                                    .arg(&encoded_drv_ver)
-                                    .arg("--bus-id")
-                                    .arg(&encoded_bus)
-                                    .arg("--vendor-id")
-                                    .arg(&encoded_vid)
-                                    .arg("--device-id")
-                                    .arg(&encoded_did)
-                                    .arg("--repositories")
-                                    .arg(&encoded_repos)
-                                    .spawn()
-                                    .ok();
-                            },
-                            |_| Message::ProfileOperationComplete,
-                        )
+                        let cached_profile = self.cached_profile(dev_type, &class, device_idx, &profile_codename);
+                        spawn_device_install_dialog(profile_name, script, cached_profile, crate::gui::device_install_dialog::DeviceInfo {
+                            vendor_name,
+                            device_name,
+                            driver,
+                            driver_version,
+                            bus_id,
+                            vendor_id,
+                            device_id,
+                            repositories,
+                        })
                     } else {
                         self.error = Some("This profile does not have an install script.".to_string());
                         iced::Command::none()
@@ -776,6 +831,7 @@ impl DeviceTab {
                         let exe_str = exe_path.to_string_lossy().into_owned();
                         let profile_name_clone = profile_name.clone();
                         let script_clone = script.clone();
+                        let cached_profile = self.cached_profile(dev_type, &class, device_idx, &profile_codename);
 
                         iced::Command::perform(
                             async move {
@@ -792,8 +848,8 @@ impl DeviceTab {
                                 let encoded_did = general_purpose::STANDARD.encode(device_id.as_bytes());
                                 let encoded_repos = general_purpose::STANDARD.encode(serde_json::to_string(&repositories).unwrap_or_default().as_bytes());
 
-                                TokioCommand::new(&exe_str)
-                                    .arg("device-remove-dialog")
+                                let mut cmd = TokioCommand::new(&exe_str);
+                                cmd.arg("device-remove-dialog")
                                     .arg("--profile-name")
                                     .arg(&profile_name_clone)
                                     .arg("--remove-script")
@@ -813,9 +869,11 @@ impl DeviceTab {
                                     .arg("--device-id")
                                     .arg(&encoded_did)
                                     .arg("--repositories")
-                                    .arg(&encoded_repos)
-                                    .spawn()
-                                    .ok();
+                                    .arg(&encoded_repos);
+                                if let Some(profile) = cached_profile {
+                                    cmd.arg("--profile-codename").arg(&profile.codename).arg("--profile-bus").arg(&profile.bus);
+                                }
+                                cmd.spawn().ok();
                             },
                             |_| Message::ProfileOperationComplete,
                         )
@@ -891,6 +949,201 @@ impl DeviceTab {
                     }
                 })
             }
+            Message::ExportHardwareReport => {
+                self.exporting_report = true;
+                self.report_status = None;
+                iced::Command::perform(
+                    async {
+                        tokio::task::spawn_blocking(|| {
+                            let report = crate::gui::hw_report::HardwareReport::collect();
+                            crate::gui::hw_report::export_report(&report)
+                        })
+                        .await
+                        .map_err(|e| format!("Report task failed: {}", e))?
+                        .map(|(md_path, _json_path)| md_path.display().to_string())
+                    },
+                    Message::HardwareReportExported,
+                )
+            }
+            Message::HardwareReportExported(result) => {
+                self.exporting_report = false;
+                match result {
+                    Ok(path) => {
+                        logger::Logger::log_debug(&format!("[Device Tab] Hardware report exported to {}", path));
+                        self.report_status = Some(format!("Saved to {} (JSON copy alongside)", path));
+                    }
+                    Err(e) => {
+                        logger::Logger::log_debug(&format!("[Device Tab] Hardware report export failed: {}", e));
+                        self.report_status = Some(format!("Export failed: {}", e));
+                    }
+                }
+                iced::Command::none()
+            }
+            Message::ToggleOfflineProfiles => {
+                let mut current_settings = crate::gui::settings::AppSettings::load();
+                current_settings.offline_profiles = !current_settings.offline_profiles;
+                current_settings.save();
+                logger::Logger::log_debug(&format!("[Device Tab] Toggled offline profiles: {}", current_settings.offline_profiles));
+                iced::Command::none()
+            }
+            Message::ShowProfileHistory => {
+                self.selected_device = None;
+                self.show_firmware = false;
+                iced::Command::perform(
+                    async {
+                        tokio::task::spawn_blocking(|| (cfhdb_cache::list_snapshots(), cfhdb_cache::current_snapshot_id()))
+                            .await
+                            .unwrap_or_default()
+                    },
+                    |(snapshots, current)| Message::ProfileHistoryLoaded(snapshots, current),
+                )
+            }
+            Message::ProfileHistoryLoaded(snapshots, current) => {
+                self.profile_history = Some(snapshots);
+                self.active_snapshot = current;
+                iced::Command::none()
+            }
+            Message::CloseProfileHistory => {
+                self.profile_history = None;
+                iced::Command::none()
+            }
+            Message::RestoreProfileSnapshot(id) => {
+                self.restoring_snapshot = true;
+                iced::Command::perform(
+                    async move { cfhdb_cache::restore_snapshot(&id).await },
+                    Message::ProfileSnapshotRestored,
+                )
+            }
+            Message::ShowNvidiaManager(class, device_idx) => {
+                let device_id = self.pci_devices.iter()
+                    .find(|(c, _)| c == &class)
+                    .and_then(|(_, devices)| devices.get(device_idx))
+                    .map(|d| d.device.device_id.clone())
+                    .unwrap_or_default();
+                self.nvidia_device = Some((class, device_idx));
+                self.nvidia_state = None;
+                self.show_firmware = false;
+                iced::Command::perform(
+                    async move {
+                        tokio::task::spawn_blocking(move || crate::gui::nvidia::detect_state(&device_id))
+                            .await
+                            .map_err(|e| format!("NVIDIA detection failed: {}", e))
+                    },
+                    |result| match result {
+                        Ok(state) => Message::NvidiaStateLoaded(state),
+                        Err(e) => Message::Error(e),
+                    },
+                )
+            }
+            Message::NvidiaStateLoaded(state) => {
+                logger::Logger::log_debug(&format!(
+                    "[Device Tab] NVIDIA {} GPU, {} branch options, installed: {:?}",
+                    state.generation.label(),
+                    state.options.len(),
+                    state.installed.as_ref().map(|i| (i.branch, i.provider, i.open_modules))
+                ));
+                self.nvidia_state = Some(state);
+                iced::Command::none()
+            }
+            Message::CloseNvidiaManager => {
+                self.nvidia_device = None;
+                self.nvidia_state = None;
+                iced::Command::none()
+            }
+            Message::ShowFirmware => {
+                self.show_firmware = true;
+                self.profile_history = None;
+                self.nvidia_device = None;
+                self.firmware_devices = None;
+                self.firmware_status = None;
+                iced::Command::perform(crate::gui::firmware::list_devices(), Message::FirmwareLoaded)
+            }
+            Message::FirmwareLoaded(result) => {
+                match result {
+                    Ok(devices) => self.firmware_devices = Some(devices),
+                    Err(e) => {
+                        logger::Logger::log_debug(&format!("[Device Tab] fwupd query failed: {}", e));
+                        self.firmware_devices = Some(Vec::new());
+                        self.firmware_status = Some(Err(e));
+                    }
+                }
+                iced::Command::none()
+            }
+            Message::CloseFirmware => {
+                self.show_firmware = false;
+                iced::Command::none()
+            }
+            Message::RefreshFirmwareMetadata => {
+                self.firmware_busy = true;
+                self.firmware_status = None;
+                iced::Command::perform(crate::gui::firmware::refresh_metadata(), Message::FirmwareMetadataRefreshed)
+            }
+            Message::FirmwareMetadataRefreshed(result) => {
+                self.firmware_busy = false;
+                self.firmware_status = Some(result);
+                iced::Command::perform(crate::gui::firmware::list_devices(), Message::FirmwareLoaded)
+            }
+            Message::InstallFirmware(device_id, version, downgrade) => {
+                self.firmware_busy = true;
+                self.firmware_status = None;
+                iced::Command::perform(
+                    crate::gui::firmware::install_release(device_id, version, downgrade),
+                    Message::FirmwareInstalled,
+                )
+            }
+            Message::FirmwareInstalled(result) => {
+                self.firmware_busy = false;
+                self.firmware_status = Some(result);
+                iced::Command::perform(crate::gui::firmware::list_devices(), Message::FirmwareLoaded)
+            }
+            Message::SwitchNvidiaBranch(option_idx) => {
+                let (class, device_idx) = match self.nvidia_device.clone() {
+                    Some(d) => d,
+                    None => return iced::Command::none(),
+                };
+                let (state, option) = match self.nvidia_state.as_ref().and_then(|s| s.options.get(option_idx).map(|o| (s, o))) {
+                    Some(found) => found,
+                    None => return iced::Command::none(),
+                };
+                let device = match self.pci_devices.iter()
+                    .find(|(c, _)| c == &class)
+                    .and_then(|(_, devices)| devices.get(device_idx))
+                {
+                    Some(d) => &d.device,
+                    None => return iced::Command::none(),
+                };
+
+                let script = crate::gui::nvidia::switch_script(option, state);
+                let profile_name = format!("NVIDIA {} ({})", option.title(), option.provider.label());
+                spawn_device_install_dialog(profile_name, script, None, crate::gui::device_install_dialog::DeviceInfo {
+                    vendor_name: device.vendor_name.clone(),
+                    device_name: device.device_name.clone(),
+                    driver: option.title(),
+                    driver_version: option.available_version.clone().unwrap_or_default(),
+                    bus_id: device.sysfs_busid.clone(),
+                    vendor_id: device.vendor_id.clone(),
+                    device_id: device.device_id.clone(),
+                    repositories: option.repo.iter().cloned().collect(),
+                })
+            }
+            Message::ProfileSnapshotRestored(result) => {
+                self.restoring_snapshot = false;
+                match result {
+                    Ok(_) => {
+                        // Reload so devices are matched against the restored profiles
+                        self.is_loading = true;
+                        self.loading_message = "Reloading devices...".into();
+                        iced::Command::batch(vec![
+                            iced::Command::perform(async {}, |_| Message::ShowProfileHistory),
+                            iced::Command::perform(async {}, |_| Message::LoadDevicesAfterCache),
+                        ])
+                    }
+                    Err(e) => {
+                        self.error = Some(format!("Failed to restore profiles: {}", e));
+                        iced::Command::none()
+                    }
+                }
+            }
         }
     }
 
@@ -944,7 +1197,7 @@ impl DeviceTab {
             .center_y()
             .padding(20)
             .into()
-        } else if self.pci_devices.is_empty() && self.usb_devices.is_empty() {
+        } else if self.pci_devices.is_empty() && self.usb_devices.is_empty() && !self.show_firmware {
             container(
                 column![
                     text("No devices found").size(body_font_size * 1.14),
@@ -1068,6 +1321,97 @@ impl DeviceTab {
             })));
         
         sidebar_items = sidebar_items.push(cfhdb_toggle);
+
+        let offline_toggle = button(
+            row![
+                text(if settings.offline_profiles { glyphs::CHECK_SYMBOL } else { " " }).font(*material_font).size(icon_size * 0.9),
+                text(" Offline Profiles").size(button_font_size * 0.9),
+            ]
+            .spacing(8)
+            .align_items(Alignment::Center)
+        )
+        .on_press(Message::ToggleOfflineProfiles)
+        .width(Length::Fill)
+        .padding(Padding::from([12.0, 16.0, 12.0, 16.0]))
+        .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+            is_primary: settings.offline_profiles,
+            radius: settings.border_radius,
+        })));
+        sidebar_items = sidebar_items.push(offline_toggle);
+
+        let history_button = button(
+            row![
+                text(glyphs::REFRESH_SYMBOL).font(*material_font).size(icon_size * 0.9),
+                text(" Profile History").size(button_font_size * 0.9),
+            ]
+            .spacing(8)
+            .align_items(Alignment::Center)
+        )
+        .on_press(Message::ShowProfileHistory)
+        .width(Length::Fill)
+        .padding(Padding::from([12.0, 16.0, 12.0, 16.0]))
+        .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+            is_primary: self.profile_history.is_some(),
+            radius: settings.border_radius,
+        })));
+        sidebar_items = sidebar_items.push(history_button);
+        if !self.is_loading && !self.pci_devices.is_empty() {
+            let unverified = match self.active_profiles {
+                Some(ref snapshot) if snapshot.verified => None,
+                Some(ref snapshot) if snapshot.source == "bundled" => Some("Profiles unverified: using the offline bundle shipped with Rustora"),
+                Some(_) => Some("Profiles unverified: no checksum was published for this download"),
+                None => Some("Profiles unverified: the cache matches no recorded snapshot"),
+            };
+            if let Some(warning) = unverified {
+                sidebar_items = sidebar_items.push(
+                    text(warning)
+                        .size(button_font_size * 0.75)
+                        .style(iced::theme::Text::Color(theme.danger()))
+                );
+            }
+        }
+
+        let firmware_button = button(
+            row![
+                text(glyphs::DOWNLOAD_SYMBOL).font(*material_font).size(icon_size * 0.9),
+                text(" Firmware").size(button_font_size * 0.9),
+            ]
+            .spacing(8)
+            .align_items(Alignment::Center)
+        )
+        .on_press(Message::ShowFirmware)
+        .width(Length::Fill)
+        .padding(Padding::from([12.0, 16.0, 12.0, 16.0]))
+        .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+            is_primary: self.show_firmware,
+            radius: settings.border_radius,
+        })));
+        sidebar_items = sidebar_items.push(firmware_button);
+
+        // Markdown/JSON summary of devices, drivers and packages for bug reports
+        let export_button = button(
+            row![
+                text(glyphs::SAVE_SYMBOL).font(*material_font).size(icon_size * 0.9),
+                text(if self.exporting_report { " Exporting..." } else { " Export Hardware Report" }).size(button_font_size * 0.9),
+            ]
+            .spacing(8)
+            .align_items(Alignment::Center)
+        )
+        .on_press_maybe(if self.exporting_report { None } else { Some(Message::ExportHardwareReport) })
+        .width(Length::Fill)
+        .padding(Padding::from([12.0, 16.0, 12.0, 16.0]))
+        .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+            is_primary: false,
+            radius: settings.border_radius,
+        })));
+        sidebar_items = sidebar_items.push(export_button);
+        if let Some(ref status) = self.report_status {
+            sidebar_items = sidebar_items.push(
+                text(status)
+                    .size(button_font_size * 0.75)
+                    .style(iced::theme::Text::Color(theme.secondary_text_with_settings(Some(settings))))
+            );
+        }
         sidebar_items = sidebar_items.push(Space::with_height(Length::Fixed(24.0)));
         sidebar_items = sidebar_items.push(
             container(
@@ -1164,6 +1508,15 @@ impl DeviceTab {
     }
 
     fn view_content(&self, theme: &crate::gui::Theme, material_font: &iced::Font, settings: &crate::gui::settings::AppSettings) -> Element<'_, Message> {
+        if let Some(ref snapshots) = self.profile_history {
+            return self.view_profile_history(theme, snapshots, settings);
+        }
+        if self.nvidia_device.is_some() {
+            return self.view_nvidia_manager(theme, settings);
+        }
+        if self.show_firmware {
+            return self.view_firmware(theme, settings);
+        }
         if let Some((cat_type, class)) = &self.selected_category {
             if let Some((dev_type, _, device_idx)) = &self.selected_device {
 
@@ -1195,6 +1548,402 @@ impl DeviceTab {
         }
     }
 
+    fn view_profile_history<'a>(&'a self, theme: &crate::gui::Theme, snapshots: &'a [cfhdb_cache::ProfileSnapshot], settings: &crate::gui::settings::AppSettings) -> Element<'a, Message> {
+        let title_font_size = (settings.font_size_titles * settings.scale_titles).round();
+        let body_font_size = (settings.font_size_body * settings.scale_body).round();
+        let button_font_size = (settings.font_size_buttons * settings.scale_buttons).round();
+
+        let mut list = column![
+            row![
+                text("Profile History").size(title_font_size * 0.86).style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
+                Space::with_width(Length::Fill),
+                button(text("Close").size(button_font_size))
+                    .on_press(Message::CloseProfileHistory)
+                    .padding(Padding::from([8.0, 16.0, 8.0, 16.0]))
+                    .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                        is_primary: false,
+                        radius: settings.border_radius,
+                    }))),
+            ]
+            .align_items(Alignment::Center),
+            text("Every profile update is kept as a snapshot. Restore an older one if an update broke something.")
+                .size(body_font_size)
+                .style(iced::theme::Text::Color(theme.secondary_text_with_settings(Some(settings)))),
+        ]
+        .spacing(12);
+
+        if snapshots.is_empty() {
+            list = list.push(
+                text("No snapshots yet. Download profiles to create one.")
+                    .size(body_font_size)
+                    .style(iced::theme::Text::Color(theme.secondary_text_with_settings(Some(settings))))
+            );
+        }
+
+        for snapshot in snapshots {
+            let is_active = self.active_snapshot.as_deref() == Some(snapshot.id.as_str());
+            let verification = if snapshot.verified { "verified" } else { "unverified" };
+            let restore_button = button(text(if is_active { "Current" } else { "Restore" }).size(button_font_size))
+                .on_press_maybe(if is_active || self.restoring_snapshot {
+                    None
+                } else {
+                    Some(Message::RestoreProfileSnapshot(snapshot.id.clone()))
+                })
+                .padding(Padding::from([8.0, 16.0, 8.0, 16.0]))
+                .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                    is_primary: !is_active,
+                    radius: settings.border_radius,
+                })));
+
+            list = list.push(
+                container(
+                    row![
+                        column![
+                            text(&snapshot.created).size(body_font_size * 1.1),
+                            text(format!("Source: {} · {} · pci {} · usb {}",
+                                snapshot.source,
+                                verification,
+                                &snapshot.pci_sha256[..snapshot.pci_sha256.len().min(12)],
+                                &snapshot.usb_sha256[..snapshot.usb_sha256.len().min(12)],
+                            ))
+                            .size(body_font_size * 0.9)
+                            .style(iced::theme::Text::Color(theme.secondary_text_with_settings(Some(settings)))),
+                        ]
+                        .spacing(4)
+                        .width(Length::Fill),
+                        restore_button,
+                    ]
+                    .spacing(12)
+                    .align_items(Alignment::Center)
+                )
+                .padding(Padding::from([12.0, 16.0, 12.0, 16.0]))
+                .style(iced::theme::Container::Custom(Box::new(ProfileCardStyle {
+                    radius: settings.border_radius,
+                })))
+            );
+        }
+
+        scrollable(list.padding(Padding::from([16.0, 20.0, 16.0, 20.0])))
+            .height(Length::Fill)
+            .into()
+    }
+
+    fn view_nvidia_manager(&self, theme: &crate::gui::Theme, settings: &crate::gui::settings::AppSettings) -> Element<'_, Message> {
+        let title_font_size = (settings.font_size_titles * settings.scale_titles).round();
+        let body_font_size = (settings.font_size_body * settings.scale_body).round();
+        let button_font_size = (settings.font_size_buttons * settings.scale_buttons).round();
+
+        let mut content = column![
+            row![
+                text("NVIDIA Driver Branches").size(title_font_size * 0.86).style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
+                Space::with_width(Length::Fill),
+                button(text("Back").size(button_font_size))
+                    .on_press(Message::CloseNvidiaManager)
+                    .padding(Padding::from([8.0, 16.0, 8.0, 16.0]))
+                    .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                        is_primary: false,
+                        radius: settings.border_radius,
+                    }))),
+            ]
+            .align_items(Alignment::Center),
+        ]
+        .spacing(12);
+
+        let state = match self.nvidia_state {
+            Some(ref state) => state,
+            None => {
+                content = content.push(
+                    text("Checking GPU architecture and repositories...")
+                        .size(body_font_size)
+                        .style(iced::theme::Text::Color(theme.secondary_text_with_settings(Some(settings))))
+                );
+                return container(content.padding(Padding::from([16.0, 20.0, 16.0, 20.0])))
+                    .width(Length::Fill)
+                    .height(Length::Fill)
+                    .into();
+            }
+        };
+
+        let installed_text = match state.installed {
+            Some(ref installed) => format!(
+                "Installed: {} {} from {}{}",
+                installed.branch.map(|b| b.label()).unwrap_or("unknown branch"),
+                installed.version,
+                installed.provider.map(|p| p.label()).unwrap_or("an unknown repository"),
+                if installed.open_modules { ", open kernel modules" } else { "" },
+            ),
+            None => "No NVIDIA driver packages are installed.".to_string(),
+        };
+        let mut info = format!("GPU architecture: {}", state.generation.label());
+        if let Some(limit) = state.generation.max_driver_major() {
+            info.push_str(&format!(" · supported up to the {}.xx series", limit));
+        }
+        if state.generation.requires_open_modules() {
+            info.push_str(" · open kernel modules required");
+        }
+        content = content.push(
+            column![
+                text(info).size(body_font_size),
+                text(installed_text).size(body_font_size),
+                text(if state.secure_boot {
+                    "Secure Boot is enabled: switching sets up an akmods signing key and queues it for MOK enrollment."
+                } else {
+                    "Secure Boot is disabled: no module signing needed."
+                })
+                .size(body_font_size * 0.9)
+                .style(iced::theme::Text::Color(theme.secondary_text_with_settings(Some(settings)))),
+            ]
+            .spacing(4)
+        );
+
+        for (idx, option) in state.options.iter().enumerate() {
+            let version_text = match (&option.available_version, &option.repo) {
+                (Some(version), Some(repo)) => format!("{} · {} ({})", version, option.provider.label(), repo),
+                _ => format!("Not available from {}", option.provider.label()),
+            };
+            let mut title_row = row![text(option.title()).size(body_font_size * 1.1)].spacing(8).align_items(Alignment::Center);
+            if option.recommended {
+                title_row = title_row.push(
+                    container(text("RECOMMENDED").size(body_font_size * 0.75))
+                        .padding(Padding::from([2.0, 8.0, 2.0, 8.0]))
+                        .style(iced::theme::Container::Custom(Box::new(InfoBadgeStyle {
+                            radius: settings.border_radius,
+                        })))
+                );
+            }
+            if option.available_version.is_some() && !option.supported {
+                title_row = title_row.push(
+                    text("Not supported by this GPU")
+                        .size(body_font_size * 0.85)
+                        .style(iced::theme::Text::Color(theme.danger()))
+                );
+            }
+
+            let action = button(text(if option.installed { "Installed" } else { "Switch" }).size(button_font_size))
+                .on_press_maybe(if option.installed || !option.supported {
+                    None
+                } else {
+                    Some(Message::SwitchNvidiaBranch(idx))
+                })
+                .padding(Padding::from([8.0, 16.0, 8.0, 16.0]))
+                .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                    is_primary: option.supported && !option.installed,
+                    radius: settings.border_radius,
+                })));
+
+            content = content.push(
+                container(
+                    row![
+                        column![
+                            title_row,
+                            text(version_text)
+                                .size(body_font_size * 0.9)
+                                .style(iced::theme::Text::Color(theme.secondary_text_with_settings(Some(settings)))),
+                            text(option.packages.join(" "))
+                                .size(body_font_size * 0.85)
+                                .font(iced::Font::MONOSPACE)
+                                .style(iced::theme::Text::Color(theme.secondary_text_with_settings(Some(settings)))),
+                        ]
+                        .spacing(4)
+                        .width(Length::Fill),
+                        action,
+                    ]
+                    .spacing(12)
+                    .align_items(Alignment::Center)
+                )
+                .padding(Padding::from([12.0, 16.0, 12.0, 16.0]))
+                .style(iced::theme::Container::Custom(Box::new(ProfileCardStyle {
+                    radius: settings.border_radius,
+                })))
+            );
+        }
+
+        scrollable(content.padding(Padding::from([16.0, 20.0, 16.0, 20.0])))
+            .height(Length::Fill)
+            .into()
+    }
+
+    fn view_firmware(&self, theme: &crate::gui::Theme, settings: &crate::gui::settings::AppSettings) -> Element<'_, Message> {
+        let title_font_size = (settings.font_size_titles * settings.scale_titles).round();
+        let body_font_size = (settings.font_size_body * settings.scale_body).round();
+        let button_font_size = (settings.font_size_buttons * settings.scale_buttons).round();
+        let secondary = theme.secondary_text_with_settings(Some(settings));
+
+        let mut content = column![
+            row![
+                text("Firmware Updates").size(title_font_size * 0.86).style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
+                Space::with_width(Length::Fill),
+                button(text(if self.firmware_busy { "Working..." } else { "Refresh Metadata" }).size(button_font_size))
+                    .on_press_maybe(if self.firmware_busy { None } else { Some(Message::RefreshFirmwareMetadata) })
+                    .padding(Padding::from([8.0, 16.0, 8.0, 16.0]))
+                    .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                        is_primary: true,
+                        radius: settings.border_radius,
+                    }))),
+                button(text("Back").size(button_font_size))
+                    .on_press(Message::CloseFirmware)
+                    .padding(Padding::from([8.0, 16.0, 8.0, 16.0]))
+                    .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                        is_primary: false,
+                        radius: settings.border_radius,
+                    }))),
+            ]
+            .spacing(8)
+            .align_items(Alignment::Center),
+            text("BIOS, SSD, dock and peripheral firmware from the Linux Vendor Firmware Service via fwupd.")
+                .size(body_font_size)
+                .style(iced::theme::Text::Color(secondary)),
+        ]
+        .spacing(12);
+
+        match self.firmware_status {
+            Some(Ok(ref msg)) => {
+                content = content.push(text(msg).size(body_font_size * 0.9).style(iced::theme::Text::Color(secondary)));
+            }
+            Some(Err(ref err)) => {
+                content = content.push(text(err).size(body_font_size * 0.9).style(iced::theme::Text::Color(theme.danger())));
+            }
+            None => {}
+        }
+
+        let devices = match self.firmware_devices {
+            Some(ref devices) => devices,
+            None => {
+                content = content.push(
+                    text("Querying fwupd for devices and releases...")
+                        .size(body_font_size)
+                        .style(iced::theme::Text::Color(secondary))
+                );
+                return container(content.padding(Padding::from([16.0, 20.0, 16.0, 20.0])))
+                    .width(Length::Fill)
+                    .height(Length::Fill)
+                    .into();
+            }
+        };
+
+        if devices.is_empty() {
+            content = content.push(
+                text("No devices with firmware from LVFS were found. Try refreshing the metadata.")
+                    .size(body_font_size)
+                    .style(iced::theme::Text::Color(secondary))
+            );
+        }
+
+        for device in devices {
+            let mut title_row = row![
+                text(&device.name).size(body_font_size * 1.1),
+            ]
+            .spacing(8)
+            .align_items(Alignment::Center);
+            if device.needs_reboot {
+                title_row = title_row.push(
+                    container(text("REBOOT REQUIRED").size(body_font_size * 0.75))
+                        .padding(Padding::from([2.0, 8.0, 2.0, 8.0]))
+                        .style(iced::theme::Container::Custom(Box::new(InfoBadgeStyle {
+                            radius: settings.border_radius,
+                        })))
+                );
+            }
+
+            let mut card = column![
+                title_row,
+                text(format!("{} · installed version {}", device.vendor, device.version))
+                    .size(body_font_size * 0.9)
+                    .style(iced::theme::Text::Color(secondary)),
+            ]
+            .spacing(6);
+
+            if device.pending_reboot {
+                card = card.push(
+                    text("An update is staged and will be applied on the next reboot.")
+                        .size(body_font_size * 0.9)
+                        .style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings))))
+                );
+            }
+
+            if let Some(update) = device.available_update() {
+                card = card.push(
+                    row![
+                        text(format!("Update available: {} ({})", update.version, crate::gui::firmware::format_size(update.size)))
+                            .size(body_font_size)
+                            .width(Length::Fill),
+                        button(text("Update").size(button_font_size))
+                            .on_press_maybe(if self.firmware_busy {
+                                None
+                            } else {
+                                Some(Message::InstallFirmware(device.id.clone(), update.version.clone(), false))
+                            })
+                            .padding(Padding::from([8.0, 16.0, 8.0, 16.0]))
+                            .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                                is_primary: true,
+                                radius: settings.border_radius,
+                            }))),
+                    ]
+                    .spacing(12)
+                    .align_items(Alignment::Center)
+                );
+                if !update.summary.is_empty() {
+                    card = card.push(text(&update.summary).size(body_font_size * 0.9));
+                }
+                if !update.description.is_empty() {
+                    card = card.push(
+                        text(&update.description)
+                            .size(body_font_size * 0.85)
+                            .style(iced::theme::Text::Color(secondary))
+                    );
+                }
+            } else {
+                card = card.push(
+                    text("Up to date")
+                        .size(body_font_size * 0.9)
+                        .style(iced::theme::Text::Color(secondary))
+                );
+            }
+
+            let mut downgrades = row![].spacing(8).align_items(Alignment::Center);
+            let mut has_downgrades = false;
+            for release in device.downgrades() {
+                has_downgrades = true;
+                downgrades = downgrades.push(
+                    button(text(format!("Downgrade to {}", release.version)).size(button_font_size * 0.9))
+                        .on_press_maybe(if self.firmware_busy {
+                            None
+                        } else {
+                            Some(Message::InstallFirmware(device.id.clone(), release.version.clone(), true))
+                        })
+                        .padding(Padding::from([6.0, 12.0, 6.0, 12.0]))
+                        .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                            is_primary: false,
+                            radius: settings.border_radius,
+                        })))
+                );
+            }
+            if has_downgrades {
+                card = card.push(
+                    text("Older releases (only downgrade to work around a regression):")
+                        .size(body_font_size * 0.85)
+                        .style(iced::theme::Text::Color(secondary))
+                );
+                card = card.push(scrollable(downgrades).direction(iced::widget::scrollable::Direction::Horizontal(
+                    iced::widget::scrollable::Properties::default(),
+                )));
+            }
+
+            content = content.push(
+                container(card)
+                    .width(Length::Fill)
+                    .padding(Padding::from([12.0, 16.0, 12.0, 16.0]))
+                    .style(iced::theme::Container::Custom(Box::new(ProfileCardStyle {
+                        radius: settings.border_radius,
+                    })))
+            );
+        }
+
+        scrollable(content.padding(Padding::from([16.0, 20.0, 16.0, 20.0])))
+            .height(Length::Fill)
+            .into()
+    }
+
     fn view_device_list(&self, theme: &crate::gui::Theme, _material_font: &iced::Font, cat_type: CategoryType, class: &str, settings: &crate::gui::settings::AppSettings) -> Element<'_, Message> {
         let body_font_size = (settings.font_size_body * settings.scale_body * 1.15).round();
         match cat_type {
@@ -1439,6 +2188,21 @@ impl DeviceTab {
             radius: settings.border_radius,
         })))
         .padding(Padding::from([14.0, 20.0, 14.0, 20.0]));
+        // NVIDIA display controllers get the branch manager
+        let is_nvidia_gpu = matches!(&device_info, DeviceInfo::Pci { vendor_id, .. } if vendor_id.eq_ignore_ascii_case("10de"))
+            && class.starts_with("03");
+        let nvidia_button: Element<Message> = if is_nvidia_gpu {
+            button(text("Driver Branches").size(button_font_size))
+                .on_press(Message::ShowNvidiaManager(class.to_string(), device_idx))
+                .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                    is_primary: true,
+                    radius: settings.border_radius,
+                })))
+                .padding(Padding::from([14.0, 20.0, 14.0, 20.0]))
+                .into()
+        } else {
+            Space::with_width(Length::Shrink).into()
+        };
         let device_title = text(&device_name)
             .size(title_font_size)
             .style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings))))
@@ -1474,6 +2238,7 @@ impl DeviceTab {
                         row![
                             back_button,
                             Space::with_width(Length::Fill),
+                            nvidia_button,
                         ]
                         .spacing(0)
                         .align_items(Alignment::Center)
@@ -2672,7 +3437,7 @@ fn extract_repositories_from_script(install_script: &Option<String>) -> Vec<Stri
     repos.dedup();
     repos
 }
-fn get_driver_version(driver: &str) -> String {
+pub fn get_driver_version(driver: &str) -> String {
     if driver.is_empty() || driver == "none" {
         return String::new();
     }
@@ -2731,7 +3496,7 @@ fn get_driver_version(driver: &str) -> String {
     String::new()
 }
 
-fn get_pci_class_name(class: &str) -> String {
+pub fn get_pci_class_name(class: &str) -> String {
 
     match class {
         "0300" => "VGA Compatible Controller".to_string(),
@@ -2742,7 +3507,7 @@ fn get_pci_class_name(class: &str) -> String {
     }
 }
 
-fn get_usb_class_name(class: &str) -> String {
+pub fn get_usb_class_name(class: &str) -> String {
 
     match class {
         "01" => "Audio".to_string(),
@@ -3451,7 +4216,11 @@ async fn load_all_devices() -> Result<(
     let pci_profiles_arc: Vec<Arc<PreCheckedPciProfile>> = all_pci_profiles
         .into_iter()
         .map(|p| {
-            let profile = PreCheckedPciProfile::new(p.clone());
+            let profile = if repo_info_map.contains_key(&p.codename) {
+                PreCheckedPciProfile::from_repo(p.clone())
+            } else {
+                PreCheckedPciProfile::new(p.clone())
+            };
             if p.vendor_ids.contains(&"10de".to_string()) {
 
                 if let Some(version_part) = p.codename.strip_prefix("nvidia-") {
@@ -3612,297 +4381,142 @@ async fn load_all_devices() -> Result<(
 }
 
 async fn load_pci_profiles() -> Result<Vec<CfhdbPciProfile>, String> {
-    let cached_db_path = Path::new("/var/cache/cfhdb/pci.json");
-    if cached_db_path.exists() {
-        match std::fs::read_to_string(cached_db_path) {
-            Ok(data) => {
-                match parse_pci_profiles(&data) {
-                    Ok(profiles) => {
-                        return Ok(profiles);
-                    }
-                    Err(_e) => {
-                    }
-                }
-            }
-            Err(_e) => {
-            }
-        }
-    } else {
-    }
-    let profile_url = match get_profile_url_config() {
-        Ok(url) => {
-            url
-        }
-        Err(e) => {
-            return Err(format!("Failed to read profile config: {}", e));
-        }
-    };
-
-    match reqwest::get(&profile_url.pci_json_url).await {
-        Ok(response) => {
-            if response.status().is_success() {
-                match response.text().await {
-                    Ok(text) => {
-                        let _ = cache_profile_file(cached_db_path, &text).await;
-                        match parse_pci_profiles(&text) {
-                            Ok(profiles) => {
-                                Ok(profiles)
-                            }
-                            Err(e) => {
-                                Err(format!("Failed to parse PCI profiles: {}", e))
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        Err(format!("Failed to read response: {}", e))
-                    }
-                }
-            } else {
-                Err(format!("HTTP error: {}", response.status()))
-            }
-        }
-        Err(e) => {
-            Err(format!("Failed to download PCI profiles: {}", e))
-        }
-    }
+    let data = read_cached_profiles("pci.json")?;
+    parse_pci_profiles(&data).map_err(|e| format!("Failed to parse PCI profiles: {}", e))
 }
 
 async fn load_usb_profiles() -> Result<Vec<CfhdbUsbProfile>, String> {
-    let cached_db_path = Path::new("/var/cache/cfhdb/usb.json");
-    if cached_db_path.exists() {
-        match std::fs::read_to_string(cached_db_path) {
-            Ok(data) => {
-                match parse_usb_profiles(&data) {
-                    Ok(profiles) => {
-                        return Ok(profiles);
-                    }
-                    Err(_e) => {
-                    }
-                }
-            }
-            Err(_e) => {
-            }
-        }
-    } else {
-    }
-    let profile_url = match get_profile_url_config() {
-        Ok(url) => {
-            url
-        }
-        Err(e) => {
-            return Err(format!("Failed to read profile config: {}", e));
-        }
-    };
-
-    match reqwest::get(&profile_url.usb_json_url).await {
-        Ok(response) => {
-            if response.status().is_success() {
-                match response.text().await {
-                    Ok(text) => {
-                        let _ = cache_profile_file(cached_db_path, &text).await;
-                        match parse_usb_profiles(&text) {
-                            Ok(profiles) => {
-                                Ok(profiles)
-                            }
-                            Err(e) => {
-                                Err(format!("Failed to parse USB profiles: {}", e))
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        Err(format!("Failed to read response: {}", e))
-                    }
-                }
-            } else {
-                Err(format!("HTTP error: {}", response.status()))
-            }
-        }
-        Err(e) => {
-            Err(format!("Failed to download USB profiles: {}", e))
-        }
-    }
+    let data = read_cached_profiles("usb.json")?;
+    parse_usb_profiles(&data).map_err(|e| format!("Failed to parse USB profiles: {}", e))
 }
 
-fn get_profile_url_config() -> Result<ProfileUrlConfig, std::io::Error> {
-    let file_path = "/etc/cfhdb/profile-config.json";
-    if let Ok(json_content) = std::fs::read_to_string(file_path) {
-        if let Ok(config) = serde_json::from_str::<ProfileUrlConfig>(&json_content) {
-            return Ok(config);
-        }
-    }
-
-    Ok(ProfileUrlConfig {
-        pci_json_url: "https://raw.githubusercontent.com/Nobara-Project/cfhdb/refs/heads/master/data/profiles/pci.json".to_string(),
-        usb_json_url: "https://raw.githubusercontent.com/Nobara-Project/cfhdb/refs/heads/master/data/profiles/usb.json".to_string(),
-    })
+fn read_cached_profiles(file_name: &str) -> Result<String, String> {
+    let cached_db_path = cfhdb_cache::cache_path(file_name);
+    std::fs::read_to_string(&cached_db_path)
+        .map_err(|_| format!("No cached {} available. Please download profiles.", file_name))
 }
-fn profiles_need_update(cached_path: &Path) -> bool {
-    if !cached_path.exists() {
-        return true;
-    }
-    match std::fs::metadata(cached_path) {
-        Ok(metadata) => {
-            match metadata.modified() {
-                Ok(modified) => {
-                    match modified.elapsed() {
-                        Ok(elapsed) => {
-                            let _hours = elapsed.as_secs() / 3600;
-                            let needs_update = elapsed.as_secs() > 24 * 60 * 60;
-                            needs_update
-                        }
-                        Err(_) => {
-                            true
-                        }
-                    }
-                }
-                Err(_) => {
-                    true
-                }
+
+// Opens the install dialog (review, simulate, run) in its own window. Scripts taken from the
+// profile cache pass `cached_profile` so the dialog re-checks them against the cache before running
+fn spawn_device_install_dialog(profile_name: String, script: String, cached_profile: Option<cfhdb_cache::CachedProfile>, info: crate::gui::device_install_dialog::DeviceInfo) -> iced::Command<Message> {
+    let exe_path = std::env::current_exe()
+        .unwrap_or_else(|_| std::path::PathBuf::from("rustora"));
+    let exe_str = exe_path.to_string_lossy().into_owned();
+
+    iced::Command::perform(
+        async move {
+            use tokio::process::Command as TokioCommand;
+
+            use base64::{Engine as _, engine::general_purpose};
+            let encoded_script = general_purpose::STANDARD.encode(script.as_bytes());
+            let encoded_vendor = general_purpose::STANDARD.encode(info.vendor_name.as_bytes());
+            let encoded_device = general_purpose::STANDARD.encode(info.device_name.as_bytes());
+            let encoded_driver = general_purpose::STANDARD.encode(info.driver.as_bytes());
+            let encoded_drv_ver = general_purpose::STANDARD.encode(info.driver_version.as_bytes());
+            let encoded_bus = general_purpose::STANDARD.encode(info.bus_id.as_bytes());
+            let encoded_vid = general_purpose::STANDARD.encode(info.vendor_id.as_bytes());
+            let encoded_did = general_purpose::STANDARD.encode(info.device_id.as_bytes());
+            let encoded_repos = general_purpose::STANDARD.encode(serde_json::to_string(&info.repositories).unwrap_or_default().as_bytes());
+
+            let mut cmd = TokioCommand::new(&exe_str);
+            cmd.arg("device-install-dialog")
+                .arg("--profile-name")
+                .arg(&profile_name)
+                .arg("--install-script")
+                .arg(&encoded_script)
+                .arg("--vendor-name")
+                .arg(&encoded_vendor)
+                .arg("--device-name")
+                .arg(&encoded_device)
+                .arg("--driver")
+                .arg(&encoded_driver)
+                .arg("--driver-version")
+                .arg(&encoded_drv_ver)
+                .arg("--bus-id")
+                .arg(&encoded_bus)
+                .arg("--vendor-id")
+                .arg(&encoded_vid)
+                .arg("--device-id")
+                .arg(&encoded_did)
+                .arg("--repositories")
+                .arg(&encoded_repos);
+            if let Some(profile) = cached_profile {
+                cmd.arg("--profile-codename").arg(&profile.codename).arg("--profile-bus").arg(&profile.bus);
             }
-        }
-        Err(_) => {
-            true
-        }
-    }
+            cmd.spawn().ok();
+        },
+        |_| Message::ProfileOperationComplete,
+    )
 }
-async fn ensure_profiles_cached_force() -> Result<(), String> {
 
-    let pci_path = Path::new("/var/cache/cfhdb/pci.json");
-    let usb_path = Path::new("/var/cache/cfhdb/usb.json");
+// An empty cache is seeded from the profile bundle installed with Rustora
+async fn seed_from_bundle() -> Result<(), String> {
+    let bundle = cfhdb_cache::load_bundled_profiles()
+        .map_err(|e| format!("No profiles are cached yet and the offline bundle can't be used: {}", e))?;
+    cfhdb_cache::store_profiles(&bundle, "bundled").await
+}
 
-    let profile_url = match get_profile_url_config() {
-        Ok(url) => {
-            url
-        }
-        Err(e) => {
-            return Err(format!("Failed to read profile config: {}", e));
-        }
-    };
-    match reqwest::get(&profile_url.pci_json_url).await {
-        Ok(response) => {
-            if response.status().is_success() {
-                match response.text().await {
-                    Ok(text) => {
-                        if let Err(e) = cache_profile_file(pci_path, &text).await {
-                            return Err(format!("Failed to cache PCI profiles: {}", e));
-                        }
-                    }
-                    Err(e) => {
-                        return Err(format!("Failed to read PCI profile response: {}", e));
-                    }
-                }
-            } else {
-                return Err(format!("Failed to download PCI profiles: HTTP {}", response.status()));
-            }
-        }
-        Err(e) => {
-            return Err(format!("Failed to download PCI profiles: {}", e));
-        }
-    }
-    match reqwest::get(&profile_url.usb_json_url).await {
-        Ok(response) => {
-            if response.status().is_success() {
-                match response.text().await {
-                    Ok(text) => {
-                        if let Err(e) = cache_profile_file(usb_path, &text).await {
-                            return Err(format!("Failed to cache USB profiles: {}", e));
-                        }
-                    }
-                    Err(e) => {
-                        return Err(format!("Failed to read USB profile response: {}", e));
-                    }
-                }
-            } else {
-                return Err(format!("Failed to download USB profiles: HTTP {}", response.status()));
-            }
-        }
-        Err(e) => {
-            return Err(format!("Failed to download USB profiles: {}", e));
-        }
+async fn ensure_profiles_cached_force() -> Result<(), String> {
+    if crate::gui::settings::AppSettings::load().offline_profiles {
+        let have_cache = cfhdb_cache::cache_path("pci.json").exists() && cfhdb_cache::cache_path("usb.json").exists();
+        return if have_cache { Ok(()) } else { seed_from_bundle().await };
     }
 
-    Ok(())
+    let profile_url = cfhdb_cache::ProfileUrlConfig::load();
+    let profiles = cfhdb_cache::download_profiles(&profile_url).await?;
+    cfhdb_cache::mark_checked();
+    cfhdb_cache::store_profiles(&profiles, "network").await
 }
+
 async fn ensure_profiles_cached() -> Result<(), String> {
+    let pci_path = cfhdb_cache::cache_path("pci.json");
+    let usb_path = cfhdb_cache::cache_path("usb.json");
+    let have_cache = pci_path.exists() && usb_path.exists();
 
-    let pci_path = Path::new("/var/cache/cfhdb/pci.json");
-    let usb_path = Path::new("/var/cache/cfhdb/usb.json");
+    if crate::gui::settings::AppSettings::load().offline_profiles {
+        return if have_cache { Ok(()) } else { seed_from_bundle().await };
+    }
 
-    let profile_url = match get_profile_url_config() {
-        Ok(url) => {
-            url
-        }
-        Err(e) => {
-            return Err(format!("Failed to read profile config: {}", e));
-        }
-    };
-    if profiles_need_update(pci_path) {
-        match reqwest::get(&profile_url.pci_json_url).await {
-            Ok(response) => {
-                if response.status().is_success() {
-                    match response.text().await {
-                        Ok(text) => {
-                            if let Err(e) = cache_profile_file(pci_path, &text).await {
-                                return Err(format!("Failed to cache PCI profiles: {}", e));
-                            }
-                        }
-                        Err(e) => {
-                            return Err(format!("Failed to read PCI profile response: {}", e));
-                        }
-                    }
-                } else {
-                    return Err(format!("Failed to download PCI profiles: HTTP {}", response.status()));
-                }
-            }
-            Err(e) => {
-                return Err(format!("Failed to download PCI profiles: {}", e));
-            }
-        }
-    } else {
+    if have_cache && cfhdb_cache::checked_recently() {
+        return Ok(());
     }
-    if profiles_need_update(usb_path) {
-        match reqwest::get(&profile_url.usb_json_url).await {
-            Ok(response) => {
-                if response.status().is_success() {
-                    match response.text().await {
-                        Ok(text) => {
-                            if let Err(e) = cache_profile_file(usb_path, &text).await {
-                                return Err(format!("Failed to cache USB profiles: {}", e));
-                            }
-                        }
-                        Err(e) => {
-                            return Err(format!("Failed to read USB profile response: {}", e));
-                        }
-                    }
-                } else {
-                    return Err(format!("Failed to download USB profiles: HTTP {}", response.status()));
-                }
-            }
-            Err(e) => {
-                return Err(format!("Failed to download USB profiles: {}", e));
+
+    let profile_url = cfhdb_cache::ProfileUrlConfig::load();
+    let refresh_error = match cfhdb_cache::download_profiles(&profile_url).await {
+        Ok(profiles) => {
+            cfhdb_cache::mark_checked();
+            match cfhdb_cache::store_profiles(&profiles, "network").await {
+                Ok(()) => return Ok(()),
+                Err(e) => e,
             }
         }
-    } else {
-    }
+        Err(e) => e,
+    };
 
-    Ok(())
+    // Unreachable server, a rejected download or a cancelled authentication: keep working
+    // with what we have
+    if have_cache {
+        logger::Logger::log_debug(&format!("[Device Tab] Profile refresh failed, keeping cached profiles: {}", refresh_error));
+        return Ok(());
+    }
+    seed_from_bundle()
+        .await
+        .map_err(|e| format!("{}. {}", refresh_error, e))
 }
 
+// The cache holds scripts that later run through pkexec, so it must stay root-owned.
+// Only asks for authentication when the directory is missing or has loose permissions
 async fn request_permissions() -> Result<(), String> {
     use tokio::process::Command as TokioCommand;
 
-    let current_user = users::get_current_username()
-        .ok_or("Failed to get current username")?
-        .to_string_lossy()
-        .to_string();
+    if tokio::task::spawn_blocking(cfhdb_cache::cache_is_trusted).await.unwrap_or(false) {
+        return Ok(());
+    }
 
     let mut cmd = TokioCommand::new("pkexec");
     cmd.arg("sh");
     cmd.arg("-c");
-    cmd.arg(&format!(
-        "mkdir -p /var/cache/cfhdb && chown -R {}:{} /var/cache/cfhdb && chmod -R 777 /var/cache/cfhdb",
-        current_user, current_user
-    ));
+    cmd.arg("install -d -o root -g root -m 755 /var/cache/cfhdb /var/cache/cfhdb/history && chown -R root:root /var/cache/cfhdb && chmod -R u=rwX,go=rX /var/cache/cfhdb");
     if let Ok(display) = std::env::var("DISPLAY") {
         cmd.env("DISPLAY", display);
     }
@@ -3925,97 +4539,8 @@ async fn request_permissions() -> Result<(), String> {
 
     Ok(())
 }
-async fn cache_profile_file(path: &Path, content: &str) -> Result<(), String> {
-    use tokio::process::Command as TokioCommand;
-    if let Some(parent) = path.parent() {
-        match std::fs::create_dir_all(parent) {
-            Ok(_) => {
-                match std::fs::write(path, content) {
-                    Ok(_) => {
-                        return Ok(());
-                    }
-                    Err(_e) => {
-                    }
-                }
-            }
-            Err(_e) => {
-            }
-        }
-    }
-
-    use std::time::{SystemTime, UNIX_EPOCH};
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_nanos();
-    let temp_file = std::env::temp_dir().join(format!("cfhdb_{}.json", timestamp));
-
-    if let Err(e) = std::fs::write(&temp_file, content) {
-        return Err(format!("Failed to write temp file: {}", e));
-    }
-    let parent_dir = path.parent().ok_or("Invalid path")?;
-    let parent_str = parent_dir.to_str().ok_or("Invalid path")?;
-    let path_str = path.to_str().ok_or("Invalid path")?;
-    let temp_str = temp_file.to_str().ok_or("Invalid temp path")?;
-    let mut cmd = TokioCommand::new("pkexec");
-    cmd.arg("sh");
-    cmd.arg("-c");
-    cmd.arg(&format!(
-        "mkdir -p {} && chmod 755 {} && cp {} {} && chmod 644 {}",
-        parent_str, parent_str, temp_str, path_str, path_str
-    ));
-
-    if let Ok(display) = std::env::var("DISPLAY") {
-        cmd.env("DISPLAY", display);
-    }
-    let output = cmd
-        .output()
-        .await
-        .map_err(|e| {
-            let _ = std::fs::remove_file(&temp_file);
-            format!("Failed to execute command: {}. Make sure polkit is installed.", e)
-        })?;
-    let _ = std::fs::remove_file(&temp_file);
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-
-        if output.status.code() == Some(126) || output.status.code() == Some(127) {
-            return Err("Authentication cancelled or failed. Please try again.".to_string());
-        }
-        return Err(format!("Failed to cache profile: {}", stderr));
-    }
-
-    let mut fix_perms_cmd = TokioCommand::new("pkexec");
-    fix_perms_cmd.arg("sh");
-    fix_perms_cmd.arg("-c");
-    fix_perms_cmd.arg("find /var/cache/cfhdb/ -type d -exec chmod 755 {} + && find /var/cache/cfhdb/ -type f -exec chmod 644 {} +");
-    if let Ok(display) = std::env::var("DISPLAY") {
-        fix_perms_cmd.env("DISPLAY", display);
-    }
-    let fix_perms_output = fix_perms_cmd
-        .output()
-        .await;
-
-    match fix_perms_output {
-        Ok(output) if output.status.success() => {
-        }
-        Ok(_output) => {
-        }
-        Err(_e) => {
-        }
-    }
-
-    Ok(())
-}
-
-#[derive(serde::Deserialize)]
-struct ProfileUrlConfig {
-    pci_json_url: String,
-    usb_json_url: String,
-}
 
-fn parse_pci_profiles(data: &str) -> Result<Vec<CfhdbPciProfile>, String> {
+pub fn parse_pci_profiles(data: &str) -> Result<Vec<CfhdbPciProfile>, String> {
     let res: serde_json::Value = serde_json::from_str(data)
         .map_err(|e| format!("Failed to parse JSON: {}", e))?;
 
@@ -4102,7 +4627,7 @@ fn parse_pci_profiles(data: &str) -> Result<Vec<CfhdbPciProfile>, String> {
     Ok(profiles)
 }
 
-fn parse_usb_profiles(data: &str) -> Result<Vec<CfhdbUsbProfile>, String> {
+pub fn parse_usb_profiles(data: &str) -> Result<Vec<CfhdbUsbProfile>, String> {
     let res: serde_json::Value = serde_json::from_str(data)
         .map_err(|e| format!("Failed to parse JSON: {}", e))?;
 
@@ -4189,7 +4714,7 @@ fn parse_usb_profiles(data: &str) -> Result<Vec<CfhdbUsbProfile>, String> {
     Ok(profiles)
 }
 
-fn get_pci_devices(profiles: &[Arc<PreCheckedPciProfile>]) -> Option<HashMap<String, Vec<PreCheckedPciDevice>>> {
+pub fn get_pci_devices(profiles: &[Arc<PreCheckedPciProfile>]) -> Option<HashMap<String, Vec<PreCheckedPciDevice>>> {
     let devices = CfhdbPciDevice::get_devices()?;
     let hashmap = CfhdbPciDevice::create_class_hashmap(devices);
 
@@ -4207,7 +4732,7 @@ fn get_pci_devices(profiles: &[Arc<PreCheckedPciProfile>]) -> Option<HashMap<Str
     )
 }
 
-fn get_usb_devices(profiles: &[Arc<PreCheckedUsbProfile>]) -> Option<HashMap<String, Vec<PreCheckedUsbDevice>>> {
+pub fn get_usb_devices(profiles: &[Arc<PreCheckedUsbProfile>]) -> Option<HashMap<String, Vec<PreCheckedUsbDevice>>> {
     let devices = CfhdbUsbDevice::get_devices()?;
     let hashmap = CfhdbUsbDevice::create_class_hashmap(devices);
 
@@ -1,4 +1,4 @@
-use iced::widget::{button, checkbox, column, container, row, scrollable, text, text_input, Space};
+use iced::widget::{button, checkbox, column, container, pick_list, row, scrollable, text, text_input, Space};
 use iced::{Alignment, Element, Length, Padding, Border};
 use iced::widget::container::Appearance;
 use iced::widget::button::Appearance as ButtonAppearance;
@@ -8,6 +8,17 @@ use iced::widget::checkbox::StyleSheet as CheckboxStyleSheet;
 use iced::widget::text_input::Appearance as TextInputAppearance;
 use iced::widget::text_input::StyleSheet as TextInputStyleSheet;
 use tokio::process::Command as TokioCommand;
+use std::path::PathBuf;
+use std::sync::Arc;
+use crate::gui::appstream::{self, Catalog};
+use crate::gui::flatpak_history::{self, AppHistory, Mask};
+use crate::gui::flatpak_permissions::{self, AppPermissions, OverrideScope, PermissionSection};
+use crate::gui::flatpak_remotes::{self, FlatpakRemote, Installation, RemotesState};
+use crate::gui::flatpak_storage;
+
+// Rendering thousands of catalog rows per frame is slow; categories narrow it down
+const BROWSE_LIMIT: usize = 200;
+const ALL_CATEGORIES: &str = "All categories";
 
 #[derive(Debug, Clone)]
 pub enum Message {
@@ -15,12 +26,14 @@ pub enum Message {
     SwitchToSearch,
     SwitchToInstalled,
     SwitchToUpdates,
+    SwitchToRemotes,
 
     SearchQueryChanged(String),
     Search,
     SearchResult(Vec<FlatpakInfo>),
 
     TogglePackage(String),
+    InstallTargetSelected(Installation),
     InstallSelected,
     InstallComplete,
 
@@ -31,7 +44,7 @@ pub enum Message {
     RemoveComplete,
 
     CheckUpdates,
-    UpdatesFound(Vec<FlatpakInfo>),
+    UpdatesFound(Vec<FlatpakUpdate>),
     InstallUpdates,
     UpdatesInstalled,
 
@@ -39,6 +52,44 @@ pub enum Message {
     PackageDetailsLoaded(FlatpakDetails),
     ClosePanel,
 
+    LoadCatalog,
+    CatalogLoaded(Arc<Catalog>),
+    SelectCategory(String),
+    ScreenshotLoaded(String, Result<PathBuf, String>),
+
+    ShowPermissions(String, Option<Installation>),
+    PermissionsLoaded(Result<Box<AppPermissions>, String>),
+    ClosePermissions,
+    SelectOverrideScope(OverrideScope),
+    SetPermission(PermissionSection, String, bool),
+    PermissionInputChanged(PermissionSection, String),
+    ResetOverrides,
+    OverrideApplied(Result<String, String>),
+    MoveInstallation,
+    InstallationMoved(Result<String, String>),
+
+    SelectAppPanel(AppPanel),
+    HistoryLoaded(Result<Box<AppHistory>, String>),
+    DeployCommit(String),
+    MasksLoaded(Result<Vec<Mask>, String>),
+    TogglePin,
+    VersionChanged(Result<String, String>),
+
+    LoadRemotes,
+    RemotesLoaded(Result<RemotesState, String>),
+    SelectRemote(String),
+    RemoteInstallationSelected(Installation),
+    AddRemotePreset(usize),
+    CustomRemoteNameChanged(String),
+    CustomRemoteLocationChanged(String),
+    AddCustomRemote,
+    ToggleRemoteEnabled,
+    RemotePriorityChanged(String),
+    RemoteFilterChanged(String),
+    SaveRemoteSettings,
+    RemoveRemote,
+    RemoteChanged(Result<String, String>),
+
     Error(String),
 }
 
@@ -49,6 +100,39 @@ pub struct FlatpakInfo {
     pub description: String,
     pub version: String,
     pub remote: Option<String>,
+    // Only known for installed entries; search results can go to either installation
+    pub installation: Option<Installation>,
+}
+
+impl FlatpakInfo {
+    // The same app can be installed for the user and system-wide at once
+    pub fn key(&self) -> String {
+        match self.installation {
+            Some(installation) => format!("{}:{}", installation.label(), self.application_id),
+            None => self.application_id.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FlatpakUpdate {
+    pub name: String,
+    pub application_id: String,
+    // e.g. app/org.gnome.Maps/x86_64/stable or runtime/org.freedesktop.Platform.GL.default/x86_64/24.08
+    pub flatpak_ref: String,
+    pub version: String,
+    pub branch: String,
+    pub remote: Option<String>,
+    pub installation: Installation,
+    pub installed_commit: String,
+    pub commit: String,
+    pub download_size: String,
+}
+
+impl FlatpakUpdate {
+    pub fn kind(&self) -> &str {
+        self.flatpak_ref.split('/').next().unwrap_or("app")
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -74,6 +158,7 @@ pub struct FlatpakTab {
     is_searching: bool,
 
     selected_packages: std::collections::HashSet<String>,
+    install_target: Installation,
     is_installing: bool,
 
     installed_flatpaks: Vec<FlatpakInfo>,
@@ -81,7 +166,7 @@ pub struct FlatpakTab {
 
     is_removing: bool,
 
-    updates: Vec<FlatpakInfo>,
+    updates: Vec<FlatpakUpdate>,
     is_checking_updates: bool,
     is_updating: bool,
     update_error: Option<String>,
@@ -91,6 +176,38 @@ pub struct FlatpakTab {
     selected_package: Option<String>,
     package_details: Option<FlatpakDetails>,
     panel_open: bool,
+
+    catalog: Option<Arc<Catalog>>,
+    browse_category: Option<String>,
+    screenshot: Option<PathBuf>,
+
+    permissions_app: Option<String>,
+    permissions_installation: Option<Installation>,
+    app_permissions: Option<Result<Box<AppPermissions>, String>>,
+    override_scope: OverrideScope,
+    filesystem_input: String,
+    bus_input: String,
+    env_input: String,
+    is_applying_override: bool,
+    override_status: Option<Result<String, String>>,
+    is_moving: bool,
+
+    app_panel: AppPanel,
+    app_history: Option<Result<Box<AppHistory>, String>>,
+    masks: Vec<Mask>,
+    is_changing_version: bool,
+
+    remotes: RemotesState,
+    is_loading_remotes: bool,
+    selected_remote: Option<String>,
+    remote_installation: Installation,
+    custom_remote_name: String,
+    custom_remote_location: String,
+    remote_priority_input: String,
+    remote_filter_input: String,
+    confirm_remove_remote: bool,
+    is_changing_remote: bool,
+    remote_status: Option<Result<String, String>>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -98,6 +215,14 @@ enum ViewMode {
     Search,
     Installed,
     Updates,
+    Remotes,
+}
+
+// What the side panel of an installed app shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppPanel {
+    Permissions,
+    History,
 }
 
 impl FlatpakTab {
@@ -107,6 +232,7 @@ impl FlatpakTab {
             search_results: Vec::new(),
             is_searching: false,
             selected_packages: std::collections::HashSet::new(),
+            install_target: Installation::System,
             is_installing: false,
             installed_flatpaks: Vec::new(),
             is_loading_installed: false,
@@ -119,6 +245,34 @@ impl FlatpakTab {
             selected_package: None,
             package_details: None,
             panel_open: false,
+            catalog: None,
+            browse_category: None,
+            screenshot: None,
+            permissions_app: None,
+            permissions_installation: None,
+            app_permissions: None,
+            override_scope: OverrideScope::User,
+            filesystem_input: String::new(),
+            bus_input: String::new(),
+            env_input: String::new(),
+            is_applying_override: false,
+            override_status: None,
+            is_moving: false,
+            app_panel: AppPanel::Permissions,
+            app_history: None,
+            masks: Vec::new(),
+            is_changing_version: false,
+            remotes: RemotesState::default(),
+            is_loading_remotes: false,
+            selected_remote: None,
+            remote_installation: Installation::System,
+            custom_remote_name: String::new(),
+            custom_remote_location: String::new(),
+            remote_priority_input: String::new(),
+            remote_filter_input: String::new(),
+            confirm_remove_remote: false,
+            is_changing_remote: false,
+            remote_status: None,
         }
     }
 
@@ -150,6 +304,10 @@ impl FlatpakTab {
                     }
                 })
             }
+            Message::SwitchToRemotes => {
+                self.view_mode = ViewMode::Remotes;
+                self.update(Message::LoadRemotes)
+            }
             Message::SearchQueryChanged(query) => {
                 self.search_query = query.clone();
                 if !query.trim().is_empty() && query.len() >= 2 {
@@ -192,6 +350,10 @@ impl FlatpakTab {
                 }
                 iced::Command::none()
             }
+            Message::InstallTargetSelected(installation) => {
+                self.install_target = installation;
+                iced::Command::none()
+            }
             Message::InstallSelected => {
                 if self.selected_packages.is_empty() {
                     return iced::Command::none();
@@ -203,17 +365,26 @@ impl FlatpakTab {
                     let remote = self.search_results
                         .iter()
                         .find(|p| &p.application_id == first_pkg)
-                        .and_then(|p| p.remote.clone());
+                        .and_then(|p| p.remote.clone())
+                        .or_else(|| {
+                            self.catalog
+                                .as_ref()?
+                                .find_flatpak(first_pkg)?
+                                .remote()
+                                .map(str::to_string)
+                        });
 
                     let app_id = first_pkg.clone();
                     let remote_clone = remote.clone();
+                    let installation = self.install_target;
                     iced::Command::perform(
                         async move {
                             use tokio::process::Command as TokioCommand;
                             let exe_path = std::env::current_exe()
                                 .unwrap_or_else(|_| std::path::PathBuf::from("rustora"));
                             let mut cmd = TokioCommand::new(&exe_path);
-                            cmd.arg("flatpak-install-dialog").arg(&app_id);
+                            cmd.arg("flatpak-install-dialog").arg(&app_id)
+                                .arg("--installation").arg(installation.label());
                             if let Some(ref r) = remote_clone {
                                 cmd.arg("--remote").arg(r);
                             }
@@ -249,24 +420,34 @@ impl FlatpakTab {
             Message::InstalledLoaded(packages) => {
                 self.is_loading_installed = false;
                 self.installed_flatpaks = packages;
-                iced::Command::none()
+                iced::Command::perform(flatpak_history::load_masks(), Message::MasksLoaded)
             }
             Message::RemoveSelected => {
                 if self.selected_packages.is_empty() {
                     return iced::Command::none();
                 }
 
-                let packages: Vec<String> = self.selected_packages.iter().cloned().collect();
+                // One dialog per installation, since uninstall takes a single --user/--system
+                let mut groups: Vec<(Option<Installation>, Vec<String>)> = Vec::new();
+                for pkg in self.installed_flatpaks.iter().filter(|p| self.selected_packages.contains(&p.key())) {
+                    match groups.iter_mut().find(|(i, _)| *i == pkg.installation) {
+                        Some((_, ids)) => ids.push(pkg.application_id.clone()),
+                        None => groups.push((pkg.installation, vec![pkg.application_id.clone()])),
+                    }
+                }
                 iced::Command::perform(
                     async move {
                         use tokio::process::Command as TokioCommand;
                         let exe_path = std::env::current_exe()
                             .unwrap_or_else(|_| std::path::PathBuf::from("rustora"));
-                        let _ = TokioCommand::new(&exe_path)
-                            .arg("flatpak-remove-dialog")
-                            .args(packages)
-                            .spawn()
-                            .ok();
+                        for (installation, packages) in groups {
+                            let mut cmd = TokioCommand::new(&exe_path);
+                            cmd.arg("flatpak-remove-dialog").args(packages);
+                            if let Some(installation) = installation {
+                                cmd.arg("--installation").arg(installation.label());
+                            }
+                            let _ = cmd.spawn().ok();
+                        }
                     },
                     |_| Message::RemoveComplete,
                 )
@@ -309,8 +490,11 @@ impl FlatpakTab {
                     .map(|u| crate::gui::flatpak_update_dialog::FlatpakUpdateInfo {
                         name: u.name.clone(),
                         application_id: u.application_id.clone(),
-                        version: u.version.clone(),
+                        version: if u.version.is_empty() { u.branch.clone() } else { u.version.clone() },
                         remote: u.remote.clone(),
+                        flatpak_ref: u.flatpak_ref.clone(),
+                        download_size: u.download_size.clone(),
+                        installation: Some(u.installation.label().to_string()),
                     })
                     .collect();
 
@@ -347,8 +531,24 @@ impl FlatpakTab {
             }
             Message::PackageSelected(app_id, remote) => {
                 self.selected_package = Some(app_id.clone());
+                self.package_details = None;
+                self.screenshot = None;
                 self.panel_open = true;
-                iced::Command::perform(load_flatpak_details(app_id, remote), Message::PackageDetailsLoaded)
+                let load_details = iced::Command::perform(load_flatpak_details(app_id.clone(), remote), Message::PackageDetailsLoaded);
+                let url = self
+                    .catalog
+                    .as_ref()
+                    .and_then(|c| c.find_flatpak(&app_id))
+                    .and_then(|a| a.screenshots.first().cloned());
+                match url {
+                    Some(url) => iced::Command::batch(vec![
+                        load_details,
+                        iced::Command::perform(appstream::fetch_screenshot(url), move |result| {
+                            Message::ScreenshotLoaded(app_id.clone(), result)
+                        }),
+                    ]),
+                    None => load_details,
+                }
             }
             Message::PackageDetailsLoaded(details) => {
                 self.package_details = Some(details);
@@ -358,8 +558,311 @@ impl FlatpakTab {
                 self.panel_open = false;
                 self.selected_package = None;
                 self.package_details = None;
+                self.screenshot = None;
+                iced::Command::none()
+            }
+            Message::LoadCatalog => {
+                if self.catalog.is_some() {
+                    return iced::Command::none();
+                }
+                iced::Command::perform(appstream::catalog(), Message::CatalogLoaded)
+            }
+            Message::CatalogLoaded(catalog) => {
+                self.catalog = Some(catalog);
+                iced::Command::none()
+            }
+            Message::SelectCategory(label) => {
+                self.browse_category = appstream::CATEGORIES
+                    .iter()
+                    .find(|(_, l)| *l == label)
+                    .map(|(id, _)| id.to_string());
+                iced::Command::none()
+            }
+            Message::ScreenshotLoaded(app_id, result) => {
+                if self.selected_package.as_deref() == Some(app_id.as_str()) {
+                    match result {
+                        Ok(path) => self.screenshot = Some(path),
+                        Err(e) => crate::logger::Logger::log_debug(&format!("[Flatpak Tab] {}", e)),
+                    }
+                }
+                iced::Command::none()
+            }
+            Message::ShowPermissions(app_id, installation) => {
+                self.permissions_app = Some(app_id.clone());
+                self.permissions_installation = installation;
+                self.app_permissions = None;
+                self.app_history = None;
+                self.override_status = None;
+                let load_permissions = iced::Command::perform(flatpak_permissions::load(app_id, installation), |result| Message::PermissionsLoaded(result.map(Box::new)));
+                if self.app_panel == AppPanel::History {
+                    iced::Command::batch(vec![load_permissions, self.load_history()])
+                } else {
+                    load_permissions
+                }
+            }
+            Message::PermissionsLoaded(result) => {
+                // Ignore a late reply for an app the user already navigated away from
+                let current = match &result {
+                    Ok(permissions) => self.permissions_app.as_deref() == Some(permissions.app_id.as_str()),
+                    Err(_) => self.permissions_app.is_some(),
+                };
+                if current {
+                    self.app_permissions = Some(result);
+                }
+                iced::Command::none()
+            }
+            Message::ClosePermissions => {
+                self.permissions_app = None;
+                self.permissions_installation = None;
+                self.app_history = None;
+                self.app_permissions = None;
+                self.override_status = None;
+                iced::Command::none()
+            }
+            Message::SelectOverrideScope(scope) => {
+                self.override_scope = scope;
+                iced::Command::none()
+            }
+            Message::PermissionInputChanged(section, value) => {
+                match section {
+                    PermissionSection::Filesystems => self.filesystem_input = value,
+                    PermissionSection::Environment => self.env_input = value,
+                    _ => self.bus_input = value,
+                }
+                iced::Command::none()
+            }
+            Message::SetPermission(section, value, grant) => {
+                let Some(app_id) = self.permissions_app.clone() else {
+                    return iced::Command::none();
+                };
+                if self.is_applying_override || value.trim().is_empty() {
+                    return iced::Command::none();
+                }
+                if grant {
+                    match section {
+                        PermissionSection::Filesystems => self.filesystem_input.clear(),
+                        PermissionSection::Environment => self.env_input.clear(),
+                        PermissionSection::SessionBus | PermissionSection::SystemBus => self.bus_input.clear(),
+                        _ => {}
+                    }
+                }
+                self.is_applying_override = true;
+                let scope = self.override_scope;
+                let value = value.trim().to_string();
+                if grant {
+                    iced::Command::perform(flatpak_permissions::grant(app_id, scope, section, value), Message::OverrideApplied)
+                } else {
+                    iced::Command::perform(flatpak_permissions::revoke(app_id, scope, section, value), Message::OverrideApplied)
+                }
+            }
+            Message::ResetOverrides => {
+                let Some(app_id) = self.permissions_app.clone() else {
+                    return iced::Command::none();
+                };
+                self.is_applying_override = true;
+                iced::Command::perform(flatpak_permissions::reset(app_id, self.override_scope), Message::OverrideApplied)
+            }
+            Message::OverrideApplied(result) => {
+                self.is_applying_override = false;
+                self.override_status = Some(result);
+                match self.permissions_app.clone() {
+                    Some(app_id) => iced::Command::perform(flatpak_permissions::load(app_id, self.permissions_installation), |result| Message::PermissionsLoaded(result.map(Box::new))),
+                    None => iced::Command::none(),
+                }
+            }
+            Message::MoveInstallation => {
+                let (Some(app_id), Some(from)) = (self.permissions_app.clone(), self.permissions_installation) else {
+                    return iced::Command::none();
+                };
+                let Some(origin) = self.installed_flatpaks
+                    .iter()
+                    .find(|p| p.application_id == app_id && p.installation == Some(from))
+                    .and_then(|p| p.remote.clone())
+                else {
+                    self.override_status = Some(Err(format!("{} was not installed from a remote and cannot be moved", app_id)));
+                    return iced::Command::none();
+                };
+                self.is_moving = true;
+                self.override_status = None;
+                iced::Command::perform(move_flatpak(app_id, origin, from), Message::InstallationMoved)
+            }
+            Message::InstallationMoved(result) => {
+                self.is_moving = false;
+                if result.is_ok() {
+                    self.permissions_installation = self.permissions_installation.map(|i| i.other());
+                }
+                self.override_status = Some(result);
+                self.is_loading_installed = true;
+                let mut commands = vec![iced::Command::perform(load_installed_flatpaks(), |result| {
+                    match result {
+                        Ok(packages) => Message::InstalledLoaded(packages),
+                        Err(e) => Message::Error(e),
+                    }
+                })];
+                if let Some(app_id) = self.permissions_app.clone() {
+                    commands.push(iced::Command::perform(flatpak_permissions::load(app_id, self.permissions_installation), |result| Message::PermissionsLoaded(result.map(Box::new))));
+                }
+                iced::Command::batch(commands)
+            }
+            Message::SelectAppPanel(panel) => {
+                self.app_panel = panel;
+                if panel == AppPanel::History && self.app_history.is_none() {
+                    return self.load_history();
+                }
+                iced::Command::none()
+            }
+            Message::HistoryLoaded(result) => {
+                let current = match &result {
+                    Ok(history) => self.permissions_app.as_deref() == Some(history.app_id.as_str()),
+                    Err(_) => self.permissions_app.is_some(),
+                };
+                if current {
+                    self.app_history = Some(result);
+                }
+                iced::Command::none()
+            }
+            Message::DeployCommit(commit) => {
+                let Some(Ok(history)) = self.app_history.as_ref() else {
+                    return iced::Command::none();
+                };
+                self.is_changing_version = true;
+                self.override_status = None;
+                iced::Command::perform(flatpak_history::deploy_commit(history.as_ref().clone(), commit), Message::VersionChanged)
+            }
+            Message::MasksLoaded(result) => {
+                match result {
+                    Ok(masks) => self.masks = masks,
+                    Err(e) => crate::logger::Logger::log_debug(&format!("[Flatpak] Failed to read masks: {}", e)),
+                }
+                iced::Command::none()
+            }
+            Message::TogglePin => {
+                let (Some(app_id), Some(installation)) = (self.permissions_app.clone(), self.permissions_installation) else {
+                    return iced::Command::none();
+                };
+                let pinned = flatpak_history::is_masked(&self.masks, installation, &app_id, "");
+                self.is_changing_version = true;
+                self.override_status = None;
+                iced::Command::perform(flatpak_history::set_pinned(app_id, installation, !pinned), Message::VersionChanged)
+            }
+            Message::VersionChanged(result) => {
+                self.is_changing_version = false;
+                self.override_status = Some(result);
+                iced::Command::batch(vec![
+                    iced::Command::perform(flatpak_history::load_masks(), Message::MasksLoaded),
+                    self.load_history(),
+                ])
+            }
+            Message::LoadRemotes => {
+                self.is_loading_remotes = true;
+                iced::Command::perform(flatpak_remotes::load(), Message::RemotesLoaded)
+            }
+            Message::RemotesLoaded(result) => {
+                self.is_loading_remotes = false;
+                match result {
+                    Ok(state) => {
+                        self.remotes = state;
+                        let selected = self.selected_remote();
+                        if selected.is_none() {
+                            self.selected_remote = None;
+                        }
+                        self.load_remote_form(selected);
+                    }
+                    Err(e) => self.remote_status = Some(Err(e)),
+                }
+                iced::Command::none()
+            }
+            Message::SelectRemote(key) => {
+                self.confirm_remove_remote = false;
+                self.selected_remote = if self.selected_remote.as_deref() == Some(key.as_str()) { None } else { Some(key) };
+                self.load_remote_form(self.selected_remote());
+                iced::Command::none()
+            }
+            Message::RemoteInstallationSelected(installation) => {
+                self.remote_installation = installation;
+                iced::Command::none()
+            }
+            Message::AddRemotePreset(index) => {
+                let Some(preset) = flatpak_remotes::PRESETS.get(index) else {
+                    return iced::Command::none();
+                };
+                self.is_changing_remote = true;
+                iced::Command::perform(
+                    flatpak_remotes::add(preset.name.to_string(), preset.location.to_string(), self.remote_installation),
+                    Message::RemoteChanged,
+                )
+            }
+            Message::CustomRemoteNameChanged(name) => {
+                self.custom_remote_name = name;
+                iced::Command::none()
+            }
+            Message::CustomRemoteLocationChanged(location) => {
+                // Suggest a name from the file name, e.g. gnome-nightly.flatpakrepo
+                if self.custom_remote_name.is_empty() || self.custom_remote_location.contains(&self.custom_remote_name) {
+                    if let Some(stem) = location.rsplit('/').next().and_then(|f| f.strip_suffix(".flatpakrepo")) {
+                        self.custom_remote_name = stem.to_string();
+                    }
+                }
+                self.custom_remote_location = location;
+                iced::Command::none()
+            }
+            Message::AddCustomRemote => {
+                if self.custom_remote_name.trim().is_empty() || self.custom_remote_location.trim().is_empty() {
+                    return iced::Command::none();
+                }
+                self.is_changing_remote = true;
+                let name = std::mem::take(&mut self.custom_remote_name);
+                let location = std::mem::take(&mut self.custom_remote_location);
+                iced::Command::perform(flatpak_remotes::add(name, location, self.remote_installation), Message::RemoteChanged)
+            }
+            Message::ToggleRemoteEnabled => {
+                let Some(remote) = self.selected_remote() else {
+                    return iced::Command::none();
+                };
+                self.is_changing_remote = true;
+                let enable = remote.disabled;
+                iced::Command::perform(flatpak_remotes::set_enabled(remote, enable), Message::RemoteChanged)
+            }
+            Message::RemotePriorityChanged(priority) => {
+                self.remote_priority_input = priority;
+                iced::Command::none()
+            }
+            Message::RemoteFilterChanged(filter) => {
+                self.remote_filter_input = filter;
                 iced::Command::none()
             }
+            Message::SaveRemoteSettings => {
+                let Some(remote) = self.selected_remote() else {
+                    return iced::Command::none();
+                };
+                let Ok(priority) = self.remote_priority_input.trim().parse::<i32>() else {
+                    self.remote_status = Some(Err("Priority must be a whole number".to_string()));
+                    return iced::Command::none();
+                };
+                self.is_changing_remote = true;
+                iced::Command::perform(
+                    flatpak_remotes::modify(remote, priority, self.remote_filter_input.clone()),
+                    Message::RemoteChanged,
+                )
+            }
+            Message::RemoveRemote => {
+                let Some(remote) = self.selected_remote() else {
+                    return iced::Command::none();
+                };
+                // First press asks for confirmation
+                if !self.confirm_remove_remote {
+                    self.confirm_remove_remote = true;
+                    return iced::Command::none();
+                }
+                self.confirm_remove_remote = false;
+                self.is_changing_remote = true;
+                iced::Command::perform(flatpak_remotes::remove(remote), Message::RemoteChanged)
+            }
+            Message::RemoteChanged(result) => {
+                self.is_changing_remote = false;
+                self.remote_status = Some(result);
+                self.update(Message::LoadRemotes)
+            }
             Message::Error(msg) => {
                 self.is_searching = false;
                 self.is_installing = false;
@@ -376,6 +879,36 @@ impl FlatpakTab {
         }
     }
 
+    fn load_history(&mut self) -> iced::Command<Message> {
+        let (Some(app_id), Some(installation)) = (self.permissions_app.clone(), self.permissions_installation) else {
+            return iced::Command::none();
+        };
+        let remote = self.installed_flatpaks
+            .iter()
+            .find(|p| p.application_id == app_id && p.installation == Some(installation))
+            .and_then(|p| p.remote.clone());
+        match remote {
+            Some(remote) => iced::Command::perform(flatpak_history::load(app_id, remote, installation), |result| {
+                Message::HistoryLoaded(result.map(Box::new))
+            }),
+            None => {
+                self.app_history = Some(Err(format!("{} was not installed from a remote, so it has no history", app_id)));
+                iced::Command::none()
+            }
+        }
+    }
+
+    fn selected_remote(&self) -> Option<FlatpakRemote> {
+        self.remotes.remotes.iter().find(|r| Some(r.key()) == self.selected_remote).cloned()
+    }
+
+    fn load_remote_form(&mut self, remote: Option<FlatpakRemote>) {
+        if let Some(remote) = remote {
+            self.remote_priority_input = remote.priority.to_string();
+            self.remote_filter_input = remote.filter;
+        }
+    }
+
     pub fn view(&self, theme: &crate::gui::Theme, settings: &crate::gui::settings::AppSettings) -> Element<'_, Message> {
         let tab_font_size = settings.font_size_tabs * settings.scale_tabs;
         let icon_size = (settings.font_size_icons * settings.scale_icons).round();
@@ -428,10 +961,26 @@ impl FlatpakTab {
         })))
         .padding(Padding::new(12.0));
 
+        let remotes_mode_button = button(
+            row![
+                text(crate::gui::fonts::glyphs::CLOUD_SYMBOL).font(material_font).size(icon_size),
+                text(" Remotes").size(tab_font_size)
+            ]
+            .spacing(4)
+            .align_items(Alignment::Center)
+        )
+        .on_press(Message::SwitchToRemotes)
+        .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+            is_primary: self.view_mode == ViewMode::Remotes,
+            radius: settings.border_radius,
+        })))
+        .padding(Padding::new(12.0));
+
         let mode_selector = row![
             search_mode_button,
             installed_mode_button,
-            updates_mode_button
+            updates_mode_button,
+            remotes_mode_button
         ]
         .spacing(10)
         .align_items(Alignment::Center);
@@ -441,6 +990,7 @@ impl FlatpakTab {
             ViewMode::Search => self.view_search(theme, settings, material_font),
             ViewMode::Installed => self.view_installed(theme, settings, material_font),
             ViewMode::Updates => self.view_updates(theme, settings, material_font),
+            ViewMode::Remotes => self.view_remotes(theme, settings, material_font),
         };
 
         container(column![mode_selector, content].spacing(15).padding(20))
@@ -538,13 +1088,7 @@ impl FlatpakTab {
                 .into()
         } else {
             let package_list: Element<Message> = if self.search_results.is_empty() {
-                container(text("Enter a search query to find Flatpak applications").size(body_font_size))
-                    .width(Length::Fill)
-                    .padding(20)
-                    .style(iced::theme::Container::Custom(Box::new(RoundedMessageStyle {
-                        radius: settings.border_radius,
-                    })))
-                    .into()
+                self.view_catalog(theme, settings)
             } else {
                 scrollable(
                     column(
@@ -561,11 +1105,25 @@ impl FlatpakTab {
                                     })));
                                 let pkg_id_for_click = pkg.application_id.clone();
                                 let pkg_remote_for_click = pkg.remote.clone();
+                                let catalog_app = self.catalog.as_ref().and_then(|c| c.find_flatpak(&pkg.application_id));
+                                let app_icon: Element<Message> = match catalog_app {
+                                    Some(app) => appstream::icon(Some(app), 32.0),
+                                    None => Space::with_width(Length::Shrink).into(),
+                                };
+                                let mut name_column = column![text(&pkg_name).size(package_name_size)].spacing(2);
+                                if catalog_app.is_some_and(|app| app.verified) {
+                                    name_column = name_column.push(
+                                        text("✓ Verified")
+                                            .size(package_detail_size * 0.85)
+                                            .style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
+                                    );
+                                }
                                 button(
                                     container(
                                         row![
                                             checkbox_widget,
-                                            text(&pkg_name).size(package_name_size).width(Length::FillPortion(2)),
+                                            app_icon,
+                                            name_column.width(Length::FillPortion(2)),
                                             text(&pkg.version).size(package_detail_size).width(Length::FillPortion(1)),
                                             text(&pkg.description).size(package_detail_size).width(Length::FillPortion(4)),
                                         ]
@@ -590,7 +1148,22 @@ impl FlatpakTab {
                 )
                 .into()
             };
-            column![install_button, package_list].spacing(10).into()
+            let mut target_row = row![install_button, Space::with_width(Length::Fill), text("Install for:").size(body_font_size)]
+                .spacing(8)
+                .align_items(Alignment::Center);
+            // The user installation needs no administrator rights
+            for (installation, label) in [(Installation::System, "All users"), (Installation::User, "Only me")] {
+                target_row = target_row.push(
+                    button(text(label).size(button_font_size))
+                        .on_press(Message::InstallTargetSelected(installation))
+                        .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                            is_primary: self.install_target == installation,
+                            radius: settings.border_radius,
+                        })))
+                        .padding(Padding::from([10.0, 14.0, 10.0, 14.0]))
+                );
+            }
+            column![target_row, package_list].spacing(10).into()
         };
 
         // Create the slide-out panel
@@ -648,6 +1221,7 @@ impl FlatpakTab {
                         .width(Length::Fill)
                         .align_items(Alignment::Center),
                         Space::with_height(Length::Fixed(20.0)),
+                        self.view_catalog_details(theme, settings),
                         // Package name
                         text(&details.name)
                             .size(title_font_size * 1.1)
@@ -760,9 +1334,15 @@ impl FlatpakTab {
                         .padding(Padding::new(8.0))
                     ]
                     .width(Length::Fill),
-                    Space::with_height(Length::Fill),
-                    text("Loading...").size(body_font_size).horizontal_alignment(iced::alignment::Horizontal::Center),
-                    Space::with_height(Length::Fill),
+                    scrollable(
+                        column![
+                            self.view_catalog_details(theme, settings),
+                            text("Loading...").size(body_font_size).horizontal_alignment(iced::alignment::Horizontal::Center),
+                        ]
+                        .width(Length::Fill)
+                        .align_items(Alignment::Center),
+                    )
+                    .height(Length::Fill),
                 ]
                 .padding(Padding::new(20.0))
             )
@@ -775,7 +1355,136 @@ impl FlatpakTab {
         }
     }
 
-    fn view_installed(&self, _theme: &crate::gui::Theme, settings: &crate::gui::settings::AppSettings, material_font: iced::Font) -> Element<'_, Message> {
+    // AppStream details of the selected app; empty when it is not in the cached catalog
+    fn view_catalog_details(&self, theme: &crate::gui::Theme, settings: &crate::gui::settings::AppSettings) -> Element<'_, Message> {
+        let body_font_size = settings.font_size_body * settings.scale_body;
+        let app = self
+            .selected_package
+            .as_ref()
+            .and_then(|app_id| self.catalog.as_ref()?.find_flatpak(app_id));
+        match app {
+            Some(app) => container(appstream::details(
+                app,
+                self.screenshot.as_deref(),
+                body_font_size,
+                theme.primary_with_settings(Some(settings)),
+                iced::Color::from_rgba(0.5, 0.5, 0.5, 1.0),
+            ))
+            .width(Length::Fill)
+            .padding(Padding::from([0.0, 0.0, 20.0, 0.0]))
+            .into(),
+            None => Space::with_height(Length::Shrink).into(),
+        }
+    }
+
+    // Shown while the search box is empty: apps from the remotes' cached AppStream data
+    fn view_catalog(&self, theme: &crate::gui::Theme, settings: &crate::gui::settings::AppSettings) -> Element<'_, Message> {
+        let body_font_size = settings.font_size_body * settings.scale_body;
+        let package_name_size = settings.font_size_package_names * settings.scale_package_cards;
+        let package_detail_size = settings.font_size_package_details * settings.scale_package_cards;
+        let muted = iced::Color::from_rgba(0.5, 0.5, 0.5, 1.0);
+
+        let message = |label: &str| -> Element<'_, Message> {
+            container(text(label.to_string()).size(body_font_size))
+                .width(Length::Fill)
+                .padding(20)
+                .style(iced::theme::Container::Custom(Box::new(RoundedMessageStyle {
+                    radius: settings.border_radius,
+                })))
+                .into()
+        };
+        let Some(catalog) = self.catalog.as_ref() else {
+            return message("Enter a search query to find Flatpak applications");
+        };
+        let apps = catalog.browse(true, self.browse_category.as_deref());
+        if apps.is_empty() && self.browse_category.is_none() {
+            return message("Enter a search query to find Flatpak applications (no AppStream data is cached yet; it is downloaded with the next update check)");
+        }
+
+        let mut categories = vec![ALL_CATEGORIES.to_string()];
+        categories.extend(catalog.categories(true).iter().map(|c| appstream::category_label(c).to_string()));
+        let selected_category = self
+            .browse_category
+            .as_deref()
+            .map(appstream::category_label)
+            .unwrap_or(ALL_CATEGORIES)
+            .to_string();
+        let count = if apps.len() > BROWSE_LIMIT {
+            format!("Showing {} of {} applications", BROWSE_LIMIT, apps.len())
+        } else {
+            format!("{} applications", apps.len())
+        };
+        let toolbar = row![
+            pick_list(categories, Some(selected_category), Message::SelectCategory).text_size(body_font_size),
+            Space::with_width(Length::Fill),
+            text(count).size(package_detail_size).style(iced::theme::Text::Color(muted)),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center);
+
+        let rows: Vec<Element<Message>> = apps
+            .into_iter()
+            .take(BROWSE_LIMIT)
+            .map(|app| {
+                let app_id = app.id.clone();
+                let is_selected = self.selected_packages.contains(&app_id);
+                let mut name_column = column![
+                    text(&app.name)
+                        .size(package_name_size)
+                        .style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
+                    text(&app.summary).size(package_detail_size),
+                ]
+                .spacing(2)
+                .width(Length::Fill);
+                if !app.developer.is_empty() {
+                    name_column = name_column.push(text(&app.developer).size(package_detail_size * 0.85).style(iced::theme::Text::Color(muted)));
+                }
+                let mut badges = column![].spacing(2).align_items(Alignment::End);
+                if app.verified {
+                    badges = badges.push(
+                        text("✓ Verified")
+                            .size(package_detail_size * 0.85)
+                            .style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
+                    );
+                }
+                badges = badges.push(text(app.remote().unwrap_or_default()).size(package_detail_size * 0.85).style(iced::theme::Text::Color(muted)));
+                button(
+                    container(
+                        row![
+                            checkbox("", is_selected)
+                                .on_toggle(move |_| Message::TogglePackage(app_id.clone()))
+                                .style(iced::theme::Checkbox::Custom(Box::new(RoundedCheckboxStyle {
+                                    radius: settings.border_radius,
+                                }))),
+                            appstream::icon(Some(app), 40.0),
+                            name_column,
+                            badges,
+                        ]
+                        .spacing(12)
+                        .align_items(Alignment::Center)
+                        .padding(12)
+                    )
+                    .style(iced::theme::Container::Custom(Box::new(PackageItemStyle {
+                        is_selected,
+                        radius: settings.border_radius,
+                    })))
+                )
+                .on_press(Message::PackageSelected(app.id.clone(), app.remote().map(str::to_string)))
+                .style(iced::theme::Button::Text)
+                .padding(0)
+                .into()
+            })
+            .collect();
+
+        column![
+            toolbar,
+            scrollable(column(rows).spacing(6).padding(10)).height(Length::Fill),
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    fn view_installed(&self, theme: &crate::gui::Theme, settings: &crate::gui::settings::AppSettings, material_font: iced::Font) -> Element<'_, Message> {
         let body_font_size = settings.font_size_body * settings.scale_body;
         let button_font_size = settings.font_size_buttons * settings.scale_buttons;
         let icon_size = (settings.font_size_icons * settings.scale_icons).round();
@@ -856,28 +1565,42 @@ impl FlatpakTab {
                     self.installed_flatpaks
                         .iter()
                         .map(|pkg| {
-                            let pkg_id = pkg.application_id.clone();
-                            let is_selected = self.selected_packages.contains(&pkg_id);
+                            let pkg_key = pkg.key();
+                            let is_selected = self.selected_packages.contains(&pkg_key);
                             let checkbox_widget = checkbox("", is_selected)
-                                .on_toggle(move |_| Message::TogglePackage(pkg_id.clone()))
+                                .on_toggle(move |_| Message::TogglePackage(pkg_key.clone()))
                                 .style(iced::theme::Checkbox::Custom(Box::new(RoundedCheckboxStyle {
                                     radius: settings.border_radius,
                                 })));
-                            container(
-                                row![
-                                    checkbox_widget,
-                                    text(&pkg.name).size(package_name_size).width(Length::FillPortion(3)),
-                                    text(&pkg.version).size(package_detail_size).width(Length::FillPortion(2)),
-                                    text(pkg.remote.as_deref().unwrap_or("local")).size(package_detail_size).width(Length::FillPortion(2)),
-                                ]
-                                .spacing(12)
-                                .align_items(Alignment::Center)
-                                .padding(12)
+                            let is_open = self.permissions_app.as_deref() == Some(pkg.application_id.as_str())
+                                && self.permissions_installation == pkg.installation;
+                            button(
+                                container(
+                                    row![
+                                        checkbox_widget,
+                                        text(&pkg.name).size(package_name_size).width(Length::FillPortion(3)),
+                                        text(&pkg.version).size(package_detail_size).width(Length::FillPortion(2)),
+                                        text(pkg.remote.as_deref().unwrap_or("local")).size(package_detail_size).width(Length::FillPortion(2)),
+                                        text(match pkg.installation {
+                                            Some(installation) if flatpak_history::is_masked(&self.masks, installation, &pkg.application_id, "") => format!("{} (pinned)", installation.label()),
+                                            Some(installation) => installation.label().to_string(),
+                                            None => String::new(),
+                                        })
+                                        .size(package_detail_size)
+                                        .width(Length::FillPortion(1)),
+                                    ]
+                                    .spacing(12)
+                                    .align_items(Alignment::Center)
+                                    .padding(12)
+                                )
+                                .style(iced::theme::Container::Custom(Box::new(PackageItemStyle {
+                                    is_selected: is_selected || is_open,
+                                    radius: settings.border_radius,
+                                })))
                             )
-                            .style(iced::theme::Container::Custom(Box::new(PackageItemStyle {
-                                is_selected,
-                                radius: settings.border_radius,
-                            })))
+                            .on_press(Message::ShowPermissions(pkg.application_id.clone(), pkg.installation))
+                            .style(iced::theme::Button::Text)
+                            .padding(0)
                             .into()
                         })
                         .collect::<Vec<_>>(),
@@ -888,26 +1611,673 @@ impl FlatpakTab {
             .into()
         };
 
-        column![header, content].spacing(10).into()
+        if self.permissions_app.is_none() {
+            return column![header, content].spacing(10).into();
+        }
+        row![
+            column![header, content]
+                .spacing(10)
+                .width(Length::FillPortion(2)),
+            container(self.view_permissions_panel(theme, settings, material_font))
+                .width(Length::FillPortion(1))
+                .height(Length::Fill),
+        ]
+        .spacing(15)
+        .into()
     }
 
-    fn view_updates(&self, _theme: &crate::gui::Theme, settings: &crate::gui::settings::AppSettings, material_font: iced::Font) -> Element<'_, Message> {
-        let body_font_size = settings.font_size_body * settings.scale_body;
+    fn view_app_panel_header(&self, settings: &crate::gui::settings::AppSettings, material_font: iced::Font) -> Element<'_, Message> {
         let button_font_size = settings.font_size_buttons * settings.scale_buttons;
         let icon_size = (settings.font_size_icons * settings.scale_icons).round();
-        let package_name_size = settings.font_size_package_names * settings.scale_package_cards;
-        let package_detail_size = settings.font_size_package_details * settings.scale_package_cards;
 
-        let check_button = if self.is_checking_updates {
-            button(
-                row![
-                    text(crate::gui::fonts::glyphs::REFRESH_SYMBOL).font(material_font).size(icon_size),
-                    text(" Checking...").size(button_font_size)
-                ]
-                .spacing(4)
-                .align_items(Alignment::Center)
+        let mut header = row![].spacing(6).width(Length::Fill).align_items(Alignment::Center);
+        for (panel, label) in [(AppPanel::Permissions, "Permissions"), (AppPanel::History, "History")] {
+            header = header.push(
+                button(text(label).size(button_font_size))
+                    .on_press(Message::SelectAppPanel(panel))
+                    .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                        is_primary: self.app_panel == panel,
+                        radius: settings.border_radius,
+                    })))
+                    .padding(Padding::from([8.0, 14.0, 8.0, 14.0]))
+            );
+        }
+        header
+            .push(Space::with_width(Length::Fill))
+            .push(
+                button(
+                    text(crate::gui::fonts::glyphs::CLOSE_SYMBOL).font(material_font).size(icon_size)
+                )
+                .on_press(Message::ClosePermissions)
+                .style(iced::theme::Button::Custom(Box::new(CloseButtonStyle)))
+                .padding(Padding::new(6.0))
             )
-            .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+            .into()
+    }
+
+    fn view_history_panel(&self, theme: &crate::gui::Theme, settings: &crate::gui::settings::AppSettings, material_font: iced::Font) -> Element<'_, Message> {
+        let body_font_size = settings.font_size_body * settings.scale_body;
+        let button_font_size = settings.font_size_buttons * settings.scale_buttons;
+        let package_detail_size = settings.font_size_package_details * settings.scale_package_cards;
+        let primary = theme.primary_with_settings(Some(settings));
+        let muted = iced::Color::from_rgba(0.5, 0.5, 0.5, 1.0);
+        let busy = self.is_changing_version;
+
+        let mut content = column![self.view_app_panel_header(settings, material_font)].spacing(6);
+
+        let history = match self.app_history {
+            Some(Ok(ref history)) => history,
+            ref other => {
+                let message = match other {
+                    Some(Err(e)) => e.clone(),
+                    _ => "Loading history...".to_string(),
+                };
+                return container(
+                    content.push(Space::with_height(Length::Fixed(20.0))).push(text(message).size(body_font_size))
+                        .padding(Padding::new(20.0))
+                )
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .style(iced::theme::Container::Custom(Box::new(PanelStyle {
+                    radius: settings.border_radius,
+                })))
+                .into();
+            }
+        };
+
+        // Only exact patterns can be removed again from here; wider ones are managed with `flatpak mask`
+        let pinned_by = self.masks
+            .iter()
+            .find(|m| m.installation == history.installation && m.matches(&history.app_id, &history.flatpak_ref));
+        let (pin_label, pin_message) = match pinned_by {
+            Some(mask) if mask.pattern == history.app_id => ("Unpin", Some(Message::TogglePin)),
+            Some(_) => ("Unpin", None),
+            None => ("Pin", Some(Message::TogglePin)),
+        };
+        content = content
+            .push(text(&history.flatpak_ref).size(package_detail_size).style(iced::theme::Text::Color(muted)))
+            .push(Space::with_height(Length::Fixed(10.0)))
+            .push(
+                row![
+                    text(match pinned_by {
+                        Some(mask) if mask.pattern != history.app_id => format!("Pinned by the mask \"{}\"", mask.pattern),
+                        Some(_) => "Pinned: updates skip this app".to_string(),
+                        None => "Receives updates".to_string(),
+                    })
+                    .size(body_font_size),
+                    Space::with_width(Length::Fill),
+                    button(text(pin_label).size(button_font_size * 0.9))
+                        .on_press_maybe(if busy { None } else { pin_message })
+                        .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                            is_primary: pinned_by.is_none(),
+                            radius: settings.border_radius,
+                        })))
+                        .padding(Padding::from([8.0, 12.0, 8.0, 12.0])),
+                ]
+                .align_items(Alignment::Center)
+            )
+            .push(
+                text("Installing an older commit is undone by the next update unless the app is pinned")
+                    .size(package_detail_size * 0.9)
+                    .style(iced::theme::Text::Color(muted))
+            );
+
+        if let Some(ref status) = self.override_status {
+            let (message, color) = match status {
+                Ok(message) => (message.clone(), primary),
+                Err(e) => (e.clone(), iced::Color::from_rgb(1.0, 0.3, 0.3)),
+            };
+            content = content.push(text(message).size(package_detail_size).style(iced::theme::Text::Color(color)));
+        }
+
+        content = content.push(Space::with_height(Length::Fixed(8.0)));
+        if history.commits.is_empty() {
+            content = content.push(text("The remote keeps no history for this app").size(body_font_size).style(iced::theme::Text::Color(muted)));
+        }
+        for (index, entry) in history.commits.iter().enumerate() {
+            let installed = history.installed_commit == entry.commit;
+            let action: Element<Message> = if installed {
+                text("Installed").size(package_detail_size).style(iced::theme::Text::Color(primary)).into()
+            } else {
+                button(text(if index == 0 { "Update" } else { "Install" }).size(button_font_size * 0.85))
+                    .on_press_maybe(if busy { None } else { Some(Message::DeployCommit(entry.commit.clone())) })
+                    .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                        is_primary: false,
+                        radius: settings.border_radius,
+                    })))
+                    .padding(Padding::from([6.0, 12.0, 6.0, 12.0]))
+                    .into()
+            };
+            content = content.push(
+                container(
+                    row![
+                        column![
+                            row![
+                                text(entry.short()).size(package_detail_size).font(iced::Font::MONOSPACE),
+                                text(if index == 0 { "latest" } else { "" }).size(package_detail_size * 0.85).style(iced::theme::Text::Color(muted)),
+                            ]
+                            .spacing(8),
+                            text(&entry.date).size(package_detail_size * 0.85).style(iced::theme::Text::Color(muted)),
+                            text(&entry.subject).size(package_detail_size),
+                        ]
+                        .spacing(2)
+                        .width(Length::Fill),
+                        action,
+                    ]
+                    .spacing(8)
+                    .align_items(Alignment::Center)
+                )
+                .width(Length::Fill)
+                .padding(Padding::new(12.0))
+                .style(iced::theme::Container::Custom(Box::new(InfoContainerStyle {
+                    radius: settings.border_radius,
+                })))
+            );
+        }
+
+        container(scrollable(content.padding(Padding::new(20.0))).height(Length::Fill))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .style(iced::theme::Container::Custom(Box::new(PanelStyle {
+                radius: settings.border_radius,
+            })))
+            .into()
+    }
+
+    fn view_permissions_panel(&self, theme: &crate::gui::Theme, settings: &crate::gui::settings::AppSettings, material_font: iced::Font) -> Element<'_, Message> {
+        let title_font_size = settings.font_size_titles * settings.scale_titles;
+        let body_font_size = settings.font_size_body * settings.scale_body;
+        let button_font_size = settings.font_size_buttons * settings.scale_buttons;
+        let input_font_size = settings.font_size_inputs * settings.scale_inputs;
+        let package_detail_size = settings.font_size_package_details * settings.scale_package_cards;
+        let primary = theme.primary_with_settings(Some(settings));
+        let muted = iced::Color::from_rgba(0.5, 0.5, 0.5, 1.0);
+
+        if self.app_panel == AppPanel::History {
+            return self.view_history_panel(theme, settings, material_font);
+        }
+        let header = self.view_app_panel_header(settings, material_font);
+
+        let permissions = match self.app_permissions {
+            Some(Ok(ref permissions)) => permissions,
+            ref other => {
+                let message = match other {
+                    Some(Err(e)) => e.clone(),
+                    _ => "Loading...".to_string(),
+                };
+                return container(
+                    column![header, Space::with_height(Length::Fixed(20.0)), text(message).size(body_font_size)]
+                        .padding(Padding::new(20.0))
+                )
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .style(iced::theme::Container::Custom(Box::new(PanelStyle {
+                    radius: settings.border_radius,
+                })))
+                .into();
+            }
+        };
+
+        let busy = self.is_applying_override;
+        let scope = self.override_scope;
+        let mut scope_row = row![].spacing(8).align_items(Alignment::Center);
+        for option in [OverrideScope::User, OverrideScope::System] {
+            scope_row = scope_row.push(
+                button(text(format!("{} overrides", option.label())).size(button_font_size * 0.9))
+                    .on_press(Message::SelectOverrideScope(option))
+                    .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                        is_primary: scope == option,
+                        radius: settings.border_radius,
+                    })))
+                    .padding(Padding::from([8.0, 12.0, 8.0, 12.0]))
+            );
+        }
+        scope_row = scope_row.push(Space::with_width(Length::Fill)).push(
+            button(text("Reset").size(button_font_size * 0.9))
+                .on_press_maybe(if busy || permissions.overrides(scope).is_empty() { None } else { Some(Message::ResetOverrides) })
+                .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                    is_primary: false,
+                    radius: settings.border_radius,
+                })))
+                .padding(Padding::from([8.0, 12.0, 8.0, 12.0]))
+        );
+
+        let installation_row: Element<Message> = match self.permissions_installation {
+            Some(installation) => row![
+                text(format!("Installed for {}", match installation {
+                    Installation::User => "you only (user)",
+                    Installation::System => "all users (system)",
+                }))
+                .size(package_detail_size)
+                .style(iced::theme::Text::Color(muted)),
+                Space::with_width(Length::Fill),
+                button(text(if self.is_moving { "Moving...".to_string() } else { format!("Move to {}", installation.other().label()) }).size(button_font_size * 0.9))
+                    .on_press_maybe(if busy || self.is_moving { None } else { Some(Message::MoveInstallation) })
+                    .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                        is_primary: false,
+                        radius: settings.border_radius,
+                    })))
+                    .padding(Padding::from([8.0, 12.0, 8.0, 12.0])),
+            ]
+            .spacing(8)
+            .align_items(Alignment::Center)
+            .into(),
+            None => Space::with_height(Length::Shrink).into(),
+        };
+
+        let mut content = column![
+            header,
+            text(&permissions.app_id).size(package_detail_size).style(iced::theme::Text::Color(muted)),
+            installation_row,
+            Space::with_height(Length::Fixed(10.0)),
+            scope_row,
+            text(match scope {
+                OverrideScope::User => "Changes apply to your account only",
+                OverrideScope::System => "Changes apply to every user and ask for administrator rights",
+            })
+            .size(package_detail_size * 0.9)
+            .style(iced::theme::Text::Color(muted)),
+        ]
+        .spacing(6);
+
+        if let Some(ref status) = self.override_status {
+            let (message, color) = match status {
+                Ok(message) => (message.clone(), iced::Color::from_rgb(0.1, 0.7, 0.1)),
+                Err(e) => (e.clone(), iced::Color::from_rgb(1.0, 0.3, 0.3)),
+            };
+            content = content.push(text(message).size(package_detail_size * 0.9).style(iced::theme::Text::Color(color)));
+        }
+
+        for section in PermissionSection::ALL {
+            let entries = permissions.effective(section);
+            let mut items = column![
+                text(section.label()).size(title_font_size * 0.8).style(iced::theme::Text::Color(primary))
+            ]
+            .spacing(4);
+            if entries.is_empty() {
+                items = items.push(text("None").size(package_detail_size).style(iced::theme::Text::Color(muted)));
+            }
+            for entry in entries {
+                let label = match section {
+                    PermissionSection::Environment if !entry.detail.is_empty() => format!("{}={}", entry.value, entry.detail),
+                    PermissionSection::SessionBus | PermissionSection::SystemBus if entry.granted => format!("{} ({})", entry.value, entry.detail),
+                    _ => entry.value.clone(),
+                };
+                // Granting again keeps the bus policy or variable value the entry had
+                let grant_value = match section {
+                    PermissionSection::SessionBus | PermissionSection::SystemBus if entry.detail == "own" => format!("{}=own", entry.value),
+                    PermissionSection::Environment => format!("{}={}", entry.value, entry.detail),
+                    _ => entry.value.clone(),
+                };
+                // An unset variable has no value left to grant again
+                let unset_variable = section == PermissionSection::Environment && !entry.granted && entry.detail.is_empty();
+                let can_toggle = !busy && !unset_variable;
+                let revoke_value = entry.value.clone();
+                items = items.push(
+                    row![
+                        checkbox(label, entry.granted)
+                            .on_toggle_maybe(if can_toggle {
+                                Some(move |grant: bool| Message::SetPermission(
+                                    section,
+                                    if grant { grant_value.clone() } else { revoke_value.clone() },
+                                    grant,
+                                ))
+                            } else {
+                                None
+                            })
+                            .text_size(package_detail_size)
+                            .style(iced::theme::Checkbox::Custom(Box::new(RoundedCheckboxStyle {
+                                radius: settings.border_radius,
+                            })))
+                            .width(Length::Fill),
+                        text(entry.source).size(package_detail_size * 0.85).style(iced::theme::Text::Color(muted)),
+                    ]
+                    .spacing(8)
+                    .align_items(Alignment::Center)
+                );
+            }
+
+            let input = match section {
+                PermissionSection::Filesystems => Some((&self.filesystem_input, "~/Games:ro, xdg-download, /mnt/data")),
+                PermissionSection::SessionBus => Some((&self.bus_input, "org.freedesktop.Notifications")),
+                PermissionSection::Environment => Some((&self.env_input, "VARIABLE=value")),
+                _ => None,
+            };
+            if let Some((value, placeholder)) = input {
+                let can_add = !busy && !value.trim().is_empty()
+                    && (section != PermissionSection::Environment || value.contains('='));
+                let mut add_row = row![
+                    text_input(placeholder, value)
+                        .on_input(move |v| Message::PermissionInputChanged(section, v))
+                        .on_submit(Message::SetPermission(section, value.clone(), true))
+                        .size(input_font_size * 0.85)
+                        .padding(8)
+                        .width(Length::Fill)
+                        .style(iced::theme::TextInput::Custom(Box::new(RoundedTextInputStyle {
+                            radius: settings.border_radius,
+                        }))),
+                    button(text(if section == PermissionSection::SessionBus { "Talk" } else { "Add" }).size(button_font_size * 0.85))
+                        .on_press_maybe(if can_add { Some(Message::SetPermission(section, value.clone(), true)) } else { None })
+                        .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                            is_primary: false,
+                            radius: settings.border_radius,
+                        })))
+                        .padding(Padding::from([8.0, 12.0, 8.0, 12.0])),
+                ]
+                .spacing(6)
+                .align_items(Alignment::Center);
+                // The bus name input doubles for own and system bus grants
+                if section == PermissionSection::SessionBus {
+                    add_row = add_row
+                        .push(
+                            button(text("Own").size(button_font_size * 0.85))
+                                .on_press_maybe(if can_add { Some(Message::SetPermission(section, format!("{}=own", value.trim()), true)) } else { None })
+                                .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                                    is_primary: false,
+                                    radius: settings.border_radius,
+                                })))
+                                .padding(Padding::from([8.0, 12.0, 8.0, 12.0]))
+                        )
+                        .push(
+                            button(text("System").size(button_font_size * 0.85))
+                                .on_press_maybe(if can_add { Some(Message::SetPermission(PermissionSection::SystemBus, value.clone(), true)) } else { None })
+                                .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                                    is_primary: false,
+                                    radius: settings.border_radius,
+                                })))
+                                .padding(Padding::from([8.0, 12.0, 8.0, 12.0]))
+                        );
+                }
+                items = items.push(add_row);
+            }
+
+            content = content.push(Space::with_height(Length::Fixed(8.0))).push(
+                container(items)
+                    .width(Length::Fill)
+                    .padding(Padding::new(14.0))
+                    .style(iced::theme::Container::Custom(Box::new(InfoContainerStyle {
+                        radius: settings.border_radius,
+                    })))
+            );
+        }
+
+        container(scrollable(content.padding(Padding::new(20.0))).height(Length::Fill))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .style(iced::theme::Container::Custom(Box::new(PanelStyle {
+                radius: settings.border_radius,
+            })))
+            .into()
+    }
+
+    fn view_remotes(&self, theme: &crate::gui::Theme, settings: &crate::gui::settings::AppSettings, material_font: iced::Font) -> Element<'_, Message> {
+        let title_font_size = settings.font_size_titles * settings.scale_titles;
+        let body_font_size = settings.font_size_body * settings.scale_body;
+        let button_font_size = settings.font_size_buttons * settings.scale_buttons;
+        let input_font_size = settings.font_size_inputs * settings.scale_inputs;
+        let icon_size = (settings.font_size_icons * settings.scale_icons).round();
+        let package_name_size = settings.font_size_package_names * settings.scale_package_cards;
+        let package_detail_size = settings.font_size_package_details * settings.scale_package_cards;
+        let primary = theme.primary_with_settings(Some(settings));
+        let muted = iced::Color::from_rgba(0.5, 0.5, 0.5, 1.0);
+        let busy = self.is_changing_remote;
+
+        let refresh_button = button(
+            row![
+                text(crate::gui::fonts::glyphs::REFRESH_SYMBOL).font(material_font).size(icon_size),
+                text(" Refresh").size(button_font_size)
+            ]
+            .spacing(4)
+            .align_items(Alignment::Center)
+        )
+        .on_press_maybe(if self.is_loading_remotes || busy { None } else { Some(Message::LoadRemotes) })
+        .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+            is_primary: false,
+            radius: settings.border_radius,
+        })))
+        .padding(Padding::new(14.0));
+
+        let mut status_row = row![refresh_button].spacing(12).align_items(Alignment::Center);
+        if let Some(ref status) = self.remote_status {
+            let (message, color) = match status {
+                Ok(message) => (message.clone(), iced::Color::from_rgb(0.1, 0.7, 0.1)),
+                Err(e) => (e.clone(), iced::Color::from_rgb(1.0, 0.3, 0.3)),
+            };
+            status_row = status_row.push(text(message).size(package_detail_size).style(iced::theme::Text::Color(color)));
+        }
+
+        let mut content = column![status_row].spacing(10);
+
+        for warning in &self.remotes.warnings {
+            content = content.push(
+                container(
+                    text(warning)
+                        .size(package_detail_size)
+                        .style(iced::theme::Text::Color(iced::Color::from_rgb(0.9, 0.6, 0.1)))
+                )
+                .width(Length::Fill)
+                .padding(12)
+                .style(iced::theme::Container::Custom(Box::new(RoundedMessageStyle {
+                    radius: settings.border_radius,
+                })))
+            );
+        }
+
+        if self.is_loading_remotes {
+            content = content.push(
+                container(text("Loading remotes...").size(body_font_size))
+                    .width(Length::Fill)
+                    .padding(20)
+                    .style(iced::theme::Container::Custom(Box::new(RoundedMessageStyle {
+                        radius: settings.border_radius,
+                    })))
+            );
+        } else if self.remotes.remotes.is_empty() {
+            content = content.push(
+                container(text("No remotes configured. Add Flathub below to get started.").size(body_font_size))
+                    .width(Length::Fill)
+                    .padding(20)
+                    .style(iced::theme::Container::Custom(Box::new(RoundedMessageStyle {
+                        radius: settings.border_radius,
+                    })))
+            );
+        }
+
+        for remote in &self.remotes.remotes {
+            let key = remote.key();
+            let is_selected = self.selected_remote.as_deref() == Some(key.as_str());
+            let mut details = column![
+                row![
+                    text(&remote.title).size(package_name_size).width(Length::FillPortion(3)),
+                    text(&remote.name).size(package_detail_size).width(Length::FillPortion(2)),
+                    text(remote.installation.label()).size(package_detail_size).width(Length::FillPortion(1)),
+                    text(format!("priority {}", remote.priority)).size(package_detail_size).width(Length::FillPortion(1)),
+                    text(if remote.disabled { "disabled" } else { "enabled" })
+                        .size(package_detail_size)
+                        .width(Length::FillPortion(1))
+                        .style(iced::theme::Text::Color(if remote.disabled { muted } else { iced::Color::from_rgb(0.1, 0.7, 0.1) })),
+                ]
+                .spacing(12)
+                .align_items(Alignment::Center),
+                text(&remote.url).size(package_detail_size * 0.9).style(iced::theme::Text::Color(muted)),
+            ]
+            .spacing(4);
+
+            if is_selected {
+                let can_save = !busy
+                    && self.remote_priority_input.trim().parse::<i32>().is_ok()
+                    && (self.remote_priority_input.trim() != remote.priority.to_string() || self.remote_filter_input.trim() != remote.filter);
+                details = details.push(Space::with_height(Length::Fixed(6.0))).push(
+                    row![
+                        text("Priority").size(package_detail_size),
+                        text_input("1", &self.remote_priority_input)
+                            .on_input(Message::RemotePriorityChanged)
+                            .size(input_font_size * 0.85)
+                            .padding(8)
+                            .width(Length::Fixed(80.0))
+                            .style(iced::theme::TextInput::Custom(Box::new(RoundedTextInputStyle {
+                                radius: settings.border_radius,
+                            }))),
+                        text("Filter").size(package_detail_size),
+                        text_input("Filter file (empty for none)", &self.remote_filter_input)
+                            .on_input(Message::RemoteFilterChanged)
+                            .size(input_font_size * 0.85)
+                            .padding(8)
+                            .width(Length::Fill)
+                            .style(iced::theme::TextInput::Custom(Box::new(RoundedTextInputStyle {
+                                radius: settings.border_radius,
+                            }))),
+                        button(text("Save").size(button_font_size * 0.9))
+                            .on_press_maybe(if can_save { Some(Message::SaveRemoteSettings) } else { None })
+                            .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                                is_primary: true,
+                                radius: settings.border_radius,
+                            })))
+                            .padding(Padding::from([8.0, 12.0, 8.0, 12.0])),
+                        button(text(if remote.disabled { "Enable" } else { "Disable" }).size(button_font_size * 0.9))
+                            .on_press_maybe(if busy { None } else { Some(Message::ToggleRemoteEnabled) })
+                            .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                                is_primary: false,
+                                radius: settings.border_radius,
+                            })))
+                            .padding(Padding::from([8.0, 12.0, 8.0, 12.0])),
+                        button(text(if self.confirm_remove_remote { "Confirm removal" } else { "Remove" }).size(button_font_size * 0.9))
+                            .on_press_maybe(if busy { None } else { Some(Message::RemoveRemote) })
+                            .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                                is_primary: self.confirm_remove_remote,
+                                radius: settings.border_radius,
+                            })))
+                            .padding(Padding::from([8.0, 12.0, 8.0, 12.0])),
+                    ]
+                    .spacing(8)
+                    .align_items(Alignment::Center)
+                );
+                if self.confirm_remove_remote {
+                    details = details.push(
+                        text("Apps installed from this remote stay installed but stop receiving updates.")
+                            .size(package_detail_size * 0.9)
+                            .style(iced::theme::Text::Color(iced::Color::from_rgb(0.9, 0.6, 0.1)))
+                    );
+                }
+            }
+
+            content = content.push(
+                button(
+                    container(details.padding(12))
+                        .width(Length::Fill)
+                        .style(iced::theme::Container::Custom(Box::new(PackageItemStyle {
+                            is_selected,
+                            radius: settings.border_radius,
+                        })))
+                )
+                .on_press(Message::SelectRemote(key))
+                .style(iced::theme::Button::Text)
+                .padding(0)
+            );
+        }
+
+        // Adding remotes
+        let target = self.remote_installation;
+        let mut target_row = row![text("Add to").size(package_detail_size)].spacing(8).align_items(Alignment::Center);
+        for option in [Installation::System, Installation::User] {
+            target_row = target_row.push(
+                button(text(option.label()).size(button_font_size * 0.9))
+                    .on_press(Message::RemoteInstallationSelected(option))
+                    .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                        is_primary: target == option,
+                        radius: settings.border_radius,
+                    })))
+                    .padding(Padding::from([8.0, 12.0, 8.0, 12.0]))
+            );
+        }
+
+        let mut presets = row![].spacing(8).align_items(Alignment::Center);
+        for (index, preset) in flatpak_remotes::PRESETS.iter().enumerate() {
+            let exists = self.remotes.remotes.iter().any(|r| r.installation == target && r.name == preset.name);
+            presets = presets.push(
+                button(
+                    row![
+                        text(crate::gui::fonts::glyphs::ADD_SYMBOL).font(material_font).size(icon_size * 0.9),
+                        text(format!(" {}", preset.title)).size(button_font_size * 0.9)
+                    ]
+                    .align_items(Alignment::Center)
+                )
+                .on_press_maybe(if busy || exists { None } else { Some(Message::AddRemotePreset(index)) })
+                .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                    is_primary: false,
+                    radius: settings.border_radius,
+                })))
+                .padding(Padding::from([8.0, 12.0, 8.0, 12.0]))
+            );
+        }
+
+        let can_add_custom = !busy && !self.custom_remote_name.trim().is_empty() && !self.custom_remote_location.trim().is_empty();
+        let custom = row![
+            text_input("Name", &self.custom_remote_name)
+                .on_input(Message::CustomRemoteNameChanged)
+                .size(input_font_size * 0.9)
+                .padding(8)
+                .width(Length::FillPortion(1))
+                .style(iced::theme::TextInput::Custom(Box::new(RoundedTextInputStyle {
+                    radius: settings.border_radius,
+                }))),
+            text_input(".flatpakrepo URL or file", &self.custom_remote_location)
+                .on_input(Message::CustomRemoteLocationChanged)
+                .on_submit(Message::AddCustomRemote)
+                .size(input_font_size * 0.9)
+                .padding(8)
+                .width(Length::FillPortion(3))
+                .style(iced::theme::TextInput::Custom(Box::new(RoundedTextInputStyle {
+                    radius: settings.border_radius,
+                }))),
+            button(text("Add").size(button_font_size * 0.9))
+                .on_press_maybe(if can_add_custom { Some(Message::AddCustomRemote) } else { None })
+                .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                    is_primary: true,
+                    radius: settings.border_radius,
+                })))
+                .padding(Padding::from([8.0, 12.0, 8.0, 12.0])),
+        ]
+        .spacing(8)
+        .align_items(Alignment::Center);
+
+        content = content.push(Space::with_height(Length::Fixed(10.0))).push(
+            container(
+                column![
+                    text("Add a remote").size(title_font_size * 0.85).style(iced::theme::Text::Color(primary)),
+                    target_row,
+                    presets,
+                    custom,
+                ]
+                .spacing(10)
+            )
+            .width(Length::Fill)
+            .padding(Padding::new(16.0))
+            .style(iced::theme::Container::Custom(Box::new(InfoContainerStyle {
+                radius: settings.border_radius,
+            })))
+        );
+
+        scrollable(content.padding(Padding::from([0.0, 10.0, 10.0, 0.0]))).height(Length::Fill).into()
+    }
+
+    fn view_updates(&self, _theme: &crate::gui::Theme, settings: &crate::gui::settings::AppSettings, material_font: iced::Font) -> Element<'_, Message> {
+        let body_font_size = settings.font_size_body * settings.scale_body;
+        let button_font_size = settings.font_size_buttons * settings.scale_buttons;
+        let icon_size = (settings.font_size_icons * settings.scale_icons).round();
+        let package_name_size = settings.font_size_package_names * settings.scale_package_cards;
+        let package_detail_size = settings.font_size_package_details * settings.scale_package_cards;
+
+        let check_button = if self.is_checking_updates {
+            button(
+                row![
+                    text(crate::gui::fonts::glyphs::REFRESH_SYMBOL).font(material_font).size(icon_size),
+                    text(" Checking...").size(button_font_size)
+                ]
+                .spacing(4)
+                .align_items(Alignment::Center)
+            )
+            .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
                 is_primary: false,
                 radius: settings.border_radius,
             })))
@@ -929,6 +2299,12 @@ impl FlatpakTab {
             .padding(Padding::new(14.0))
         };
 
+        let muted = iced::Color::from_rgba(0.5, 0.5, 0.5, 1.0);
+        let total_download = self.updates
+            .iter()
+            .map(|u| flatpak_storage::parse_size(&u.download_size))
+            .sum::<Option<u64>>();
+
         let install_button: Element<Message> = if self.updates.is_empty() || self.is_updating {
             if self.is_updating {
                 button(
@@ -958,7 +2334,10 @@ impl FlatpakTab {
             button(
                 row![
                     text(crate::gui::fonts::glyphs::DOWNLOAD_SYMBOL).font(material_font).size(icon_size),
-                    text(format!(" Update {} Package(s)", self.updates.len())).size(button_font_size)
+                    text(match total_download {
+                        Some(total) => format!(" Update {} Package(s) ({})", self.updates.len(), flatpak_storage::format_size(total)),
+                        None => format!(" Update {} Package(s)", self.updates.len()),
+                    }).size(button_font_size)
                 ]
                 .spacing(4)
                 .align_items(Alignment::Center)
@@ -1037,11 +2416,25 @@ impl FlatpakTab {
                     self.updates
                         .iter()
                         .map(|update| {
+                            let short = |commit: &str| commit.chars().take(10).collect::<String>();
+                            let commits = if update.installed_commit.is_empty() {
+                                short(&update.commit)
+                            } else {
+                                format!("{} -> {}", short(&update.installed_commit), short(&update.commit))
+                            };
                             container(
                                 row![
-                                    text(&update.name).size(package_name_size).width(Length::FillPortion(3)),
-                                    text(&update.version).size(package_detail_size).width(Length::FillPortion(2)),
-                                    text(update.remote.as_deref().unwrap_or("local")).size(package_detail_size).width(Length::FillPortion(2)),
+                                    column![
+                                        text(&update.name).size(package_name_size),
+                                        text(&update.application_id).size(package_detail_size * 0.85).style(iced::theme::Text::Color(muted)),
+                                    ]
+                                    .width(Length::FillPortion(3)),
+                                    text(update.kind()).size(package_detail_size).width(Length::FillPortion(1)),
+                                    text(if update.version.is_empty() { &update.branch } else { &update.version }).size(package_detail_size).width(Length::FillPortion(1)),
+                                    text(commits).size(package_detail_size * 0.85).font(iced::Font::MONOSPACE).width(Length::FillPortion(2)),
+                                    text(&update.download_size).size(package_detail_size).width(Length::FillPortion(1)),
+                                    text(update.remote.as_deref().unwrap_or("local")).size(package_detail_size).width(Length::FillPortion(1)),
+                                    text(update.installation.label()).size(package_detail_size).width(Length::FillPortion(1)),
                                 ]
                                 .spacing(12)
                                 .align_items(Alignment::Center)
@@ -1105,6 +2498,7 @@ async fn search_flatpaks(query: String) -> Result<Vec<FlatpakInfo>, String> {
                 description,
                 version,
                 remote,
+                installation: None,
             });
         }
     }
@@ -1116,7 +2510,7 @@ async fn load_installed_flatpaks() -> Result<Vec<FlatpakInfo>, String> {
     // List all installed items (applications, runtimes, extensions)
     // Use --columns to get structured output
     let output = TokioCommand::new("flatpak")
-        .args(["list", "--columns=name,application,version,origin"])
+        .args(["list", "--columns=name,application,version,origin,installation"])
         .output()
         .await
         .map_err(|e| format!("Failed to execute flatpak list: {}", e))?;
@@ -1134,7 +2528,7 @@ async fn load_installed_flatpaks() -> Result<Vec<FlatpakInfo>, String> {
             continue;
         }
 
-        // Parse tab-separated values: name, application, version, origin
+        // Parse tab-separated values: name, application, version, origin, installation
         // Note: version can be empty for some entries (like runtimes)
         let parts: Vec<&str> = line.split('\t').collect();
         if parts.len() >= 2 {
@@ -1142,6 +2536,7 @@ async fn load_installed_flatpaks() -> Result<Vec<FlatpakInfo>, String> {
             let application_id = parts[1].trim().to_string();
             let version = parts.get(2).map(|s| s.trim()).unwrap_or("").to_string();
             let remote = parts.get(3).map(|s| s.trim()).filter(|s| !s.is_empty()).map(|s| s.to_string());
+            let installation = parts.get(4).and_then(|s| Installation::from_label(s));
 
             packages.push(FlatpakInfo {
                 name,
@@ -1149,6 +2544,7 @@ async fn load_installed_flatpaks() -> Result<Vec<FlatpakInfo>, String> {
                 description: String::new(),
                 version,
                 remote,
+                installation,
             });
         }
     }
@@ -1156,16 +2552,11 @@ async fn load_installed_flatpaks() -> Result<Vec<FlatpakInfo>, String> {
     Ok(packages)
 }
 
-async fn check_flatpak_updates() -> Result<Vec<FlatpakInfo>, String> {
-    // First, update the appstream to get latest information
-    let _ = TokioCommand::new("flatpak")
-        .args(["update", "--appstream", "-y"])
-        .status()
-        .await;
-
-    // Get list of installed applications first
+// One `remote-ls --updates` per installation covers apps, runtimes and extensions; an update is
+// a ref whose remote commit differs from the installed one, whatever the version strings say
+async fn check_flatpak_updates() -> Result<Vec<FlatpakUpdate>, String> {
     let installed_output = TokioCommand::new("flatpak")
-        .args(["list", "--app", "--columns=application,version,origin"])
+        .args(["list", "--columns=ref,active,installation"])
         .output()
         .await
         .map_err(|e| format!("Failed to execute flatpak list: {}", e))?;
@@ -1175,111 +2566,101 @@ async fn check_flatpak_updates() -> Result<Vec<FlatpakInfo>, String> {
             String::from_utf8_lossy(&installed_output.stderr)));
     }
 
-    let installed_stdout = String::from_utf8_lossy(&installed_output.stdout);
-    let mut updates = Vec::new();
+    let installed: std::collections::HashMap<(String, String), String> = String::from_utf8_lossy(&installed_output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split('\t').map(str::trim).collect();
+            Some(((parts.get(2)?.to_string(), parts.first()?.to_string()), parts.get(1)?.to_string()))
+        })
+        .collect();
 
-    // For each installed app, check if there's an update available
-    for line in installed_stdout.lines() {
-        let line = line.trim();
-        if line.is_empty() {
-            continue;
-        }
+    let app_refs = flatpak_storage::app_refs().await?;
 
-        // Parse: application_id<TAB>version<TAB>origin
-        let parts: Vec<&str> = line.split('\t').collect();
-        if parts.len() >= 3 {
-            let application_id = parts[0].trim().to_string();
-            let installed_version = parts[1].trim().to_string();
-            let remote = parts[2].trim().to_string();
+    // Pinned (masked) refs are left out of the list and the count
+    let masks = flatpak_history::load_masks().await.unwrap_or_default();
+    let mut updates: Vec<FlatpakUpdate> = Vec::new();
+    for installation in [Installation::System, Installation::User] {
+        let output = TokioCommand::new("flatpak")
+            .args(["remote-ls", installation.flag(), "--updates", "--columns=name,application,version,branch,origin,ref,commit,download-size"])
+            .output()
+            .await
+            .map_err(|e| format!("Failed to execute flatpak remote-ls: {}", e))?;
+
+        if !output.status.success() {
+            return Err(format!("Failed to check for {} updates: {}", installation.label(), String::from_utf8_lossy(&output.stderr).trim()));
+        }
 
-            if remote.is_empty() {
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            let parts: Vec<&str> = line.split('\t').map(str::trim).collect();
+            if parts.len() < 7 {
                 continue;
             }
-
-            // Check remote version
-            let remote_info_output = TokioCommand::new("flatpak")
-                .args(["remote-info", &remote, &application_id])
-                .output()
-                .await;
-
-            if let Ok(remote_info) = remote_info_output {
-                if remote_info.status.success() {
-                    let remote_stdout = String::from_utf8_lossy(&remote_info.stdout);
-                    let mut remote_version = String::new();
-                    let mut name = application_id.clone();
-
-                    // Parse remote-info output
-                    for info_line in remote_stdout.lines() {
-                        let info_line = info_line.trim();
-                        if info_line.starts_with("Name:") {
-                            name = info_line.splitn(2, ':')
-                                .nth(1)
-                                .unwrap_or("")
-                                .trim()
-                                .to_string();
-                        } else if info_line.starts_with("Version:") {
-                            remote_version = info_line.splitn(2, ':')
-                                .nth(1)
-                                .unwrap_or("")
-                                .trim()
-                                .to_string();
-                        }
-                    }
-
-                    // Compare versions - if remote version differs from installed, there's an update
-                    if !remote_version.is_empty() && remote_version != installed_version {
-                        updates.push(FlatpakInfo {
-                            name: if name.is_empty() { application_id.clone() } else { name },
-                            application_id,
-                            description: String::new(),
-                            version: remote_version,
-                            remote: Some(remote),
-                        });
-                    }
-                }
+            let key = (installation.label().to_string(), parts[5].to_string());
+            let kind = if app_refs.contains(&key) { "app" } else { "runtime" };
+            let flatpak_ref = format!("{}/{}", kind, parts[5]);
+            let commit = parts[6].to_string();
+            let installed_commit = installed.get(&key).cloned().unwrap_or_default();
+            // remote-ls can list a ref installed from several remotes; skip anything already current
+            if commit.is_empty()
+                || commit == installed_commit
+                || flatpak_history::is_masked(&masks, installation, parts[1], &flatpak_ref)
+                || updates.iter().any(|u| u.flatpak_ref == flatpak_ref && u.installation == installation)
+            {
+                continue;
             }
+            let application_id = parts[1].to_string();
+            updates.push(FlatpakUpdate {
+                name: if parts[0].is_empty() { application_id.clone() } else { parts[0].to_string() },
+                application_id,
+                version: parts[2].to_string(),
+                branch: parts[3].to_string(),
+                remote: Some(parts[4].to_string()).filter(|r| !r.is_empty()),
+                installation,
+                flatpak_ref,
+                installed_commit,
+                commit,
+                download_size: parts.get(7).map(|s| s.to_string()).unwrap_or_default(),
+            });
         }
     }
 
-    // Alternative: Also try the remote-ls --updates method as a fallback
-    // This might catch updates that the version comparison missed
-    let remote_ls_output = TokioCommand::new("flatpak")
-        .args(["remote-ls", "--updates", "--app", "--columns=name,application,version,origin"])
-        .output()
-        .await;
-
-    if let Ok(remote_ls) = remote_ls_output {
-        if remote_ls.status.success() {
-            let remote_ls_stdout = String::from_utf8_lossy(&remote_ls.stdout);
-            for line in remote_ls_stdout.lines() {
-                let line = line.trim();
-                if line.is_empty() {
-                    continue;
-                }
+    appstream::refresh_flatpak_appstream().await;
 
-                let parts: Vec<&str> = line.split('\t').collect();
-                if parts.len() >= 2 {
-                    let name = parts[0].trim().to_string();
-                    let application_id = parts[1].trim().to_string();
-                    let version = parts.get(2).map(|s| s.trim()).unwrap_or("").to_string();
-                    let remote = parts.get(3).map(|s| s.trim()).filter(|s| !s.is_empty()).map(|s| s.to_string());
-
-                    // Only add if not already in updates list
-                    if !updates.iter().any(|u| u.application_id == application_id) {
-                        updates.push(FlatpakInfo {
-                            name,
-                            application_id,
-                            description: String::new(),
-                            version,
-                            remote,
-                        });
-                    }
-                }
-            }
-        }
+    // Apps first, then the runtimes and extensions they pull in
+    updates.sort_by_key(|u| (u.kind() != "app", u.name.to_lowercase()));
+    Ok(updates)
+}
+
+// App data lives in ~/.var/app for both installations, so moving keeps settings and files
+async fn move_flatpak(app_id: String, origin: String, from: Installation) -> Result<String, String> {
+    let to = from.other();
+    flatpak_remotes::ensure_remote(&origin, to).await?;
+    crate::logger::Logger::log_debug(&format!("[Flatpak] Moving {} from {} to {}", app_id, from.label(), to.label()));
+
+    let install = TokioCommand::new("flatpak")
+        .args(["install", to.flag(), "-y", "--noninteractive", &origin, &app_id])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute flatpak install: {}", e))?;
+    if !install.status.success() {
+        return Err(format!("Failed to install {} for {}: {}", app_id, to.label(), String::from_utf8_lossy(&install.stderr).trim()));
     }
 
-    Ok(updates)
+    let uninstall = TokioCommand::new("flatpak")
+        .args(["uninstall", from.flag(), "-y", "--noninteractive", &app_id])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute flatpak uninstall: {}", e))?;
+    if !uninstall.status.success() {
+        return Err(format!(
+            "{} is now installed for {} but removing the {} copy failed: {}",
+            app_id,
+            to.label(),
+            from.label(),
+            String::from_utf8_lossy(&uninstall.stderr).trim()
+        ));
+    }
+    Ok(format!("Moved {} to the {} installation", app_id, to.label()))
 }
 
 async fn load_flatpak_details(app_id: String, remote: Option<String>) -> FlatpakDetails {
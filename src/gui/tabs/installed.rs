@@ -8,6 +8,8 @@ use iced::widget::checkbox::StyleSheet as CheckboxStyleSheet;
 use iced::widget::text_input::Appearance as TextInputAppearance;
 use iced::widget::text_input::StyleSheet as TextInputStyleSheet;
 use tokio::process::Command as TokioCommand;
+use std::collections::HashMap;
+use crate::gui::package_versions::{self, PackageVersions, VersionAction};
 
 #[derive(Debug, Clone)]
 pub enum Message {
@@ -16,13 +18,28 @@ pub enum Message {
     SearchQueryChanged(String),
     TogglePackage(String),
     PackageSelected(String),
-    PackageDetailsLoaded(PackageDetails),
+    PackageDetailsLoaded(Box<PackageDetails>),
     ClosePanel,
     RemoveSelected,
     RemoveComplete,
+    SelectDetailsView(DetailsView),
+    VersionsLoaded(Result<PackageVersions, String>),
+    VersionAction(VersionAction),
+    VersionActionComplete(Result<String, String>),
+    OpenFileDir(String),
+    VerificationLoaded(String, Result<HashMap<String, String>, String>),
     Error(String),
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DetailsView {
+    Details,
+    Dependencies,
+    Files,
+    Changelog,
+    Versions,
+}
+
 #[derive(Debug, Clone)]
 pub struct PackageInfo {
     pub name: String,
@@ -41,8 +58,27 @@ pub struct PackageDetails {
     pub description: String,
     pub size: String,
     pub icon_path: Option<String>,
+    // Whether dnf installed it on request or to satisfy another package
+    pub install_reason: String,
+    pub requires: Vec<String>,
+    pub provides: Vec<String>,
+    // Installed packages that depend on this one
+    pub required_by: Vec<String>,
+    pub recommends: Vec<String>,
+    pub suggests: Vec<String>,
+    pub changelog: Vec<String>,
+    pub files: Vec<PackageFile>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PackageFile {
+    pub path: String,
+    pub is_dir: bool,
 }
 
+// Older entries are rarely useful and some packages carry decades of them
+const CHANGELOG_LIMIT: usize = 30;
+
 #[derive(Debug)]
 pub struct InstalledTab {
     packages: Vec<PackageInfo>,
@@ -54,6 +90,14 @@ pub struct InstalledTab {
     selected_package: Option<String>,
     package_details: Option<PackageDetails>,
     panel_open: bool,
+    details_view: DetailsView,
+    versions: Option<Result<PackageVersions, String>>,
+    is_changing_version: bool,
+    version_status: Option<Result<String, String>>,
+    // Directory shown in the file browser
+    file_dir: String,
+    // `rpm -V` results; loaded when the Files view opens since it reads every file
+    verification: Option<Result<HashMap<String, String>, String>>,
 }
 
 impl InstalledTab {
@@ -68,6 +112,30 @@ impl InstalledTab {
             selected_package: None,
             package_details: None,
             panel_open: false,
+            details_view: DetailsView::Details,
+            versions: None,
+            is_changing_version: false,
+            version_status: None,
+            file_dir: String::from("/"),
+            verification: None,
+        }
+    }
+
+    // Versions and file verification are slow, so they load when their view is opened
+    fn load_view_data(&self) -> iced::Command<Message> {
+        let Some(name) = self.selected_package.clone() else {
+            return iced::Command::none();
+        };
+        match self.details_view {
+            DetailsView::Versions if self.versions.is_none() => {
+                iced::Command::perform(package_versions::load(name), Message::VersionsLoaded)
+            }
+            DetailsView::Files if self.verification.is_none() => {
+                iced::Command::perform(verify_files(name.clone()), move |result| {
+                    Message::VerificationLoaded(name.clone(), result)
+                })
+            }
+            _ => iced::Command::none(),
         }
     }
 
@@ -131,18 +199,79 @@ impl InstalledTab {
             Message::PackageSelected(name) => {
                 self.selected_package = Some(name.clone());
                 self.panel_open = true;
-                iced::Command::perform(load_package_details(name), Message::PackageDetailsLoaded)
+                self.versions = None;
+                self.version_status = None;
+                self.verification = None;
+                iced::Command::batch(vec![
+                    iced::Command::perform(load_package_details(name), |details| Message::PackageDetailsLoaded(Box::new(details))),
+                    self.load_view_data(),
+                ])
             }
             Message::PackageDetailsLoaded(details) => {
-                self.package_details = Some(details);
+                // Reloads of the same package keep the browser where it was
+                if self.package_details.as_ref().map(|d| &d.name) != Some(&details.name) {
+                    self.file_dir = common_dir(&details.files);
+                }
+                self.package_details = Some(*details);
                 iced::Command::none()
             }
             Message::ClosePanel => {
                 self.panel_open = false;
                 self.selected_package = None;
                 self.package_details = None;
+                self.versions = None;
+                self.version_status = None;
+                self.verification = None;
+                iced::Command::none()
+            }
+            Message::SelectDetailsView(view) => {
+                self.details_view = view;
+                self.load_view_data()
+            }
+            Message::OpenFileDir(dir) => {
+                self.file_dir = dir;
+                iced::Command::none()
+            }
+            Message::VerificationLoaded(name, result) => {
+                if self.selected_package.as_deref() == Some(name.as_str()) {
+                    self.verification = Some(result);
+                }
+                iced::Command::none()
+            }
+            Message::VersionsLoaded(result) => {
+                // Ignore a late answer for a package that is no longer shown
+                if let Ok(ref versions) = result {
+                    if self.selected_package.as_deref() != Some(versions.name.as_str()) {
+                        return iced::Command::none();
+                    }
+                }
+                self.versions = Some(result);
                 iced::Command::none()
             }
+            Message::VersionAction(action) => {
+                self.is_changing_version = true;
+                self.version_status = None;
+                iced::Command::perform(package_versions::apply(action), Message::VersionActionComplete)
+            }
+            Message::VersionActionComplete(result) => {
+                self.is_changing_version = false;
+                self.version_status = Some(result);
+                let Some(name) = self.selected_package.clone() else {
+                    return iced::Command::none();
+                };
+                self.versions = None;
+                self.verification = None;
+                iced::Command::batch(vec![
+                    self.load_view_data(),
+                    iced::Command::perform(load_package_details(name), |details| Message::PackageDetailsLoaded(Box::new(details))),
+                    iced::Command::perform(load_installed_packages(), |result| {
+                        match result {
+                            Ok(packages) => Message::PackagesLoaded(packages),
+                            Err(e) => Message::Error(e),
+                        }
+                    }),
+                ])
+            }
             Message::RemoveSelected => {
                 if self.selected_packages.is_empty() {
                     return iced::Command::none();
@@ -179,10 +308,337 @@ impl InstalledTab {
         }
     }
 
-    fn view_panel(&self, theme: &crate::gui::Theme, settings: &crate::gui::settings::AppSettings) -> Element<'_, Message> {
+    fn view_tab_button<'a>(&self, label: &'a str, view: DetailsView, settings: &crate::gui::settings::AppSettings) -> Element<'a, Message> {
+        let package_detail_size = settings.font_size_package_details * settings.scale_package_cards;
+        button(text(label).size(package_detail_size))
+            .on_press(Message::SelectDetailsView(view))
+            .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                is_primary: self.details_view == view,
+                radius: settings.border_radius,
+            })))
+            .padding(Padding::from([8.0, 10.0, 8.0, 10.0]))
+            .into()
+    }
+
+    fn view_section<'a>(
+        title: String,
+        items: Vec<Element<'a, Message>>,
+        empty: &'a str,
+        theme: &crate::gui::Theme,
+        settings: &crate::gui::settings::AppSettings,
+    ) -> Element<'a, Message> {
         let title_font_size = settings.font_size_titles * settings.scale_titles;
+        let package_detail_size = settings.font_size_package_details * settings.scale_package_cards;
+        let body: Element<Message> = if items.is_empty() {
+            text(empty)
+                .size(package_detail_size)
+                .style(iced::theme::Text::Color(theme.secondary_text_with_settings(Some(settings))))
+                .into()
+        } else {
+            column(items).spacing(4).into()
+        };
+        column![
+            text(title).size(title_font_size * 0.9).style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
+            container(body)
+                .width(Length::Fill)
+                .padding(Padding::new(14.0))
+                .style(iced::theme::Container::Custom(Box::new(InfoContainerStyle))),
+        ]
+        .spacing(8)
+        .into()
+    }
+
+    fn view_dependencies<'a>(
+        &'a self,
+        details: &'a PackageDetails,
+        theme: &crate::gui::Theme,
+        settings: &crate::gui::settings::AppSettings,
+    ) -> Element<'a, Message> {
+        let package_detail_size = settings.font_size_package_details * settings.scale_package_cards;
+        let plain = |items: &'a [String]| -> Vec<Element<'a, Message>> {
+            items
+                .iter()
+                .map(|item| text(item).size(package_detail_size).width(Length::Fill).into())
+                .collect()
+        };
+        // Dependents are installed packages, so they open in this panel
+        let required_by = details
+            .required_by
+            .iter()
+            .map(|name| {
+                button(text(name).size(package_detail_size).style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))))
+                    .on_press(Message::PackageSelected(name.clone()))
+                    .style(iced::theme::Button::Text)
+                    .padding(0)
+                    .into()
+            })
+            .collect();
+
+        column![
+            Space::with_height(Length::Fixed(20.0)),
+            row![
+                text("Reason:").size(package_detail_size).width(Length::Fixed(110.0)).style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
+                text(&details.install_reason).size(package_detail_size).width(Length::Fill),
+            ]
+            .spacing(12),
+            Self::view_section(format!("Requires ({})", details.requires.len()), plain(&details.requires), "No requirements", theme, settings),
+            Self::view_section(format!("Required by ({})", details.required_by.len()), required_by, "No installed package depends on it", theme, settings),
+            Self::view_section(format!("Recommends ({})", details.recommends.len()), plain(&details.recommends), "No recommendations", theme, settings),
+            Self::view_section(format!("Suggests ({})", details.suggests.len()), plain(&details.suggests), "No suggestions", theme, settings),
+            Self::view_section(format!("Provides ({})", details.provides.len()), plain(&details.provides), "Provides nothing", theme, settings),
+        ]
+        .spacing(16)
+        .into()
+    }
+
+    fn view_files<'a>(
+        &'a self,
+        details: &'a PackageDetails,
+        theme: &crate::gui::Theme,
+        settings: &crate::gui::settings::AppSettings,
+    ) -> Element<'a, Message> {
+        let package_detail_size = settings.font_size_package_details * settings.scale_package_cards;
+        let icon_size = (settings.font_size_icons * settings.scale_icons).round();
+        let muted = theme.secondary_text_with_settings(Some(settings));
+        let problem = iced::Color::from_rgb(0.9, 0.3, 0.3);
+        let verification = self.verification.as_ref().and_then(|v| v.as_ref().ok());
+
+        let summary = match self.verification {
+            None => "Verifying files...".to_string(),
+            Some(Err(ref e)) => e.clone(),
+            Some(Ok(ref changes)) if changes.is_empty() => format!("All {} files match the package", details.files.len()),
+            Some(Ok(ref changes)) => format!("{} of {} files differ from the package", changes.len(), details.files.len()),
+        };
+
+        // Immediate children of the current directory; a path with more components
+        // below it is a directory even when the package does not own it
+        let prefix = if self.file_dir == "/" { "/".to_string() } else { format!("{}/", self.file_dir) };
+        let mut children: std::collections::BTreeMap<&str, (String, bool)> = std::collections::BTreeMap::new();
+        for file in &details.files {
+            let Some(rest) = file.path.strip_prefix(&prefix) else {
+                continue;
+            };
+            let (component, is_dir) = match rest.split_once('/') {
+                Some((component, _)) => (component, true),
+                None => (rest, file.is_dir),
+            };
+            if component.is_empty() {
+                continue;
+            }
+            let entry = children.entry(component).or_insert_with(|| (format!("{}{}", prefix, component), is_dir));
+            entry.1 |= is_dir;
+        }
+
+        let material_font = crate::gui::fonts::get_material_symbols_font();
+        let mut list = column![].spacing(4);
+        if self.file_dir != "/" {
+            list = list.push(
+                button(text("..").size(package_detail_size))
+                    .on_press(Message::OpenFileDir(parent_dir(&self.file_dir)))
+                    .style(iced::theme::Button::Text)
+                    .padding(0),
+            );
+        }
+        for (component, (path, is_dir)) in children {
+            let status = match verification {
+                Some(changes) if is_dir => {
+                    let changed = changes.keys().filter(|p| p.starts_with(&format!("{}/", path))).count();
+                    (changed > 0).then(|| format!("{} changed inside", changed))
+                }
+                Some(changes) => changes.get(&path).cloned(),
+                None => None,
+            };
+            let name: Element<Message> = if is_dir {
+                button(
+                    row![
+                        text(crate::gui::fonts::glyphs::FOLDER_SYMBOL).font(material_font).size(icon_size),
+                        text(component).size(package_detail_size).style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
+                    ]
+                    .spacing(6)
+                    .align_items(Alignment::Center)
+                )
+                .on_press(Message::OpenFileDir(path))
+                .style(iced::theme::Button::Text)
+                .padding(0)
+                .into()
+            } else {
+                row![
+                    Space::with_width(Length::Fixed(icon_size)),
+                    text(component).size(package_detail_size),
+                ]
+                .spacing(6)
+                .into()
+            };
+            let mut entry = column![name].spacing(2);
+            if let Some(status) = status {
+                entry = entry.push(
+                    text(status)
+                        .size(package_detail_size * 0.85)
+                        .style(iced::theme::Text::Color(problem)),
+                );
+            }
+            list = list.push(entry);
+        }
+
+        column![
+            Space::with_height(Length::Fixed(20.0)),
+            text(summary).size(package_detail_size).style(iced::theme::Text::Color(muted)),
+            text(&self.file_dir)
+                .size(package_detail_size)
+                .style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
+            container(list)
+                .width(Length::Fill)
+                .padding(Padding::new(14.0))
+                .style(iced::theme::Container::Custom(Box::new(InfoContainerStyle))),
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    fn view_changelog<'a>(
+        &'a self,
+        details: &'a PackageDetails,
+        theme: &crate::gui::Theme,
+        settings: &crate::gui::settings::AppSettings,
+    ) -> Element<'a, Message> {
+        let package_detail_size = settings.font_size_package_details * settings.scale_package_cards;
+        let entries = details
+            .changelog
+            .iter()
+            .map(|entry| {
+                let (header, body) = entry.split_once('\n').unwrap_or((entry, ""));
+                column![
+                    text(header)
+                        .size(package_detail_size)
+                        .style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
+                    text(body).size(package_detail_size).width(Length::Fill),
+                ]
+                .spacing(4)
+                .into()
+            })
+            .collect();
+        column![
+            Space::with_height(Length::Fixed(20.0)),
+            Self::view_section(
+                format!("Latest {} entries", details.changelog.len()),
+                entries,
+                "The package has no changelog",
+                theme,
+                settings,
+            ),
+        ]
+        .into()
+    }
+
+    fn view_details<'a>(
+        &'a self,
+        details: &'a PackageDetails,
+        icon_widget: iced::widget::Container<'a, Message>,
+        theme: &crate::gui::Theme,
+        settings: &crate::gui::settings::AppSettings,
+    ) -> Element<'a, Message> {
+        let title_font_size = settings.font_size_titles * settings.scale_titles;
+        let package_detail_size = settings.font_size_package_details * settings.scale_package_cards;
+        column![
+                Space::with_height(Length::Fixed(20.0)),
+                icon_widget,
+                Space::with_height(Length::Fixed(20.0)),
+                text(&details.name)
+                    .size(title_font_size * 1.2)
+                    .style(iced::theme::Text::Color(theme.text_with_settings(Some(settings))))
+                    .horizontal_alignment(iced::alignment::Horizontal::Center),
+                Space::with_height(Length::Fixed(20.0)),
+                container(
+                    column![
+                        row![
+                            text("Version:").size(package_detail_size).width(Length::Fixed(110.0)).style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
+                            text(&details.version).size(package_detail_size).width(Length::Fill),
+                        ]
+                        .spacing(12),
+                        Space::with_height(Length::Fixed(8.0)),
+                        row![
+                            text("Release:").size(package_detail_size).width(Length::Fixed(110.0)).style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
+                            text(&details.release).size(package_detail_size).width(Length::Fill),
+                        ]
+                        .spacing(12),
+                        Space::with_height(Length::Fixed(8.0)),
+                        row![
+                            text("Architecture:").size(package_detail_size).width(Length::Fixed(110.0)).style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
+                            text(&details.arch).size(package_detail_size).width(Length::Fill),
+                        ]
+                        .spacing(12),
+                        Space::with_height(Length::Fixed(8.0)),
+                        row![
+                            text("Repository:").size(package_detail_size).width(Length::Fixed(110.0)).style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
+                            text(&details.repository).size(package_detail_size).width(Length::Fill),
+                        ]
+                        .spacing(12),
+                        Space::with_height(Length::Fixed(8.0)),
+                        row![
+                            text("Size:").size(package_detail_size).width(Length::Fixed(110.0)).style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
+                            text(&details.size).size(package_detail_size).width(Length::Fill),
+                        ]
+                        .spacing(12),
+                    ]
+                    .spacing(0)
+                )
+                .padding(Padding::new(18.0))
+                .style(iced::theme::Container::Custom(Box::new(InfoContainerStyle))),
+                Space::with_height(Length::Fixed(20.0)),
+                text("Summary").size(title_font_size * 0.9).style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
+                Space::with_height(Length::Fixed(8.0)),
+                text(&details.summary).size(package_detail_size),
+                Space::with_height(Length::Fixed(20.0)),
+                text("Description").size(title_font_size * 0.9).style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
+                Space::with_height(Length::Fixed(8.0)),
+                text(&details.description).size(package_detail_size).width(Length::Fill),
+        ]
+        .width(Length::Fill)
+        .align_items(Alignment::Start)
+        .into()
+    }
+
+    fn view_versions(&self, theme: &crate::gui::Theme, settings: &crate::gui::settings::AppSettings) -> Element<'_, Message> {
         let body_font_size = settings.font_size_body * settings.scale_body;
         let package_detail_size = settings.font_size_package_details * settings.scale_package_cards;
+        let mut content = column![Space::with_height(Length::Fixed(20.0))].spacing(10);
+
+        if self.is_changing_version {
+            content = content.push(text("Applying change...").size(body_font_size));
+        }
+        if let Some(ref status) = self.version_status {
+            let (message, color) = match status {
+                Ok(message) => (message.as_str(), theme.primary_with_settings(Some(settings))),
+                Err(e) => (e.as_str(), iced::Color::from_rgb(0.9, 0.3, 0.3)),
+            };
+            content = content.push(text(message).size(package_detail_size).style(iced::theme::Text::Color(color)));
+        }
+
+        let list: Element<Message> = match self.versions {
+            None => text("Loading versions...").size(body_font_size).into(),
+            Some(Err(ref e)) => text(e).size(package_detail_size).into(),
+            Some(Ok(ref versions)) => container(package_versions::view(
+                versions,
+                self.is_changing_version,
+                Message::VersionAction,
+                |is_primary| iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                    is_primary,
+                    radius: settings.border_radius,
+                })),
+                package_detail_size,
+                theme.primary_with_settings(Some(settings)),
+                theme.secondary_text_with_settings(Some(settings)),
+            ))
+            .padding(Padding::new(18.0))
+            .style(iced::theme::Container::Custom(Box::new(InfoContainerStyle)))
+            .into(),
+        };
+        content.push(list).into()
+    }
+
+    fn view_panel(&self, theme: &crate::gui::Theme, settings: &crate::gui::settings::AppSettings) -> Element<'_, Message> {
+        let title_font_size = settings.font_size_titles * settings.scale_titles;
+        let body_font_size = settings.font_size_body * settings.scale_body;
         let icon_size = (settings.font_size_icons * settings.scale_icons).round();
         if let Some(ref details) = self.package_details {
             let icon_widget = if let Some(ref icon_path) = details.icon_path {
@@ -242,58 +698,22 @@ impl InstalledTab {
                         ]
                         .width(Length::Fill)
                         .align_items(Alignment::Center),
-                        Space::with_height(Length::Fixed(20.0)),
-                        icon_widget,
-                        Space::with_height(Length::Fixed(20.0)),
-                        text(&details.name)
-                            .size(title_font_size * 1.2)
-                            .style(iced::theme::Text::Color(theme.text_with_settings(Some(settings))))
-                            .horizontal_alignment(iced::alignment::Horizontal::Center),
-                        Space::with_height(Length::Fixed(20.0)),
-                        container(
-                            column![
-                                row![
-                                    text("Version:").size(package_detail_size).width(Length::Fixed(110.0)).style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
-                                    text(&details.version).size(package_detail_size).width(Length::Fill),
-                                ]
-                                .spacing(12),
-                                Space::with_height(Length::Fixed(8.0)),
-                                row![
-                                    text("Release:").size(package_detail_size).width(Length::Fixed(110.0)).style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
-                                    text(&details.release).size(package_detail_size).width(Length::Fill),
-                                ]
-                                .spacing(12),
-                                Space::with_height(Length::Fixed(8.0)),
-                                row![
-                                    text("Architecture:").size(package_detail_size).width(Length::Fixed(110.0)).style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
-                                    text(&details.arch).size(package_detail_size).width(Length::Fill),
-                                ]
-                                .spacing(12),
-                                Space::with_height(Length::Fixed(8.0)),
-                                row![
-                                    text("Repository:").size(package_detail_size).width(Length::Fixed(110.0)).style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
-                                    text(&details.repository).size(package_detail_size).width(Length::Fill),
-                                ]
-                                .spacing(12),
-                                Space::with_height(Length::Fixed(8.0)),
-                                row![
-                                    text("Size:").size(package_detail_size).width(Length::Fixed(110.0)).style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
-                                    text(&details.size).size(package_detail_size).width(Length::Fill),
-                                ]
-                                .spacing(12),
-                            ]
-                            .spacing(0)
-                        )
-                        .padding(Padding::new(18.0))
-                        .style(iced::theme::Container::Custom(Box::new(InfoContainerStyle))),
-                        Space::with_height(Length::Fixed(20.0)),
-                        text("Summary").size(title_font_size * 0.9).style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
-                        Space::with_height(Length::Fixed(8.0)),
-                        text(&details.summary).size(package_detail_size),
-                        Space::with_height(Length::Fixed(20.0)),
-                        text("Description").size(title_font_size * 0.9).style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
-                        Space::with_height(Length::Fixed(8.0)),
-                        text(&details.description).size(package_detail_size).width(Length::Fill),
+                        Space::with_height(Length::Fixed(16.0)),
+                        row![
+                            self.view_tab_button("Details", DetailsView::Details, settings),
+                            self.view_tab_button("Dependencies", DetailsView::Dependencies, settings),
+                            self.view_tab_button("Files", DetailsView::Files, settings),
+                            self.view_tab_button("Changelog", DetailsView::Changelog, settings),
+                            self.view_tab_button("Versions", DetailsView::Versions, settings),
+                        ]
+                        .spacing(6),
+                        match self.details_view {
+                            DetailsView::Details => self.view_details(details, icon_widget, theme, settings),
+                            DetailsView::Dependencies => self.view_dependencies(details, theme, settings),
+                            DetailsView::Files => self.view_files(details, theme, settings),
+                            DetailsView::Changelog => self.view_changelog(details, theme, settings),
+                            DetailsView::Versions => self.view_versions(theme, settings),
+                        },
                     ]
                     .spacing(0)
                     .padding(Padding::new(25.0))
@@ -631,61 +1051,65 @@ async fn load_package_details(package_name: String) -> PackageDetails {
         }
     }
 
-    let desktop_output = TokioCommand::new("rpm")
-        .args(["-ql", &package_name])
-        .output()
-        .await;
-
-    if let Ok(output) = desktop_output {
-        if output.status.success() {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            for line in stdout.lines() {
-                if line.trim().ends_with(".desktop") {
-                    if let Ok(desktop_content) = std::fs::read_to_string(line.trim()) {
-                        for desktop_line in desktop_content.lines() {
-                            if desktop_line.trim().starts_with("Icon=") {
-                                let icon_value = desktop_line.splitn(2, '=').nth(1).unwrap_or("").trim();
-                                if icon_value.starts_with('/') {
-                                    if std::path::Path::new(icon_value).exists() {
-                                        icon_path = Some(icon_value.to_string());
-                                    }
-                                } else {
-                                    let icon_dirs = [
-                                        "/usr/share/icons/hicolor/256x256/apps",
-                                        "/usr/share/icons/hicolor/128x128/apps",
-                                        "/usr/share/icons/hicolor/96x96/apps",
-                                        "/usr/share/icons/hicolor/64x64/apps",
-                                        "/usr/share/icons/hicolor/48x48/apps",
-                                        "/usr/share/icons/hicolor/32x32/apps",
-                                        "/usr/share/pixmaps",
-                                        "/usr/share/applications",
-                                    ];
-                                    for dir in &icon_dirs {
-                                        for ext in &["png", "svg", "xpm", "ico"] {
-                                            let icon_file = format!("{}/{}.{}", dir, icon_value, ext);
-                                            if std::path::Path::new(&icon_file).exists() {
-                                                icon_path = Some(icon_file);
-                                                break;
-                                            }
-                                        }
-                                        if icon_path.is_some() {
-                                            break;
-                                        }
-                                    }
-                                    if icon_path.is_none() {
-                                        let pixmap_file = format!("/usr/share/pixmaps/{}", icon_value);
-                                        if std::path::Path::new(&pixmap_file).exists() {
-                                            icon_path = Some(pixmap_file);
-                                        }
+    let (install_reason, requires, provides, recommends, suggests, required_by, changelog, files) = tokio::join!(
+        load_install_reason(&package_name),
+        query_lines("rpm", vec!["-qR", &package_name]),
+        query_lines("rpm", vec!["-q", "--provides", &package_name]),
+        query_lines("rpm", vec!["-q", "--recommends", &package_name]),
+        query_lines("rpm", vec!["-q", "--suggests", &package_name]),
+        query_lines("dnf", vec!["repoquery", "--installed", "--quiet", "--whatrequires", &package_name, "--qf", "%{name}\n"]),
+        load_changelog(&package_name),
+        load_files(&package_name),
+    );
+    // rpmlib() entries are rpm's own feature flags, not packages
+    let requires: Vec<String> = requires.into_iter().filter(|r| !r.starts_with("rpmlib(")).collect();
+    let required_by: Vec<String> = required_by.into_iter().filter(|r| *r != name).collect();
+
+    for line in files.iter().map(|f| f.path.as_str()) {
+        if line.trim().ends_with(".desktop") {
+            if let Ok(desktop_content) = std::fs::read_to_string(line.trim()) {
+                for desktop_line in desktop_content.lines() {
+                    if desktop_line.trim().starts_with("Icon=") {
+                        let icon_value = desktop_line.splitn(2, '=').nth(1).unwrap_or("").trim();
+                        if icon_value.starts_with('/') {
+                            if std::path::Path::new(icon_value).exists() {
+                                icon_path = Some(icon_value.to_string());
+                            }
+                        } else {
+                            let icon_dirs = [
+                                "/usr/share/icons/hicolor/256x256/apps",
+                                "/usr/share/icons/hicolor/128x128/apps",
+                                "/usr/share/icons/hicolor/96x96/apps",
+                                "/usr/share/icons/hicolor/64x64/apps",
+                                "/usr/share/icons/hicolor/48x48/apps",
+                                "/usr/share/icons/hicolor/32x32/apps",
+                                "/usr/share/pixmaps",
+                                "/usr/share/applications",
+                            ];
+                            for dir in &icon_dirs {
+                                for ext in &["png", "svg", "xpm", "ico"] {
+                                    let icon_file = format!("{}/{}.{}", dir, icon_value, ext);
+                                    if std::path::Path::new(&icon_file).exists() {
+                                        icon_path = Some(icon_file);
+                                        break;
                                     }
                                 }
-                                break;
+                                if icon_path.is_some() {
+                                    break;
+                                }
+                            }
+                            if icon_path.is_none() {
+                                let pixmap_file = format!("/usr/share/pixmaps/{}", icon_value);
+                                if std::path::Path::new(&pixmap_file).exists() {
+                                    icon_path = Some(pixmap_file);
+                                }
                             }
                         }
+                        break;
                     }
-                    break;
                 }
             }
+            break;
         }
     }
 
@@ -699,6 +1123,157 @@ async fn load_package_details(package_name: String) -> PackageDetails {
         description,
         size,
         icon_path,
+        install_reason,
+        requires,
+        provides,
+        required_by,
+        recommends,
+        suggests,
+        changelog,
+        files,
+    }
+}
+
+// Sorted, de-duplicated output lines; the query failing just leaves the section empty
+async fn query_lines(program: &str, args: Vec<&str>) -> Vec<String> {
+    let Ok(output) = TokioCommand::new(program).args(args).output().await else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+    let mut lines: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect();
+    lines.sort();
+    lines.dedup();
+    lines
+}
+
+async fn load_install_reason(package_name: &str) -> String {
+    let reason = query_lines("dnf", vec!["repoquery", "--installed", "--quiet", "--qf", "%{reason}\n", package_name]).await;
+    match reason.first().map(|r| r.to_lowercase()).as_deref() {
+        Some("user") => "Installed by you".to_string(),
+        Some("dependency") => "Pulled in as a dependency".to_string(),
+        Some("weak-dependency" | "weak dependency") => "Pulled in as a weak dependency".to_string(),
+        Some("group") => "Installed as part of a group".to_string(),
+        Some("external user") => "Installed outside dnf (rpm or another tool)".to_string(),
+        Some(other) if !other.is_empty() && other != "unknown" => other.to_string(),
+        _ => "Unknown".to_string(),
+    }
+}
+
+// `rpm --changelog` starts each entry with "* <date> <author>"
+async fn load_changelog(package_name: &str) -> Vec<String> {
+    let Ok(output) = TokioCommand::new("rpm").args(["-q", "--changelog", package_name]).output().await else {
+        return Vec::new();
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut entries: Vec<String> = Vec::new();
+    for line in stdout.lines() {
+        if line.starts_with("* ") {
+            if entries.len() == CHANGELOG_LIMIT {
+                break;
+            }
+            entries.push(line.to_string());
+        } else if let Some(entry) = entries.last_mut() {
+            if !line.trim().is_empty() {
+                entry.push('\n');
+                entry.push_str(line.trim_end());
+            }
+        }
+    }
+    entries
+}
+
+async fn load_files(package_name: &str) -> Vec<PackageFile> {
+    let Ok(output) = TokioCommand::new("rpm")
+        .args(["-q", "--qf", "[%{FILEMODES:perms} %{FILENAMES}\n]", package_name])
+        .output()
+        .await
+    else {
+        return Vec::new();
+    };
+    let mut files: Vec<PackageFile> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .filter(|(_, path)| path.starts_with('/'))
+        .map(|(perms, path)| PackageFile {
+            path: path.to_string(),
+            is_dir: perms.starts_with('d'),
+        })
+        .collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    files.dedup_by(|a, b| a.path == b.path);
+    files
+}
+
+// Maps each file `rpm -V` reports to what changed; unlisted files match the package
+async fn verify_files(package_name: String) -> Result<std::collections::HashMap<String, String>, String> {
+    let output = TokioCommand::new("rpm")
+        .args(["-V", &package_name])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute rpm: {}", e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() && stdout.trim().is_empty() {
+        return Err(format!("Verification failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+
+    const FLAGS: [(char, &str); 9] = [
+        ('S', "size"),
+        ('M', "mode"),
+        ('5', "checksum"),
+        ('D', "device"),
+        ('L', "link target"),
+        ('U', "owner"),
+        ('G', "group"),
+        ('T', "mtime"),
+        ('P', "capabilities"),
+    ];
+    let mut results = std::collections::HashMap::new();
+    for line in stdout.lines() {
+        // "S.5....T.  c /etc/foo.conf" or "missing     /usr/bin/foo"
+        let Some(start) = line.find(" /") else {
+            continue;
+        };
+        let mut head = line[..start].split_whitespace();
+        let flags = head.next().unwrap_or("");
+        let is_config = head.next() == Some("c");
+        let mut status = if flags == "missing" {
+            "missing".to_string()
+        } else {
+            let changed: Vec<&str> = FLAGS.iter().filter(|(c, _)| flags.contains(*c)).map(|(_, label)| *label).collect();
+            format!("changed: {}", changed.join(", "))
+        };
+        if is_config {
+            status.push_str(" (config file)");
+        }
+        results.insert(line[start + 1..].trim().to_string(), status);
+    }
+    Ok(results)
+}
+
+// Deepest directory holding every file, so the browser opens past /usr/share/...
+fn common_dir(files: &[PackageFile]) -> String {
+    let mut common: Option<Vec<&str>> = None;
+    for file in files {
+        let components: Vec<&str> = file.path.split('/').filter(|c| !c.is_empty()).collect();
+        let dir = if file.is_dir { &components[..] } else { &components[..components.len().saturating_sub(1)] };
+        common = Some(match common {
+            None => dir.to_vec(),
+            Some(prefix) => prefix.iter().zip(dir).take_while(|(a, b)| a == b).map(|(a, _)| *a).collect(),
+        });
+    }
+    format!("/{}", common.unwrap_or_default().join("/"))
+}
+
+fn parent_dir(dir: &str) -> String {
+    match dir.rsplit_once('/') {
+        Some(("", _)) | None => "/".to_string(),
+        Some((parent, _)) => parent.to_string(),
     }
 }
 
@@ -16,6 +16,8 @@ use tokio::process::Command as TokioCommand;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::fs;
+use crate::cpu::CpuInfo;
+use crate::logger;
 
 #[derive(Debug, Clone)]
 pub enum Message {
@@ -31,7 +33,7 @@ pub enum Message {
     InstallKernelComplete(Result<String, String>),
     RemoveKernel(String),
     RemoveKernelComplete(Result<String, String>),
-    RunningKernelInfoLoaded(RunningKernelInfo, Option<String>, u32),
+    RunningKernelInfoLoaded(RunningKernelInfo, Option<String>, CpuInfo),
     StoreBranchDbAndLoadKernels(String, String, Vec<EnhancedKernelInfo>),
     SwitchView(KernelView),
     SchedulersLoaded(Vec<ScxScheduler>, String),
@@ -125,6 +127,11 @@ pub struct EnhancedKernelInfo {
     pub installed: bool,
     pub branch: String,
     pub min_x86_march: u32,
+    // False when the build needs a newer x86-64 level or a different -march than this CPU
+    pub supported: bool,
+    // Which check failed when the build is unsupported, shown when an install is refused
+    pub unsupported_reason: Option<String>,
+    pub recommended: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -173,7 +180,8 @@ pub struct KernelTab {
     removing_kernels: std::collections::HashSet<String>,
     running_kernel_info: Option<RunningKernelInfo>,
     latest_version: Option<String>,
-    cpu_feature_level: u32,
+    cpu_info: Option<CpuInfo>,
+    install_refusal: Option<String>,
     // View state
     current_view: KernelView,
     // SCX Scheduler state
@@ -201,7 +209,8 @@ impl KernelTab {
             removing_kernels: std::collections::HashSet::new(),
             running_kernel_info: None,
             latest_version: None,
-            cpu_feature_level: 1,
+            cpu_info: None,
+            install_refusal: None,
             current_view: KernelView::Kernels,
             scx_schedulers: Vec::new(),
             selected_scheduler: None,
@@ -271,6 +280,7 @@ impl KernelTab {
             }
             Message::BranchSelected(branch_name) => {
                 self.selected_branch = Some(branch_name.clone());
+                self.install_refusal = None;
                 self.is_loading = true;
                 let branches_clone = self.branches.clone();
                 let branch_name_for_db = branch_name.clone();
@@ -298,9 +308,9 @@ impl KernelTab {
                 let branches_clone = self.branches.clone();
                 iced::Command::perform(
                     async move {
-                        let (info, cpu_level) = tokio::join!(
+                        let (info, cpu_info) = tokio::join!(
                             get_running_kernel_info(),
-                            get_cpu_feature_level()
+                            detect_cpu_info()
                         );
                         // Get latest version from selected branch
                         let latest = if let Some(ref branch_name) = branch_name_for_info {
@@ -324,9 +334,9 @@ impl KernelTab {
                         } else {
                             None
                         };
-                        (info, latest, cpu_level)
+                        (info, latest, cpu_info)
                     },
-                    |(info, latest, cpu_level)| Message::RunningKernelInfoLoaded(info, latest, cpu_level),
+                    |(info, latest, cpu_info)| Message::RunningKernelInfoLoaded(info, latest, cpu_info),
                 )
             }
             Message::KernelsLoaded(kernels) => {
@@ -338,9 +348,9 @@ impl KernelTab {
                 let branches = self.branches.clone();
                 iced::Command::perform(
                     async move {
-                        let (info, cpu_level) = tokio::join!(
+                        let (info, cpu_info) = tokio::join!(
                             get_running_kernel_info(),
-                            get_cpu_feature_level()
+                            detect_cpu_info()
                         );
                         // Get latest version from selected branch
                         let latest = if let Some(ref branch_name) = branch_name {
@@ -364,15 +374,15 @@ impl KernelTab {
                         } else {
                             None
                         };
-                        (info, latest, cpu_level)
+                        (info, latest, cpu_info)
                     },
-                    |(info, latest, cpu_level)| Message::RunningKernelInfoLoaded(info, latest, cpu_level),
+                    |(info, latest, cpu_info)| Message::RunningKernelInfoLoaded(info, latest, cpu_info),
                 )
             }
-            Message::RunningKernelInfoLoaded(info, latest, cpu_level) => {
+            Message::RunningKernelInfoLoaded(info, latest, cpu_info) => {
                 self.running_kernel_info = Some(info);
                 self.latest_version = latest;
-                self.cpu_feature_level = cpu_level;
+                self.cpu_info = Some(cpu_info);
                 // Reload details if panel is open
                 if let Some(ref selected) = self.selected_kernel {
                     return iced::Command::perform(
@@ -403,6 +413,20 @@ impl KernelTab {
                 iced::Command::none()
             }
             Message::InstallKernel(kernel_name) => {
+                // Refuse builds that need a newer x86-64 level or a different -march than this CPU
+                if let Some(kernel) = self.kernels.iter().find(|k| k.name == kernel_name || k.main_package == kernel_name) {
+                    if !kernel.supported {
+                        let refusal = format!(
+                            "Cannot install {}: {}",
+                            kernel.main_package,
+                            kernel.unsupported_reason.as_deref().unwrap_or("this CPU does not support it")
+                        );
+                        logger::Logger::log_debug(&format!("[Kernel Tab] {}", refusal));
+                        self.install_refusal = Some(refusal);
+                        return iced::Command::none();
+                    }
+                }
+                self.install_refusal = None;
                 self.installing_kernels.insert(kernel_name.clone());
                 // Find the kernel to get its packages
                 if let Some(kernel) = self.kernels.iter().find(|k| k.name == kernel_name || k.main_package == kernel_name) {
@@ -685,46 +709,83 @@ impl KernelTab {
 
         // Running kernel info - removed from header to save space
 
+        // Detected CPU, used to pick the recommended build in each branch
+        let cpu_line: Element<Message> = if let Some(ref cpu_info) = self.cpu_info {
+            row![
+                text("CPU:")
+                    .size(body_font_size)
+                    .style(iced::theme::Text::Color(theme.secondary_text_with_settings(Some(settings)))),
+                text(if cpu_info.model_name.is_empty() {
+                    cpu_info.summary()
+                } else {
+                    format!("{} ({})", cpu_info.model_name, cpu_info.summary())
+                })
+                    .size(body_font_size)
+                    .style(iced::theme::Text::Color(theme.text_with_settings(Some(settings))))
+                    .shaping(iced::widget::text::Shaping::Advanced),
+            ]
+            .spacing(8)
+            .align_items(Alignment::Center)
+            .into()
+        } else {
+            Space::with_height(Length::Shrink).into()
+        };
+
+        // Last refused install, naming the x86-64 level or -march this CPU lacks
+        let refusal_line: Element<Message> = if let Some(ref refusal) = self.install_refusal {
+            text(refusal)
+                .size(body_font_size)
+                .style(iced::theme::Text::Color(theme.danger()))
+                .into()
+        } else {
+            Space::with_height(Length::Shrink).into()
+        };
+
         // Branch selector
         let branch_selector = if !self.branches.is_empty() {
             container(
-                row![
-                    text("Branch:")
-                        .size(body_font_size * 1.29)
-                        .style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
-                    Space::with_width(Length::Fixed(16.0)),
-                    scrollable(
-                        row(
-                            self.branches
-                                .iter()
-                                .map(|branch| {
-                                    let branch_name = branch.name.clone();
-                                    let is_selected = self.selected_branch.as_ref().map(|s| s == &branch.name).unwrap_or(false);
-                                    button(
-                                        text(&branch.name)
-                                            .size(body_font_size * 1.07)
-                                    )
-                                    .style(iced::theme::Button::Custom(Box::new(BranchButtonStyle {
-                                        is_selected,
-                                        radius: settings.border_radius,
-                                    })))
-                                    .on_press(Message::BranchSelected(branch_name))
-                                    .padding(Padding::from([10.0, 20.0, 10.0, 20.0]))
-                                    .into()
-                                })
-                                .collect::<Vec<_>>(),
+                column![
+                    row![
+                        text("Branch:")
+                            .size(body_font_size * 1.29)
+                            .style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
+                        Space::with_width(Length::Fixed(16.0)),
+                        scrollable(
+                            row(
+                                self.branches
+                                    .iter()
+                                    .map(|branch| {
+                                        let branch_name = branch.name.clone();
+                                        let is_selected = self.selected_branch.as_ref().map(|s| s == &branch.name).unwrap_or(false);
+                                        button(
+                                            text(&branch.name)
+                                                .size(body_font_size * 1.07)
+                                        )
+                                        .style(iced::theme::Button::Custom(Box::new(BranchButtonStyle {
+                                            is_selected,
+                                            radius: settings.border_radius,
+                                        })))
+                                        .on_press(Message::BranchSelected(branch_name))
+                                        .padding(Padding::from([10.0, 20.0, 10.0, 20.0]))
+                                        .into()
+                                    })
+                                    .collect::<Vec<_>>(),
+                            )
+                            .spacing(12)
                         )
-                        .spacing(12)
-                    )
-                    .width(Length::Fill)
-                    .height(Length::Shrink)
-                    .style(iced::theme::Scrollable::Custom(Box::new(CustomScrollableStyle::new(
-                        Color::from(settings.background_color.clone()),
-                        settings.border_radius,
-                    ))))
+                        .width(Length::Fill)
+                        .height(Length::Shrink)
+                        .style(iced::theme::Scrollable::Custom(Box::new(CustomScrollableStyle::new(
+                            Color::from(settings.background_color.clone()),
+                            settings.border_radius,
+                        ))))
+                    ]
+                    .align_items(Alignment::Center)
+                    .spacing(0),
+                    cpu_line,
+                    refusal_line,
                 ]
-                .align_items(Alignment::Center)
-                .spacing(0)
+                .spacing(12)
             )
             .width(Length::Fill)
             .padding(Padding::new(20.0))
@@ -923,6 +984,33 @@ impl KernelTab {
             Space::with_width(Length::Shrink).into()
         };
 
+        // Best build for this CPU in the branch, or a warning when the CPU can't run it
+        let fit_badge: Element<Message> = if !kernel.supported {
+            container(
+                text("UNSUPPORTED CPU")
+                    .size(body_font_size * 0.64)
+                    .style(iced::theme::Text::Color(iced::Color::from_rgb(0.9, 0.2, 0.2)))
+            )
+            .padding(Padding::from([3.0, 7.0, 3.0, 7.0]))
+            .style(iced::theme::Container::Custom(Box::new(UnsupportedBadgeStyle {
+                radius: settings.border_radius,
+            })))
+            .into()
+        } else if kernel.recommended {
+            container(
+                text("RECOMMENDED")
+                    .size(body_font_size * 0.64)
+                    .style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings))))
+            )
+            .padding(Padding::from([3.0, 7.0, 3.0, 7.0]))
+            .style(iced::theme::Container::Custom(Box::new(RecommendedBadgeStyle {
+                radius: settings.border_radius,
+            })))
+            .into()
+        } else {
+            Space::with_width(Length::Shrink).into()
+        };
+
         // Action button
         let action_button = if kernel.installed {
             button(
@@ -948,7 +1036,13 @@ impl KernelTab {
                     text(crate::gui::fonts::glyphs::DOWNLOAD_SYMBOL)
                         .font(*material_font)
                         .size(icon_size * 0.78),
-                    text(if is_installing { "Installing..." } else { "Install" })
+                    text(if is_installing {
+                        "Installing..."
+                    } else if !kernel.supported {
+                        "Unsupported"
+                    } else {
+                        "Install"
+                    })
                         .size(button_font_size)
                 ]
                 .spacing(6)
@@ -960,6 +1054,7 @@ impl KernelTab {
             .on_press(if is_installing {
                 Message::Error(())
             } else {
+                // Unsupported builds are refused in update, which explains why
                 Message::InstallKernel(kernel.main_package.clone())
             })
             .padding(Padding::from([10.0, 16.0, 10.0, 16.0]))
@@ -993,6 +1088,7 @@ impl KernelTab {
                         // Right: Badges and action button
                         row![
                             status_badge,
+                            fit_badge,
                             cpu_badge,
                             action_button,
                         ]
@@ -1754,11 +1850,12 @@ async fn select_branch_and_load_kernels(branch_name: String, branches: Vec<Kerne
         }
     };
 
-    // Get CPU feature level and running kernel info in parallel for speed
-    let (cpu_feature_level, running_info) = tokio::join!(
-        get_cpu_feature_level(),
+    // Get CPU info and running kernel info in parallel for speed
+    let (cpu_info, running_info) = tokio::join!(
+        detect_cpu_info(),
         get_running_kernel_info()
     );
+    let cpu_feature_level = cpu_info.level.as_u32();
 
     // Get latest version using the script (same way as original)
     let latest_version = if let Some(pkg) = &db.latest_kernel_version_deter_pkg {
@@ -1793,19 +1890,32 @@ async fn select_branch_and_load_kernels(branch_name: String, branches: Vec<Kerne
     let mut kernels = Vec::new();
     let mut branch_package_names = std::collections::HashSet::new();
 
-    // Pre-filter entries by CPU feature level
-    let compatible_kernels: Vec<_> = db.kernels.into_iter()
-        .filter(|e| e.min_x86_march <= cpu_feature_level)
+    // Incompatible builds stay listed (so users can see why) but are sorted last and can't be installed
+    let mut compatible_kernels: Vec<_> = db.kernels.into_iter()
+        .map(|e| {
+            let unsupported_reason = if e.min_x86_march > cpu_feature_level {
+                Some(format!(
+                    "needs {}, this CPU supports {}",
+                    crate::cpu::X86Level::from_u32(e.min_x86_march).label(),
+                    cpu_info.level.label()
+                ))
+            } else {
+                cpu_info.march_mismatch(&e.main_package)
+                    .or_else(|| cpu_info.march_mismatch(&e.name))
+            };
+            (e, unsupported_reason)
+        })
         .collect();
+    compatible_kernels.sort_by_key(|(_, unsupported_reason)| unsupported_reason.is_some());
 
     // Collect package names first
-    for entry in &compatible_kernels {
+    for (entry, _) in &compatible_kernels {
         branch_package_names.insert(entry.main_package.clone());
     }
 
     // Batch check installed packages in parallel
     let installed_futures: Vec<_> = compatible_kernels.iter()
-        .map(|entry| {
+        .map(|(entry, _)| {
             let pkg = entry.main_package.clone();
             (entry.main_package.clone(), tokio::spawn(async move {
                 let check_output = TokioCommand::new("rpm")
@@ -1827,7 +1937,7 @@ async fn select_branch_and_load_kernels(branch_name: String, branches: Vec<Kerne
 
     // Build kernel list with parallel version/description fetching
     let kernel_futures: Vec<_> = compatible_kernels.into_iter()
-        .map(|entry| {
+        .map(|(entry, unsupported_reason)| {
             let pkg = entry.main_package.clone();
             let installed = installed_map.get(&pkg).copied().unwrap_or(false);
             let branch_name = branch.name.clone();
@@ -1845,6 +1955,9 @@ async fn select_branch_and_load_kernels(branch_name: String, branches: Vec<Kerne
                     installed,
                     branch: branch_name,
                     min_x86_march: entry.min_x86_march,
+                    supported: unsupported_reason.is_none(),
+                    unsupported_reason,
+                    recommended: false,
                 }
             })
         })
@@ -1857,52 +1970,46 @@ async fn select_branch_and_load_kernels(branch_name: String, branches: Vec<Kerne
         }
     }
 
+    mark_recommended_kernel(&mut kernels, &cpu_info);
+
     // Skip repository search for now to keep loading fast
     // Repository kernels can be added later if needed
 
     Ok((branch_name, kernels, running_info, latest_version, db_content))
 }
 
-async fn get_cpu_feature_level() -> u32 {
-    // Detect CPU feature level using ld-linux-x86-64.so.2 (same way as original)
-    // Use blocking spawn for the pipe chain
-    let result = tokio::task::spawn_blocking(|| {
-        use std::process::{Command, Stdio};
-        let base_process = match Command::new("/lib64/ld-linux-x86-64.so.2")
-            .arg("--help")
-            .env("LANG", "en_US")
-            .stdout(Stdio::piped())
-            .spawn()
-        {
-            Ok(p) => p,
-            Err(_) => return 1,
-        };
+async fn detect_cpu_info() -> CpuInfo {
+    tokio::task::spawn_blocking(CpuInfo::detect)
+        .await
+        .unwrap_or_else(|_| CpuInfo::from_cpuinfo(""))
+}
 
-        let grep_output = match Command::new("grep")
-            .arg("(supported, searched)")
-            .stdin(base_process.stdout.unwrap())
-            .output()
-        {
-            Ok(o) => o,
-            Err(_) => return 1,
-        };
+// Pick the best build of the branch for this CPU: an exact -march match (e.g. znver4) wins,
+// otherwise the highest x86-64 level the CPU supports, keeping the branch's order on ties
+fn mark_recommended_kernel(kernels: &mut [EnhancedKernelInfo], cpu_info: &CpuInfo) {
+    let score = |kernel: &EnhancedKernelInfo| {
+        let march_match = cpu_info.matches_march_name(&kernel.main_package)
+            || cpu_info.matches_march_name(&kernel.name);
+        (march_match as u32) * 10 + kernel.min_x86_march
+    };
 
-        if let Ok(stdout) = String::from_utf8(grep_output.stdout) {
-            if let Some(line) = stdout.lines().next() {
-                let level = line.trim_end_matches("(supported, searched)").trim();
-                return match level {
-                    "x86-64-v4" => 4,
-                    "x86-64-v3" => 3,
-                    "x86-64-v2" => 2,
-                    _ => 1,
-                };
-            }
+    let mut best: Option<(usize, u32)> = None;
+    for (idx, kernel) in kernels.iter().enumerate() {
+        if !kernel.supported {
+            continue;
         }
+        let kernel_score = score(kernel);
+        if best.map(|(_, s)| kernel_score > s).unwrap_or(true) {
+            best = Some((idx, kernel_score));
+        }
+    }
 
-        1
-    }).await;
-
-    result.unwrap_or(1)
+    for kernel in kernels.iter_mut() {
+        kernel.recommended = false;
+    }
+    if let Some((idx, _)) = best {
+        kernels[idx].recommended = true;
+    }
 }
 
 async fn get_running_kernel_info() -> RunningKernelInfo {
@@ -2357,6 +2464,42 @@ impl iced::widget::container::StyleSheet for CpuBadgeStyle {
     }
 }
 
+struct RecommendedBadgeStyle {
+    radius: f32,
+}
+impl iced::widget::container::StyleSheet for RecommendedBadgeStyle {
+    type Style = iced::Theme;
+
+    fn appearance(&self, style: &Self::Style) -> Appearance {
+        let palette = style.palette();
+        Appearance {
+            background: Some(iced::Color::from_rgba(
+                palette.primary.r,
+                palette.primary.g,
+                palette.primary.b,
+                0.2,
+            ).into()),
+            border: Border::with_radius(self.radius * 0.25),
+            ..Default::default()
+        }
+    }
+}
+
+struct UnsupportedBadgeStyle {
+    radius: f32,
+}
+impl iced::widget::container::StyleSheet for UnsupportedBadgeStyle {
+    type Style = iced::Theme;
+
+    fn appearance(&self, _style: &Self::Style) -> Appearance {
+        Appearance {
+            background: Some(iced::Color::from_rgba(0.9, 0.2, 0.2, 0.2).into()),
+            border: Border::with_radius(self.radius * 0.25),
+            ..Default::default()
+        }
+    }
+}
+
 struct BranchButtonStyle {
     is_selected: bool,
     radius: f32,
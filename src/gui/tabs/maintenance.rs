@@ -4,6 +4,8 @@ use iced::widget::container::Appearance;
 use iced::widget::button::Appearance as ButtonAppearance;
 use iced::widget::button::StyleSheet as ButtonStyleSheet;
 use tokio::process::Command as TokioCommand;
+use crate::gui::secure_boot::{self, ModuleSignature, SecureBootStatus};
+use crate::gui::flatpak_storage::{self, FlatpakStorage, RefKind};
 
 #[derive(Debug, Clone)]
 pub enum Message {
@@ -17,6 +19,39 @@ pub enum Message {
     CleanPackageCacheComplete,
     RunAllMaintenance,
     AllMaintenanceComplete(Result<String, String>),
+    LoadSecureBootStatus,
+    SecureBootStatusLoaded(SecureBootStatus),
+    GenerateSigningKey,
+    SigningKeyGenerated(Result<String, String>),
+    EnrollSigningKey,
+    SigningKeyEnrolled(Result<String, String>),
+    VerifyModuleSignatures,
+    ModuleSignaturesVerified(Vec<ModuleSignature>),
+    ShowActivityLog,
+    ShowFlatpakStorage,
+    LoadFlatpakStorage,
+    FlatpakStorageLoaded(Result<FlatpakStorage, String>),
+    RemoveUnusedFlatpaks,
+    DeleteFlatpakData(Vec<String>),
+    RepairFlatpak,
+    FlatpakActionComplete(Result<String, String>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FlatpakAction {
+    RemoveUnused,
+    DeleteData,
+    Repair,
+}
+
+impl FlatpakAction {
+    fn label(&self) -> &'static str {
+        match self {
+            FlatpakAction::RemoveUnused => "Removing unused Flatpak runtimes",
+            FlatpakAction::DeleteData => "Deleting app data",
+            FlatpakAction::Repair => "Repairing Flatpak installations",
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -27,6 +62,17 @@ pub struct MaintenanceTab {
     is_cleaning_cache: bool,
     is_running_all: bool,
     output_log: Vec<String>,
+    secure_boot: Option<SecureBootStatus>,
+    is_generating_key: bool,
+    is_enrolling_key: bool,
+    is_verifying_signatures: bool,
+    // Set after a successful mokutil --import until the key shows up as enrolled
+    mok_password: Option<String>,
+    show_flatpak_storage: bool,
+    flatpak_storage: Option<Result<FlatpakStorage, String>>,
+    is_loading_flatpak_storage: bool,
+    // Only one flatpak operation at a time; they all lock the installation
+    flatpak_action: Option<FlatpakAction>,
 }
 
 impl MaintenanceTab {
@@ -38,6 +84,15 @@ impl MaintenanceTab {
             is_cleaning_cache: false,
             is_running_all: false,
             output_log: Vec::new(),
+            secure_boot: None,
+            is_generating_key: false,
+            is_enrolling_key: false,
+            is_verifying_signatures: false,
+            mok_password: None,
+            show_flatpak_storage: false,
+            flatpak_storage: None,
+            is_loading_flatpak_storage: false,
+            flatpak_action: None,
         }
     }
 
@@ -137,9 +192,271 @@ impl MaintenanceTab {
                 }
                 iced::Command::none()
             }
+            Message::LoadSecureBootStatus => {
+                iced::Command::perform(
+                    async { secure_boot::detect_status() },
+                    Message::SecureBootStatusLoaded,
+                )
+            }
+            Message::SecureBootStatusLoaded(status) => {
+                if status.enrollment == secure_boot::KeyEnrollment::Enrolled {
+                    self.mok_password = None;
+                }
+                self.secure_boot = Some(status);
+                iced::Command::none()
+            }
+            Message::GenerateSigningKey => {
+                self.is_generating_key = true;
+                self.output_log.push("Generating akmods signing key...".to_string());
+                iced::Command::perform(secure_boot::generate_signing_key(), Message::SigningKeyGenerated)
+            }
+            Message::SigningKeyGenerated(result) => {
+                self.is_generating_key = false;
+                match result {
+                    Ok(msg) => self.output_log.push(format!("[OK] {}", msg)),
+                    Err(e) => self.output_log.push(format!("[FAIL] {}", e)),
+                }
+                self.update(Message::LoadSecureBootStatus)
+            }
+            Message::EnrollSigningKey => {
+                self.is_enrolling_key = true;
+                self.output_log.push("Queueing akmods key for MOK enrollment...".to_string());
+                iced::Command::perform(secure_boot::enroll_signing_key(), Message::SigningKeyEnrolled)
+            }
+            Message::SigningKeyEnrolled(result) => {
+                self.is_enrolling_key = false;
+                match result {
+                    Ok(password) => {
+                        self.output_log.push("[OK] Signing key queued for enrollment".to_string());
+                        self.output_log.extend(secure_boot::reboot_instructions(&password));
+                        self.mok_password = Some(password);
+                    }
+                    Err(e) => self.output_log.push(format!("[FAIL] {}", e)),
+                }
+                self.update(Message::LoadSecureBootStatus)
+            }
+            Message::VerifyModuleSignatures => {
+                self.is_verifying_signatures = true;
+                self.output_log.push("Checking signatures of akmods-built modules...".to_string());
+                iced::Command::perform(
+                    async { secure_boot::verify_module_signatures() },
+                    Message::ModuleSignaturesVerified,
+                )
+            }
+            Message::ModuleSignaturesVerified(modules) => {
+                self.is_verifying_signatures = false;
+                if modules.is_empty() {
+                    self.output_log.push("No akmods-built modules found under /lib/modules/*/extra".to_string());
+                }
+                for module in &modules {
+                    let line = match (&module.signer, module.matches_akmods_key) {
+                        (None, _) => format!("[FAIL] {} {}: unsigned", module.kernel, module.module),
+                        (Some(signer), Some(false)) => format!(
+                            "[FAIL] {} {}: signed by '{}', not the akmods key",
+                            module.kernel, module.module, signer
+                        ),
+                        (Some(signer), _) => format!("[OK] {} {}: signed by '{}'", module.kernel, module.module, signer),
+                    };
+                    self.output_log.push(line);
+                }
+                iced::Command::none()
+            }
+            Message::ShowActivityLog => {
+                self.show_flatpak_storage = false;
+                iced::Command::none()
+            }
+            Message::ShowFlatpakStorage => {
+                self.show_flatpak_storage = true;
+                if self.flatpak_storage.is_none() {
+                    return self.update(Message::LoadFlatpakStorage);
+                }
+                iced::Command::none()
+            }
+            Message::LoadFlatpakStorage => {
+                self.is_loading_flatpak_storage = true;
+                iced::Command::perform(flatpak_storage::load(), Message::FlatpakStorageLoaded)
+            }
+            Message::FlatpakStorageLoaded(result) => {
+                self.is_loading_flatpak_storage = false;
+                self.flatpak_storage = Some(result);
+                iced::Command::none()
+            }
+            Message::RemoveUnusedFlatpaks => {
+                self.flatpak_action = Some(FlatpakAction::RemoveUnused);
+                self.output_log.push(format!("{}...", FlatpakAction::RemoveUnused.label()));
+                iced::Command::perform(flatpak_storage::remove_unused(), Message::FlatpakActionComplete)
+            }
+            Message::DeleteFlatpakData(app_ids) => {
+                if app_ids.is_empty() {
+                    return iced::Command::none();
+                }
+                self.flatpak_action = Some(FlatpakAction::DeleteData);
+                self.output_log.push(format!("Deleting data of {} removed app(s)...", app_ids.len()));
+                iced::Command::perform(flatpak_storage::delete_app_data(app_ids), Message::FlatpakActionComplete)
+            }
+            Message::RepairFlatpak => {
+                self.flatpak_action = Some(FlatpakAction::Repair);
+                self.output_log.push(format!("{}...", FlatpakAction::Repair.label()));
+                iced::Command::perform(flatpak_storage::repair(), Message::FlatpakActionComplete)
+            }
+            Message::FlatpakActionComplete(result) => {
+                let action = self.flatpak_action.take().map(|a| a.label()).unwrap_or("Flatpak maintenance");
+                match result {
+                    Ok(output) => {
+                        self.output_log.push(format!("[OK] {} finished", action));
+                        self.output_log.extend(output.lines().filter(|l| !l.trim().is_empty()).map(str::to_string));
+                    }
+                    Err(e) => self.output_log.push(format!("[FAIL] {}", e)),
+                }
+                self.update(Message::LoadFlatpakStorage)
+            }
         }
     }
 
+    fn view_flatpak_storage(&self, theme: &crate::gui::Theme, settings: &crate::gui::settings::AppSettings) -> Element<'_, Message> {
+        let body_font_size = (settings.font_size_body * settings.scale_body).round();
+        let button_font_size = (settings.font_size_buttons * settings.scale_buttons).round();
+        let muted = theme.secondary_text_with_settings(Some(settings));
+        let busy = self.flatpak_action.is_some();
+
+        let storage = match &self.flatpak_storage {
+            Some(Ok(storage)) if !self.is_loading_flatpak_storage || !storage.entries.is_empty() => storage,
+            Some(Err(e)) if !self.is_loading_flatpak_storage => {
+                return text(format!("Could not read Flatpak storage: {}", e))
+                    .size(body_font_size)
+                    .style(iced::theme::Text::Color(theme.danger()))
+                    .into();
+            }
+            _ => {
+                return container(text("Measuring Flatpak storage...").size(body_font_size).style(iced::theme::Text::Color(muted)))
+                    .width(Length::Fill)
+                    .height(Length::Fill)
+                    .center_x()
+                    .center_y()
+                    .into();
+            }
+        };
+
+        let small_button = |label: String, is_primary: bool, message: Option<Message>| {
+            button(text(label).size(button_font_size * 0.9))
+                .on_press_maybe(if busy { None } else { message })
+                .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                    is_primary,
+                    radius: settings.border_radius,
+                })))
+                .padding(Padding::from([6.0, 12.0, 6.0, 12.0]))
+        };
+
+        let unused_count = storage.unused().count();
+        let summary = column![
+            text(format!(
+                "Apps {}  ·  Runtimes {}  ·  App data {}",
+                flatpak_storage::format_size(storage.total(RefKind::App)),
+                flatpak_storage::format_size(storage.total(RefKind::Runtime)),
+                flatpak_storage::format_size(storage.data_total()),
+            ))
+            .size(body_font_size),
+            row![
+                text(if unused_count == 0 {
+                    "No unused runtimes".to_string()
+                } else {
+                    format!("{} unused runtime(s) take {}", unused_count, flatpak_storage::format_size(storage.unused_size()))
+                })
+                .size(body_font_size)
+                .style(iced::theme::Text::Color(muted)),
+                Space::with_width(Length::Fill),
+                small_button("Remove Unused".to_string(), unused_count > 0, (unused_count > 0).then_some(Message::RemoveUnusedFlatpaks)),
+                small_button("Refresh".to_string(), false, Some(Message::LoadFlatpakStorage)),
+            ]
+            .spacing(8)
+            .align_items(Alignment::Center),
+        ]
+        .spacing(8);
+
+        let mut list = column![].spacing(6);
+        for entry in &storage.entries {
+            let mut badges = row![].spacing(8);
+            if entry.unused {
+                badges = badges.push(text("Unused").size(body_font_size * 0.85).style(iced::theme::Text::Color(muted)));
+            }
+            if let Some(ref reason) = entry.eol {
+                badges = badges.push(
+                    text(format!("End of life: {}", reason))
+                        .size(body_font_size * 0.85)
+                        .style(iced::theme::Text::Color(theme.danger())),
+                );
+            }
+            list = list.push(
+                container(
+                    row![
+                        column![
+                            text(&entry.name).size(body_font_size),
+                            text(format!("{} {}", entry.id, entry.branch)).size(body_font_size * 0.85).style(iced::theme::Text::Color(muted)),
+                            badges,
+                        ]
+                        .spacing(2)
+                        .width(Length::FillPortion(4)),
+                        text(match entry.kind {
+                            RefKind::App => "App",
+                            RefKind::Runtime => "Runtime",
+                        })
+                        .size(body_font_size * 0.9)
+                        .width(Length::FillPortion(1)),
+                        text(entry.installation.label()).size(body_font_size * 0.9).width(Length::FillPortion(1)),
+                        text(flatpak_storage::format_size(entry.size)).size(body_font_size * 0.9).width(Length::FillPortion(1)),
+                        text(if entry.data_size > 0 { format!("+{} data", flatpak_storage::format_size(entry.data_size)) } else { String::new() })
+                            .size(body_font_size * 0.9)
+                            .style(iced::theme::Text::Color(muted))
+                            .width(Length::FillPortion(1)),
+                    ]
+                    .spacing(12)
+                    .align_items(Alignment::Center)
+                )
+                .width(Length::Fill)
+                .padding(Padding::new(10.0))
+                .style(iced::theme::Container::Custom(Box::new(LogItemStyle {
+                    radius: settings.border_radius,
+                }))),
+            );
+        }
+
+        if !storage.leftover_data.is_empty() {
+            let all_ids: Vec<String> = storage.leftover_data.iter().map(|d| d.app_id.clone()).collect();
+            list = list.push(Space::with_height(Length::Fixed(12.0))).push(
+                row![
+                    text(format!("Data of removed apps ({})", flatpak_storage::format_size(storage.leftover_size())))
+                        .size(body_font_size)
+                        .style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
+                    Space::with_width(Length::Fill),
+                    small_button("Delete All".to_string(), false, Some(Message::DeleteFlatpakData(all_ids))),
+                ]
+                .align_items(Alignment::Center),
+            );
+            for data in &storage.leftover_data {
+                list = list.push(
+                    container(
+                        row![
+                            text(format!("~/.var/app/{}", data.app_id)).size(body_font_size * 0.9).width(Length::Fill),
+                            text(flatpak_storage::format_size(data.size)).size(body_font_size * 0.9),
+                            small_button("Delete".to_string(), false, Some(Message::DeleteFlatpakData(vec![data.app_id.clone()]))),
+                        ]
+                        .spacing(12)
+                        .align_items(Alignment::Center)
+                    )
+                    .width(Length::Fill)
+                    .padding(Padding::new(10.0))
+                    .style(iced::theme::Container::Custom(Box::new(LogItemStyle {
+                        radius: settings.border_radius,
+                    }))),
+                );
+            }
+        }
+
+        column![summary, scrollable(list.padding(Padding::new(4.0))).height(Length::Fill)]
+            .spacing(12)
+            .into()
+    }
+
     pub fn view(&self, theme: &crate::gui::Theme, settings: &crate::gui::settings::AppSettings) -> Element<'_, Message> {
         let material_font = crate::gui::fonts::get_material_symbols_font();
 
@@ -255,6 +572,90 @@ impl MaintenanceTab {
             radius: settings.border_radius,
         })));
 
+        let secure_boot_section = {
+            let mut status_column = column![
+                text("Secure Boot")
+                    .size(title_font_size * 0.6)
+                    .style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings))))
+                    .width(Length::Fill),
+                Space::with_height(Length::Fixed(12.0)),
+            ]
+            .spacing(0);
+
+            match &self.secure_boot {
+                Some(status) => {
+                    status_column = status_column
+                        .push(text(format!("State: {}", status.state_label())).size(body_font_size))
+                        .push(
+                            text(format!("akmods signing key: {}", status.enrollment_label()))
+                                .size(body_font_size),
+                        );
+                    if status.needs_attention() {
+                        status_column = status_column.push(Space::with_height(Length::Fixed(8.0))).push(
+                            text("Kernel modules built by akmods (NVIDIA, VirtualBox, ...) will not load until the signing key is enrolled.")
+                                .size(body_font_size)
+                                .style(iced::theme::Text::Color(theme.danger()))
+                                .width(Length::Fill),
+                        );
+                    }
+                }
+                None => {
+                    status_column = status_column.push(
+                        text("Checking Secure Boot state...")
+                            .size(body_font_size)
+                            .style(iced::theme::Text::Color(theme.secondary_text_with_settings(Some(settings)))),
+                    );
+                }
+            }
+
+            if let Some(password) = &self.mok_password {
+                let instructions = secure_boot::reboot_instructions(password)
+                    .into_iter()
+                    .fold(column![].spacing(4), |col, line| col.push(text(line).size(body_font_size)));
+                status_column = status_column.push(Space::with_height(Length::Fixed(12.0))).push(
+                    container(instructions)
+                        .width(Length::Fill)
+                        .padding(Padding::new(16.0))
+                        .style(iced::theme::Container::Custom(Box::new(ActionCardStyle {
+                            radius: settings.border_radius,
+                        }))),
+                );
+            }
+
+            container(
+                status_column
+                    .push(Space::with_height(Length::Fixed(16.0)))
+                    .push(create_action_card(
+                        crate::gui::fonts::glyphs::SETTINGS_SYMBOL,
+                        "Generate Signing Key",
+                        "Creates the akmods module signing key with kmodgenca. Existing keys are kept.",
+                        self.is_generating_key,
+                        Message::GenerateSigningKey,
+                    ))
+                    .push(Space::with_height(Length::Fixed(12.0)))
+                    .push(create_action_card(
+                        crate::gui::fonts::glyphs::CHECK_SYMBOL,
+                        "Enroll Signing Key",
+                        "Queues the key with mokutil --import. Confirm it in MokManager on the next reboot.",
+                        self.is_enrolling_key,
+                        Message::EnrollSigningKey,
+                    ))
+                    .push(Space::with_height(Length::Fixed(12.0)))
+                    .push(create_action_card(
+                        crate::gui::fonts::glyphs::REFRESH_SYMBOL,
+                        "Verify Module Signatures",
+                        "Checks that every akmods-built module is signed with the enrolled key.",
+                        self.is_verifying_signatures,
+                        Message::VerifyModuleSignatures,
+                    )),
+            )
+            .width(Length::Fill)
+            .padding(Padding::new(24.0))
+            .style(iced::theme::Container::Custom(Box::new(SectionCardStyle {
+                radius: settings.border_radius,
+            })))
+        };
+
         let package_section = container(
             column![
                 text("Package Maintenance")
@@ -286,6 +687,45 @@ impl MaintenanceTab {
             radius: settings.border_radius,
         })));
 
+        let flatpak_section = container(
+            column![
+                text("Flatpak Maintenance")
+                    .size(title_font_size * 0.6)
+                    .style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings))))
+                    .width(Length::Fill),
+                Space::with_height(Length::Fixed(16.0)),
+                create_action_card(
+                    crate::gui::fonts::glyphs::FOLDER_SYMBOL,
+                    "Show Storage Usage",
+                    "Disk usage of every app and runtime including its data in ~/.var/app, unused and end-of-life runtimes, and data left behind by removed apps.",
+                    self.is_loading_flatpak_storage,
+                    Message::ShowFlatpakStorage
+                ),
+                Space::with_height(Length::Fixed(12.0)),
+                create_action_card(
+                    crate::gui::fonts::glyphs::DELETE_SYMBOL,
+                    "Remove Unused Runtimes",
+                    "Runs flatpak uninstall --unused for the system and user installations.",
+                    self.flatpak_action == Some(FlatpakAction::RemoveUnused),
+                    Message::RemoveUnusedFlatpaks
+                ),
+                Space::with_height(Length::Fixed(12.0)),
+                create_action_card(
+                    crate::gui::fonts::glyphs::SETTINGS_SYMBOL,
+                    "Repair Flatpak",
+                    "Verifies installed files with flatpak repair and re-downloads anything missing or corrupt.",
+                    self.flatpak_action == Some(FlatpakAction::Repair),
+                    Message::RepairFlatpak
+                ),
+            ]
+            .spacing(0)
+        )
+        .width(Length::Fill)
+        .padding(Padding::new(24.0))
+        .style(iced::theme::Container::Custom(Box::new(SectionCardStyle {
+            radius: settings.border_radius,
+        })));
+
         let run_all_button = if self.is_running_all {
             button(
                 row![
@@ -338,8 +778,12 @@ impl MaintenanceTab {
             column![
                 kernel_section,
                 Space::with_height(Length::Fixed(20.0)),
+                secure_boot_section,
+                Space::with_height(Length::Fixed(20.0)),
                 package_section,
                 Space::with_height(Length::Fixed(20.0)),
+                flatpak_section,
+                Space::with_height(Length::Fixed(20.0)),
                 run_all_section,
             ]
             .spacing(0)
@@ -347,19 +791,30 @@ impl MaintenanceTab {
         .width(Length::Fill)
         .height(Length::Fill);
 
+        let pane_button = |label: &'static str, selected: bool, message: Message| {
+            button(text(label).size(title_font_size * 0.5))
+                .on_press(message)
+                .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                    is_primary: selected,
+                    radius: settings.border_radius,
+                })))
+                .padding(Padding::from([8.0, 14.0, 8.0, 14.0]))
+        };
         let log_header = container(
             row![
-                text("Activity Log")
-                    .size(title_font_size * 0.65)
-                    .style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
+                pane_button("Activity Log", !self.show_flatpak_storage, Message::ShowActivityLog),
+                pane_button("Flatpak Storage", self.show_flatpak_storage, Message::ShowFlatpakStorage),
                 Space::with_width(Length::Fill),
             ]
+            .spacing(8)
             .align_items(Alignment::Center)
         )
         .width(Length::Fill)
         .padding(0.0);
 
-        let log_content: Element<Message> = if self.output_log.is_empty() {
+        let log_content: Element<Message> = if self.show_flatpak_storage {
+            self.view_flatpak_storage(theme, settings)
+        } else if self.output_log.is_empty() {
             container(
                 column![
                     Space::with_height(Length::Fill),
@@ -543,7 +998,14 @@ async fn rebuild_kernel_modules_streaming() -> Result<String, String> {
             status.code().unwrap_or(-1), combined_output));
     }
 
-    Ok(format!("Kernel modules rebuilt successfully\n\n{}", combined_output))
+    let report = tokio::task::spawn_blocking(crate::gui::secure_boot::post_rebuild_report)
+        .await
+        .unwrap_or_default();
+    Ok(format!(
+        "Kernel modules rebuilt successfully\n\n{}\n{}",
+        combined_output,
+        report
+    ))
 }
 
 async fn regenerate_initramfs_streaming() -> Result<String, String> {
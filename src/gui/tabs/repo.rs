@@ -2210,7 +2210,7 @@ async fn install_nvidia_repo() -> Result<(), String> {
     }
 }
 
-async fn install_rpmfusion_repos() -> Result<(), String> {
+pub async fn install_rpmfusion_repos() -> Result<(), String> {
     use tokio::process::Command as TokioCommand;
     use std::process::Command as StdCommand;
 
@@ -1,4 +1,4 @@
-use iced::widget::{button, checkbox, column, container, row, scrollable, text, text_input, Space};
+use iced::widget::{button, checkbox, column, container, pick_list, row, scrollable, text, text_input, Space};
 use iced::{Alignment, Element, Length, Padding, Border};
 use iced::widget::container::Appearance;
 use iced::widget::button::Appearance as ButtonAppearance;
@@ -8,6 +8,14 @@ use iced::widget::checkbox::StyleSheet as CheckboxStyleSheet;
 use iced::widget::text_input::Appearance as TextInputAppearance;
 use iced::widget::text_input::StyleSheet as TextInputStyleSheet;
 use crate::logger;
+use crate::gui::appstream::{self, Catalog};
+use crate::gui::package_versions::{self, PackageVersions, VersionAction};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+// Rendering thousands of catalog rows per frame is slow; categories narrow it down
+const BROWSE_LIMIT: usize = 200;
+const ALL_CATEGORIES: &str = "All categories";
 
 #[derive(Debug, Clone)]
 pub enum Message {
@@ -18,6 +26,16 @@ pub enum Message {
     InstallSelected,
     InstallComplete,
     Error(String),
+    LoadCatalog,
+    CatalogLoaded(Arc<Catalog>),
+    SelectCategory(String),
+    ShowApp(String),
+    CloseApp,
+    ScreenshotLoaded(String, Result<PathBuf, String>),
+    ShowVersions(String),
+    VersionsLoaded(Result<PackageVersions, String>),
+    VersionAction(VersionAction),
+    VersionActionComplete(Result<String, String>),
 }
 
 #[derive(Debug, Clone)]
@@ -38,6 +56,16 @@ pub struct SearchTab {
     selected_packages: std::collections::HashSet<String>,
     is_searching: bool,
     is_installing: bool,
+    catalog: Option<Arc<Catalog>>,
+    browse_category: Option<String>,
+    // Package name of the catalog app shown in the side panel
+    selected_app: Option<String>,
+    screenshot: Option<PathBuf>,
+    // Package whose available versions are shown in the side panel
+    versions_package: Option<String>,
+    versions: Option<Result<PackageVersions, String>>,
+    is_changing_version: bool,
+    version_status: Option<Result<String, String>>,
 }
 
 impl SearchTab {
@@ -48,6 +76,14 @@ impl SearchTab {
             selected_packages: std::collections::HashSet::new(),
             is_searching: false,
             is_installing: false,
+            catalog: None,
+            browse_category: None,
+            selected_app: None,
+            screenshot: None,
+            versions_package: None,
+            versions: None,
+            is_changing_version: false,
+            version_status: None,
         }
     }
 
@@ -151,6 +187,87 @@ impl SearchTab {
                 self.is_installing = false;
                 iced::Command::none()
             }
+            Message::LoadCatalog => {
+                if self.catalog.is_some() {
+                    return iced::Command::none();
+                }
+                iced::Command::perform(appstream::catalog(), Message::CatalogLoaded)
+            }
+            Message::CatalogLoaded(catalog) => {
+                logger::Logger::log_debug(&format!("[Search Tab] AppStream catalog loaded: {:?}", catalog));
+                self.catalog = Some(catalog);
+                iced::Command::none()
+            }
+            Message::SelectCategory(label) => {
+                self.browse_category = appstream::CATEGORIES
+                    .iter()
+                    .find(|(_, l)| *l == label)
+                    .map(|(id, _)| id.to_string());
+                iced::Command::none()
+            }
+            Message::ShowApp(package) => {
+                self.screenshot = None;
+                let url = self
+                    .catalog
+                    .as_ref()
+                    .and_then(|c| c.find_package(&package))
+                    .and_then(|a| a.screenshots.first().cloned());
+                self.selected_app = Some(package.clone());
+                self.versions_package = None;
+                match url {
+                    Some(url) => iced::Command::perform(appstream::fetch_screenshot(url), move |result| {
+                        Message::ScreenshotLoaded(package.clone(), result)
+                    }),
+                    None => iced::Command::none(),
+                }
+            }
+            Message::CloseApp => {
+                self.selected_app = None;
+                self.screenshot = None;
+                self.versions_package = None;
+                self.versions = None;
+                iced::Command::none()
+            }
+            Message::ShowVersions(package) => {
+                self.selected_app = None;
+                self.screenshot = None;
+                self.versions_package = Some(package.clone());
+                self.versions = None;
+                self.version_status = None;
+                iced::Command::perform(package_versions::load(package), Message::VersionsLoaded)
+            }
+            Message::VersionsLoaded(result) => {
+                // Ignore a late answer for a package that is no longer shown
+                if let Ok(ref versions) = result {
+                    if self.versions_package.as_deref() != Some(versions.name.as_str()) {
+                        return iced::Command::none();
+                    }
+                }
+                self.versions = Some(result);
+                iced::Command::none()
+            }
+            Message::VersionAction(action) => {
+                self.is_changing_version = true;
+                self.version_status = None;
+                iced::Command::perform(package_versions::apply(action), Message::VersionActionComplete)
+            }
+            Message::VersionActionComplete(result) => {
+                self.is_changing_version = false;
+                self.version_status = Some(result);
+                match self.versions_package.clone() {
+                    Some(package) => iced::Command::perform(package_versions::load(package), Message::VersionsLoaded),
+                    None => iced::Command::none(),
+                }
+            }
+            Message::ScreenshotLoaded(package, result) => {
+                if self.selected_app.as_deref() == Some(package.as_str()) {
+                    match result {
+                        Ok(path) => self.screenshot = Some(path),
+                        Err(e) => logger::Logger::log_debug(&format!("[Search Tab] {}", e)),
+                    }
+                }
+                iced::Command::none()
+            }
         }
     }
 
@@ -248,13 +365,7 @@ impl SearchTab {
                 .into()
         } else {
             let package_list: Element<Message> = if self.packages.is_empty() {
-                container(text("Enter a search query to find packages").size(body_font_size))
-                    .width(Length::Fill)
-                    .padding(20)
-                    .style(iced::theme::Container::Custom(Box::new(RoundedMessageStyle {
-                        radius: settings.border_radius,
-                    })))
-                    .into()
+                self.view_catalog(theme, settings)
             } else {
                 scrollable(
                     column(
@@ -291,8 +402,14 @@ impl SearchTab {
                                     Space::with_height(Length::Shrink).into()
                                 };
 
+                                let app_icon: Element<Message> = match self.catalog.as_ref().and_then(|c| c.find_package(&pkg.name)) {
+                                    Some(app) => appstream::icon(Some(app), 40.0),
+                                    None => Space::with_width(Length::Shrink).into(),
+                                };
+
                                 let header = row![
                                     checkbox_widget,
+                                    app_icon,
                                     column![
                                         row![
                                             text(&pkg.name)
@@ -363,12 +480,22 @@ impl SearchTab {
                                     Space::with_width(Length::Shrink).into()
                                 };
 
+                                let versions_button = button(text("Versions").size(package_detail_size * 0.85))
+                                    .on_press(Message::ShowVersions(pkg.name.clone()))
+                                    .padding(Padding::from([4.0, 10.0, 4.0, 10.0]))
+                                    .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                                        is_primary: self.versions_package.as_deref() == Some(pkg.name.as_str()),
+                                        radius: settings.border_radius,
+                                    })));
+
                                 let metadata = row![
                                     arch_info,
                                     Space::with_width(Length::Fill),
                                     size_info,
+                                    versions_button,
                                 ]
                                 .width(Length::Fill)
+                                .align_items(Alignment::Center)
                                 .spacing(8);
 
                                 container(
@@ -400,9 +527,269 @@ impl SearchTab {
             column![install_button, package_list].spacing(10).into()
         };
 
-        container(column![search_row, content].spacing(15).padding(20))
+        let main = column![search_row, content].spacing(15).padding(20);
+        let app = self
+            .selected_app
+            .as_ref()
+            .and_then(|package| self.catalog.as_ref()?.find_package(package));
+        let panel = match (app, self.versions_package.as_deref()) {
+            (Some(app), _) => Some(self.view_app_panel(app, theme, settings)),
+            (None, Some(package)) => Some(self.view_versions_panel(package, theme, settings)),
+            (None, None) => None,
+        };
+        match panel {
+            Some(panel) => container(
+                row![
+                    container(main).width(Length::FillPortion(3)),
+                    container(panel)
+                        .width(Length::FillPortion(2))
+                        .padding(Padding::from([20.0, 20.0, 20.0, 0.0])),
+                ]
+            )
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into(),
+            None => container(main)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into(),
+        }
+    }
+
+    // Shown while the search box is empty: apps from the RPM appstream-data catalog
+    fn view_catalog(&self, theme: &crate::gui::Theme, settings: &crate::gui::settings::AppSettings) -> Element<'_, Message> {
+        let body_font_size = settings.font_size_body * settings.scale_body;
+        let package_name_size = settings.font_size_package_names * settings.scale_package_cards;
+        let package_detail_size = settings.font_size_package_details * settings.scale_package_cards;
+        let muted = theme.secondary_text_with_settings(Some(settings));
+
+        let message = |label: &str| -> Element<'_, Message> {
+            container(text(label.to_string()).size(body_font_size))
+                .width(Length::Fill)
+                .padding(20)
+                .style(iced::theme::Container::Custom(Box::new(RoundedMessageStyle {
+                    radius: settings.border_radius,
+                })))
+                .into()
+        };
+        let Some(catalog) = self.catalog.as_ref() else {
+            return message("Enter a search query to find packages");
+        };
+        let apps = catalog.browse(false, self.browse_category.as_deref());
+        if apps.is_empty() && self.browse_category.is_none() {
+            return message("Enter a search query to find packages (install appstream-data to browse applications)");
+        }
+
+        let mut categories = vec![ALL_CATEGORIES.to_string()];
+        categories.extend(catalog.categories(false).iter().map(|c| appstream::category_label(c).to_string()));
+        let selected_category = self
+            .browse_category
+            .as_deref()
+            .map(appstream::category_label)
+            .unwrap_or(ALL_CATEGORIES)
+            .to_string();
+        let count = if apps.len() > BROWSE_LIMIT {
+            format!("Showing {} of {} applications", BROWSE_LIMIT, apps.len())
+        } else {
+            format!("{} applications", apps.len())
+        };
+        let toolbar = row![
+            pick_list(categories, Some(selected_category), Message::SelectCategory).text_size(body_font_size),
+            Space::with_width(Length::Fill),
+            text(count).size(package_detail_size).style(iced::theme::Text::Color(muted)),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center);
+
+        let rows = apps.into_iter().take(BROWSE_LIMIT).filter_map(|app| {
+            let package = app.package()?.to_string();
+            let is_selected = self.selected_packages.contains(&package);
+            let toggle_package = package.clone();
+            let row_content = row![
+                checkbox("", is_selected)
+                    .on_toggle(move |_| Message::TogglePackage(toggle_package.clone()))
+                    .style(iced::theme::Checkbox::Custom(Box::new(RoundedCheckboxStyle {
+                        radius: settings.border_radius,
+                    }))),
+                appstream::icon(Some(app), 40.0),
+                column![
+                    text(&app.name)
+                        .size(package_name_size)
+                        .style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
+                    text(&app.summary)
+                        .size(package_detail_size)
+                        .shaping(iced::widget::text::Shaping::Advanced),
+                ]
+                .spacing(2)
+                .width(Length::Fill),
+                text(&app.license)
+                    .size(package_detail_size * 0.85)
+                    .style(iced::theme::Text::Color(muted)),
+            ]
+            .spacing(12)
+            .align_items(Alignment::Center);
+            Some(
+                button(row_content)
+                    .on_press(Message::ShowApp(package))
+                    .padding(Padding::new(12.0))
+                    .width(Length::Fill)
+                    .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                        is_primary: false,
+                        radius: settings.border_radius,
+                    })))
+                    .into(),
+            )
+        });
+
+        column![
+            toolbar,
+            scrollable(column(rows.collect::<Vec<Element<Message>>>()).spacing(6).padding(10)).height(Length::Fill),
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    fn view_app_panel<'a>(
+        &'a self,
+        app: &'a appstream::AppComponent,
+        theme: &crate::gui::Theme,
+        settings: &crate::gui::settings::AppSettings,
+    ) -> Element<'a, Message> {
+        use crate::gui::fonts::glyphs;
+        let body_font_size = settings.font_size_body * settings.scale_body;
+        let button_font_size = settings.font_size_buttons * settings.scale_buttons;
+        let icon_size = (settings.font_size_icons * settings.scale_icons).round();
+        let package = app.package().unwrap_or_default().to_string();
+        let is_selected = self.selected_packages.contains(&package);
+
+        let close_button = button(text(glyphs::CLOSE_SYMBOL).font(crate::gui::fonts::get_material_symbols_font()).size(icon_size))
+            .on_press(Message::CloseApp)
+            .padding(Padding::new(6.0))
+            .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                is_primary: false,
+                radius: settings.border_radius,
+            })));
+        let select_button = button(text(if is_selected { "Remove from selection" } else { "Select for install" }).size(button_font_size))
+            .on_press(Message::TogglePackage(package.clone()))
+            .padding(Padding::from([8.0, 12.0, 8.0, 12.0]))
+            .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                is_primary: !is_selected,
+                radius: settings.border_radius,
+            })));
+
+        container(
+            scrollable(
+                column![
+                    row![
+                        text(format!("Package: {}", package))
+                            .size(body_font_size * 0.9)
+                            .style(iced::theme::Text::Color(theme.secondary_text_with_settings(Some(settings)))),
+                        Space::with_width(Length::Fill),
+                        close_button,
+                    ]
+                    .align_items(Alignment::Center),
+                    appstream::details(
+                        app,
+                        self.screenshot.as_deref(),
+                        body_font_size,
+                        theme.primary_with_settings(Some(settings)),
+                        theme.secondary_text_with_settings(Some(settings)),
+                    ),
+                    row![
+                        select_button,
+                        button(text("Versions").size(button_font_size))
+                            .on_press(Message::ShowVersions(package))
+                            .padding(Padding::from([8.0, 12.0, 8.0, 12.0]))
+                            .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                                is_primary: false,
+                                radius: settings.border_radius,
+                            }))),
+                    ]
+                    .spacing(8),
+                ]
+                .spacing(12)
+                .padding(16),
+            )
+            .height(Length::Fill),
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(iced::theme::Container::Custom(Box::new(RoundedMessageStyle {
+            radius: settings.border_radius,
+        })))
+        .into()
+    }
+
+    fn view_versions_panel<'a>(
+        &'a self,
+        package: &'a str,
+        theme: &crate::gui::Theme,
+        settings: &crate::gui::settings::AppSettings,
+    ) -> Element<'a, Message> {
+        let body_font_size = settings.font_size_body * settings.scale_body;
+        let package_detail_size = settings.font_size_package_details * settings.scale_package_cards;
+        let icon_size = (settings.font_size_icons * settings.scale_icons).round();
+
+        let close_button = button(text(crate::gui::fonts::glyphs::CLOSE_SYMBOL).font(crate::gui::fonts::get_material_symbols_font()).size(icon_size))
+            .on_press(Message::CloseApp)
+            .padding(Padding::new(6.0))
+            .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                is_primary: false,
+                radius: settings.border_radius,
+            })));
+
+        let mut content = column![
+            row![
+                text(format!("Versions of {}", package))
+                    .size(body_font_size)
+                    .style(iced::theme::Text::Color(theme.primary_with_settings(Some(settings)))),
+                Space::with_width(Length::Fill),
+                close_button,
+            ]
+            .align_items(Alignment::Center),
+        ]
+        .spacing(12)
+        .padding(16);
+
+        if self.is_changing_version {
+            content = content.push(text("Applying change...").size(package_detail_size));
+        }
+        if let Some(ref status) = self.version_status {
+            content = content.push(
+                text(match status {
+                    Ok(message) => message.as_str(),
+                    Err(e) => e.as_str(),
+                })
+                .size(package_detail_size)
+                .style(iced::theme::Text::Color(match status {
+                    Ok(_) => theme.primary_with_settings(Some(settings)),
+                    Err(_) => iced::Color::from_rgb(0.9, 0.3, 0.3),
+                })),
+            );
+        }
+        let list: Element<Message> = match self.versions {
+            None => text("Loading versions...").size(package_detail_size).into(),
+            Some(Err(ref e)) => text(e).size(package_detail_size).into(),
+            Some(Ok(ref versions)) => package_versions::view(
+                versions,
+                self.is_changing_version,
+                Message::VersionAction,
+                |is_primary| iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                    is_primary,
+                    radius: settings.border_radius,
+                })),
+                package_detail_size,
+                theme.primary_with_settings(Some(settings)),
+                theme.secondary_text_with_settings(Some(settings)),
+            ),
+        };
+
+        container(scrollable(content.push(list)).height(Length::Fill))
             .width(Length::Fill)
             .height(Length::Fill)
+            .style(iced::theme::Container::Custom(Box::new(RoundedMessageStyle {
+                radius: settings.border_radius,
+            })))
             .into()
     }
 }
@@ -1,4 +1,4 @@
-use iced::widget::{button, column, container, row, scrollable, text, text_input, Space, checkbox, progress_bar};
+use iced::widget::{button, column, container, row, scrollable, text, text_input, Space, checkbox, pick_list, progress_bar};
 use iced::{Alignment, Element, Length, Padding, Border, Color};
 use iced::widget::container::Appearance;
 use iced::widget::button::Appearance as ButtonAppearance;
@@ -6,15 +6,27 @@ use iced::widget::button::StyleSheet as ButtonStyleSheet;
 use iced::widget::text_input::Appearance as TextInputAppearance;
 use iced::widget::text_input::StyleSheet as TextInputStyleSheet;
 use crate::gui::app::CustomScrollableStyle;
+use crate::gui::launchers::{self, CustomLaunchers, DetectedLauncher};
+use crate::gui::mangohud::{self, MangoHudConfig, MangoHudPreset};
+use crate::gui::multimedia::{self, MultimediaStatus, MultimediaStep, VaInfo};
+use crate::gui::performance::{self, KnobStatus, TuningKnob};
+use crate::gui::proton_catalog::{self, CatalogSettings, CatalogSource};
+use crate::gui::proton_download;
+use crate::gui::proton_policy::{ProtonPolicies, ProtonPolicy};
+use crate::gui::steam::{self, SteamLibrary};
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TweaksView {
     GamingMeta,
+    Performance,
+    MangoHud,
     DnfConfig,
     CachyosKernel,
     Hyprland,
     Proton,
+    SteamGames,
+    Multimedia,
 }
 
 #[derive(Debug, Clone)]
@@ -37,7 +49,7 @@ pub enum Message {
     HyprlandStatusChecked(Result<HyprlandStatus, String>),
     InstallHyprland,
     LoadProtonBuilds,
-    ProtonBuildsLoaded(Result<Vec<ProtonRunner>, String>),
+    ProtonBuildsLoaded(Result<(Vec<ProtonRunner>, Option<String>), String>),
     LaunchersDetected(Result<Vec<DetectedLauncher>, String>),
     SelectProtonRunner(String),
     SelectLauncher(String),
@@ -46,7 +58,7 @@ pub enum Message {
     ToggleFilterUnused,
     ProtonUsageChecked(Result<Vec<ProtonRunner>, String>),
     DownloadProtonBuild(String, String, String),
-    ProtonBuildDownloaded(Result<(String, String, String), String>),
+    ProtonBuildDownloaded(Result<(String, String, String, String), String>),
     ProtonBuildInstalled(Result<(String, String), String>),
     CloseCompletionDialog,
     RemoveProtonBuild(String, String),
@@ -56,7 +68,53 @@ pub enum Message {
     OpenProtonBuildDirectory(String, String),
     ShowProtonBuildInfo(String, String, String, String),
     LoadMoreProtonBuilds(String),
-    MoreProtonBuildsLoaded(Result<(String, Vec<ProtonBuild>), String>),
+    MoreProtonBuildsLoaded(Result<(String, Vec<ProtonBuild>, Option<String>), String>),
+    MigrateProtonBuildUsers(String, String),
+    LoadSteamGames,
+    SteamGamesLoaded(Result<SteamLibrary, String>),
+    AssignCompatTool(String, String),
+    MigrateFromSelected(String),
+    MigrateToSelected(String),
+    MigrateCompatTool,
+    CompatToolsChanged(Result<String, String>),
+    PolicyKeepChanged(String),
+    PolicyAutoInstallToggled(bool),
+    PolicyProtectToggled(bool),
+    SaveProtonPolicy,
+    RemoveProtonPolicy,
+    ApplyProtonPolicy,
+    ProtonPolicyApplied(Result<Vec<String>, String>),
+    ToggleInstallTarget(String, bool),
+    CustomLauncherPathChanged(String),
+    CustomLauncherKindSelected(String),
+    AddCustomLauncher,
+    RemoveCustomLauncher(String),
+    ToggleCatalogSettings,
+    CatalogSourceSelected(String),
+    CatalogLocationChanged(String),
+    CatalogTokenChanged(String),
+    CatalogApiBaseChanged(String),
+    SaveCatalogSettings,
+    CheckPerformanceStatus,
+    PerformanceStatusChecked(Vec<KnobStatus>),
+    ApplyTuning(TuningKnob),
+    RevertTuning(TuningKnob),
+    TuningFinished(TuningKnob, Result<String, String>),
+    SelectMangoHudTarget(String),
+    MangoHudAppNameChanged(String),
+    AddMangoHudApp,
+    MangoHudOptionToggled(String, bool),
+    MangoHudValueChanged(String, String),
+    MangoHudPositionSelected(String),
+    ApplyMangoHudPreset(MangoHudPreset),
+    SaveMangoHud,
+    ReloadMangoHud,
+    CheckMultimediaStatus,
+    MultimediaStatusChecked(MultimediaStatus),
+    RunMultimediaStep(MultimediaStep),
+    MultimediaStepFinished(MultimediaStep, Result<String, String>),
+    VerifyVaapi,
+    VaapiVerified(Result<VaInfo, String>),
 }
 
 #[derive(Debug, Clone)]
@@ -137,6 +195,9 @@ pub struct ProtonBuild {
     pub download_url: String,
     pub page_url: String,
     pub download_size: u64,
+    // `.sha512sum`/`.sha256sum` asset published with the release, if any
+    #[serde(default)]
+    pub checksum_url: Option<String>,
     pub runner_title: String,
     #[serde(skip)]
     pub is_installed: bool,
@@ -162,13 +223,12 @@ pub struct ProtonRunner {
     pub builds: Vec<ProtonBuild>,
     pub has_latest_support: bool,
     pub compat_layer_type: String,
-}
-
-#[derive(Debug, Clone)]
-pub struct DetectedLauncher {
-    pub title: String,
-    pub directory: String,
-    pub installation_type: String,
+    // Why the release listing could not be fetched (rate limit, network), shown instead of builds
+    #[serde(default)]
+    pub fetch_error: Option<String>,
+    // Builds came from the cached listing because the fetch failed (rate limit); never cached itself
+    #[serde(skip)]
+    pub fetch_warning: Option<String>,
 }
 
 #[derive(Debug)]
@@ -199,7 +259,11 @@ pub struct TweaksTab {
     is_loading_proton_builds: bool,
     is_detecting_launchers: bool,
     proton_builds_error: Option<String>,
+    // The configured catalog source failed and the bundled runner list is shown instead
+    catalog_warning: Option<String>,
     downloading_build: Option<String>,
+    // Checksum result of the last download, shown until its install finishes
+    download_verification: Option<String>,
     installing_build: Option<String>,
     download_progress: f32,
     install_progress: f32,
@@ -212,10 +276,55 @@ pub struct TweaksTab {
     show_installed_only: bool,
     show_used_only: bool,
     show_unused_only: bool,
+
+    steam_library: Option<SteamLibrary>,
+    is_loading_steam_games: bool,
+    is_changing_compat_tools: bool,
+    steam_games_error: Option<String>,
+    steam_games_status: Option<Result<String, String>>,
+    migrate_from: Option<String>,
+    migrate_to: Option<String>,
+
+    proton_policies: ProtonPolicies,
+    policy_keep_input: String,
+    policy_auto_install: bool,
+    policy_protect_in_use: bool,
+    is_applying_policy: bool,
+
+    // Launchers installed into alongside the selected one
+    install_targets: Vec<String>,
+    custom_launcher_path: String,
+    custom_launcher_kind: Option<String>,
+
+    show_catalog_settings: bool,
+    catalog_source_label: String,
+    catalog_location: String,
+    catalog_token: String,
+    catalog_api_base: String,
+
+    performance_status: Option<Vec<KnobStatus>>,
+    is_checking_performance: bool,
+    running_tuning: Option<TuningKnob>,
+    performance_log: Vec<String>,
+
+    mangohud_targets: Vec<String>,
+    mangohud_target: String,
+    mangohud_config: Option<MangoHudConfig>,
+    // Last saved or loaded state, to tell whether there are unsaved edits
+    mangohud_saved: Option<MangoHudConfig>,
+    mangohud_app_input: String,
+    mangohud_status: Option<Result<String, String>>,
+
+    multimedia_status: Option<MultimediaStatus>,
+    is_checking_multimedia: bool,
+    running_multimedia_step: Option<MultimediaStep>,
+    multimedia_log: Vec<String>,
+    vaapi_info: Option<Result<VaInfo, String>>,
 }
 
 impl TweaksTab {
     pub fn new() -> Self {
+        let catalog = CatalogSettings::load();
         let mut tab = Self {
             current_view: TweaksView::GamingMeta,
             output_log: Vec::new(),
@@ -238,7 +347,9 @@ impl TweaksTab {
             is_loading_proton_builds: false,
             is_detecting_launchers: false,
             proton_builds_error: None,
+            catalog_warning: None,
             downloading_build: None,
+            download_verification: None,
             installing_build: None,
             download_progress: 0.0,
             install_progress: 0.0,
@@ -250,6 +361,41 @@ impl TweaksTab {
             show_installed_only: false,
             show_used_only: false,
             show_unused_only: false,
+            steam_library: None,
+            is_loading_steam_games: false,
+            is_changing_compat_tools: false,
+            steam_games_error: None,
+            steam_games_status: None,
+            migrate_from: None,
+            migrate_to: None,
+            proton_policies: ProtonPolicies::load(),
+            policy_keep_input: "3".to_string(),
+            policy_auto_install: true,
+            policy_protect_in_use: true,
+            is_applying_policy: false,
+            install_targets: Vec::new(),
+            custom_launcher_path: String::new(),
+            custom_launcher_kind: None,
+            show_catalog_settings: false,
+            catalog_source_label: catalog.source.label().to_string(),
+            catalog_location: catalog.source.location().to_string(),
+            catalog_token: catalog.github_token.clone().unwrap_or_default(),
+            catalog_api_base: catalog.api_base.clone().unwrap_or_default(),
+            performance_status: None,
+            is_checking_performance: false,
+            running_tuning: None,
+            performance_log: Vec::new(),
+            mangohud_targets: Vec::new(),
+            mangohud_target: mangohud::GLOBAL_TARGET.to_string(),
+            mangohud_config: None,
+            mangohud_saved: None,
+            mangohud_app_input: String::new(),
+            mangohud_status: None,
+            multimedia_status: None,
+            is_checking_multimedia: false,
+            running_multimedia_step: None,
+            multimedia_log: Vec::new(),
+            vaapi_info: None,
         };
 
         tab.is_checking_gaming_meta = true;
@@ -282,6 +428,26 @@ impl TweaksTab {
                     return iced::Command::perform(check_hyprland_status(), Message::HyprlandStatusChecked);
                 }
 
+                if view == TweaksView::Performance && self.performance_status.is_none() {
+                    return self.update(Message::CheckPerformanceStatus);
+                }
+
+                if view == TweaksView::MangoHud && self.mangohud_config.is_none() {
+                    return self.update(Message::ReloadMangoHud);
+                }
+
+                if view == TweaksView::Multimedia && self.multimedia_status.is_none() {
+                    self.is_checking_multimedia = true;
+                    return iced::Command::perform(
+                        async { multimedia::detect_status() },
+                        Message::MultimediaStatusChecked,
+                    );
+                }
+
+                if view == TweaksView::SteamGames && self.steam_library.is_none() && !self.is_loading_steam_games {
+                    return self.update(Message::LoadSteamGames);
+                }
+
                 if view == TweaksView::Proton {
 
                     if !self.is_loading_proton_builds {
@@ -301,6 +467,184 @@ impl TweaksTab {
                 }
                 iced::Command::none()
             }
+            Message::CheckPerformanceStatus => {
+                self.is_checking_performance = true;
+                iced::Command::perform(
+                    async { tokio::task::spawn_blocking(performance::detect_all).await.unwrap_or_default() },
+                    Message::PerformanceStatusChecked,
+                )
+            }
+            Message::PerformanceStatusChecked(statuses) => {
+                self.is_checking_performance = false;
+                self.performance_status = Some(statuses);
+                iced::Command::none()
+            }
+            Message::ApplyTuning(_) | Message::RevertTuning(_) if self.running_tuning.is_some() => iced::Command::none(),
+            Message::ApplyTuning(knob) => {
+                self.running_tuning = Some(knob);
+                self.performance_log.push(format!("Applying: {}...", knob.label()));
+                iced::Command::perform(performance::apply(knob), move |result| Message::TuningFinished(knob, result))
+            }
+            Message::RevertTuning(knob) => {
+                self.running_tuning = Some(knob);
+                self.performance_log.push(format!("Reverting: {}...", knob.label()));
+                iced::Command::perform(performance::revert(knob), move |result| Message::TuningFinished(knob, result))
+            }
+            Message::TuningFinished(knob, result) => {
+                self.running_tuning = None;
+                match result {
+                    Ok(message) => self.performance_log.push(format!("[OK] {}", message)),
+                    Err(e) => {
+                        self.performance_log.extend(e.lines().filter(|l| !l.trim().is_empty()).map(String::from));
+                        self.performance_log.push(format!("[FAIL] {}", knob.label()));
+                    }
+                }
+                self.update(Message::CheckPerformanceStatus)
+            }
+            Message::SelectMangoHudTarget(target) => {
+                self.mangohud_target = target;
+                self.update(Message::ReloadMangoHud)
+            }
+            Message::MangoHudAppNameChanged(name) => {
+                self.mangohud_app_input = name;
+                iced::Command::none()
+            }
+            Message::AddMangoHudApp => {
+                let name = self.mangohud_app_input.trim().trim_end_matches(".conf").to_string();
+                if !mangohud::valid_app_name(&name) {
+                    self.mangohud_status = Some(Err(format!("\"{}\" is not a valid application name", name)));
+                    return iced::Command::none();
+                }
+                if !self.mangohud_targets.contains(&name) {
+                    self.mangohud_targets.push(name.clone());
+                }
+                self.mangohud_app_input.clear();
+                self.mangohud_target = name;
+                self.update(Message::ReloadMangoHud)
+            }
+            Message::MangoHudOptionToggled(key, enabled) => {
+                if let Some(config) = self.mangohud_config.as_mut() {
+                    config.set_enabled(&key, enabled);
+                }
+                iced::Command::none()
+            }
+            Message::MangoHudValueChanged(key, value) => {
+                if let Some(config) = self.mangohud_config.as_mut() {
+                    config.set_value(&key, &value);
+                }
+                iced::Command::none()
+            }
+            Message::MangoHudPositionSelected(position) => {
+                if let Some(config) = self.mangohud_config.as_mut() {
+                    config.set_value("position", &position);
+                }
+                iced::Command::none()
+            }
+            Message::ApplyMangoHudPreset(preset) => {
+                if let Some(config) = self.mangohud_config.as_mut() {
+                    config.apply_preset(preset);
+                }
+                iced::Command::none()
+            }
+            Message::SaveMangoHud => {
+                if let Some(config) = self.mangohud_config.clone() {
+                    let result = mangohud::save(&self.mangohud_target, &config);
+                    if result.is_ok() {
+                        self.mangohud_saved = Some(config);
+                    }
+                    self.mangohud_status = Some(result.map(|path| format!("Saved {}", path)));
+                }
+                iced::Command::none()
+            }
+            Message::ReloadMangoHud => {
+                // Per-app files added in the editor but not saved yet stay in the list
+                let mut targets = mangohud::list_targets();
+                for target in &self.mangohud_targets {
+                    if !targets.contains(target) {
+                        targets.push(target.clone());
+                    }
+                }
+                if !targets.contains(&self.mangohud_target) {
+                    targets.push(self.mangohud_target.clone());
+                }
+                self.mangohud_targets = targets;
+                match mangohud::load(&self.mangohud_target) {
+                    Ok(config) => {
+                        self.mangohud_saved = Some(config.clone());
+                        self.mangohud_config = Some(config);
+                        self.mangohud_status = None;
+                    }
+                    Err(e) => {
+                        self.mangohud_config = Some(MangoHudConfig::default());
+                        self.mangohud_saved = None;
+                        self.mangohud_status = Some(Err(e));
+                    }
+                }
+                iced::Command::none()
+            }
+            Message::CheckMultimediaStatus => {
+                self.is_checking_multimedia = true;
+                iced::Command::perform(
+                    async { multimedia::detect_status() },
+                    Message::MultimediaStatusChecked,
+                )
+            }
+            Message::MultimediaStatusChecked(status) => {
+                self.is_checking_multimedia = false;
+                self.multimedia_status = Some(status);
+                iced::Command::none()
+            }
+            Message::RunMultimediaStep(step) => {
+                if self.running_multimedia_step.is_some() {
+                    return iced::Command::none();
+                }
+                let vendors = self.multimedia_status
+                    .as_ref()
+                    .map(|s| s.gpu_vendors.clone())
+                    .unwrap_or_default();
+                self.running_multimedia_step = Some(step);
+                self.multimedia_log.push(format!("Running: {}...", step.label()));
+                iced::Command::perform(
+                    multimedia::run_wizard_step(step, vendors),
+                    move |result| Message::MultimediaStepFinished(step, result),
+                )
+            }
+            Message::MultimediaStepFinished(step, result) => {
+                self.running_multimedia_step = None;
+                match result {
+                    Ok(output) => {
+                        self.multimedia_log.extend(output.lines().filter(|l| !l.trim().is_empty()).map(String::from));
+                        self.multimedia_log.push(format!("[OK] {}", step.label()));
+                    }
+                    Err(e) => {
+                        self.multimedia_log.extend(e.lines().map(String::from));
+                        self.multimedia_log.push(format!("[FAIL] {}", step.label()));
+                    }
+                }
+                self.is_checking_multimedia = true;
+                iced::Command::perform(
+                    async { multimedia::detect_status() },
+                    Message::MultimediaStatusChecked,
+                )
+            }
+            Message::VerifyVaapi => {
+                self.vaapi_info = None;
+                self.multimedia_log.push("Running: vainfo".to_string());
+                iced::Command::perform(multimedia::verify_vaapi(), Message::VaapiVerified)
+            }
+            Message::VaapiVerified(result) => {
+                match &result {
+                    Ok(info) => self.multimedia_log.push(format!(
+                        "[OK] VA-API driver: {} ({} decode, {} encode profiles)",
+                        info.driver,
+                        info.decode.len(),
+                        info.encode.len()
+                    )),
+                    Err(e) => self.multimedia_log.push(format!("[FAIL] {}", e.lines().next().unwrap_or_default())),
+                }
+                self.vaapi_info = Some(result);
+                iced::Command::none()
+            }
             Message::CheckGamingMetaStatus => {
                 self.is_checking_gaming_meta = true;
                 iced::Command::perform(check_gaming_meta_status(), Message::GamingMetaStatusChecked)
@@ -531,12 +875,14 @@ impl TweaksTab {
             Message::ProtonBuildsLoaded(result) => {
                 self.is_loading_proton_builds = false;
                 match result {
-                    Ok(runners) => {
+                    Ok((runners, catalog_warning)) => {
                         self.proton_runners = runners;
                         self.proton_builds_error = None;
+                        self.catalog_warning = catalog_warning;
                         if self.selected_proton_runner.is_none() && !self.proton_runners.is_empty() {
                             self.selected_proton_runner = Some(self.proton_runners[0].title.clone());
                         }
+                        self.load_policy_form();
                         if !self.detected_launchers.is_empty() {
                             self.update_proton_installation_status();
                             return iced::Command::perform(check_proton_usage(self.proton_runners.clone(), self.detected_launchers.clone()), Message::ProtonUsageChecked);
@@ -553,26 +899,42 @@ impl TweaksTab {
                 match result {
                     Ok(launchers) => {
                         self.detected_launchers = launchers;
+                        let detected = &self.detected_launchers;
+                        self.install_targets.retain(|t| detected.iter().any(|l| &l.title == t));
+                        if self.selected_launcher.as_ref().is_some_and(|s| !detected.iter().any(|l| &l.title == s)) {
+                            self.selected_launcher = None;
+                        }
 
                         if self.selected_launcher.is_none() && !self.detected_launchers.is_empty() {
                             self.selected_launcher = Some(self.detected_launchers[0].title.clone());
                         }
+                        self.load_policy_form();
 
                         self.update_proton_installation_status();
 
-                        return iced::Command::perform(check_proton_usage(self.proton_runners.clone(), self.detected_launchers.clone()), Message::ProtonUsageChecked);
+                        let usage = iced::Command::perform(check_proton_usage(self.proton_runners.clone(), self.detected_launchers.clone()), Message::ProtonUsageChecked);
+                        if self.is_loading_steam_games {
+                            return iced::Command::batch(vec![usage, self.update(Message::LoadSteamGames)]);
+                        }
+                        return usage;
                     }
-                    Err(_e) => {
+                    Err(e) => {
+                        if self.is_loading_steam_games {
+                            self.is_loading_steam_games = false;
+                            self.steam_games_error = Some(e);
+                        }
                     }
                 }
                 iced::Command::none()
             }
             Message::SelectProtonRunner(runner_title) => {
                 self.selected_proton_runner = Some(runner_title);
+                self.load_policy_form();
                 iced::Command::none()
             }
             Message::SelectLauncher(launcher_title) => {
                 self.selected_launcher = Some(launcher_title);
+                self.load_policy_form();
                 self.update_proton_installation_status();
                 iced::Command::perform(check_proton_usage(self.proton_runners.clone(), self.detected_launchers.clone()), Message::ProtonUsageChecked)
             }
@@ -614,14 +976,29 @@ impl TweaksTab {
                 let runner_info = self.proton_runners.iter()
                     .find(|r| r.title == runner_title)
                     .and_then(|r| serde_json::to_string(r).ok());
+                let checksum_url = self.proton_runners.iter()
+                    .find(|r| r.title == runner_title)
+                    .and_then(|r| r.builds.iter().find(|b| b.title == title))
+                    .and_then(|b| b.checksum_url.clone());
 
                 let exe_path = std::env::current_exe()
                     .unwrap_or_else(|_| std::path::PathBuf::from("rustora"));
 
+                let compat_layer_type = self.proton_runners.iter()
+                    .find(|r| r.title == runner_title)
+                    .map(|r| r.compat_layer_type.clone())
+                    .unwrap_or_else(|| "Proton".to_string());
+                let targets = self.install_target_titles(&compat_layer_type);
+                if targets.is_empty() {
+                    self.show_completion_dialog = true;
+                    self.completion_message = format!("None of the selected launchers use {} builds", compat_layer_type);
+                    self.completion_success = false;
+                    return iced::Command::none();
+                }
+
                 let runner_title_clone = runner_title.clone();
                 let title_clone = title.clone();
                 let download_url_clone = download_url.clone();
-                let selected_launcher_clone = self.selected_launcher.clone();
                 let runner_info_clone = runner_info.clone();
 
                 iced::Command::perform(
@@ -632,7 +1009,10 @@ impl TweaksTab {
                         cmd.arg(&runner_title_clone);
                         cmd.arg(&title_clone);
                         cmd.arg(&download_url_clone);
-                        if let Some(launcher) = &selected_launcher_clone {
+                        if let Some(checksum_url) = &checksum_url {
+                            cmd.arg("--checksum-url").arg(checksum_url);
+                        }
+                        for launcher in &targets {
                             cmd.arg("--launcher").arg(launcher);
                         }
                         if let Some(runner_info) = &runner_info_clone {
@@ -650,12 +1030,13 @@ impl TweaksTab {
             }
             Message::ProtonBuildDownloaded(result) => {
                 match result {
-                    Ok((runner_title, title, path)) => {
+                    Ok((runner_title, title, path, verification)) => {
                         self.downloading_build = None;
                         self.download_progress = 1.0;
                         self.installing_build = Some(title.clone());
                         self.install_progress = 0.0;
-                        self.progress_text = format!("Installing {}...", title);
+                        self.progress_text = format!("Installing {}... {}", title, verification);
+                        self.download_verification = Some(verification);
                         let selected_launcher = self.selected_launcher.clone();
                         let runner = self.proton_runners.iter().find(|r| r.title == runner_title).cloned();
                         let is_update = self.proton_runners.iter()
@@ -694,6 +1075,9 @@ impl TweaksTab {
                         self.show_progress_dialog = false;
                         self.show_completion_dialog = true;
                         self.completion_message = format!("Successfully installed {} {}", runner_title, title);
+                        if let Some(verification) = self.download_verification.take() {
+                            self.completion_message.push_str(&format!("\n{}", verification));
+                        }
                         self.completion_success = true;
                         iced::Command::none()
                     }
@@ -701,6 +1085,7 @@ impl TweaksTab {
                         self.show_progress_dialog = false;
                         self.show_completion_dialog = true;
                         self.completion_message = format!("Installation failed: {}", e);
+                        self.download_verification = None;
                         self.completion_success = false;
                         self.proton_builds_error = Some(e);
                         iced::Command::none()
@@ -731,7 +1116,7 @@ impl TweaksTab {
                     if let Some(_build) = runner.builds.iter().find(|b| b.title == title && b.is_latest) {
                         if let Some(latest_release) = runner.builds.iter().find(|b| !b.is_latest) {
                             self.downloading_build = Some(title.clone());
-                            iced::Command::perform(download_proton_build(runner_title, latest_release.title.clone(), latest_release.download_url.clone()), Message::ProtonBuildDownloaded)
+                            iced::Command::perform(download_proton_build(runner_title, latest_release.title.clone(), latest_release.download_url.clone(), latest_release.checksum_url.clone()), Message::ProtonBuildDownloaded)
                         } else {
                             iced::Command::none()
                         }
@@ -752,6 +1137,9 @@ impl TweaksTab {
                         self.show_progress_dialog = false;
                         self.show_completion_dialog = true;
                         self.completion_message = format!("Successfully updated {} {}", runner_title, title);
+                        if let Some(verification) = self.download_verification.take() {
+                            self.completion_message.push_str(&format!("\n{}", verification));
+                        }
                         self.completion_success = true;
                         iced::Command::none()
                     }
@@ -761,6 +1149,7 @@ impl TweaksTab {
                         self.show_progress_dialog = false;
                         self.show_completion_dialog = true;
                         self.completion_message = format!("Update failed: {}", e);
+                        self.download_verification = None;
                         self.completion_success = false;
                         self.proton_builds_error = Some(e);
                         iced::Command::none()
@@ -807,8 +1196,11 @@ impl TweaksTab {
             }
             Message::MoreProtonBuildsLoaded(result) => {
                 match result {
-                    Ok((runner_title, new_builds)) => {
+                    Ok((runner_title, new_builds, warning)) => {
                         if let Some(runner) = self.proton_runners.iter_mut().find(|r| r.title == runner_title) {
+                            if warning.is_some() {
+                                runner.fetch_warning = warning;
+                            }
                             let latest_count = runner.builds.iter().filter(|b| b.is_latest).count();
                             for build in new_builds {
                                 runner.builds.insert(latest_count, build);
@@ -821,7 +1213,340 @@ impl TweaksTab {
                     }
                 }
             }
+            Message::MigrateProtonBuildUsers(runner_title, title) => {
+                let steam_dir = match self.steam_directory() {
+                    Some(dir) => dir,
+                    None => return iced::Command::none(),
+                };
+                let runner = match self.proton_runners.iter().find(|r| r.title == runner_title) {
+                    Some(runner) => runner,
+                    None => return iced::Command::none(),
+                };
+                // Builds are listed newest first; move games to the newest other installed release
+                let target = runner.builds.iter()
+                    .find(|b| b.is_installed && !b.is_latest && b.title != title);
+                let target = match target {
+                    Some(target) => target,
+                    None => {
+                        self.show_completion_dialog = true;
+                        self.completion_message = format!("Install a newer {} build before migrating games away from {}", runner_title, title);
+                        self.completion_success = false;
+                        return iced::Command::none();
+                    }
+                };
+                let from = steam_tool_name(runner, &title);
+                let to = steam_tool_name(runner, &target.title);
+                self.is_changing_compat_tools = true;
+                iced::Command::perform(steam::migrate_compat_tool(steam_dir, vec![from], to), Message::CompatToolsChanged)
+            }
+            Message::LoadSteamGames => {
+                let steam_dir = match self.steam_directory() {
+                    Some(dir) => dir,
+                    None if self.detected_launchers.is_empty() && !self.is_detecting_launchers => {
+                        // Launchers are normally detected by the Proton view; do it here too
+                        self.is_detecting_launchers = true;
+                        self.is_loading_steam_games = true;
+                        return iced::Command::perform(detect_launchers(), Message::LaunchersDetected);
+                    }
+                    None => {
+                        self.steam_games_error = Some("Steam was not found".to_string());
+                        return iced::Command::none();
+                    }
+                };
+                self.is_loading_steam_games = true;
+                self.steam_games_error = None;
+                iced::Command::perform(
+                    async move {
+                        tokio::task::spawn_blocking(move || steam::load_library(&steam_dir))
+                            .await
+                            .map_err(|e| format!("Failed to read Steam library: {}", e))
+                    },
+                    Message::SteamGamesLoaded,
+                )
+            }
+            Message::SteamGamesLoaded(result) => {
+                self.is_loading_steam_games = false;
+                match result {
+                    Ok(library) => {
+                        let used = library.tools_in_use();
+                        if self.migrate_from.as_ref().map(|f| !used.contains(f)).unwrap_or(true) {
+                            self.migrate_from = used.first().cloned();
+                        }
+                        self.steam_library = Some(library);
+                    }
+                    Err(e) => {
+                        self.steam_games_error = Some(e);
+                    }
+                }
+                iced::Command::none()
+            }
+            Message::AssignCompatTool(appid, tool) => {
+                let steam_dir = match self.steam_library.as_ref() {
+                    Some(library) => library.steam_dir.clone(),
+                    None => return iced::Command::none(),
+                };
+                let tool = if tool == steam::STEAM_DEFAULT_TOOL { None } else { Some(tool) };
+                self.is_changing_compat_tools = true;
+                self.steam_games_status = None;
+                iced::Command::perform(steam::assign_compat_tool(steam_dir, appid, tool), Message::CompatToolsChanged)
+            }
+            Message::MigrateFromSelected(tool) => {
+                self.migrate_from = Some(tool);
+                iced::Command::none()
+            }
+            Message::MigrateToSelected(tool) => {
+                self.migrate_to = Some(tool);
+                iced::Command::none()
+            }
+            Message::MigrateCompatTool => {
+                let steam_dir = match self.steam_library.as_ref() {
+                    Some(library) => library.steam_dir.clone(),
+                    None => return iced::Command::none(),
+                };
+                match (self.migrate_from.clone(), self.migrate_to.clone()) {
+                    (Some(from), Some(to)) if from != to => {
+                        self.is_changing_compat_tools = true;
+                        self.steam_games_status = None;
+                        iced::Command::perform(steam::migrate_compat_tool(steam_dir, vec![from], to), Message::CompatToolsChanged)
+                    }
+                    _ => iced::Command::none(),
+                }
+            }
+            Message::CompatToolsChanged(result) => {
+                self.is_changing_compat_tools = false;
+                if self.current_view == TweaksView::Proton {
+                    self.show_completion_dialog = true;
+                    self.completion_success = result.is_ok();
+                    self.completion_message = match &result {
+                        Ok(msg) => msg.clone(),
+                        Err(e) => format!("Migration failed: {}", e),
+                    };
+                }
+                self.steam_games_status = Some(result);
+                let mut commands = vec![iced::Command::perform(
+                    check_proton_usage(self.proton_runners.clone(), self.detected_launchers.clone()),
+                    Message::ProtonUsageChecked,
+                )];
+                if self.steam_library.is_some() {
+                    commands.push(self.update(Message::LoadSteamGames));
+                }
+                iced::Command::batch(commands)
+            }
+            Message::PolicyKeepChanged(value) => {
+                if value.chars().all(|c| c.is_ascii_digit()) {
+                    self.policy_keep_input = value;
+                }
+                iced::Command::none()
+            }
+            Message::PolicyAutoInstallToggled(enabled) => {
+                self.policy_auto_install = enabled;
+                iced::Command::none()
+            }
+            Message::PolicyProtectToggled(enabled) => {
+                self.policy_protect_in_use = enabled;
+                iced::Command::none()
+            }
+            Message::SaveProtonPolicy => {
+                if let Err(e) = self.save_policy_form() {
+                    self.show_completion_dialog = true;
+                    self.completion_message = e;
+                    self.completion_success = false;
+                }
+                iced::Command::none()
+            }
+            Message::RemoveProtonPolicy => {
+                if let (Some(runner), Some(launcher)) = (self.selected_proton_runner.clone(), self.selected_launcher.clone()) {
+                    self.proton_policies.remove(&runner, &launcher);
+                    if let Err(e) = self.proton_policies.save() {
+                        self.proton_builds_error = Some(e);
+                    }
+                    self.load_policy_form();
+                }
+                iced::Command::none()
+            }
+            Message::ApplyProtonPolicy => {
+                match self.save_policy_form() {
+                    Ok(policy) => {
+                        self.is_applying_policy = true;
+                        iced::Command::perform(sync_proton_builds(vec![policy], false), Message::ProtonPolicyApplied)
+                    }
+                    Err(e) => {
+                        self.show_completion_dialog = true;
+                        self.completion_message = e;
+                        self.completion_success = false;
+                        iced::Command::none()
+                    }
+                }
+            }
+            Message::ToggleInstallTarget(launcher_title, enabled) => {
+                self.install_targets.retain(|t| t != &launcher_title);
+                if enabled {
+                    self.install_targets.push(launcher_title);
+                }
+                iced::Command::none()
+            }
+            Message::CustomLauncherPathChanged(path) => {
+                self.custom_launcher_path = path;
+                iced::Command::none()
+            }
+            Message::CustomLauncherKindSelected(kind) => {
+                self.custom_launcher_kind = Some(kind);
+                iced::Command::none()
+            }
+            Message::AddCustomLauncher => {
+                let kind = match &self.custom_launcher_kind {
+                    Some(kind) => kind.clone(),
+                    None => return iced::Command::none(),
+                };
+                let mut custom = CustomLaunchers::load();
+                let result = custom.add(&kind, &self.custom_launcher_path).and_then(|_| custom.save());
+                match result {
+                    Ok(()) => {
+                        self.custom_launcher_path.clear();
+                        self.is_detecting_launchers = true;
+                        iced::Command::perform(detect_launchers(), Message::LaunchersDetected)
+                    }
+                    Err(e) => {
+                        self.show_completion_dialog = true;
+                        self.completion_message = e;
+                        self.completion_success = false;
+                        iced::Command::none()
+                    }
+                }
+            }
+            Message::RemoveCustomLauncher(launcher_title) => {
+                let mut custom = CustomLaunchers::load();
+                custom.remove(&launcher_title);
+                if let Err(e) = custom.save() {
+                    self.proton_builds_error = Some(e);
+                    return iced::Command::none();
+                }
+                if self.selected_launcher.as_ref() == Some(&launcher_title) {
+                    self.selected_launcher = None;
+                }
+                self.install_targets.retain(|t| t != &launcher_title);
+                self.is_detecting_launchers = true;
+                iced::Command::perform(detect_launchers(), Message::LaunchersDetected)
+            }
+            Message::ToggleCatalogSettings => {
+                self.show_catalog_settings = !self.show_catalog_settings;
+                iced::Command::none()
+            }
+            Message::CatalogSourceSelected(label) => {
+                self.catalog_source_label = label;
+                iced::Command::none()
+            }
+            Message::CatalogLocationChanged(location) => {
+                self.catalog_location = location;
+                iced::Command::none()
+            }
+            Message::CatalogTokenChanged(token) => {
+                self.catalog_token = token;
+                iced::Command::none()
+            }
+            Message::CatalogApiBaseChanged(base) => {
+                self.catalog_api_base = base;
+                iced::Command::none()
+            }
+            Message::SaveCatalogSettings => {
+                let source = CatalogSource::from_label(&self.catalog_source_label, &self.catalog_location);
+                if matches!(&source, CatalogSource::LocalFile(l) | CatalogSource::Mirror(l) if l.is_empty()) {
+                    self.show_completion_dialog = true;
+                    self.completion_message = format!("{} needs a location", source.label());
+                    self.completion_success = false;
+                    return iced::Command::none();
+                }
+                let non_empty = |value: &str| Some(value.trim().to_string()).filter(|v| !v.is_empty());
+                let catalog = CatalogSettings {
+                    source,
+                    github_token: non_empty(&self.catalog_token),
+                    api_base: non_empty(&self.catalog_api_base),
+                };
+                if let Err(e) = catalog.save() {
+                    self.show_completion_dialog = true;
+                    self.completion_message = e;
+                    self.completion_success = false;
+                    return iced::Command::none();
+                }
+                // The cached catalog came from the old source
+                if let Ok(cache_path) = get_proton_cache_path() {
+                    let _ = std::fs::remove_file(cache_path);
+                }
+                self.show_catalog_settings = false;
+                self.update(Message::LoadProtonBuilds)
+            }
+            Message::ProtonPolicyApplied(result) => {
+                self.is_applying_policy = false;
+                self.show_completion_dialog = true;
+                self.completion_success = result.is_ok();
+                self.completion_message = match result {
+                    Ok(lines) => lines.join("\n"),
+                    Err(e) => format!("Policy sync failed: {}", e),
+                };
+                self.update_proton_installation_status();
+                iced::Command::perform(check_proton_usage(self.proton_runners.clone(), self.detected_launchers.clone()), Message::ProtonUsageChecked)
+            }
+        }
+    }
+
+    // Show the saved policy for the selected runner/launcher, or the defaults
+    fn load_policy_form(&mut self) {
+        let (runner, launcher) = match (&self.selected_proton_runner, &self.selected_launcher) {
+            (Some(runner), Some(launcher)) => (runner, launcher),
+            _ => return,
+        };
+        let policy = self.proton_policies
+            .find(runner, launcher)
+            .cloned()
+            .unwrap_or_else(|| ProtonPolicy::new(runner, launcher));
+        self.policy_keep_input = policy.keep_latest.to_string();
+        self.policy_auto_install = policy.auto_install;
+        self.policy_protect_in_use = policy.protect_in_use;
+    }
+
+    fn save_policy_form(&mut self) -> Result<ProtonPolicy, String> {
+        let (runner, launcher) = match (&self.selected_proton_runner, &self.selected_launcher) {
+            (Some(runner), Some(launcher)) => (runner.clone(), launcher.clone()),
+            _ => return Err("Select a runner and a launcher first".to_string()),
+        };
+        let keep_latest = if self.policy_keep_input.trim().is_empty() {
+            0
+        } else {
+            self.policy_keep_input.trim().parse::<usize>()
+                .map_err(|_| "Builds to keep must be a number".to_string())?
+        };
+        let policy = ProtonPolicy {
+            runner,
+            launcher,
+            keep_latest,
+            auto_install: self.policy_auto_install,
+            protect_in_use: self.policy_protect_in_use,
+        };
+        self.proton_policies.upsert(policy.clone());
+        self.proton_policies.save()?;
+        Ok(policy)
+    }
+
+    // Selected launcher first, then the ticked extra launchers that can use this layer type
+    fn install_target_titles(&self, compat_layer_type: &str) -> Vec<String> {
+        let mut titles: Vec<String> = Vec::new();
+        for title in self.selected_launcher.iter().chain(self.install_targets.iter()) {
+            let usable = self.detected_launchers.iter()
+                .any(|l| &l.title == title && l.supports(compat_layer_type));
+            if usable && !titles.contains(title) {
+                titles.push(title.clone());
+            }
         }
+        titles
+    }
+
+    // The selected Steam install, or the first one when another launcher is selected
+    fn steam_directory(&self) -> Option<String> {
+        let steam_installs = || self.detected_launchers.iter().filter(|l| l.kind == "Steam");
+        steam_installs()
+            .find(|l| Some(&l.title) == self.selected_launcher.as_ref())
+            .or_else(|| steam_installs().next())
+            .map(|l| l.directory.clone())
     }
 
     fn update_proton_installation_status(&mut self) {
@@ -1111,6 +1836,36 @@ impl TweaksTab {
                 })))
                 .on_press(Message::SwitchView(TweaksView::GamingMeta))
                 .padding(Padding::from([14.0, 20.0, 14.0, 20.0])),
+                button(
+                    text("Gaming Tuning")
+                        .size(tab_font_size)
+                        .style(iced::theme::Text::Color(if self.current_view == TweaksView::Performance {
+                            iced::Color::WHITE
+                        } else {
+                            theme.text()
+                        }))
+                )
+                .style(iced::theme::Button::Custom(Box::new(SubTabButtonStyle {
+                    is_active: self.current_view == TweaksView::Performance,
+                    radius: settings.border_radius,
+                })))
+                .on_press(Message::SwitchView(TweaksView::Performance))
+                .padding(Padding::from([14.0, 20.0, 14.0, 20.0])),
+                button(
+                    text("MangoHud")
+                        .size(tab_font_size)
+                        .style(iced::theme::Text::Color(if self.current_view == TweaksView::MangoHud {
+                            iced::Color::WHITE
+                        } else {
+                            theme.text()
+                        }))
+                )
+                .style(iced::theme::Button::Custom(Box::new(SubTabButtonStyle {
+                    is_active: self.current_view == TweaksView::MangoHud,
+                    radius: settings.border_radius,
+                })))
+                .on_press(Message::SwitchView(TweaksView::MangoHud))
+                .padding(Padding::from([14.0, 20.0, 14.0, 20.0])),
                 button(
                     text("DNF Config")
                         .size(tab_font_size)
@@ -1171,6 +1926,36 @@ impl TweaksTab {
                 })))
                 .on_press(Message::SwitchView(TweaksView::Proton))
                 .padding(Padding::from([14.0, 20.0, 14.0, 20.0])),
+                button(
+                    text("Steam Games")
+                        .size(tab_font_size)
+                        .style(iced::theme::Text::Color(if self.current_view == TweaksView::SteamGames {
+                            iced::Color::WHITE
+                        } else {
+                            theme.text()
+                        }))
+                )
+                .style(iced::theme::Button::Custom(Box::new(SubTabButtonStyle {
+                    is_active: self.current_view == TweaksView::SteamGames,
+                    radius: settings.border_radius,
+                })))
+                .on_press(Message::SwitchView(TweaksView::SteamGames))
+                .padding(Padding::from([14.0, 20.0, 14.0, 20.0])),
+                button(
+                    text("Multimedia")
+                        .size(tab_font_size)
+                        .style(iced::theme::Text::Color(if self.current_view == TweaksView::Multimedia {
+                            iced::Color::WHITE
+                        } else {
+                            theme.text()
+                        }))
+                )
+                .style(iced::theme::Button::Custom(Box::new(SubTabButtonStyle {
+                    is_active: self.current_view == TweaksView::Multimedia,
+                    radius: settings.border_radius,
+                })))
+                .on_press(Message::SwitchView(TweaksView::Multimedia))
+                .padding(Padding::from([14.0, 20.0, 14.0, 20.0])),
             ]
             .spacing(10)
         )
@@ -2210,6 +2995,10 @@ impl TweaksTab {
                 .height(Length::Fill)
                 .into()
             }
+            TweaksView::Performance => self.view_performance(theme, settings),
+            TweaksView::MangoHud => self.view_mangohud(theme, settings),
+            TweaksView::Multimedia => self.view_multimedia(theme, settings),
+            TweaksView::SteamGames => self.view_steam_games(theme, settings),
             TweaksView::Proton => {
                 // Sidebar with Proton runners
                 let sidebar_items: Vec<Element<Message>> = self.proton_runners.iter().map(|runner| {
@@ -2313,9 +3102,22 @@ impl TweaksTab {
                                 radius: settings.border_radius,
                             })))
                             .padding(Padding::from([14.0, 20.0, 14.0, 20.0])),
-                        ]
-                        .spacing(0)
-                        .align_items(Alignment::Center)
+                            Space::with_height(Length::Fixed(8.0)),
+                            button(
+                                text("Catalog settings")
+                                    .size(button_font_size)
+                            )
+                            .on_press(Message::ToggleCatalogSettings)
+                            .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                                is_primary: false,
+                                radius: settings.border_radius,
+                            })))
+                            .padding(Padding::from([14.0, 20.0, 14.0, 20.0])),
+                            Space::with_height(Length::Fixed(16.0)),
+                            self.view_catalog_settings(theme, settings),
+                        ]
+                        .spacing(0)
+                        .align_items(Alignment::Center)
                     )
                     .width(Length::Fill)
                     .height(Length::Fill)
@@ -2331,7 +3133,7 @@ impl TweaksTab {
                         let launcher_buttons: Vec<Element<Message>> = self.detected_launchers.iter().map(|launcher| {
                             let is_selected = self.selected_launcher.as_ref().map(|s| s == &launcher.title).unwrap_or(false);
                             button(
-                                text(format!("{} ({})", launcher.title, launcher.installation_type))
+                                text(launcher.label())
                                     .size(body_font_size * 0.9)
                                     .style(iced::theme::Text::Color(if is_selected {
                                         iced::Color::WHITE
@@ -2430,6 +3232,30 @@ impl TweaksTab {
                                     .padding(Padding::from([8.0, 12.0, 8.0, 12.0]))
                                     .into()
                                 );
+                                // Games still on this build should be moved before it is removed
+                                if build.usage_count > 0 && !build.is_latest && self.steam_directory().is_some() {
+                                    buttons.push(
+                                        button(
+                                            row![
+                                                text(crate::gui::fonts::glyphs::SYNC_SYMBOL).font(material_font).size(icon_size * 0.7),
+                                                text(" Migrate games").size(button_font_size * 0.85)
+                                            ]
+                                            .spacing(6)
+                                            .align_items(Alignment::Center)
+                                        )
+                                        .on_press_maybe(if self.is_changing_compat_tools {
+                                            None
+                                        } else {
+                                            Some(Message::MigrateProtonBuildUsers(runner.title.clone(), build.title.clone()))
+                                        })
+                                        .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                                            is_primary: false,
+                                            radius: settings.border_radius,
+                                        })))
+                                        .padding(Padding::from([8.0, 12.0, 8.0, 12.0]))
+                                        .into()
+                                    );
+                                }
                                 buttons.push(
                                     button(
                                         row![
@@ -2570,6 +3396,8 @@ impl TweaksTab {
                                     };
                                     launcher_selector
                                 },
+                                Space::with_height(Length::Fixed(12.0)),
+                                self.view_launcher_options(runner, theme, settings),
                                 Space::with_height(Length::Fixed(20.0)),
                                 // Filter buttons
                                 row![
@@ -2608,13 +3436,45 @@ impl TweaksTab {
                                         radius: settings.border_radius,
                                     })))
                                     .padding(Padding::from([14.0, 20.0, 14.0, 20.0])),
+                                    button(
+                                        text("Catalog settings")
+                                            .size(button_font_size)
+                                    )
+                                    .on_press(Message::ToggleCatalogSettings)
+                                    .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                                        is_primary: false,
+                                        radius: settings.border_radius,
+                                    })))
+                                    .padding(Padding::from([14.0, 20.0, 14.0, 20.0])),
                                 ]
-                                .spacing(0),
+                                .spacing(8),
+                                Space::with_height(Length::Fixed(12.0)),
+                                self.view_catalog_settings(theme, settings),
+                                Space::with_height(Length::Fixed(16.0)),
+                                self.view_proton_policy(&runner.title, theme, settings),
                                 Space::with_height(Length::Fixed(20.0)),
+                                {
+                                    let warnings: Vec<&str> = self.catalog_warning.iter()
+                                        .chain(runner.fetch_warning.iter())
+                                        .map(|w| w.as_str())
+                                        .collect();
+                                    let warning: Element<Message> = if warnings.is_empty() {
+                                        Space::with_height(Length::Shrink).into()
+                                    } else {
+                                        container(
+                                            text(warnings.join("\n"))
+                                                .size(body_font_size * 0.9)
+                                                .style(iced::theme::Text::Color(theme.danger()))
+                                        )
+                                        .padding(Padding::from([0.0, 0.0, 12.0, 0.0]))
+                                        .into()
+                                    };
+                                    warning
+                                },
                                 {
                                     let builds_content: Element<Message> = if builds_list.is_empty() {
                                         container(
-                                            text("No builds available")
+                                            text(runner.fetch_error.clone().unwrap_or_else(|| "No builds available".to_string()))
                                                 .size(body_font_size)
                                                 .style(iced::theme::Text::Color(theme.secondary_text()))
                                         )
@@ -2738,34 +3598,1168 @@ impl TweaksTab {
         .height(Length::Fill)
         .padding(Padding::new(32.0));
 
-        // Overlay progress dialog if active
-        let final_content = if self.show_progress_dialog {
+        // Overlay progress dialog if active
+        let final_content = if self.show_progress_dialog {
+            container(
+                column![
+                    main_content,
+                    self.view_progress_dialog(theme, settings),
+                ]
+                .spacing(0)
+            )
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+        } else if self.show_completion_dialog {
+            container(
+                column![
+                    main_content,
+                    self.view_completion_dialog(theme, settings),
+                ]
+                .spacing(0)
+            )
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+        } else {
+            main_content.into()
+        };
+
+        final_content
+    }
+
+    fn view_multimedia(&self, theme: &crate::gui::Theme, settings: &crate::gui::settings::AppSettings) -> Element<'_, Message> {
+        let title_font_size = (settings.font_size_titles * settings.scale_titles * 1.2).round();
+        let body_font_size = (settings.font_size_body * settings.scale_body * 1.15).round();
+        let button_font_size = (settings.font_size_buttons * settings.scale_buttons * 1.2).round();
+        let ok_color = iced::Color::from_rgb(0.1, 0.7, 0.1);
+
+        let status = match self.multimedia_status {
+            Some(ref status) => status,
+            None => {
+                return container(
+                    text("Checking codecs and graphics hardware...")
+                        .size(body_font_size)
+                        .style(iced::theme::Text::Color(theme.secondary_text()))
+                )
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .center_x()
+                .center_y()
+                .into();
+            }
+        };
+
+        let busy = self.running_multimedia_step.is_some();
+        let step_card = |number: usize, step: MultimediaStep, description: String, state: String, done: bool, enabled: bool, action: &str| -> Element<'_, Message> {
+            let is_running = self.running_multimedia_step == Some(step);
+            container(
+                column![
+                    text(format!("{}. {}", number, step.label()))
+                        .size(body_font_size * 1.1)
+                        .style(iced::theme::Text::Color(theme.primary())),
+                    text(description)
+                        .size(body_font_size * 0.85)
+                        .style(iced::theme::Text::Color(theme.secondary_text())),
+                    row![
+                        text(if done { format!("[OK] {}", state) } else { state })
+                            .size(body_font_size * 0.9)
+                            .style(iced::theme::Text::Color(if done { ok_color } else { theme.text() }))
+                            .width(Length::Fill),
+                        button(text(if is_running { "Running..." } else { action }).size(button_font_size * 0.85))
+                            .on_press_maybe(if enabled && !busy { Some(Message::RunMultimediaStep(step)) } else { None })
+                            .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                                is_primary: enabled && !done,
+                                radius: settings.border_radius,
+                            })))
+                            .padding(Padding::from([10.0, 16.0, 10.0, 16.0])),
+                    ]
+                    .spacing(12)
+                    .align_items(Alignment::Center),
+                ]
+                .spacing(6)
+            )
+            .width(Length::Fill)
+            .padding(Padding::from([14.0, 18.0, 14.0, 18.0]))
+            .style(iced::theme::Container::Custom(Box::new(StatusItemStyle {
+                is_installed: done,
+                radius: settings.border_radius * 0.6,
+            })))
+            .into()
+        };
+
+        let rpmfusion = status.rpmfusion_enabled();
+        let ffmpeg_state = if status.ffmpeg_full {
+            "Full FFmpeg from RPM Fusion is installed".to_string()
+        } else if status.ffmpeg_free {
+            "ffmpeg-free is installed (H.264, HEVC and AAC are missing)".to_string()
+        } else {
+            "FFmpeg is not installed".to_string()
+        };
+        let gst_installed = status.gstreamer.iter().filter(|(_, i)| *i).count();
+        let vendors = status.gpu_vendors.iter().map(|v| v.label()).collect::<Vec<_>>().join(", ");
+        let needs_rpmfusion = status.gpu_vendors.contains(&multimedia::GpuVendor::Amd);
+        let hw_state = if status.gpu_vendors.is_empty() {
+            "No AMD, Intel or NVIDIA graphics detected".to_string()
+        } else {
+            format!("Detected {}: {}", vendors, status.hwaccel
+                .iter()
+                .map(|(pkg, installed)| format!("{}{}", pkg, if *installed { " ✓" } else { "" }))
+                .collect::<Vec<_>>()
+                .join(", "))
+        };
+
+        let steps = column![
+            text("Multimedia Codecs")
+                .size(title_font_size)
+                .style(iced::theme::Text::Color(theme.primary())),
+            text("Set up full codec support and hardware video decoding step by step")
+                .size(body_font_size)
+                .style(iced::theme::Text::Color(theme.secondary_text())),
+            Space::with_height(Length::Fixed(8.0)),
+            step_card(
+                1,
+                MultimediaStep::RpmFusion,
+                "Patent encumbered codecs are packaged by RPM Fusion.".to_string(),
+                if rpmfusion { "Free and nonfree repositories enabled".to_string() } else { "RPM Fusion is not enabled".to_string() },
+                rpmfusion,
+                !rpmfusion,
+                "Enable",
+            ),
+            step_card(
+                2,
+                MultimediaStep::Ffmpeg,
+                "Swaps Fedora's ffmpeg-free for the full build with dnf swap.".to_string(),
+                ffmpeg_state,
+                status.ffmpeg_full,
+                rpmfusion && !status.ffmpeg_full,
+                "Swap",
+            ),
+            step_card(
+                3,
+                MultimediaStep::GStreamer,
+                "Installs the GStreamer plugin sets used by GNOME/KDE apps and browsers.".to_string(),
+                format!("{} of {} plugin sets installed", gst_installed, status.gstreamer.len()),
+                status.gstreamer_complete(),
+                rpmfusion,
+                "Install",
+            ),
+            step_card(
+                4,
+                MultimediaStep::HwAccel,
+                "Installs the VA-API/VDPAU driver for your graphics card.".to_string(),
+                hw_state,
+                !status.gpu_vendors.is_empty() && status.hwaccel_complete(),
+                !status.gpu_vendors.is_empty() && (rpmfusion || !needs_rpmfusion),
+                "Configure",
+            ),
+        ]
+        .spacing(12);
+
+        let mut verify = column![
+            row![
+                text("5. Verify")
+                    .size(body_font_size * 1.1)
+                    .style(iced::theme::Text::Color(theme.primary()))
+                    .width(Length::Fill),
+                button(text("Run vainfo").size(button_font_size * 0.85))
+                    .on_press_maybe(if busy { None } else { Some(Message::VerifyVaapi) })
+                    .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                        is_primary: true,
+                        radius: settings.border_radius,
+                    })))
+                    .padding(Padding::from([10.0, 16.0, 10.0, 16.0])),
+                button(text(if self.is_checking_multimedia { "Checking..." } else { "Refresh" }).size(button_font_size * 0.85))
+                    .on_press_maybe(if busy || self.is_checking_multimedia { None } else { Some(Message::CheckMultimediaStatus) })
+                    .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                        is_primary: false,
+                        radius: settings.border_radius,
+                    })))
+                    .padding(Padding::from([10.0, 16.0, 10.0, 16.0])),
+            ]
+            .spacing(8)
+            .align_items(Alignment::Center),
+        ]
+        .spacing(6);
+        verify = match self.vaapi_info {
+            Some(Ok(ref info)) => verify
+                .push(text(format!("Driver: {}", info.driver)).size(body_font_size * 0.9))
+                .push(text(format!("Decode: {}", if info.decode.is_empty() { "none".to_string() } else { info.decode.join(", ") }))
+                    .size(body_font_size * 0.85))
+                .push(text(format!("Encode: {}", if info.encode.is_empty() { "none".to_string() } else { info.encode.join(", ") }))
+                    .size(body_font_size * 0.85)),
+            Some(Err(ref e)) => verify.push(
+                text(e)
+                    .size(body_font_size * 0.85)
+                    .style(iced::theme::Text::Color(iced::Color::from_rgb(0.9, 0.2, 0.2)))
+            ),
+            None => verify.push(
+                text(if status.vainfo_installed {
+                    "Lists the codecs your GPU can decode and encode in hardware."
+                } else {
+                    "vainfo is installed by step 4 (libva-utils)."
+                })
+                .size(body_font_size * 0.85)
+                .style(iced::theme::Text::Color(theme.secondary_text()))
+            ),
+        };
+
+        let log: Element<Message> = scrollable(
+            column(
+                self.multimedia_log
+                    .iter()
+                    .map(|line| {
+                        let color = if line.starts_with("[OK]") {
+                            iced::Color::from_rgb(0.1, 0.5, 0.1)
+                        } else if line.starts_with("[FAIL]") {
+                            iced::Color::from_rgb(0.9, 0.2, 0.2)
+                        } else {
+                            theme.text()
+                        };
+                        text(line)
+                            .size(body_font_size * 0.8)
+                            .font(iced::Font::MONOSPACE)
+                            .style(iced::theme::Text::Color(color))
+                            .into()
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .spacing(2)
+            .padding(12)
+        )
+        .style(iced::theme::Scrollable::Custom(Box::new(CustomScrollableStyle::new(
+            Color::from(settings.background_color.clone()),
+            settings.border_radius,
+        ))))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into();
+
+        container(
+            row![
+                scrollable(steps.padding(Padding::from([0.0, 12.0, 0.0, 0.0])))
+                    .width(Length::FillPortion(1))
+                    .height(Length::Fill),
+                Space::with_width(Length::Fixed(20.0)),
+                column![
+                    container(verify)
+                        .width(Length::Fill)
+                        .padding(Padding::from([14.0, 18.0, 14.0, 18.0]))
+                        .style(iced::theme::Container::Custom(Box::new(StatusSectionStyle {
+                            radius: settings.border_radius,
+                            theme: *theme,
+                        }))),
+                    Space::with_height(Length::Fixed(16.0)),
+                    container(log)
+                        .width(Length::Fill)
+                        .height(Length::Fill)
+                        .style(iced::theme::Container::Custom(Box::new(RoundedMessageStyle {
+                            radius: settings.border_radius,
+                        }))),
+                ]
+                .width(Length::FillPortion(1))
+                .height(Length::Fill),
+            ]
+            .spacing(0)
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
+
+    fn view_performance(&self, theme: &crate::gui::Theme, settings: &crate::gui::settings::AppSettings) -> Element<'_, Message> {
+        let title_font_size = (settings.font_size_titles * settings.scale_titles * 1.2).round();
+        let body_font_size = (settings.font_size_body * settings.scale_body * 1.15).round();
+        let button_font_size = (settings.font_size_buttons * settings.scale_buttons * 1.2).round();
+        let ok_color = iced::Color::from_rgb(0.1, 0.7, 0.1);
+
+        let statuses = match self.performance_status {
+            Some(ref statuses) => statuses,
+            None => {
+                return container(
+                    text("Reading current tuning values...")
+                        .size(body_font_size)
+                        .style(iced::theme::Text::Color(theme.secondary_text()))
+                )
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .center_x()
+                .center_y()
+                .into();
+            }
+        };
+
+        let busy = self.running_tuning.is_some();
+        let mut knobs = column![
+            row![
+                column![
+                    text("Gaming Tuning")
+                        .size(title_font_size)
+                        .style(iced::theme::Text::Color(theme.primary())),
+                    text("Runtime knobs for games, applied persistently through sysctl.d, tmpfiles.d and udev drop-ins")
+                        .size(body_font_size)
+                        .style(iced::theme::Text::Color(theme.secondary_text())),
+                ]
+                .spacing(4)
+                .width(Length::Fill),
+                button(text(if self.is_checking_performance { "Checking..." } else { "Refresh" }).size(button_font_size * 0.85))
+                    .on_press_maybe(if busy || self.is_checking_performance { None } else { Some(Message::CheckPerformanceStatus) })
+                    .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                        is_primary: false,
+                        radius: settings.border_radius,
+                    })))
+                    .padding(Padding::from([10.0, 16.0, 10.0, 16.0])),
+            ]
+            .align_items(Alignment::Center),
+            Space::with_height(Length::Fixed(8.0)),
+        ]
+        .spacing(12);
+
+        for status in statuses {
+            let knob = status.knob;
+            let is_running = self.running_tuning == Some(knob);
+            knobs = knobs.push(
+                container(
+                    column![
+                        text(knob.label())
+                            .size(body_font_size * 1.1)
+                            .style(iced::theme::Text::Color(theme.primary())),
+                        text(knob.description())
+                            .size(body_font_size * 0.85)
+                            .style(iced::theme::Text::Color(theme.secondary_text())),
+                        row![
+                            column![
+                                text(format!("{}Current: {}", if status.is_recommended { "[OK] " } else { "" }, status.current))
+                                    .size(body_font_size * 0.9)
+                                    .style(iced::theme::Text::Color(if status.is_recommended { ok_color } else { theme.text() })),
+                                text(format!("Recommended: {}", status.recommended))
+                                    .size(body_font_size * 0.85)
+                                    .style(iced::theme::Text::Color(theme.text())),
+                                text(&status.note)
+                                    .size(body_font_size * 0.8)
+                                    .style(iced::theme::Text::Color(theme.secondary_text())),
+                            ]
+                            .spacing(2)
+                            .width(Length::Fill),
+                            button(text(if is_running { "Working..." } else { "Apply" }).size(button_font_size * 0.85))
+                                .on_press_maybe(if status.available && !status.is_recommended && !busy { Some(Message::ApplyTuning(knob)) } else { None })
+                                .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                                    is_primary: status.available && !status.is_recommended,
+                                    radius: settings.border_radius,
+                                })))
+                                .padding(Padding::from([10.0, 16.0, 10.0, 16.0])),
+                            button(text("Revert").size(button_font_size * 0.85))
+                                .on_press_maybe(if status.persisted && !busy { Some(Message::RevertTuning(knob)) } else { None })
+                                .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                                    is_primary: false,
+                                    radius: settings.border_radius,
+                                })))
+                                .padding(Padding::from([10.0, 16.0, 10.0, 16.0])),
+                        ]
+                        .spacing(8)
+                        .align_items(Alignment::Center),
+                    ]
+                    .spacing(6)
+                )
+                .width(Length::Fill)
+                .padding(Padding::from([14.0, 18.0, 14.0, 18.0]))
+                .style(iced::theme::Container::Custom(Box::new(StatusItemStyle {
+                    is_installed: status.is_recommended,
+                    radius: settings.border_radius * 0.6,
+                })))
+            );
+        }
+
+        let log: Element<Message> = scrollable(
+            column(
+                self.performance_log
+                    .iter()
+                    .map(|line| {
+                        let color = if line.starts_with("[OK]") {
+                            iced::Color::from_rgb(0.1, 0.5, 0.1)
+                        } else if line.starts_with("[FAIL]") {
+                            iced::Color::from_rgb(0.9, 0.2, 0.2)
+                        } else {
+                            theme.text()
+                        };
+                        text(line)
+                            .size(body_font_size * 0.8)
+                            .font(iced::Font::MONOSPACE)
+                            .style(iced::theme::Text::Color(color))
+                            .into()
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .spacing(2)
+            .padding(12)
+        )
+        .style(iced::theme::Scrollable::Custom(Box::new(CustomScrollableStyle::new(
+            Color::from(settings.background_color.clone()),
+            settings.border_radius,
+        ))))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into();
+
+        container(
+            row![
+                scrollable(knobs.padding(Padding::from([0.0, 12.0, 0.0, 0.0])))
+                    .width(Length::FillPortion(3))
+                    .height(Length::Fill),
+                Space::with_width(Length::Fixed(20.0)),
+                container(log)
+                    .width(Length::FillPortion(2))
+                    .height(Length::Fill)
+                    .style(iced::theme::Container::Custom(Box::new(RoundedMessageStyle {
+                        radius: settings.border_radius,
+                    }))),
+            ]
+            .spacing(0)
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
+
+    fn view_mangohud(&self, theme: &crate::gui::Theme, settings: &crate::gui::settings::AppSettings) -> Element<'_, Message> {
+        let title_font_size = (settings.font_size_titles * settings.scale_titles * 1.2).round();
+        let body_font_size = (settings.font_size_body * settings.scale_body * 1.15).round();
+        let button_font_size = (settings.font_size_buttons * settings.scale_buttons * 1.2).round();
+        let input_font_size = (settings.font_size_inputs * settings.scale_inputs * 1.15).round();
+
+        let config = match self.mangohud_config {
+            Some(ref config) => config,
+            None => {
+                return container(
+                    text("Reading MangoHud configuration...")
+                        .size(body_font_size)
+                        .style(iced::theme::Text::Color(theme.secondary_text()))
+                )
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .center_x()
+                .center_y()
+                .into();
+            }
+        };
+        let has_changes = self.mangohud_saved.as_ref() != Some(config);
+
+        let mut editor = column![
+            row![
+                column![
+                    text("MangoHud")
+                        .size(title_font_size)
+                        .style(iced::theme::Text::Color(theme.primary())),
+                    text("Edit the overlay for all games or per application; keys not shown here are kept as they are")
+                        .size(body_font_size)
+                        .style(iced::theme::Text::Color(theme.secondary_text())),
+                ]
+                .spacing(4)
+                .width(Length::Fill),
+                button(text("Reload").size(button_font_size * 0.85))
+                    .on_press(Message::ReloadMangoHud)
+                    .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                        is_primary: false,
+                        radius: settings.border_radius,
+                    })))
+                    .padding(Padding::from([10.0, 16.0, 10.0, 16.0])),
+            ]
+            .align_items(Alignment::Center),
+            row![
+                pick_list(self.mangohud_targets.clone(), Some(self.mangohud_target.clone()), Message::SelectMangoHudTarget)
+                    .text_size(body_font_size * 0.9)
+                    .width(Length::Fixed(260.0)),
+                text_input("Per-app config, e.g. the executable name or wine-<exe>", &self.mangohud_app_input)
+                    .on_input(Message::MangoHudAppNameChanged)
+                    .on_submit(Message::AddMangoHudApp)
+                    .size(input_font_size * 0.9)
+                    .padding(8)
+                    .width(Length::Fill)
+                    .style(iced::theme::TextInput::Custom(Box::new(RoundedTextInputStyle {
+                        radius: settings.border_radius,
+                    }))),
+                button(text("Add app").size(button_font_size * 0.85))
+                    .on_press_maybe(if self.mangohud_app_input.trim().is_empty() { None } else { Some(Message::AddMangoHudApp) })
+                    .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                        is_primary: false,
+                        radius: settings.border_radius,
+                    })))
+                    .padding(Padding::from([8.0, 12.0, 8.0, 12.0])),
+            ]
+            .spacing(8)
+            .align_items(Alignment::Center),
+        ]
+        .spacing(12);
+
+        let mut presets = row![
+            text("Presets")
+                .size(body_font_size * 0.95)
+                .style(iced::theme::Text::Color(theme.text()))
+        ]
+        .spacing(8)
+        .align_items(Alignment::Center);
+        for preset in MangoHudPreset::ALL {
+            presets = presets.push(
+                button(text(preset.label()).size(button_font_size * 0.85))
+                    .on_press(Message::ApplyMangoHudPreset(preset))
+                    .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                        is_primary: false,
+                        radius: settings.border_radius,
+                    })))
+                    .padding(Padding::from([8.0, 12.0, 8.0, 12.0]))
+            );
+        }
+        editor = editor.push(presets);
+
+        let mut toggles = column![
+            text("Overlay")
+                .size(body_font_size * 1.1)
+                .style(iced::theme::Text::Color(theme.primary()))
+        ]
+        .spacing(8);
+        for chunk in mangohud::TOGGLES.chunks(3) {
+            let mut toggle_row = row![].spacing(8);
+            for (key, label) in chunk {
+                let key = key.to_string();
+                toggle_row = toggle_row.push(
+                    container(
+                        checkbox(*label, config.is_enabled(&key))
+                            .on_toggle(move |enabled| Message::MangoHudOptionToggled(key.clone(), enabled))
+                            .text_size(body_font_size * 0.9)
+                    )
+                    .width(Length::FillPortion(1))
+                );
+            }
+            for _ in chunk.len()..3 {
+                toggle_row = toggle_row.push(Space::with_width(Length::FillPortion(1)));
+            }
+            toggles = toggles.push(toggle_row);
+        }
+        editor = editor.push(
+            container(toggles)
+                .width(Length::Fill)
+                .padding(Padding::from([14.0, 18.0, 14.0, 18.0]))
+                .style(iced::theme::Container::Custom(Box::new(StatusItemStyle {
+                    is_installed: false,
+                    radius: settings.border_radius * 0.6,
+                })))
+        );
+
+        let positions: Vec<String> = mangohud::POSITIONS.iter().map(|p| p.to_string()).collect();
+        let mut values = column![
+            text("Settings")
+                .size(body_font_size * 1.1)
+                .style(iced::theme::Text::Color(theme.primary())),
+            row![
+                text("Position")
+                    .size(body_font_size * 0.9)
+                    .width(Length::Fixed(180.0)),
+                pick_list(positions, config.value("position").map(String::from), Message::MangoHudPositionSelected)
+                    .placeholder("top-left (default)")
+                    .text_size(body_font_size * 0.9)
+                    .width(Length::Fill),
+            ]
+            .spacing(8)
+            .align_items(Alignment::Center),
+        ]
+        .spacing(8);
+        for (key, label) in mangohud::VALUES {
+            let key = key.to_string();
+            values = values.push(
+                row![
+                    text(*label)
+                        .size(body_font_size * 0.9)
+                        .width(Length::Fixed(180.0)),
+                    text_input(&key, config.value(&key).unwrap_or_default())
+                        .on_input(move |value| Message::MangoHudValueChanged(key.clone(), value))
+                        .size(input_font_size * 0.9)
+                        .padding(8)
+                        .width(Length::Fill)
+                        .style(iced::theme::TextInput::Custom(Box::new(RoundedTextInputStyle {
+                            radius: settings.border_radius,
+                        }))),
+                ]
+                .spacing(8)
+                .align_items(Alignment::Center)
+            );
+        }
+        editor = editor.push(
+            container(values)
+                .width(Length::Fill)
+                .padding(Padding::from([14.0, 18.0, 14.0, 18.0]))
+                .style(iced::theme::Container::Custom(Box::new(StatusItemStyle {
+                    is_installed: false,
+                    radius: settings.border_radius * 0.6,
+                })))
+        );
+
+        let mut save_row = row![
+            button(text("Save").size(button_font_size * 0.9))
+                .on_press_maybe(if has_changes { Some(Message::SaveMangoHud) } else { None })
+                .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                    is_primary: true,
+                    radius: settings.border_radius,
+                })))
+                .padding(Padding::from([10.0, 20.0, 10.0, 20.0])),
+        ]
+        .spacing(12)
+        .align_items(Alignment::Center);
+        if has_changes {
+            save_row = save_row.push(
+                text("Unsaved changes")
+                    .size(body_font_size * 0.85)
+                    .style(iced::theme::Text::Color(theme.secondary_text()))
+            );
+        } else if let Some(ref status) = self.mangohud_status {
+            let (message, color) = match status {
+                Ok(message) => (message.clone(), iced::Color::from_rgb(0.1, 0.7, 0.1)),
+                Err(e) => (e.clone(), iced::Color::from_rgb(0.9, 0.2, 0.2)),
+            };
+            save_row = save_row.push(
+                text(message)
+                    .size(body_font_size * 0.85)
+                    .style(iced::theme::Text::Color(color))
+            );
+        }
+        editor = editor.push(save_row);
+
+        let hud_lines = config.preview();
+        let mut preview = column![
+            text(format!("HUD preview ({})", config.value("position").unwrap_or("top-left")))
+                .size(body_font_size * 1.05)
+                .style(iced::theme::Text::Color(theme.primary())),
+        ]
+        .spacing(2);
+        if hud_lines.is_empty() {
+            preview = preview.push(
+                text("Nothing enabled")
+                    .size(body_font_size * 0.85)
+                    .style(iced::theme::Text::Color(theme.secondary_text()))
+            );
+        }
+        for line in hud_lines {
+            preview = preview.push(
+                text(line)
+                    .size(body_font_size * 0.85)
+                    .font(iced::Font::MONOSPACE)
+                    .style(iced::theme::Text::Color(theme.text()))
+            );
+        }
+
+        let path = mangohud::config_path(&self.mangohud_target)
+            .map(|p| p.display().to_string())
+            .unwrap_or_default();
+        let config_text = config.to_text();
+        preview = preview
+            .push(Space::with_height(Length::Fixed(16.0)))
+            .push(
+                text(path)
+                    .size(body_font_size * 0.9)
+                    .style(iced::theme::Text::Color(theme.primary()))
+            )
+            .push(
+                text(if config_text.is_empty() { "(empty file)".to_string() } else { config_text })
+                    .size(body_font_size * 0.8)
+                    .font(iced::Font::MONOSPACE)
+                    .style(iced::theme::Text::Color(theme.text()))
+            );
+        let unknown = config.unknown_keys();
+        if !unknown.is_empty() {
+            preview = preview.push(
+                text(format!("{} other key(s) preserved: {}", unknown.len(), unknown.join(", ")))
+                    .size(body_font_size * 0.8)
+                    .style(iced::theme::Text::Color(theme.secondary_text()))
+            );
+        }
+
+        container(
+            row![
+                scrollable(editor.padding(Padding::from([0.0, 12.0, 0.0, 0.0])))
+                    .width(Length::FillPortion(3))
+                    .height(Length::Fill),
+                Space::with_width(Length::Fixed(20.0)),
+                container(
+                    scrollable(preview.padding(12))
+                        .style(iced::theme::Scrollable::Custom(Box::new(CustomScrollableStyle::new(
+                            Color::from(settings.background_color.clone()),
+                            settings.border_radius,
+                        ))))
+                        .width(Length::Fill)
+                        .height(Length::Fill)
+                )
+                .width(Length::FillPortion(2))
+                .height(Length::Fill)
+                .style(iced::theme::Container::Custom(Box::new(RoundedMessageStyle {
+                    radius: settings.border_radius,
+                }))),
+            ]
+            .spacing(0)
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
+
+    fn view_launcher_options(&self, runner: &ProtonRunner, theme: &crate::gui::Theme, settings: &crate::gui::settings::AppSettings) -> Element<'_, Message> {
+        let body_font_size = (settings.font_size_body * settings.scale_body * 1.15).round();
+        let button_font_size = (settings.font_size_buttons * settings.scale_buttons * 1.2).round();
+        let input_font_size = (settings.font_size_inputs * settings.scale_inputs * 1.15).round();
+
+        let selected = self.detected_launchers.iter()
+            .find(|l| Some(&l.title) == self.selected_launcher.as_ref());
+        let mut content = column![].spacing(10);
+
+        if let Some(selected) = selected {
+            if !selected.supports(&runner.compat_layer_type) {
+                content = content.push(
+                    text(format!("{} does not use {} builds; pick another launcher to install them", selected.kind, runner.compat_layer_type))
+                        .size(body_font_size * 0.85)
+                        .style(iced::theme::Text::Color(theme.secondary_text()))
+                );
+            }
+        }
+
+        // Extra launchers that can take this runner's builds
+        let extra: Vec<&DetectedLauncher> = self.detected_launchers.iter()
+            .filter(|l| Some(&l.title) != self.selected_launcher.as_ref() && l.supports(&runner.compat_layer_type))
+            .collect();
+        if !extra.is_empty() {
+            let mut targets = row![
+                text("Also install into:")
+                    .size(body_font_size * 0.85)
+                    .style(iced::theme::Text::Color(theme.secondary_text())),
+            ]
+            .spacing(12)
+            .align_items(Alignment::Center);
+            for launcher in extra {
+                let title = launcher.title.clone();
+                targets = targets.push(
+                    checkbox(launcher.label(), self.install_targets.contains(&launcher.title))
+                        .on_toggle(move |enabled| Message::ToggleInstallTarget(title.clone(), enabled))
+                        .text_size(body_font_size * 0.85)
+                );
+            }
+            content = content.push(targets);
+        }
+
+        let kinds: Vec<String> = launchers::LAUNCHERS.iter().map(|d| d.title.to_string()).collect();
+        let can_add = self.custom_launcher_kind.is_some() && !self.custom_launcher_path.trim().is_empty();
+        let mut custom_row = row![
+            text_input("Custom launcher directory, e.g. ~/Games/Steam", &self.custom_launcher_path)
+                .on_input(Message::CustomLauncherPathChanged)
+                .on_submit(Message::AddCustomLauncher)
+                .size(input_font_size * 0.9)
+                .padding(8)
+                .width(Length::Fill)
+                .style(iced::theme::TextInput::Custom(Box::new(RoundedTextInputStyle {
+                    radius: settings.border_radius,
+                }))),
+            pick_list(kinds, self.custom_launcher_kind.clone(), Message::CustomLauncherKindSelected)
+                .placeholder("Launcher type")
+                .text_size(body_font_size * 0.85)
+                .width(Length::Fixed(200.0)),
+            button(text("Add directory").size(button_font_size * 0.85))
+                .on_press_maybe(if can_add { Some(Message::AddCustomLauncher) } else { None })
+                .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                    is_primary: false,
+                    radius: settings.border_radius,
+                })))
+                .padding(Padding::from([8.0, 12.0, 8.0, 12.0])),
+        ]
+        .spacing(8)
+        .align_items(Alignment::Center);
+        if let Some(selected) = selected.filter(|l| l.installation_type == "custom") {
+            custom_row = custom_row.push(
+                button(text("Remove directory").size(button_font_size * 0.85))
+                    .on_press(Message::RemoveCustomLauncher(selected.title.clone()))
+                    .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                        is_primary: false,
+                        radius: settings.border_radius,
+                    })))
+                    .padding(Padding::from([8.0, 12.0, 8.0, 12.0]))
+            );
+        }
+        content.push(custom_row).into()
+    }
+
+    fn view_catalog_settings(&self, theme: &crate::gui::Theme, settings: &crate::gui::settings::AppSettings) -> Element<'_, Message> {
+        if !self.show_catalog_settings {
+            return Space::with_height(Length::Shrink).into();
+        }
+        let body_font_size = (settings.font_size_body * settings.scale_body * 1.15).round();
+        let button_font_size = (settings.font_size_buttons * settings.scale_buttons * 1.2).round();
+        let input_font_size = (settings.font_size_inputs * settings.scale_inputs * 1.15).round();
+
+        let input = |placeholder: &str, value: &str, on_input: fn(String) -> Message| {
+            text_input(placeholder, value)
+                .on_input(on_input)
+                .size(input_font_size * 0.9)
+                .padding(8)
+                .width(Length::Fill)
+                .style(iced::theme::TextInput::Custom(Box::new(RoundedTextInputStyle {
+                    radius: settings.border_radius,
+                })))
+        };
+        let label = |content: &str| {
+            text(content.to_string())
+                .size(body_font_size * 0.9)
+                .style(iced::theme::Text::Color(theme.text()))
+                .width(Length::Fixed(150.0))
+        };
+
+        let sources: Vec<String> = CatalogSource::LABELS.iter().map(|l| l.to_string()).collect();
+        let mut content = column![
+            row![
+                label("Catalog source"),
+                pick_list(sources, Some(self.catalog_source_label.clone()), Message::CatalogSourceSelected)
+                    .text_size(body_font_size * 0.9)
+                    .width(Length::Fixed(240.0)),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center),
+        ]
+        .spacing(10);
+
+        if self.catalog_source_label == CatalogSource::LABELS[2] || self.catalog_source_label == CatalogSource::LABELS[3] {
+            let placeholder = if self.catalog_source_label == CatalogSource::LABELS[2] {
+                "/path/to/runners.json"
+            } else {
+                "https://mirror.example.com/runners.json"
+            };
+            content = content.push(
+                row![label("Location"), input(placeholder, &self.catalog_location, Message::CatalogLocationChanged)]
+                    .spacing(10)
+                    .align_items(Alignment::Center)
+            );
+        }
+
+        content = content
+            .push(
+                row![
+                    label("GitHub token"),
+                    input("Optional, raises the API limit to 5000 requests/hour", &self.catalog_token, Message::CatalogTokenChanged)
+                        .secure(true),
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center)
+            )
+            .push(
+                row![
+                    label("Releases API mirror"),
+                    input("Optional, replaces https://api.github.com", &self.catalog_api_base, Message::CatalogApiBaseChanged),
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center)
+            )
+            .push(
+                row![
+                    text("Unchanged release lists are revalidated with ETags and do not count against the limit.")
+                        .size(body_font_size * 0.8)
+                        .style(iced::theme::Text::Color(theme.secondary_text()))
+                        .width(Length::Fill),
+                    button(text("Save and reload").size(button_font_size * 0.85))
+                        .on_press(Message::SaveCatalogSettings)
+                        .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                            is_primary: true,
+                            radius: settings.border_radius,
+                        })))
+                        .padding(Padding::from([8.0, 12.0, 8.0, 12.0])),
+                ]
+                .spacing(12)
+                .align_items(Alignment::Center)
+            );
+
+        container(content)
+            .width(Length::Fill)
+            .padding(Padding::from([12.0, 16.0, 12.0, 16.0]))
+            .style(iced::theme::Container::Custom(Box::new(StatusItemStyle {
+                is_installed: false,
+                radius: settings.border_radius * 0.5,
+            })))
+            .into()
+    }
+
+    fn view_proton_policy(&self, runner_title: &str, theme: &crate::gui::Theme, settings: &crate::gui::settings::AppSettings) -> Element<'_, Message> {
+        let body_font_size = (settings.font_size_body * settings.scale_body * 1.15).round();
+        let button_font_size = (settings.font_size_buttons * settings.scale_buttons * 1.2).round();
+        let input_font_size = (settings.font_size_inputs * settings.scale_inputs * 1.15).round();
+
+        let launcher = match &self.selected_launcher {
+            Some(launcher) => launcher.clone(),
+            None => return Space::with_height(Length::Shrink).into(),
+        };
+        let saved = self.proton_policies.find(runner_title, &launcher);
+        let usage_known = self.detected_launchers.iter().any(|l| l.title == launcher && l.kind == "Steam");
+        let status = match saved {
+            Some(policy) => format!("Active for {}: {}. `rustora proton sync` applies it.", launcher, policy.summary(usage_known)),
+            None => format!("No retention policy for {} in {}", runner_title, launcher),
+        };
+
+        let mut actions = row![
+            button(text("Save policy").size(button_font_size * 0.85))
+                .on_press_maybe(if self.is_applying_policy { None } else { Some(Message::SaveProtonPolicy) })
+                .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                    is_primary: false,
+                    radius: settings.border_radius,
+                })))
+                .padding(Padding::from([8.0, 12.0, 8.0, 12.0])),
+            button(text(if self.is_applying_policy { "Applying..." } else { "Apply now" }).size(button_font_size * 0.85))
+                .on_press_maybe(if self.is_applying_policy { None } else { Some(Message::ApplyProtonPolicy) })
+                .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                    is_primary: true,
+                    radius: settings.border_radius,
+                })))
+                .padding(Padding::from([8.0, 12.0, 8.0, 12.0])),
+        ]
+        .spacing(8);
+        if saved.is_some() {
+            actions = actions.push(
+                button(text("Remove policy").size(button_font_size * 0.85))
+                    .on_press_maybe(if self.is_applying_policy { None } else { Some(Message::RemoveProtonPolicy) })
+                    .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                        is_primary: false,
+                        radius: settings.border_radius,
+                    })))
+                    .padding(Padding::from([8.0, 12.0, 8.0, 12.0]))
+            );
+        }
+
+        container(
+            column![
+                row![
+                    text("Keep latest")
+                        .size(body_font_size * 0.9)
+                        .style(iced::theme::Text::Color(theme.text())),
+                    text_input("0 = all", &self.policy_keep_input)
+                        .on_input(Message::PolicyKeepChanged)
+                        .size(input_font_size * 0.9)
+                        .width(Length::Fixed(70.0))
+                        .padding(8)
+                        .style(iced::theme::TextInput::Custom(Box::new(RoundedTextInputStyle {
+                            radius: settings.border_radius,
+                        }))),
+                    text("builds")
+                        .size(body_font_size * 0.9)
+                        .style(iced::theme::Text::Color(theme.text())),
+                    Space::with_width(Length::Fixed(8.0)),
+                    checkbox("Auto-install new releases", self.policy_auto_install)
+                        .on_toggle(Message::PolicyAutoInstallToggled)
+                        .text_size(body_font_size * 0.9),
+                    checkbox(
+                        if usage_known { "Never remove builds in use" } else { "Never remove builds (usage is only detected for Steam)" },
+                        self.policy_protect_in_use
+                    )
+                        .on_toggle(Message::PolicyProtectToggled)
+                        .text_size(body_font_size * 0.9),
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center),
+                row![
+                    text(status)
+                        .size(body_font_size * 0.8)
+                        .style(iced::theme::Text::Color(theme.secondary_text()))
+                        .width(Length::Fill),
+                    actions,
+                ]
+                .spacing(12)
+                .align_items(Alignment::Center),
+            ]
+            .spacing(10)
+        )
+        .width(Length::Fill)
+        .padding(Padding::from([12.0, 16.0, 12.0, 16.0]))
+        .style(iced::theme::Container::Custom(Box::new(StatusItemStyle {
+            is_installed: saved.is_some(),
+            radius: settings.border_radius * 0.5,
+        })))
+        .into()
+    }
+
+    fn view_steam_games(&self, theme: &crate::gui::Theme, settings: &crate::gui::settings::AppSettings) -> Element<'_, Message> {
+        let title_font_size = (settings.font_size_titles * settings.scale_titles * 1.2).round();
+        let body_font_size = (settings.font_size_body * settings.scale_body * 1.15).round();
+        let button_font_size = (settings.font_size_buttons * settings.scale_buttons * 1.2).round();
+        let error_color = iced::Color::from_rgb(0.9, 0.2, 0.2);
+
+        let busy = self.is_loading_steam_games || self.is_changing_compat_tools;
+        let header = row![
+            column![
+                text("Steam Games")
+                    .size(title_font_size)
+                    .style(iced::theme::Text::Color(theme.primary())),
+                text("Compatibility tool assigned to each installed game")
+                    .size(body_font_size)
+                    .style(iced::theme::Text::Color(theme.secondary_text())),
+            ]
+            .spacing(4)
+            .width(Length::Fill),
+            button(text(if self.is_loading_steam_games { "Loading..." } else { "Refresh" }).size(button_font_size * 0.9))
+                .on_press_maybe(if busy { None } else { Some(Message::LoadSteamGames) })
+                .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                    is_primary: false,
+                    radius: settings.border_radius,
+                })))
+                .padding(Padding::from([10.0, 16.0, 10.0, 16.0])),
+        ]
+        .align_items(Alignment::Center);
+
+        let library = match (&self.steam_library, &self.steam_games_error) {
+            (_, Some(error)) => {
+                return column![
+                    header,
+                    Space::with_height(Length::Fixed(16.0)),
+                    text(error)
+                        .size(body_font_size)
+                        .style(iced::theme::Text::Color(error_color)),
+                ]
+                .into();
+            }
+            (Some(library), None) => library,
+            (None, None) => {
+                return column![
+                    header,
+                    Space::with_height(Length::Fixed(16.0)),
+                    text("Reading Steam libraries...")
+                        .size(body_font_size)
+                        .style(iced::theme::Text::Color(theme.secondary_text())),
+                ]
+                .into();
+            }
+        };
+
+        let mut content = column![header].spacing(12);
+
+        if library.steam_running {
+            content = content.push(
+                container(
+                    text("Steam is running. Close Steam completely before changing compatibility tools; it rewrites config.vdf on exit and would undo the change.")
+                        .size(body_font_size * 0.9)
+                        .style(iced::theme::Text::Color(error_color))
+                )
+                .width(Length::Fill)
+                .padding(Padding::from([12.0, 16.0, 12.0, 16.0]))
+                .style(iced::theme::Container::Custom(Box::new(RoundedMessageStyle {
+                    radius: settings.border_radius,
+                })))
+            );
+        }
+
+        if let Some(status) = &self.steam_games_status {
+            let (message, color) = match status {
+                Ok(msg) => (msg.clone(), iced::Color::from_rgb(0.1, 0.7, 0.1)),
+                Err(e) => (e.clone(), error_color),
+            };
+            content = content.push(
+                text(message)
+                    .size(body_font_size * 0.9)
+                    .style(iced::theme::Text::Color(color))
+            );
+        }
+
+        let choices = library.tool_choices();
+        let tool_choices: Vec<String> = choices.iter().filter(|c| *c != steam::STEAM_DEFAULT_TOOL).cloned().collect();
+        let used_tools = library.tools_in_use();
+        let affected = self.migrate_from.as_ref()
+            .map(|from| library.games.iter().filter(|g| g.compat_tool.as_ref() == Some(from)).count())
+            .unwrap_or(0);
+        let can_migrate = !busy
+            && !library.steam_running
+            && affected > 0
+            && self.migrate_to.is_some()
+            && self.migrate_from != self.migrate_to;
+
+        content = content.push(
             container(
                 column![
-                    main_content,
-                    self.view_progress_dialog(theme, settings),
+                    text("Migrate games")
+                        .size(body_font_size * 1.05)
+                        .style(iced::theme::Text::Color(theme.primary())),
+                    text("Move every game from one tool to another, e.g. before removing an old GE-Proton build")
+                        .size(body_font_size * 0.85)
+                        .style(iced::theme::Text::Color(theme.secondary_text())),
+                    row![
+                        pick_list(used_tools, self.migrate_from.clone(), Message::MigrateFromSelected)
+                            .placeholder("From")
+                            .text_size(body_font_size * 0.9)
+                            .width(Length::Fill),
+                        text("→").size(body_font_size),
+                        pick_list(tool_choices, self.migrate_to.clone(), Message::MigrateToSelected)
+                            .placeholder("To")
+                            .text_size(body_font_size * 0.9)
+                            .width(Length::Fill),
+                        button(text(format!("Migrate {} game{}", affected, if affected == 1 { "" } else { "s" })).size(button_font_size * 0.85))
+                            .on_press_maybe(if can_migrate { Some(Message::MigrateCompatTool) } else { None })
+                            .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                                is_primary: true,
+                                radius: settings.border_radius,
+                            })))
+                            .padding(Padding::from([10.0, 16.0, 10.0, 16.0])),
+                    ]
+                    .spacing(10)
+                    .align_items(Alignment::Center),
                 ]
-                .spacing(0)
+                .spacing(8)
             )
             .width(Length::Fill)
-            .height(Length::Fill)
-            .into()
-        } else if self.show_completion_dialog {
+            .padding(Padding::from([14.0, 18.0, 14.0, 18.0]))
+            .style(iced::theme::Container::Custom(Box::new(StatusSectionStyle {
+                radius: settings.border_radius,
+                theme: *theme,
+            })))
+        );
+
+        let game_rows: Vec<Element<Message>> = library.games.iter().map(|game| {
+            let appid = game.appid.clone();
+            let current = game.compat_tool.clone().unwrap_or_else(|| steam::STEAM_DEFAULT_TOOL.to_string());
             container(
-                column![
-                    main_content,
-                    self.view_completion_dialog(theme, settings),
+                row![
+                    column![
+                        text(&game.name)
+                            .size(body_font_size)
+                            .style(iced::theme::Text::Color(theme.text())),
+                        text(format!("App {} · {}", game.appid, game.library))
+                            .size(body_font_size * 0.8)
+                            .style(iced::theme::Text::Color(theme.secondary_text())),
+                    ]
+                    .spacing(2)
+                    .width(Length::Fill),
+                    pick_list(choices.clone(), Some(current), move |tool| Message::AssignCompatTool(appid.clone(), tool))
+                        .text_size(body_font_size * 0.9)
+                        .width(Length::Fixed(260.0)),
                 ]
-                .spacing(0)
+                .spacing(12)
+                .align_items(Alignment::Center)
             )
             .width(Length::Fill)
-            .height(Length::Fill)
+            .padding(Padding::from([12.0, 16.0, 12.0, 16.0]))
+            .style(iced::theme::Container::Custom(Box::new(StatusItemStyle {
+                is_installed: game.compat_tool.is_some(),
+                radius: settings.border_radius * 0.5,
+            })))
             .into()
+        }).collect();
+
+        let games: Element<Message> = if game_rows.is_empty() {
+            text("No installed Steam games found")
+                .size(body_font_size)
+                .style(iced::theme::Text::Color(theme.secondary_text()))
+                .into()
         } else {
-            main_content.into()
+            scrollable(column(game_rows).spacing(8))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .style(iced::theme::Scrollable::Custom(Box::new(CustomScrollableStyle::new(
+                    Color::from(settings.background_color.clone()),
+                    settings.border_radius,
+                ))))
+                .into()
         };
 
-        final_content
+        content
+            .push(games)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
     }
 
     fn view_progress_dialog(&self, theme: &crate::gui::Theme, settings: &crate::gui::settings::AppSettings) -> Element<'_, Message> {
@@ -3639,7 +5633,8 @@ fn get_proton_cache_path() -> Result<std::path::PathBuf, String> {
     Ok(cache_dir.join("proton_builds.json"))
 }
 
-fn load_proton_cache() -> Option<Vec<ProtonRunner>> {
+// `max_age` of None accepts a stale cache, used when the catalog cannot be refreshed
+fn load_proton_cache(max_age: Option<u64>) -> Option<Vec<ProtonRunner>> {
     let cache_path = get_proton_cache_path().ok()?;
 
     if !cache_path.exists() {
@@ -3659,8 +5654,8 @@ fn load_proton_cache() -> Option<Vec<ProtonRunner>> {
                 .map(|now| now.as_secs().saturating_sub(dur.as_secs()))
         });
 
-    if let Some(age) = cache_age {
-        if age > 3600 {
+    if let (Some(age), Some(max_age)) = (cache_age, max_age) {
+        if age > max_age {
             return None;
         }
     }
@@ -3721,141 +5716,69 @@ fn has_new_builds(cached: &[ProtonRunner], new: &[ProtonRunner]) -> bool {
     cached.len() != new.len()
 }
 
-async fn load_proton_builds() -> Result<Vec<ProtonRunner>, String> {
-    if let Some(cached_runners) = load_proton_cache() {
+// A runner whose listing failed comes back without builds; keep its cached entry instead,
+// so a rate-limited refresh never replaces a good cache
+fn cacheable_runners(new: &[ProtonRunner], cached: &[ProtonRunner]) -> Vec<ProtonRunner> {
+    new.iter()
+        .filter_map(|runner| {
+            if runner.fetch_error.is_some() && runner.builds.is_empty() {
+                cached.iter().find(|c| c.title == runner.title).cloned()
+            } else {
+                Some(runner.clone())
+            }
+        })
+        .collect()
+}
+
+// Returns the runners and, when the configured catalog source failed, why the bundled list is used
+async fn load_proton_builds() -> Result<(Vec<ProtonRunner>, Option<String>), String> {
+    if let Some(cached_runners) = load_proton_cache(Some(3600)) {
         if !cached_runners.is_empty() {
             let cached_runners_clone = cached_runners.clone();
             tokio::spawn(async move {
-                if let Ok(new_runners) = fetch_proton_builds_from_github().await {
+                if let Ok((new_runners, _)) = fetch_proton_catalog().await {
+                    let new_runners = cacheable_runners(&new_runners, &cached_runners_clone);
                     if has_new_builds(&cached_runners_clone, &new_runners) {
                         let _ = save_proton_cache(&new_runners);
                     }
                 }
             });
-            return Ok(cached_runners);
-        }
-    }
-
-    let runners = fetch_proton_builds_from_github().await?;
-
-    if !runners.is_empty() {
-        let _ = save_proton_cache(&runners);
-    }
-
-    Ok(runners)
-}
-
-async fn fetch_proton_builds_from_github() -> Result<Vec<ProtonRunner>, String> {
-
-    // First, try to load from local data directory (for development and to avoid rate limits)
-    if let Ok(exe_path) = std::env::current_exe() {
-        if let Some(exe_dir) = exe_path.parent() {
-            // Try data/runners.json relative to executable
-            let local_path = exe_dir.join("data").join("runners.json");
-            if local_path.exists() {
-                    if let Ok(content) = std::fs::read_to_string(&local_path) {
-                    if let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) {
-                        return process_runners_json(json).await;
-                    } else {
-                    }
-                }
-            }
-
-            // Also try in project root (for development)
-            let project_path = exe_dir.join("..").join("data").join("runners.json");
-            if let Ok(canonical_path) = project_path.canonicalize() {
-                if canonical_path.exists() {
-                    if let Ok(content) = std::fs::read_to_string(&canonical_path) {
-                        if let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) {
-                            return process_runners_json(json).await;
-                        }
-                    }
-                }
-            }
+            return Ok((cached_runners, None));
         }
     }
 
-    // Try current working directory (for development)
-    let cwd_path = std::path::Path::new("data").join("runners.json");
-    if cwd_path.exists() {
-        if let Ok(content) = std::fs::read_to_string(&cwd_path) {
-            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) {
-                return process_runners_json(json).await;
+    // Listings unchanged since the last fetch come back as 304s, so this is cheap on the rate limit
+    let (runners, catalog_warning) = match fetch_proton_catalog().await {
+        Ok(result) => result,
+        Err(e) => match load_proton_cache(None) {
+            Some(stale) if !stale.is_empty() => {
+                crate::logger::Logger::log_debug(&format!("[Proton Catalog] Refresh failed, using cached builds: {}", e));
+                return Ok((stale, None));
             }
-        }
-    }
-
-
-    // Try multiple URL formats (GitHub raw content URLs can vary)
-    // TODO: Add our own GitHub repo URL here once we host it
-    let urls = vec![
-        "https://raw.githubusercontent.com/Vysp3r/ProtonPlus/main/data/runners.json",
-        "https://github.com/Vysp3r/ProtonPlus/raw/main/data/runners.json",
-        "https://raw.githubusercontent.com/Vysp3r/ProtonPlus/refs/heads/main/data/runners.json",
-    ];
+            _ => return Err(e),
+        },
+    };
 
-    let client = reqwest::Client::new();
-    let mut last_error = None;
-    let mut json_content = None;
-
-    for url in &urls {
-        match client
-            .get(*url)
-            .header("User-Agent", "Rustora/1.0")
-            .send()
-            .await
-        {
-            Ok(response) => {
-                if response.status().is_success() {
-                    match response.text().await {
-                        Ok(content) => {
-                            if content.trim().is_empty() {
-                                last_error = Some(format!("Empty response from {}", url));
-                                continue;
-                            }
-                            // Check if it's valid JSON by trying to parse it
-                            match serde_json::from_str::<serde_json::Value>(&content) {
-                                Ok(_) => {
-                                    json_content = Some(content);
-                                    break;
-                                }
-                                Err(e) => {
-                                    last_error = Some(format!("Invalid JSON from {}: {}", url, e));
-                                    continue;
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            last_error = Some(format!("Failed to read response from {}: {}", url, e));
-                        }
-                    }
-                } else {
-                    let status = response.status();
-                    // Try to read error body for debugging
-                    let _ = response.text().await;
-                    last_error = Some(format!("HTTP {} from {}", status, url));
-                }
-            }
-            Err(e) => {
-                last_error = Some(format!("Failed to fetch from {}: {}", url, e));
-            }
-        }
+    let cacheable = cacheable_runners(&runners, &load_proton_cache(None).unwrap_or_default());
+    if !cacheable.is_empty() {
+        let _ = save_proton_cache(&cacheable);
     }
 
-    let json_content = json_content.ok_or_else(|| {
-        last_error.unwrap_or_else(|| "All URL attempts failed".to_string())
-    })?;
-
-    let json: serde_json::Value = serde_json::from_str(&json_content)
-        .map_err(|e| {
-            format!("Failed to parse runners.json: {}", e)
-        })?;
+    Ok((runners, catalog_warning))
+}
 
-    process_runners_json(json).await
+// runners.json comes from the source picked in the catalog settings (bundled by default)
+async fn fetch_proton_catalog() -> Result<(Vec<ProtonRunner>, Option<String>), String> {
+    let settings = CatalogSettings::load();
+    let (json, catalog_warning) = proton_catalog::load_catalog(&settings).await?;
+    let runners = process_runners_json(json, &settings).await?;
+    Ok((runners, catalog_warning))
 }
 
-async fn process_runners_json(json: serde_json::Value) -> Result<Vec<ProtonRunner>, String> {
+async fn process_runners_json(json: serde_json::Value, settings: &CatalogSettings) -> Result<Vec<ProtonRunner>, String> {
     let mut proton_runners = Vec::new();
+    let client = reqwest::Client::new();
+    let mut http_cache = proton_catalog::HttpCache::load();
 
     // Get compat_layers array
     if let Some(compat_layers) = json.get("compat_layers").and_then(|v| v.as_array()) {
@@ -3887,99 +5810,90 @@ async fn process_runners_json(json: serde_json::Value) -> Result<Vec<ProtonRunne
                                             .and_then(|v| v.as_u64())
                                             .unwrap_or(0) as usize;
 
-                                        // Fetch releases from GitHub API
-                                        let client = reqwest::Client::new();
-                                        let url = format!("{}?per_page=25&page=1", endpoint);
-
+                                        // Fetch releases from GitHub API (or the configured mirror), reusing cached listings via ETag
                                         let mut builds = Vec::new();
+                                        let mut fetch_error = None;
+                                        let mut fetch_warning = None;
+
+                                        match proton_catalog::fetch_releases(&client, settings, &mut http_cache, endpoint, 1).await {
+                                            Ok((releases_json, warning)) => {
+                                                fetch_warning = warning;
+                                                if let Some(releases_array) = releases_json.as_array() {
+
+                                                    for (_release_idx, release) in releases_array.iter().enumerate() {
+                                                        if let Some(tag_name) = release.get("tag_name").and_then(|v| v.as_str()) {
+                                                            if let Some(assets) = release.get("assets").and_then(|v| v.as_array()) {
+
+                                                                // Try to find tar.gz file first, fall back to asset_position
+                                                                let mut selected_asset: Option<&serde_json::Value> = None;
+
+                                                                // First, try to find a .tar.gz file
+                                                                for asset in assets.iter() {
+                                                                    if let Some(download_url) = asset.get("browser_download_url").and_then(|v| v.as_str()) {
+                                                                        if download_url.ends_with(".tar.gz") || download_url.ends_with(".tar.gz?") {
+                                                                            selected_asset = Some(asset);
+                                                                            break;
+                                                                        }
+                                                                    }
+                                                                }
 
-                                        match client.get(&url)
-                                            .header("User-Agent", "Rustora/1.0")
-                                            .send()
-                                            .await
-                                        {
-                                            Ok(response) => {
-                                                if response.status().is_success() {
-                                                    match response.json::<serde_json::Value>().await {
-                                                        Ok(releases_json) => {
-                                                            if let Some(releases_array) = releases_json.as_array() {
-
-                                                                for (_release_idx, release) in releases_array.iter().enumerate() {
-                                                                    if let Some(tag_name) = release.get("tag_name").and_then(|v| v.as_str()) {
-                                                                        if let Some(assets) = release.get("assets").and_then(|v| v.as_array()) {
-
-                                                                            // Try to find tar.gz file first, fall back to asset_position
-                                                                            let mut selected_asset: Option<&serde_json::Value> = None;
-
-                                                                            // First, try to find a .tar.gz file
-                                                                            for asset in assets.iter() {
-                                                                                if let Some(download_url) = asset.get("browser_download_url").and_then(|v| v.as_str()) {
-                                                                                    if download_url.ends_with(".tar.gz") || download_url.ends_with(".tar.gz?") {
-                                                                                        selected_asset = Some(asset);
-                                                                                        break;
-                                                                                    }
-                                                                                }
-                                                                            }
-
-                                                                            // If no tar.gz found, use asset_position
-                                                                            if selected_asset.is_none() && assets.len() > asset_position {
-                                                                                selected_asset = assets.get(asset_position);
-                                                                            }
-
-                                                                            if let Some(asset) = selected_asset {
-                                                                                if let Some(download_url) = asset.get("browser_download_url").and_then(|v| v.as_str()) {
-                                                                                        let description = release.get("body")
-                                                                                            .and_then(|v| v.as_str())
-                                                                                            .unwrap_or("")
-                                                                                            .to_string();
-                                                                                        let release_date = release.get("created_at")
-                                                                                            .and_then(|v| v.as_str())
-                                                                                            .unwrap_or("")
-                                                                                            .to_string();
-                                                                                        let page_url = release.get("html_url")
-                                                                                            .and_then(|v| v.as_str())
-                                                                                            .unwrap_or("")
-                                                                                            .to_string();
-                                                                                        let download_size = asset.get("size")
-                                                                                            .and_then(|v| v.as_u64())
-                                                                                            .unwrap_or(0);
-
-
-                                                                                        // Check if installed (will be updated after launcher detection)
-                                                                                        let is_installed = false; // Will be checked later with proper directory format
-
-                                                                                        builds.push(ProtonBuild {
-                                                                                            title: tag_name.to_string(),
-                                                                                            description,
-                                                                                            release_date,
-                                                                                            download_url: download_url.to_string(),
-                                                                                            page_url,
-                                                                                            download_size,
-                                                                                            runner_title: runner_title.to_string(),
-                                                                                            is_installed,
-                                                                                            directory_name_formats: directory_name_formats.clone(),
-                                                                                            usage_count: 0, // Will be updated after checking Steam config
-                                                                                            is_latest: false, // Regular release, not "Latest"
-                                                                                        });
-                                                                                    } else {
-                                                                                    }
-                                                                                } else {
-                                                                                }
+                                                                // If no tar.gz found, use asset_position
+                                                                if selected_asset.is_none() && assets.len() > asset_position {
+                                                                    selected_asset = assets.get(asset_position);
+                                                                }
+
+                                                                if let Some(asset) = selected_asset {
+                                                                    if let Some(download_url) = asset.get("browser_download_url").and_then(|v| v.as_str()) {
+                                                                            let description = release.get("body")
+                                                                                .and_then(|v| v.as_str())
+                                                                                .unwrap_or("")
+                                                                                .to_string();
+                                                                            let release_date = release.get("created_at")
+                                                                                .and_then(|v| v.as_str())
+                                                                                .unwrap_or("")
+                                                                                .to_string();
+                                                                            let page_url = release.get("html_url")
+                                                                                .and_then(|v| v.as_str())
+                                                                                .unwrap_or("")
+                                                                                .to_string();
+                                                                            let download_size = asset.get("size")
+                                                                                .and_then(|v| v.as_u64())
+                                                                                .unwrap_or(0);
+                                                                            let checksum_url = find_checksum_asset(assets);
+
+
+                                                                            // Check if installed (will be updated after launcher detection)
+                                                                            let is_installed = false; // Will be checked later with proper directory format
+
+                                                                            builds.push(ProtonBuild {
+                                                                                title: tag_name.to_string(),
+                                                                                description,
+                                                                                release_date,
+                                                                                download_url: download_url.to_string(),
+                                                                                page_url,
+                                                                                download_size,
+                                                                                checksum_url,
+                                                                                runner_title: runner_title.to_string(),
+                                                                                is_installed,
+                                                                                directory_name_formats: directory_name_formats.clone(),
+                                                                                usage_count: 0, // Will be updated after checking Steam config
+                                                                                is_latest: false, // Regular release, not "Latest"
+                                                                            });
                                                                         } else {
                                                                         }
                                                                     } else {
                                                                     }
-                                                                }
                                                             } else {
                                                             }
-                                                        }
-                                                        Err(_e) => {
+                                                        } else {
                                                         }
                                                     }
                                                 } else {
                                                 }
                                             }
-                                            Err(_e) => {
+                                            Err(e) => {
+                                                crate::logger::Logger::log_debug(&format!("[Proton Catalog] {}: {}", runner_title, e));
+                                                fetch_error = Some(e);
                                             }
                                         }
 
@@ -4012,6 +5926,8 @@ async fn process_runners_json(json: serde_json::Value) -> Result<Vec<ProtonRunne
                                             builds: final_builds,
                                             has_latest_support,
                                             compat_layer_type: title.to_string(), // "Proton" or "Wine"
+                                            fetch_error,
+                                            fetch_warning,
                                         });
                                     } else {
                                     }
@@ -4029,79 +5945,34 @@ async fn process_runners_json(json: serde_json::Value) -> Result<Vec<ProtonRunne
     } else {
     }
 
-    Ok(proton_runners)
-}
+    http_cache.save();
 
-async fn detect_launchers() -> Result<Vec<DetectedLauncher>, String> {
-    let home = std::env::var("HOME").unwrap_or_else(|_| "/home".to_string());
-    let mut launchers = Vec::new();
-
-    // Detect Steam (system, flatpak, snap)
-    let steam_paths = vec![
-        (format!("{}/.local/share/Steam", home), "system"),
-        (format!("{}/.steam/steam", home), "system"),
-        (format!("{}/.steam/root", home), "system"),
-        (format!("{}/.steam/debian-installation", home), "system"),
-        (format!("{}/.var/app/com.valvesoftware.Steam/data/Steam", home), "flatpak"),
-        ("/snap/steam/common/.steam/root".to_string(), "snap"),
-    ];
-
-    for (path, install_type) in steam_paths {
-        let full_path = if path.starts_with('/') && !path.starts_with(&home) {
-            path.clone()
-        } else {
-            path.clone()
-        };
-        if std::path::Path::new(&full_path).exists() {
-            // Verify it's actually Steam by checking for steamclient.dll files
-            let steamclient = format!("{}/steamclient.dll", full_path);
-            let steamclient64 = format!("{}/steamclient64.dll", full_path);
-            if std::path::Path::new(&steamclient).exists() && std::path::Path::new(&steamclient64).exists() {
-                launchers.push(DetectedLauncher {
-                    title: "Steam".to_string(),
-                    directory: full_path,
-                    installation_type: install_type.to_string(),
-                });
-                break; // Only add one Steam installation
-            }
+    // Nothing came back at all: report the reason instead of an empty list
+    if !proton_runners.is_empty() && proton_runners.iter().all(|r| r.builds.is_empty()) {
+        if let Some(error) = proton_runners.iter().find_map(|r| r.fetch_error.clone()) {
+            return Err(error);
         }
     }
 
-    // Detect Lutris (system, flatpak)
-    let lutris_paths = vec![
-        (format!("{}/.local/share/lutris", home), "system"),
-        (format!("{}/.var/app/net.lutris.Lutris/data/lutris", home), "flatpak"),
-    ];
-
-    for (path, install_type) in lutris_paths {
-        if std::path::Path::new(&path).exists() {
-            launchers.push(DetectedLauncher {
-                title: "Lutris".to_string(),
-                directory: path,
-                installation_type: install_type.to_string(),
-            });
-            break;
-        }
-    }
+    Ok(proton_runners)
+}
 
-    // Detect Heroic Games Launcher (system, flatpak)
-    let heroic_paths = vec![
-        (format!("{}/.config/heroic", home), "system"),
-        (format!("{}/.var/app/com.heroicgameslauncher.hgl/config/heroic", home), "flatpak"),
-    ];
-
-    for (path, install_type) in heroic_paths {
-        if std::path::Path::new(&path).exists() {
-            launchers.push(DetectedLauncher {
-                title: "Heroic Games Launcher".to_string(),
-                directory: path,
-                installation_type: install_type.to_string(),
-            });
-            break;
-        }
-    }
+// GE-Proton and most other runners publish `<name>.sha512sum` next to the archive
+fn find_checksum_asset(assets: &[serde_json::Value]) -> Option<String> {
+    let urls: Vec<&str> = assets.iter()
+        .filter_map(|asset| asset.get("browser_download_url").and_then(|v| v.as_str()))
+        .collect();
+    urls.iter()
+        .find(|url| url.ends_with(".sha512sum"))
+        .or_else(|| urls.iter().find(|url| url.ends_with(".sha256sum")))
+        .map(|url| url.to_string())
+}
 
-    Ok(launchers)
+// Launcher locations live in launchers.rs; custom directories are merged in there
+async fn detect_launchers() -> Result<Vec<DetectedLauncher>, String> {
+    tokio::task::spawn_blocking(launchers::detect)
+        .await
+        .map_err(|e| format!("Launcher detection task error: {}", e))
 }
 
 fn check_proton_installed(runner_title: &str, release_name: &str, directory_name_formats: &[DirectoryNameFormat], detected_launchers: &[DetectedLauncher], compat_layer_type: &str) -> bool {
@@ -4109,14 +5980,17 @@ fn check_proton_installed(runner_title: &str, release_name: &str, directory_name
     // Get directory name format for each detected launcher
     for launcher in detected_launchers {
         let format = directory_name_formats.iter()
-            .find(|f| f.launcher == launcher.title)
+            .find(|f| f.launcher == launcher.kind)
             .or_else(|| directory_name_formats.iter().find(|f| f.launcher == "default"));
 
         if let Some(format) = format {
             let dir_name = format_directory_name(&format.directory_name_format, runner_title, release_name);
 
             // Get the directory path based on launcher type and compat layer type
-            let compat_dir = get_launcher_compat_directory_for_type(&launcher.title, &launcher.directory, compat_layer_type);
+            let compat_dir = match launcher.compat_directory(compat_layer_type) {
+                Some(dir) => dir,
+                None => continue,
+            };
             let full_path = format!("{}/{}", compat_dir, dir_name);
 
             let path_exists = std::path::Path::new(&full_path).exists();
@@ -4161,11 +6035,14 @@ fn has_proton_update(runner: &ProtonRunner, build: &ProtonBuild, detected_launch
     // For "Latest" builds, we need to find which actual release is installed
     for launcher in detected_launchers {
         let format = runner.directory_name_formats.iter()
-            .find(|f| f.launcher == launcher.title)
+            .find(|f| f.launcher == launcher.kind)
             .or_else(|| runner.directory_name_formats.iter().find(|f| f.launcher == "default"));
 
         if let Some(format) = format {
-            let compat_dir = get_launcher_compat_directory_for_type(&launcher.title, &launcher.directory, &runner.compat_layer_type);
+            let compat_dir = match launcher.compat_directory(&runner.compat_layer_type) {
+                Some(dir) => dir,
+                None => continue,
+            };
 
             // Check if the latest release is already installed
             let latest_dir_name = format_directory_name(&format.directory_name_format, &runner.title, &latest_release.title);
@@ -4196,6 +6073,20 @@ fn has_proton_update(runner: &ProtonRunner, build: &ProtonBuild, detected_launch
     false
 }
 
+// Directory a release is installed under for a launcher kind, per runners.json
+pub fn build_directory_name(runner: &ProtonRunner, launcher_kind: &str, release_name: &str) -> String {
+    runner.directory_name_formats.iter()
+        .find(|f| f.launcher == launcher_kind)
+        .or_else(|| runner.directory_name_formats.iter().find(|f| f.launcher == "default"))
+        .map(|f| format_directory_name(&f.directory_name_format, &runner.title, release_name))
+        .unwrap_or_else(|| release_name.to_string())
+}
+
+// Name Steam stores in CompatToolMapping for a build: its Steam directory name
+fn steam_tool_name(runner: &ProtonRunner, release_name: &str) -> String {
+    build_directory_name(runner, "Steam", release_name)
+}
+
 fn format_directory_name(format: &str, runner_title: &str, release_name: &str) -> String {
     let mut result = format.to_owned();
     result = result.replace("$release_name", release_name);
@@ -4232,77 +6123,21 @@ fn format_directory_name(format: &str, runner_title: &str, release_name: &str) -
     result
 }
 
-fn get_launcher_compat_directory_for_type(launcher_title: &str, launcher_dir: &str, compat_layer_type: &str) -> String {
-    match (launcher_title, compat_layer_type) {
-        ("Steam", _) => {
-            // Steam only supports Proton in compatibilitytools.d
-            let paths = vec![
-                format!("{}/compatibilitytools.d", launcher_dir),
-                format!("{}/steamapps/common", launcher_dir),
-            ];
-            for path in paths {
-                if std::path::Path::new(&path).parent().map(|p| p.exists()).unwrap_or(false) {
-                    return path;
-                }
-            }
-            format!("{}/compatibilitytools.d", launcher_dir)
-        }
-        ("Lutris", "Proton") => format!("{}/runners/proton", launcher_dir),
-        ("Lutris", "Wine") => format!("{}/runners/wine", launcher_dir),
-        ("Heroic Games Launcher", "Proton") => format!("{}/tools/proton", launcher_dir),
-        ("Heroic Games Launcher", "Wine") => format!("{}/tools/wine", launcher_dir),
-        ("Bottles", "Proton") => format!("{}/runners", launcher_dir),
-        ("Bottles", "Wine") => format!("{}/runners", launcher_dir),
-        ("WineZGUI", "Wine") => format!("{}/Runners", launcher_dir),
-        _ => format!("{}/compatibilitytools.d", launcher_dir),
-    }
-}
-
 // Progress is updated step-by-step during download and installation
 // Download progress: 0-50% (overall), 0-100% (download bar)
 // Install progress: 50-100% (overall), 0-100% (install bar)
 
-async fn download_proton_build(runner_title: String, title: String, download_url: String) -> Result<(String, String, String), String> {
-
-    let client = reqwest::Client::new();
-    let temp_dir = std::env::temp_dir();
-    let tar_path = temp_dir.join(format!("{}.tar.gz", title));
-
-    // Check if file already exists
-    if tar_path.exists() {
-        let _ = std::fs::metadata(&tar_path);
-    }
-
-    let response = client
-        .get(&download_url)
-        .header("User-Agent", "Rustora/1.0")
-        .send()
-        .await
-        .map_err(|e| {
-            format!("Failed to download: {}", e)
-        })?;
-
-    let status = response.status();
-    if !status.is_success() {
-        let _ = response.text().await;
-        return Err(format!("Failed to download: HTTP {}", status));
-    }
-
-    let _ = response.content_length();
-
-    let bytes = response.bytes().await
-        .map_err(|e| {
-            format!("Failed to read download: {}", e)
-        })?;
-
-    std::fs::write(&tar_path, bytes.as_ref())
-        .map_err(|e| {
-            format!("Failed to save file: {}", e)
-        })?;
-
-    let _ = std::fs::metadata(&tar_path);
-
-    Ok((runner_title, title, tar_path.to_string_lossy().into_owned()))
+// Returns (runner, build, archive path, checksum summary)
+async fn download_proton_build(runner_title: String, title: String, download_url: String, checksum_url: Option<String>) -> Result<(String, String, String, String), String> {
+    let (archive, verification) = proton_download::fetch_verified_archive(
+        &title,
+        &download_url,
+        checksum_url.as_deref(),
+        |_, _| {},
+    )
+    .await?;
+
+    Ok((runner_title, title, archive.to_string_lossy().into_owned(), verification))
 }
 
 async fn install_proton_build_with_launcher(
@@ -4312,17 +6147,11 @@ async fn install_proton_build_with_launcher(
     selected_launcher: Option<String>,
     runner: Option<ProtonRunner>,
 ) -> Result<(String, String), String> {
-    use std::fs::File;
-    use flate2::read::GzDecoder;
-    use tar::Archive;
-
     // Verify archive exists
     if !std::path::Path::new(&tar_path).exists() {
         return Err(format!("Archive file not found: {}", tar_path));
     }
 
-    let _ = std::fs::metadata(&tar_path);
-
     // Get the selected launcher or use first detected
     let launcher_title = selected_launcher.as_ref().map(|s| s.as_str()).unwrap_or("Steam");
 
@@ -4338,7 +6167,7 @@ async fn install_proton_build_with_launcher(
     // Get directory name format for this launcher
     let directory_name = if let Some(ref runner_data) = runner {
         let format = runner_data.directory_name_formats.iter()
-            .find(|f| f.launcher == launcher_title)
+            .find(|f| f.launcher == launcher.kind)
             .or_else(|| runner_data.directory_name_formats.iter().find(|f| f.launcher == "default"));
 
         if let Some(format) = format {
@@ -4353,147 +6182,48 @@ async fn install_proton_build_with_launcher(
 
     // Get the compatibility directory for this launcher and compat layer type
     let compat_layer_type = runner.as_ref().map(|r| r.compat_layer_type.as_str()).unwrap_or("Proton");
-    let compat_dir = get_launcher_compat_directory_for_type(&launcher.title, &launcher.directory, compat_layer_type);
+    let compat_dir = launcher.compat_directory(compat_layer_type)
+        .ok_or_else(|| format!("{} does not use {} builds", launcher.title, compat_layer_type))?;
 
     // Ensure directory exists
-    if let Some(parent) = std::path::Path::new(&compat_dir).parent() {
-        let _ = std::fs::create_dir_all(parent);
-    }
     std::fs::create_dir_all(&compat_dir)
         .map_err(|e| format!("Failed to create compatibility directory: {}", e))?;
 
-    // Extract archive (detect format)
-    let mut file = File::open(&tar_path)
-        .map_err(|e| {
-            format!("Failed to open archive: {}", e)
-        })?;
-
-    // Read first 6 bytes to detect compression format (xz needs 6 bytes)
-    use std::io::{Read, Seek, SeekFrom};
-    let mut magic_buf = [0u8; 6];
-    if file.read_exact(&mut magic_buf).is_err() {
-        return Err("Archive file appears to be corrupted or incomplete".to_string());
-    }
-
-    // Reset file position for actual extraction
-    file.seek(SeekFrom::Start(0))
-        .map_err(|e| format!("Failed to seek file: {}", e))?;
-
-    // Detect compression format
-    let is_gzip = magic_buf[0] == 0x1f && magic_buf[1] == 0x8b;
-    let is_zstd = magic_buf[0] == 0x28 && magic_buf[1] == 0xb5 && magic_buf[2] == 0x2f && magic_buf[3] == 0xfd;
-    let is_xz = magic_buf[0] == 0xfd && magic_buf[1] == 0x37 && magic_buf[2] == 0x7a && magic_buf[3] == 0x58 && magic_buf[4] == 0x5a && magic_buf[5] == 0x00;
-
-
-    if !is_gzip && !is_zstd && !is_xz {
-        return Err(format!("Unsupported archive format (magic bytes: {:02x} {:02x} {:02x} {:02x}). Expected gzip (.tar.gz), zstd (.tar.zst), or xz (.tar.xz).",
-            magic_buf[0], magic_buf[1], magic_buf[2], magic_buf[3]));
-    }
-
-    // Extract to temp directory in user's home folder (to avoid cross-device issues)
-    let home = std::env::var("HOME").unwrap_or_else(|_| "/home".to_string());
-    let home_tmp = std::path::Path::new(&home).join(".tmp");
-    std::fs::create_dir_all(&home_tmp)
-        .map_err(|e| {
-            format!("Failed to create temp directory: {}", e)
-        })?;
-    let temp_extract = home_tmp.join(format!("proton_extract_{}", title));
-    if temp_extract.exists() {
-        std::fs::remove_dir_all(&temp_extract)
-            .map_err(|e| {
-                format!("Failed to clean temp extract: {}", e)
-            })?;
-    }
-    std::fs::create_dir_all(&temp_extract)
-        .map_err(|e| format!("Failed to create temp extract: {}", e))?;
-
-    // Create appropriate decoder and extract based on format
-    if is_gzip {
-        let gz = GzDecoder::new(file);
-        let mut archive = Archive::new(gz);
-    archive.unpack(&temp_extract)
-            .map_err(|e| format!("Failed to extract gzip archive: {}", e))?;
-    } else if is_zstd {
-        // zstd
-        use zstd::stream::Decoder;
-        let decoder = Decoder::new(file)
-            .map_err(|e| format!("Failed to create zstd decoder: {}", e))?;
-        let mut archive = Archive::new(decoder);
-        archive.unpack(&temp_extract)
-            .map_err(|e| format!("Failed to extract zstd archive: {}", e))?;
-    } else {
-        // xz
-        use xz2::read::XzDecoder;
-        let xz = XzDecoder::new(file);
-        let mut archive = Archive::new(xz);
-        archive.unpack(&temp_extract)
-            .map_err(|e| format!("Failed to extract xz archive: {}", e))?;
+    // Extract into the Rustora cache (same filesystem as the compat dir in the usual case),
+    // rejecting entries that would escape the extraction directory
+    let temp_extract = proton_download::prepare_extract_dir(&title)?;
+    let archive = std::path::PathBuf::from(&tar_path);
+    let extract_target = temp_extract.clone();
+    let extracted = tokio::task::spawn_blocking(move || proton_download::unpack_archive(&archive, &extract_target))
+        .await
+        .map_err(|e| format!("Extraction task error: {}", e))?;
+    if let Err(e) = extracted {
+        let _ = std::fs::remove_dir_all(&temp_extract);
+        return Err(e);
     }
 
-    // Get the temp extract directory (same for both formats)
-    let home_tmp = std::path::Path::new(&home).join(".tmp");
-    let temp_extract = home_tmp.join(format!("proton_extract_{}", title));
-
-    // Find the extracted directory (usually the first directory in the archive)
-    let entries: Vec<_> = std::fs::read_dir(&temp_extract)
-        .map_err(|e| {
-            format!("Failed to read extract dir: {}", e)
-        })?
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| {
-            format!("Failed to read entry: {}", e)
-        })?;
-
-    let extracted_dir = entries
-        .iter()
-        .find(|entry| {
-            if let Ok(metadata) = entry.metadata() {
-                metadata.is_dir()
-            } else {
-                false
-            }
-        })
-        .ok_or_else(|| {
-            "No directory found in archive"
-        })?
-        .path();
-
-    if !extracted_dir.is_dir() {
-        return Err("Archive does not contain a directory".to_string());
-    }
+    let extracted_dir = proton_download::find_extracted_dir(&temp_extract)?;
 
     // Move to compatibility directory with proper directory name
     let dest_path = std::path::Path::new(&compat_dir).join(&directory_name);
 
-    // Check if destination exists
     if dest_path.exists() {
-        let _ = dest_path.metadata();
         std::fs::remove_dir_all(&dest_path)
-            .map_err(|e| {
-                let _ = dest_path.metadata();
-                format!("Failed to remove existing installation: {}", e)
-            })?;
-    } else {
+            .map_err(|e| format!("Failed to remove existing installation: {}", e))?;
     }
 
-    // Verify source exists before moving
-    if !extracted_dir.exists() {
-        return Err(format!("Extracted directory not found: {}", extracted_dir.display()));
-    }
-
-    let _ = extracted_dir.metadata();
-
-    let copy_start = std::time::Instant::now();
-
-    // Use copy_dir_all to handle cross-device moves
+    // Use copy_dir_all to handle cross-device moves; symlinks are recreated, not followed
     fn copy_dir_all(src: &std::path::Path, dst: &std::path::Path) -> std::io::Result<()> {
         std::fs::create_dir_all(dst)?;
         for entry in std::fs::read_dir(src)? {
             let entry = entry?;
             let path = entry.path();
             let dst_path = dst.join(entry.file_name());
+            let file_type = entry.file_type()?;
 
-            if path.is_dir() {
+            if file_type.is_symlink() {
+                std::os::unix::fs::symlink(std::fs::read_link(&path)?, &dst_path)?;
+            } else if file_type.is_dir() {
                 copy_dir_all(&path, &dst_path)?;
             } else {
                 std::fs::copy(&path, &dst_path)?;
@@ -4503,37 +6233,10 @@ async fn install_proton_build_with_launcher(
     }
 
     copy_dir_all(&extracted_dir, &dest_path)
-        .map_err(|e| {
-            if let Some(parent) = dest_path.parent() {
-                let _ = parent.metadata();
-            }
-            format!("Failed to copy to {}: {}", compat_dir, e)
-        })?;
-    let _ = copy_start.elapsed();
-
-    // Verify installation
-    if dest_path.exists() {
-        let _ = dest_path.metadata();
-    } else {
-    }
-
+        .map_err(|e| format!("Failed to copy to {}: {}", compat_dir, e))?;
 
-    // Clean up
-    let cleanup_start = std::time::Instant::now();
-
-    // Remove temp archive
-    let _ = std::fs::remove_file(&tar_path);
-    let _ = std::fs::remove_dir_all(&temp_extract);
-
-    // Also try to remove the parent .tmp directory if it's empty
-    if let Some(parent_tmp) = temp_extract.parent() {
-        if parent_tmp.exists() {
-            // Only remove if it's empty (ignore errors)
-            let _ = std::fs::remove_dir(parent_tmp);
-        }
-    }
-
-    let _ = cleanup_start.elapsed();
+    // Clean up the archive and the unpacked copy
+    proton_download::cleanup(std::path::Path::new(&tar_path), &temp_extract);
 
     Ok((runner_title, title))
 }
@@ -4541,47 +6244,13 @@ async fn install_proton_build_with_launcher(
 async fn check_proton_usage(mut runners: Vec<ProtonRunner>, launchers: Vec<DetectedLauncher>) -> Result<Vec<ProtonRunner>, String> {
 
     // For now, only check Steam usage (other launchers don't have a centralized config like Steam)
-    let steam_launcher = launchers.iter().find(|l| l.title == "Steam");
-
     let mut tool_usage: std::collections::HashMap<String, u32> = std::collections::HashMap::new();
 
-    if let Some(steam) = steam_launcher {
-
-        // Read Steam's config.vdf to get compatibility tool mappings
-        let config_path = format!("{}/config/config.vdf", steam.directory);
-
-        if let Ok(config_content) = std::fs::read_to_string(&config_path) {
-            // Parse CompatToolMapping section
-            // Format: "CompatToolMapping"\n\t\t\t\t{\n\t\t\t\t\t"<appid>"\n\t\t\t\t\t{\n\t\t\t\t\t\t"name"\t\t"<tool_name>"\n...
-            let compat_tool_mapping_start = "\"CompatToolMapping\"\n\t\t\t\t{";
-            let compat_tool_mapping_end = "\n\t\t\t\t}";
-
-            if let Some(start_pos) = config_content.find(compat_tool_mapping_start) {
-                let start = start_pos + compat_tool_mapping_start.len();
-                if let Some(end_pos) = config_content[start..].find(compat_tool_mapping_end) {
-                    let mapping_content = &config_content[start..start + end_pos];
-
-                    // Find all "name"\t\t"<tool_name>" patterns
-                    let name_pattern = "\"name\"\t\t\"";
-                    let mut search_pos = 0;
-                    while let Some(name_start) = mapping_content[search_pos..].find(name_pattern) {
-                        let name_start = search_pos + name_start + name_pattern.len();
-                        if let Some(name_end) = mapping_content[name_start..].find('"') {
-                            let tool_name = mapping_content[name_start..name_start + name_end].to_string();
-                            let count = tool_usage.entry(tool_name.clone()).or_insert(0);
-                            *count += 1;
-                            search_pos = name_start + name_end;
-                        } else {
-                            break;
-                        }
-                    }
-
-                }
-            } else {
-            }
-        } else {
+    for steam in launchers.iter().filter(|l| l.kind == "Steam") {
+        // CompatToolMapping in config/config.vdf maps app ids (and "0", the global default) to tools
+        for tool_name in steam::compat_tool_mapping(&steam.directory).into_values() {
+            *tool_usage.entry(tool_name).or_insert(0) += 1;
         }
-    } else {
     }
 
     // Update usage counts in runners
@@ -4596,7 +6265,7 @@ async fn check_proton_usage(mut runners: Vec<ProtonRunner>, launchers: Vec<Detec
             let mut usage = 0u32;
             for launcher in &launchers {
                 let format = runner.directory_name_formats.iter()
-                    .find(|f| f.launcher == launcher.title)
+                    .find(|f| f.launcher == launcher.kind)
                     .or_else(|| runner.directory_name_formats.iter().find(|f| f.launcher == "default"));
 
                 if let Some(format) = format {
@@ -4650,7 +6319,7 @@ async fn remove_proton_build(
 
     // Get directory name format for this launcher
     let format = runner.directory_name_formats.iter()
-        .find(|f| f.launcher == launcher_title)
+        .find(|f| f.launcher == launcher.kind)
         .or_else(|| runner.directory_name_formats.iter().find(|f| f.launcher == "default"));
 
     let directory_name = if let Some(format) = format {
@@ -4661,7 +6330,8 @@ async fn remove_proton_build(
 
 
     // Get the compatibility directory for this launcher and compat layer type
-    let compat_dir = get_launcher_compat_directory_for_type(&launcher.title, &launcher.directory, &runner.compat_layer_type);
+    let compat_dir = launcher.compat_directory(&runner.compat_layer_type)
+        .ok_or_else(|| format!("{} does not use {} builds", launcher.title, runner.compat_layer_type))?;
     let install_path = std::path::Path::new(&compat_dir).join(&directory_name);
 
 
@@ -4685,6 +6355,111 @@ async fn remove_proton_build(
     Ok((runner_title, title))
 }
 
+// Apply retention policies: install the newest release if asked to, then remove installed
+// releases beyond the newest `keep_latest`, skipping builds games still use. Only the
+// releases on the first catalog page are considered, so older builds are never touched.
+pub async fn sync_proton_builds(policies: Vec<ProtonPolicy>, dry_run: bool) -> Result<Vec<String>, String> {
+    let mut log = Vec::new();
+    if policies.is_empty() {
+        log.push("No Proton retention policies configured".to_string());
+        return Ok(log);
+    }
+
+    let (runners, catalog_warning) = load_proton_builds().await?;
+    if let Some(warning) = catalog_warning {
+        log.push(format!("[WARN] {}", warning));
+    }
+    let launchers = detect_launchers().await?;
+    let runners = check_proton_usage(runners, launchers.clone()).await?;
+
+    for policy in policies {
+        let prefix = format!("{} ({})", policy.runner, policy.launcher);
+        let runner = match runners.iter().find(|r| r.title == policy.runner) {
+            Some(runner) => runner.clone(),
+            None => {
+                log.push(format!("[SKIP] {}: runner not found in the catalog", prefix));
+                continue;
+            }
+        };
+        let launcher = match launchers.iter().find(|l| l.title == policy.launcher) {
+            Some(launcher) => launcher.clone(),
+            None => {
+                log.push(format!("[SKIP] {}: launcher not detected", prefix));
+                continue;
+            }
+        };
+        let only_launcher = vec![launcher.clone()];
+
+        // Newest first, as the catalog lists them
+        let releases: Vec<&ProtonBuild> = runner.builds.iter().filter(|b| !b.is_latest).collect();
+        let mut installed: Vec<String> = releases.iter()
+            .filter(|b| check_proton_installed(&runner.title, &b.title, &runner.directory_name_formats, &only_launcher, &runner.compat_layer_type))
+            .map(|b| b.title.clone())
+            .collect();
+
+        if policy.auto_install {
+            if let Some(newest) = releases.first() {
+                if installed.contains(&newest.title) {
+                    log.push(format!("[OK] {}: {} is up to date", prefix, newest.title));
+                } else if dry_run {
+                    log.push(format!("[DRY RUN] {}: would install {}", prefix, newest.title));
+                    installed.insert(0, newest.title.clone());
+                } else {
+                    let result = match download_proton_build(runner.title.clone(), newest.title.clone(), newest.download_url.clone(), newest.checksum_url.clone()).await {
+                        Ok((_, _, archive, verification)) => install_proton_build_with_launcher(
+                            runner.title.clone(),
+                            newest.title.clone(),
+                            archive,
+                            Some(launcher.title.clone()),
+                            Some(runner.clone()),
+                        ).await.map(|_| verification),
+                        Err(e) => Err(e),
+                    };
+                    match result {
+                        Ok(verification) => {
+                            log.push(format!("[OK] {}: installed {} ({})", prefix, newest.title, verification));
+                            installed.insert(0, newest.title.clone());
+                        }
+                        Err(e) => log.push(format!("[FAIL] {}: installing {} failed: {}", prefix, newest.title, e)),
+                    }
+                }
+            }
+        }
+
+        if policy.keep_latest == 0 {
+            continue;
+        }
+        // Usage counts only come from Steam's config, so other launchers can't prove a build is unused
+        if policy.protect_in_use && launcher.kind != "Steam" {
+            if installed.len() > policy.keep_latest {
+                log.push(format!("[KEEP] {}: builds in use can't be detected for {}, nothing removed", prefix, launcher.kind));
+            }
+            continue;
+        }
+        for title in installed.iter().skip(policy.keep_latest) {
+            let usage = runner.builds.iter()
+                .find(|b| &b.title == title && !b.is_latest)
+                .map(|b| b.usage_count)
+                .unwrap_or(0);
+            if policy.protect_in_use && usage > 0 {
+                log.push(format!("[KEEP] {}: {} is used by {} game{}", prefix, title, usage, if usage == 1 { "" } else { "s" }));
+                continue;
+            }
+            if dry_run {
+                log.push(format!("[DRY RUN] {}: would remove {}", prefix, title));
+                continue;
+            }
+            match remove_proton_build(runner.title.clone(), title.clone(), Some(launcher.title.clone()), runners.clone(), launchers.clone()).await {
+                Ok(_) => log.push(format!("[OK] {}: removed {}", prefix, title)),
+                Err(e) => log.push(format!("[FAIL] {}: removing {} failed: {}", prefix, title, e)),
+            }
+        }
+    }
+
+    crate::logger::Logger::log_debug(&format!("[Proton Sync] {}", log.join(" | ")));
+    Ok(log)
+}
+
 async fn open_proton_directory(
     runner_title: String,
     title: String,
@@ -4707,7 +6482,7 @@ async fn open_proton_directory(
 
     // Get directory name format for this launcher
     let format = runner.directory_name_formats.iter()
-        .find(|f| f.launcher == launcher_title)
+        .find(|f| f.launcher == launcher.kind)
         .or_else(|| runner.directory_name_formats.iter().find(|f| f.launcher == "default"));
 
     let directory_name = if let Some(format) = format {
@@ -4717,7 +6492,8 @@ async fn open_proton_directory(
     };
 
     // Get the compatibility directory for this launcher and compat layer type
-    let compat_dir = get_launcher_compat_directory_for_type(&launcher.title, &launcher.directory, &runner.compat_layer_type);
+    let compat_dir = launcher.compat_directory(&runner.compat_layer_type)
+        .ok_or_else(|| format!("{} does not use {} builds", launcher.title, runner.compat_layer_type))?;
     let install_path = std::path::Path::new(&compat_dir).join(&directory_name);
 
 
@@ -4746,24 +6522,13 @@ async fn load_more_proton_builds(
     asset_position: usize,
     page: usize,
     directory_name_formats: Vec<DirectoryNameFormat>,
-) -> Result<(String, Vec<ProtonBuild>), String> {
+) -> Result<(String, Vec<ProtonBuild>, Option<String>), String> {
 
     let client = reqwest::Client::new();
-    let url = format!("{}?per_page=25&page={}", endpoint, page);
-
-    let response = client
-        .get(&url)
-        .header("User-Agent", "Rustora/1.0")
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch releases: {}", e))?;
-
-    if !response.status().is_success() {
-        return Err(format!("Failed to fetch releases: HTTP {}", response.status()));
-    }
-
-    let releases_json: serde_json::Value = response.json().await
-        .map_err(|e| format!("Failed to parse releases JSON: {}", e))?;
+    let settings = CatalogSettings::load();
+    let mut http_cache = proton_catalog::HttpCache::load();
+    let (releases_json, warning) = proton_catalog::fetch_releases(&client, &settings, &mut http_cache, &endpoint, page).await?;
+    http_cache.save();
 
     let mut new_builds = Vec::new();
 
@@ -4790,6 +6555,7 @@ async fn load_more_proton_builds(
                                 let download_size = asset.get("size")
                                     .and_then(|v| v.as_u64())
                                     .unwrap_or(0);
+                                let checksum_url = find_checksum_asset(assets);
 
                                 new_builds.push(ProtonBuild {
                                     title: tag_name.to_string(),
@@ -4798,6 +6564,7 @@ async fn load_more_proton_builds(
                                     download_url: download_url.to_string(),
                                     page_url,
                                     download_size,
+                                    checksum_url,
                                     runner_title: runner_title.clone(),
                                     is_installed: false,
                                     directory_name_formats: directory_name_formats.clone(),
@@ -4812,7 +6579,7 @@ async fn load_more_proton_builds(
         }
     }
 
-    Ok((runner_title, new_builds))
+    Ok((runner_title, new_builds, warning))
 }
 
 #[allow(dead_code)]
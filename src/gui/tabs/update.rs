@@ -5,6 +5,7 @@ use iced::widget::button::Appearance as ButtonAppearance;
 use iced::widget::button::StyleSheet as ButtonStyleSheet;
 use tokio::process::Command as TokioCommand;
 use std::collections::HashSet;
+use crate::gui::package_versions::{self, VersionAction, VersionLock};
 
 #[derive(Debug, Clone)]
 pub enum Message {
@@ -14,6 +15,13 @@ pub enum Message {
     InstallUpdates,
     UpdatesInstalled,
     OpenSettings,
+    FirmwareUpdatesFound(Vec<crate::gui::firmware::FirmwareDevice>),
+    // Handled by the app: switches to the Device tab's firmware view
+    OpenFirmware,
+    LocksLoaded(Vec<VersionLock>),
+    ToggleLockedList,
+    Unlock(String),
+    Unlocked(Result<String, String>),
 }
 
 #[derive(Debug, Clone)]
@@ -31,6 +39,12 @@ pub struct UpdateTab {
     is_checking: bool,
     is_installing: bool,
     has_updates: bool,
+    firmware_updates: Vec<crate::gui::firmware::FirmwareDevice>,
+    // Packages held by `dnf versionlock`; never part of an update run
+    locks: Vec<VersionLock>,
+    show_locked: bool,
+    is_unlocking: bool,
+    lock_status: Option<Result<String, String>>,
 }
 
 impl UpdateTab {
@@ -41,21 +55,76 @@ impl UpdateTab {
             is_checking: false,
             is_installing: false,
             has_updates: false,
+            firmware_updates: Vec::new(),
+            locks: Vec::new(),
+            show_locked: false,
+            is_unlocking: false,
+            lock_status: None,
         }
     }
 
+    fn is_locked(&self, index: usize) -> bool {
+        self.updates
+            .get(index)
+            .is_some_and(|u| package_versions::is_locked(&self.locks, &u.name))
+    }
+
+    fn installable_count(&self) -> usize {
+        (0..self.updates.len()).filter(|&i| !self.is_locked(i)).count()
+    }
+
     pub fn update(&mut self, message: Message) -> iced::Command<Message> {
         match message {
             Message::CheckUpdates => {
                 self.is_checking = true;
                 self.has_updates = false;
-                iced::Command::perform(check_for_updates(), |result| {
-                    match result {
-                        Ok(updates) => Message::UpdatesFound(updates),
-                        Err(_) => Message::UpdatesFound(Vec::new()),
-                    }
+                iced::Command::batch(vec![
+                    iced::Command::perform(check_for_updates(), |result| {
+                        match result {
+                            Ok(updates) => Message::UpdatesFound(updates),
+                            Err(_) => Message::UpdatesFound(Vec::new()),
+                        }
+                    }),
+                    // fwupd missing or offline just means no firmware row
+                    iced::Command::perform(crate::gui::firmware::pending_updates(), |result| {
+                        Message::FirmwareUpdatesFound(result.unwrap_or_default())
+                    }),
+                    // Likewise a missing versionlock plugin means nothing is locked
+                    iced::Command::perform(package_versions::load_locks(), |result| {
+                        Message::LocksLoaded(result.unwrap_or_default())
+                    }),
+                ])
+            }
+            Message::LocksLoaded(locks) => {
+                self.locks = locks;
+                let locked: Vec<usize> = self.selected_packages.iter().copied().filter(|&i| self.is_locked(i)).collect();
+                for index in locked {
+                    self.selected_packages.remove(&index);
+                }
+                iced::Command::none()
+            }
+            Message::ToggleLockedList => {
+                self.show_locked = !self.show_locked;
+                self.lock_status = None;
+                iced::Command::none()
+            }
+            Message::Unlock(name) => {
+                self.is_unlocking = true;
+                self.lock_status = None;
+                iced::Command::perform(package_versions::apply(VersionAction::Unlock(name)), Message::Unlocked)
+            }
+            Message::Unlocked(result) => {
+                self.is_unlocking = false;
+                self.lock_status = Some(result);
+                iced::Command::perform(package_versions::load_locks(), |result| {
+                    Message::LocksLoaded(result.unwrap_or_default())
                 })
             }
+            Message::FirmwareUpdatesFound(devices) => {
+                self.firmware_updates = devices;
+                iced::Command::none()
+            }
+            Message::OpenFirmware => iced::Command::none(),
             Message::UpdatesFound(updates) => {
                 self.is_checking = false;
                 self.updates = updates.clone();
@@ -64,6 +133,9 @@ impl UpdateTab {
                 iced::Command::none()
             }
             Message::TogglePackage(index) => {
+                if self.is_locked(index) {
+                    return iced::Command::none();
+                }
                 if self.selected_packages.contains(&index) {
                     self.selected_packages.remove(&index);
                 } else {
@@ -76,13 +148,21 @@ impl UpdateTab {
                     return iced::Command::none();
                 }
                 let packages_to_install: Vec<String> = if self.selected_packages.is_empty() {
-                    self.updates.iter().map(|u| u.name.clone()).collect()
+                    self.updates
+                        .iter()
+                        .filter(|u| !package_versions::is_locked(&self.locks, &u.name))
+                        .map(|u| u.name.clone())
+                        .collect()
                 } else {
                     self.selected_packages
                         .iter()
+                        .filter(|&&idx| !self.is_locked(idx))
                         .filter_map(|&idx| self.updates.get(idx).map(|u| u.name.clone()))
                         .collect()
                 };
+                if packages_to_install.is_empty() {
+                    return iced::Command::none();
+                }
 
                 use base64::{Engine as _, engine::general_purpose};
                 let packages_json = serde_json::to_string(&packages_to_install)
@@ -164,7 +244,7 @@ impl UpdateTab {
                 .padding(Padding::new(14.0))
         };
 
-        let install_button: Element<Message> = if self.updates.is_empty() || self.is_installing {
+        let install_button: Element<Message> = if self.installable_count() == 0 || self.is_installing {
             if self.is_installing {
                 button(
                     row![
@@ -181,7 +261,7 @@ impl UpdateTab {
                     .padding(Padding::new(14.0))
                     .into()
             } else {
-                button(text("No Updates Available").size(button_font_size))
+                button(text(if self.updates.is_empty() { "No Updates Available" } else { "All Updates Locked" }).size(button_font_size))
                     .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
                         is_primary: false,
                         radius: settings.border_radius,
@@ -191,7 +271,7 @@ impl UpdateTab {
             }
         } else {
             let selected_count = if self.selected_packages.is_empty() {
-                self.updates.len()
+                self.installable_count()
             } else {
                 self.selected_packages.len()
             };
@@ -222,9 +302,21 @@ impl UpdateTab {
         })))
         .padding(Padding::new(10.0));
 
+        let locked_button = button(
+            text(if self.show_locked { "Back to Updates".to_string() } else { format!("Locked ({})", self.locks.len()) })
+                .size(button_font_size)
+        )
+        .on_press(Message::ToggleLockedList)
+        .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+            is_primary: false,
+            radius: settings.border_radius,
+        })))
+        .padding(Padding::new(14.0));
+
         let header = row![
             check_button,
             Space::with_width(Length::Fill),
+            locked_button,
             settings_button,
             Space::with_width(Length::Fixed(10.0)),
             install_button
@@ -232,7 +324,9 @@ impl UpdateTab {
         .spacing(10)
         .align_items(Alignment::Center);
 
-        let content: Element<Message> = if self.is_checking {
+        let content: Element<Message> = if self.show_locked {
+            self.view_locked(settings)
+        } else if self.is_checking {
             container(text("Checking for updates...").size(body_font_size))
                 .width(Length::Fill)
                 .height(Length::Fill)
@@ -271,12 +365,18 @@ impl UpdateTab {
                         .map(|(index, update)| {
                             let is_selected = self.selected_packages.contains(&index);
                             let index_for_toggle = index;
+                            let is_locked = self.is_locked(index);
+                            let toggle = checkbox("", is_selected).width(Length::Shrink);
                             container(
                                 row![
-                                    checkbox("", is_selected)
-                                        .on_toggle(move |_| Message::TogglePackage(index_for_toggle))
-                                        .width(Length::Shrink),
-                                    text(&update.name).size(package_name_size).width(Length::FillPortion(3)),
+                                    if is_locked {
+                                        toggle
+                                    } else {
+                                        toggle.on_toggle(move |_| Message::TogglePackage(index_for_toggle))
+                                    },
+                                    text(if is_locked { format!("{} (locked)", update.name) } else { update.name.clone() })
+                                        .size(package_name_size)
+                                        .width(Length::FillPortion(3)),
                                     text(&update.current_version).size(package_detail_size).width(Length::FillPortion(2)),
                                     text("->").size(package_detail_size),
                                     text(&update.available_version).size(package_detail_size).width(Length::FillPortion(2)),
@@ -299,11 +399,94 @@ impl UpdateTab {
             .into()
         };
 
-        container(column![header, content].spacing(15).padding(20))
+        let mut page = column![header].spacing(15).padding(20);
+        if !self.firmware_updates.is_empty() {
+            let names = self.firmware_updates
+                .iter()
+                .map(|d| d.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            page = page.push(
+                container(
+                    row![
+                        text(crate::gui::fonts::glyphs::DOWNLOAD_SYMBOL).font(material_font).size(icon_size),
+                        column![
+                            text(format!("{} firmware update(s) available", self.firmware_updates.len())).size(package_name_size),
+                            text(names).size(package_detail_size),
+                        ]
+                        .spacing(4)
+                        .width(Length::Fill),
+                        button(text("View Firmware").size(button_font_size))
+                            .on_press(Message::OpenFirmware)
+                            .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                                is_primary: true,
+                                radius: settings.border_radius,
+                            })))
+                            .padding(Padding::new(10.0)),
+                    ]
+                    .spacing(12)
+                    .align_items(Alignment::Center)
+                    .padding(12)
+                )
+                .style(iced::theme::Container::Custom(Box::new(UpdateItemStyle {
+                    radius: settings.border_radius,
+                })))
+            );
+        }
+
+        container(page.push(content))
             .width(Length::Fill)
             .height(Length::Fill)
             .into()
     }
+
+    fn view_locked(&self, settings: &crate::gui::settings::AppSettings) -> Element<'_, Message> {
+        let button_font_size = settings.font_size_buttons * settings.scale_buttons;
+        let body_font_size = settings.font_size_body * settings.scale_body;
+        let package_name_size = settings.font_size_package_names * settings.scale_package_cards;
+        let package_detail_size = settings.font_size_package_details * settings.scale_package_cards;
+
+        let mut list = column![].spacing(6).padding(10);
+        if let Some(ref status) = self.lock_status {
+            list = list.push(
+                text(match status {
+                    Ok(message) => message.clone(),
+                    Err(e) => format!("Failed to unlock: {}", e),
+                })
+                .size(package_detail_size),
+            );
+        }
+        if self.locks.is_empty() {
+            list = list.push(
+                text("No packages are locked. Lock a package from its Versions view in the Installed or Search tab.")
+                    .size(body_font_size),
+            );
+        }
+        for lock in &self.locks {
+            list = list.push(
+                container(
+                    row![
+                        text(&lock.name).size(package_name_size).width(Length::FillPortion(3)),
+                        text(&lock.evr).size(package_detail_size).width(Length::FillPortion(3)),
+                        button(text("Unlock").size(button_font_size))
+                            .on_press_maybe((!self.is_unlocking).then(|| Message::Unlock(lock.name.clone())))
+                            .style(iced::theme::Button::Custom(Box::new(RoundedButtonStyle {
+                                is_primary: false,
+                                radius: settings.border_radius,
+                            })))
+                            .padding(Padding::new(10.0)),
+                    ]
+                    .spacing(12)
+                    .align_items(Alignment::Center)
+                    .padding(12)
+                )
+                .style(iced::theme::Container::Custom(Box::new(UpdateItemStyle {
+                    radius: settings.border_radius,
+                })))
+            );
+        }
+        scrollable(list).into()
+    }
 }
 
 async fn check_for_updates() -> Result<Vec<UpdateInfo>, String> {
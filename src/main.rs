@@ -1,5 +1,6 @@
 mod gui;
 mod logger;
+mod cpu;
 
 use clap::{Parser, Subcommand};
 use colored::*;
@@ -11,7 +12,8 @@ use iced::Application;
 #[derive(Parser)]
 #[command(name = "rustora", about = "Rustora - A modern package manager for Fedora", version)]
 struct Cli {
-    #[arg(value_name = "RPM_FILE")]
+    // .rpm, .flatpakref or .flatpakrepo file, or an appstream:/flatpak+https: link
+    #[arg(value_name = "FILE_OR_URI")]
     rpm_file: Option<String>,
 
     #[command(subcommand)]
@@ -41,8 +43,16 @@ enum Commands {
         #[arg(short, long)]
         all: bool,
     },
+    Hw {
+        #[command(subcommand)]
+        command: HwCommands,
+    },
+    Proton {
+        #[command(subcommand)]
+        command: ProtonCommands,
+    },
     Gui {
-        #[arg(value_name = "RPM_FILE")]
+        #[arg(value_name = "FILE_OR_URI")]
         rpm_file: Option<String>,
     },
     RemoveDialog {
@@ -55,9 +65,13 @@ enum Commands {
         application_id: String,
         #[arg(long)]
         remote: Option<String>,
+        #[arg(long)]
+        installation: Option<String>,
     },
     FlatpakRemoveDialog {
         application_ids: Vec<String>,
+        #[arg(long)]
+        installation: Option<String>,
     },
     FlatpakUpdateDialog {
         #[arg(long)]
@@ -74,7 +88,9 @@ enum Commands {
         build_title: String,
         download_url: String,
         #[arg(long)]
-        launcher: Option<String>,
+        checksum_url: Option<String>,
+        #[arg(long)]
+        launcher: Vec<String>,
         #[arg(long)]
         runner_info: Option<String>,
     },
@@ -112,6 +128,11 @@ enum Commands {
         device_id: String,
         #[arg(long)]
         repositories: String,
+        // Cached cfhdb profile the script came from, checked again before it runs
+        #[arg(long)]
+        profile_codename: Option<String>,
+        #[arg(long)]
+        profile_bus: Option<String>,
     },
     DeviceRemoveDialog {
         #[arg(long)]
@@ -134,6 +155,11 @@ enum Commands {
         device_id: String,
         #[arg(long)]
         repositories: String,
+        // Cached cfhdb profile the script came from, checked again before it runs
+        #[arg(long)]
+        profile_codename: Option<String>,
+        #[arg(long)]
+        profile_bus: Option<String>,
     },
     KernelRemoveDialog {
         kernel_name: String,
@@ -145,6 +171,25 @@ enum Commands {
     HyprlandDotfilesDialog,
 }
 
+#[derive(Subcommand)]
+enum HwCommands {
+    Report {
+        #[arg(short, long, default_value = "markdown")]
+        format: String,
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProtonCommands {
+    // Apply the retention policies saved from the Proton view
+    Sync {
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
 fn ensure_fonts_async() {
     if !gui::fonts::fonts_exist() {
         tokio::spawn(async {
@@ -153,6 +198,59 @@ fn ensure_fonts_async() {
     }
 }
 
+// Entry point for files and links handed over by the desktop. Launched from a browser or
+// file manager nobody sees stderr, so failures are also shown in a dialog
+async fn open_file_or_uri(argument: &str) -> Result<()> {
+    let result = open_request(argument).await;
+    if let Err(ref e) = result {
+        ensure_fonts_async();
+        use crate::gui::open_error_dialog::OpenErrorDialog;
+        if let Err(dialog_error) = OpenErrorDialog::run_separate_window(argument.to_string(), e.to_string()) {
+            logger::Logger::log_debug(&format!("Failed to show the open error dialog: {}", dialog_error));
+        }
+    }
+    result
+}
+
+async fn open_request(argument: &str) -> Result<()> {
+    use crate::gui::flatpak_ref::OpenRequest;
+    use crate::gui::flatpak_remotes::Installation;
+    let request = gui::flatpak_ref::resolve(argument).await.map_err(|e| anyhow::anyhow!(e))?;
+    logger::Logger::log_debug(&format!("Opening {} as {:?}", argument, request));
+    ensure_fonts_async();
+    match request {
+        OpenRequest::Rpm(rpm_path) => {
+            use crate::gui::rpm_dialog::RpmDialog;
+            RpmDialog::run_separate_window(rpm_path)?;
+        }
+        OpenRequest::FlatpakRef(flatpak_ref) => {
+            use crate::gui::flatpak_dialog::FlatpakDialog;
+            FlatpakDialog::run_from_ref(flatpak_ref, Installation::System)?;
+        }
+        OpenRequest::FlatpakRepo(repo) => {
+            use crate::gui::flatpak_remote_dialog::FlatpakRemoteDialog;
+            FlatpakRemoteDialog::run_separate_window(repo)?;
+        }
+        OpenRequest::AppStream(id) => {
+            let catalog = gui::appstream::catalog().await;
+            let app = catalog
+                .find(&id)
+                .ok_or_else(|| anyhow::anyhow!("{} is not in the local AppStream catalog", id))?;
+            match app.package() {
+                Some(package) => {
+                    use crate::gui::install_dialog::InstallDialog;
+                    InstallDialog::run_separate_window(vec![package.to_string()])?;
+                }
+                None => {
+                    use crate::gui::flatpak_dialog::FlatpakDialog;
+                    FlatpakDialog::run_separate_window(app.id.clone(), app.remote().map(str::to_string), Installation::System)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize logger
@@ -162,21 +260,7 @@ async fn main() -> Result<()> {
     let cli = Cli::parse();
 
     if let Some(rpm_file) = cli.rpm_file {
-        let rpm_path = Path::new(&rpm_file).to_path_buf();
-        if !rpm_path.exists() {
-            return Err(anyhow::anyhow!("RPM file not found: {}", rpm_file));
-        }
-        if let Some(ext) = rpm_path.extension() {
-            if ext.to_string_lossy().to_lowercase() != "rpm" {
-                return Err(anyhow::anyhow!("File is not an RPM file: {}", rpm_file));
-            }
-        } else {
-            return Err(anyhow::anyhow!("File does not have an extension: {}", rpm_file));
-        }
-        ensure_fonts_async();
-        use crate::gui::rpm_dialog::RpmDialog;
-        RpmDialog::run_separate_window(rpm_path)?;
-        return Ok(());
+        return open_file_or_uri(&rpm_file).await;
     }
 
     match cli.command {
@@ -197,14 +281,8 @@ async fn main() -> Result<()> {
         }
         Some(Commands::Gui { rpm_file }) => {
             if let Some(rpm_file_str) = rpm_file {
-                let rpm_path = Path::new(&rpm_file_str).to_path_buf();
-                if !rpm_path.exists() {
-                    return Err(anyhow::anyhow!("RPM file not found: {}", rpm_file_str));
-                }
-                ensure_fonts_async();
-                use crate::gui::rpm_dialog::RpmDialog;
-                RpmDialog::run_separate_window(rpm_path)?;
-                } else {
+                open_file_or_uri(&rpm_file_str).await?;
+            } else {
                 ensure_fonts_async();
                 let default_font = gui::fonts::get_inter_font();
                 gui::RustoraApp::run(iced::Settings {
@@ -231,16 +309,20 @@ async fn main() -> Result<()> {
             InstallDialog::run_separate_window(packages)?;
             Ok(())
         }
-        Some(Commands::FlatpakInstallDialog { application_id, remote }) => {
+        Some(Commands::FlatpakInstallDialog { application_id, remote, installation }) => {
             ensure_fonts_async();
             use crate::gui::flatpak_dialog::FlatpakDialog;
-            FlatpakDialog::run_separate_window(application_id, remote)?;
+            use crate::gui::flatpak_remotes::Installation;
+            let installation = installation.as_deref().and_then(Installation::from_label).unwrap_or(Installation::System);
+            FlatpakDialog::run_separate_window(application_id, remote, installation)?;
             Ok(())
         }
-        Some(Commands::FlatpakRemoveDialog { application_ids }) => {
+        Some(Commands::FlatpakRemoveDialog { application_ids, installation }) => {
             ensure_fonts_async();
             use crate::gui::flatpak_remove_dialog::FlatpakRemoveDialog;
-            FlatpakRemoveDialog::run_separate_window(application_ids)?;
+            use crate::gui::flatpak_remotes::Installation;
+            let installation = installation.as_deref().and_then(Installation::from_label);
+            FlatpakRemoveDialog::run_separate_window(application_ids, installation)?;
             Ok(())
         }
         Some(Commands::FlatpakUpdateDialog { packages_b64 }) => {
@@ -297,10 +379,10 @@ async fn main() -> Result<()> {
             HyprlandDotfilesDialog::run_separate_window()?;
             Ok(())
         }
-        Some(Commands::ProtonInstallDialog { runner_title, build_title, download_url, launcher, runner_info }) => {
+        Some(Commands::ProtonInstallDialog { runner_title, build_title, download_url, checksum_url, launcher, runner_info }) => {
             ensure_fonts_async();
             use crate::gui::proton_install_dialog::ProtonInstallDialog;
-            ProtonInstallDialog::run_separate_window(runner_title, build_title, download_url, launcher, runner_info)?;
+            ProtonInstallDialog::run_separate_window(runner_title, build_title, download_url, checksum_url, launcher, runner_info)?;
             Ok(())
         }
         Some(Commands::ProtonChangelogDialog { runner_title, build_title, description, page_url }) => {
@@ -339,6 +421,8 @@ async fn main() -> Result<()> {
             vendor_id,
             device_id,
             repositories,
+            profile_codename,
+            profile_bus,
         }) => {
             ensure_fonts_async();
             use base64::{Engine as _, engine::general_purpose};
@@ -361,6 +445,10 @@ async fn main() -> Result<()> {
             let repos_json = decode(&repositories);
             let repos: Vec<String> = serde_json::from_str(&repos_json).unwrap_or_default();
 
+            let cached_profile = profile_codename
+                .zip(profile_bus)
+                .map(|(codename, bus)| crate::gui::cfhdb_cache::CachedProfile { bus, codename });
+
             use crate::gui::device_install_dialog::{DeviceInstallDialog, DeviceInfo};
             DeviceInstallDialog::run_separate_window(profile_name, script, DeviceInfo {
                 vendor_name: vendor,
@@ -371,7 +459,7 @@ async fn main() -> Result<()> {
                 vendor_id: vid,
                 device_id: did,
                 repositories: repos,
-            }, false)?;
+            }, false, cached_profile)?;
             Ok(())
         }
         Some(Commands::DeviceRemoveDialog {
@@ -385,6 +473,8 @@ async fn main() -> Result<()> {
             vendor_id,
             device_id,
             repositories,
+            profile_codename,
+            profile_bus,
         }) => {
             ensure_fonts_async();
             use base64::{Engine as _, engine::general_purpose};
@@ -407,6 +497,10 @@ async fn main() -> Result<()> {
             let repos_json = decode(&repositories);
             let repos: Vec<String> = serde_json::from_str(&repos_json).unwrap_or_default();
 
+            let cached_profile = profile_codename
+                .zip(profile_bus)
+                .map(|(codename, bus)| crate::gui::cfhdb_cache::CachedProfile { bus, codename });
+
             use crate::gui::device_install_dialog::{DeviceInstallDialog, DeviceInfo};
             DeviceInstallDialog::run_separate_window(profile_name, script, DeviceInfo {
                 vendor_name: vendor,
@@ -417,7 +511,7 @@ async fn main() -> Result<()> {
                 vendor_id: vid,
                 device_id: did,
                 repositories: repos,
-            }, true)?;
+            }, true, cached_profile)?;
             Ok(())
         }
         Some(Commands::KernelRemoveDialog { kernel_name }) => {
@@ -426,6 +520,13 @@ async fn main() -> Result<()> {
             KernelInstallDialog::run_separate_window(kernel_name)?;
             Ok(())
         }
+        Some(Commands::Proton { command: ProtonCommands::Sync { dry_run } }) => {
+            if let Err(e) = proton_sync(dry_run).await {
+                eprintln!("{} {}", "[ERROR]".red(), e);
+                std::process::exit(1);
+            }
+            Ok(())
+        }
         Some(cmd) => {
             let result = match cmd {
                 Commands::Search { query, details } => search_packages(&query, details),
@@ -433,9 +534,11 @@ async fn main() -> Result<()> {
                 Commands::List { details } => list_packages(details),
                 Commands::Info { package } => show_package_info(&package),
                 Commands::Update { all } => update_packages(all),
+                Commands::Hw { command: HwCommands::Report { format, output } } => hardware_report(&format, output.as_deref()),
                 _ => unreachable!(),
             };
-            if result.is_err() {
+            if let Err(e) = result {
+                eprintln!("{} {}", "[ERROR]".red(), e);
                 std::process::exit(1);
             }
             Ok(())
@@ -585,6 +688,57 @@ fn update_packages(all: bool) -> Result<()> {
     Ok(())
 }
 
+fn hardware_report(format: &str, output: Option<&str>) -> Result<()> {
+    use crate::gui::hw_report::HardwareReport;
+    let report = HardwareReport::collect();
+    let content = match format {
+        "markdown" | "md" => report.to_markdown(),
+        "json" => report.to_json().map_err(|e| anyhow::anyhow!(e))?,
+        _ => anyhow::bail!("Unknown report format: {} (expected markdown or json)", format),
+    };
+    if let Some(path) = output {
+        std::fs::write(path, content)?;
+        eprintln!("{} Hardware report written to {}", "[OK]".green(), path.bright_white().bold());
+    } else {
+        print!("{}", content);
+    }
+    Ok(())
+}
+
+async fn proton_sync(dry_run: bool) -> Result<()> {
+    use crate::gui::proton_policy::ProtonPolicies;
+    let policies = ProtonPolicies::load().policies;
+    println!("{} Applying {} Proton retention {}{}\n",
+        "[SYNC]".green(),
+        policies.len(),
+        if policies.len() == 1 { "policy" } else { "policies" },
+        if dry_run { " (dry run)" } else { "" });
+    match crate::gui::tabs::tweaks::sync_proton_builds(policies, dry_run).await {
+        Ok(lines) => {
+            let mut failed = false;
+            for line in lines {
+                if let Some(rest) = line.strip_prefix("[FAIL]") {
+                    failed = true;
+                    println!("{}{}", "[FAIL]".red(), rest);
+                } else if let Some(rest) = line.strip_prefix("[OK]") {
+                    println!("{}{}", "[OK]".green(), rest);
+                } else if let Some(rest) = line.strip_prefix("[KEEP]") {
+                    println!("{}{}", "[KEEP]".blue(), rest);
+                } else if let Some(rest) = line.strip_prefix("[SKIP]") {
+                    println!("{}{}", "[SKIP]".yellow(), rest);
+                } else {
+                    println!("{}", line);
+                }
+            }
+            if failed {
+                anyhow::bail!("Some Proton builds could not be synced");
+            }
+            Ok(())
+        }
+        Err(e) => anyhow::bail!(e),
+    }
+}
+
 fn check_sudo() {
     if Command::new("sudo").args(["-n", "true"]).status().is_err() {
         println!("{} This operation requires sudo privileges", "[WARN]".yellow());